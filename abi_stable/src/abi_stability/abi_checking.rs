@@ -23,9 +23,10 @@ use crate::{
     std_types::{RArc, RBox, RBoxError, RErr, RNone, ROk, RResult, RSome, RStr, RVec, UTypeId},
     traits::IntoReprC,
     type_layout::{
-        tagging::TagErrors, FmtFullType, IncompatibleWithNonExhaustive, IsExhaustive, ReprAttr,
-        TLData, TLDataDiscriminant, TLDiscriminant, TLEnum, TLField, TLFieldOrFunction, TLFunction,
-        TLNonExhaustive, TLPrimitive, TypeLayout,
+        tagging::TagErrors, validate_layout, FmtFullType, IncompatibleWithNonExhaustive,
+        InvalidLayout, IsExhaustive, ReprAttr, TLData, TLDataDiscriminant, TLDiscriminant, TLEnum,
+        TLField, TLFieldOrFunction, TLFunction, TLNonExhaustive, TLPrimitive, TypeLayout,
+        ValidationLimits,
     },
     type_level::downcasting::TD_Opaque,
     utils::{max_by, min_max_by},
@@ -1071,6 +1072,9 @@ impl AbiChecker {
 
 /// Checks that the layout of `interface` is compatible with `implementation`.
 ///
+/// Both layouts are validated with [`validate_layout`] before being compared,
+/// returning `AbiInstability::InvalidLayout` errors if either one is malformed.
+///
 /// # Warning
 ///
 /// This function is not symmetric,
@@ -1092,7 +1096,21 @@ pub fn check_layout_compatibility_with_globals(
 ) -> Result<(), AbiInstabilityErrors> {
     let mut errors: RVec<AbiInstabilityError>;
 
-    if interface.is_prefix_kind() || implementation.is_prefix_kind() {
+    let invalid = [interface, implementation]
+        .iter()
+        .filter_map(|&layout| validate_layout(layout, ValidationLimits::DEFAULT).err())
+        .map(AI::InvalidLayout)
+        .collect::<RVec<AbiInstability>>();
+
+    if !invalid.is_empty() {
+        errors = vec![AbiInstabilityError {
+            stack_trace: vec![].into(),
+            errs: invalid,
+            index: 0,
+            _priv: (),
+        }]
+        .into();
+    } else if interface.is_prefix_kind() || implementation.is_prefix_kind() {
         let mut errs = RVec::with_capacity(1);
         push_err(
             &mut errs,
@@ -1,6 +1,6 @@
 use super::*;
 
-use core_extensions::StringExt;
+use core_extensions::{matches, StringExt};
 
 /// An individual error from checking the layout of some type.
 #[derive(Debug, PartialEq, Clone)]
@@ -38,6 +38,9 @@ pub enum AbiInstability {
     TagError {
         err: TagErrors,
     },
+    /// One of the layouts is malformed,
+    /// so it wasn't compared to the other one.
+    InvalidLayout(InvalidLayout),
 }
 
 #[derive(Debug, Clone)]
@@ -70,12 +73,23 @@ impl fmt::Debug for AbiInstabilityErrors {
 }
 impl fmt::Display for AbiInstabilityErrors {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        writeln!(
-            f,
-            "Compared <this>:\n{}\nTo <other>:\n{}\n",
-            self.interface.to_string().left_padder(4),
-            self.implementation.to_string().left_padder(4),
-        )?;
+        let is_invalid = self
+            .errors
+            .iter()
+            .flat_map(|x| &x.errs)
+            .any(|x| matches!(x, AI::InvalidLayout(_)));
+
+        // Printing a malformed layout could index out of bounds.
+        if is_invalid {
+            writeln!(f, "Could not compare malformed type layouts.\n")?;
+        } else {
+            writeln!(
+                f,
+                "Compared <this>:\n{}\nTo <other>:\n{}\n",
+                self.interface.to_string().left_padder(4),
+                self.implementation.to_string().left_padder(4),
+            )?;
+        }
         for err in &self.errors {
             fmt::Display::fmt(err, f)?;
         }
@@ -188,6 +202,11 @@ impl fmt::Display for AbiInstabilityError {
                 AI::TagError { err } => {
                     extra_err = Some(err.to_string());
 
+                    ("", None)
+                }
+                AI::InvalidLayout(err) => {
+                    extra_err = Some(err.to_string());
+
                     ("", None)
                 }
             };
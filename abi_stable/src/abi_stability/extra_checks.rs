@@ -9,6 +9,10 @@
 //! - Apply the `#[sabi(extra_checks="const expression that implements ExtraChecks")]`
 //!     attribute to a type that uses `#[derive(StableAbi)]`.
 //!
+//! For checks on constants,the `#[sabi(check_const(..))]`,`#[sabi(check_subset(..))]`,
+//! and `#[sabi(check_ordered(..))]` attributes can be used instead,
+//! which are implemented with [`DeclaredChecks`](./struct.DeclaredChecks.html).
+//!
 //! # Combination
 //!
 //! This is how an ExtraChecks can be combined across all
@@ -605,6 +609,12 @@ use std::{
 
 use core_extensions::SelfOps;
 
+mod declared_checks;
+
+pub use self::declared_checks::{
+    DeclaredCheck, DeclaredCheckError, DeclaredCheckKind, DeclaredChecks,
+};
+
 /// This checks that the layout of types coming from dynamic libraries
 /// are compatible with those of the binary/dynlib that loads them.
 ///
@@ -0,0 +1,401 @@
+//! The `ExtraChecks` that `#[derive(StableAbi)]` generates from the
+//! `#[sabi(check_const(..))]`,`#[sabi(check_subset(..))]`,
+//! and `#[sabi(check_ordered(..))]` attributes.
+
+use super::*;
+
+use crate::{
+    std_types::{RSlice, RSome, RStr, RVec},
+    type_layout::{tagging::TagValue, Tag},
+    type_level::downcasting::TD_Opaque,
+};
+
+/// An `ExtraChecks` made of named checks on constant values,
+/// declared with the `#[sabi(check_*(..))]` attributes of `#[derive(StableAbi)]`.
+///
+/// Each check in the interface is compared to the check with the same name
+/// in the implementation,which must exist and be of the same kind:
+///
+/// - `check_const`: the values must be equal.
+///
+/// - `check_subset`: the elements in the interface must be a
+///   subset of the elements in the implementation.
+///
+/// - `check_ordered`: the elements in the interface must be a
+///   prefix of the elements in the implementation.
+///
+/// The values of `check_subset` and `check_ordered` must be Arrays or Sets,
+/// any other value makes the check fail.
+///
+/// Checks that only exist in the implementation are ignored,
+/// so that newer versions of a library can add them.
+///
+/// When combined across dynamic libraries,
+/// the superset/longest version of every check is the representative one,
+/// so every library must be compatible with the newest one.
+///
+/// # Example
+///
+/// ```
+/// use abi_stable::{
+///     abi_stability::check_layout_compatibility, marker_type::UnsafeIgnoredType,
+///     GetStaticEquivalent, StableAbi,
+/// };
+///
+/// #[repr(C)]
+/// #[derive(StableAbi)]
+/// #[sabi(
+///     not_stableabi(V),
+///     bound = "V: Version",
+///     check_const(MAJOR = "V::MAJOR"),
+///     check_subset(FEATURES = "V::FEATURES"),
+///     check_ordered(COMMANDS = r#"["open", "close"]"#),
+/// )]
+/// struct Interface<V> {
+///     _marker: UnsafeIgnoredType<V>,
+/// }
+///
+/// trait Version {
+///     const MAJOR: i64;
+///     const FEATURES: abi_stable::type_layout::Tag;
+/// }
+///
+/// #[derive(GetStaticEquivalent)]
+/// struct V1_0;
+///
+/// impl Version for V1_0 {
+///     const MAJOR: i64 = 1;
+///     const FEATURES: abi_stable::type_layout::Tag = abi_stable::tag!({ "a" });
+/// }
+///
+/// #[derive(GetStaticEquivalent)]
+/// struct V1_1;
+///
+/// impl Version for V1_1 {
+///     const MAJOR: i64 = 1;
+///     const FEATURES: abi_stable::type_layout::Tag = abi_stable::tag!({ "a", "b" });
+/// }
+///
+/// let v1_0 = <Interface<V1_0> as StableAbi>::LAYOUT;
+/// let v1_1 = <Interface<V1_1> as StableAbi>::LAYOUT;
+///
+/// check_layout_compatibility(v1_0, v1_1).unwrap();
+///
+/// // The interface can't have features that the implementation doesn't have.
+/// check_layout_compatibility(v1_1, v1_0).unwrap_err();
+///
+/// ```
+#[repr(C)]
+#[derive(Debug, Clone, StableAbi)]
+pub struct DeclaredChecks {
+    checks: RCow<'static, [DeclaredCheck]>,
+}
+
+/// A named check in a `DeclaredChecks`.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, StableAbi)]
+pub struct DeclaredCheck {
+    pub name: RStr<'static>,
+    pub kind: DeclaredCheckKind,
+    /// The constant for `Const` checks,
+    /// and the Array or Set of elements for `Subset` and `Ordered` checks.
+    pub value: Tag,
+}
+
+/// The kinds of `DeclaredCheck`.
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, StableAbi)]
+pub enum DeclaredCheckKind {
+    /// Declared with `#[sabi(check_const(..))]`.
+    Const,
+    /// Declared with `#[sabi(check_subset(..))]`.
+    Subset,
+    /// Declared with `#[sabi(check_ordered(..))]`.
+    Ordered,
+}
+
+impl DeclaredChecks {
+    /// Constructs a `DeclaredChecks` from a slice of checks.
+    pub const fn new(checks: RSlice<'static, DeclaredCheck>) -> Self {
+        Self {
+            checks: RCow::Borrowed(checks),
+        }
+    }
+
+    /// The checks in this `DeclaredChecks`.
+    pub fn checks(&self) -> &[DeclaredCheck] {
+        &self.checks
+    }
+
+    /// Gets the check named `name`.
+    pub fn get(&self, name: &str) -> Option<&DeclaredCheck> {
+        self.checks.iter().find(|c| c.name.as_str() == name)
+    }
+
+    /// Checks that `self`(from the interface) is compatible with `other`
+    /// (from the implementation).
+    pub fn check_compatible(&self, other: &Self) -> Result<(), DeclaredCheckError> {
+        for check in self.checks.iter() {
+            match other.get(check.name.as_str()) {
+                Some(other_check) => check.check_compatible(other_check)?,
+                None => return Err(DeclaredCheckError::MissingCheck { name: check.name }),
+            }
+        }
+        Ok(())
+    }
+
+    /// Combines `self` and `other`,
+    /// keeping the newest version of the checks that both have.
+    fn combine_inner(&self, other: &Self) -> Result<Self, DeclaredCheckError> {
+        let mut checks = self.checks.iter().cloned().collect::<RVec<DeclaredCheck>>();
+        for other_check in other.checks.iter() {
+            match checks.iter_mut().find(|c| c.name == other_check.name) {
+                Some(check) => {
+                    match (
+                        check.check_compatible(other_check),
+                        other_check.check_compatible(check),
+                    ) {
+                        (Ok(()), _) => *check = *other_check,
+                        (Err(_), Ok(())) => {}
+                        (Err(e), Err(_)) => return Err(e),
+                    }
+                }
+                None => checks.push(*other_check),
+            }
+        }
+        Ok(Self {
+            checks: RCow::Owned(checks),
+        })
+    }
+}
+
+impl DeclaredCheck {
+    /// Constructs a check declared with `#[sabi(check_const(..))]`.
+    pub const fn const_(name: &'static str, value: Tag) -> Self {
+        Self::new(name, DeclaredCheckKind::Const, value)
+    }
+
+    /// Constructs a check declared with `#[sabi(check_subset(..))]`.
+    pub const fn subset(name: &'static str, value: Tag) -> Self {
+        Self::new(name, DeclaredCheckKind::Subset, value)
+    }
+
+    /// Constructs a check declared with `#[sabi(check_ordered(..))]`.
+    pub const fn ordered(name: &'static str, value: Tag) -> Self {
+        Self::new(name, DeclaredCheckKind::Ordered, value)
+    }
+
+    const fn new(name: &'static str, kind: DeclaredCheckKind, value: Tag) -> Self {
+        Self {
+            name: RStr::from_str(name),
+            kind,
+            value,
+        }
+    }
+
+    /// Checks that `self`(from the interface) is compatible with `other`
+    /// (from the implementation).
+    pub fn check_compatible(&self, other: &Self) -> Result<(), DeclaredCheckError> {
+        let name = self.name;
+        if self.kind != other.kind {
+            return Err(DeclaredCheckError::MismatchedKind {
+                name,
+                expected: self.kind,
+                found: other.kind,
+            });
+        }
+        match self.kind {
+            DeclaredCheckKind::Const => {
+                if self.value.value() != other.value.value() {
+                    return Err(DeclaredCheckError::UnequalConst {
+                        name,
+                        expected: self.value,
+                        found: other.value,
+                    });
+                }
+            }
+            DeclaredCheckKind::Subset => {
+                let other_elems = other.elements()?;
+                let missing = self
+                    .elements()?
+                    .iter()
+                    .find(|elem| !other_elems.iter().any(|x| x.value() == elem.value()));
+                if let Some(&element) = missing {
+                    return Err(DeclaredCheckError::MissingElement { name, element });
+                }
+            }
+            DeclaredCheckKind::Ordered => {
+                let this = self.elements()?;
+                let other_elems = other.elements()?;
+                let is_prefix = this.len() <= other_elems.len()
+                    && this
+                        .iter()
+                        .zip(other_elems)
+                        .all(|(l, r)| l.value() == r.value());
+                if !is_prefix {
+                    return Err(DeclaredCheckError::NotAPrefix {
+                        name,
+                        expected: self.value,
+                        found: other.value,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl DeclaredCheck {
+    /// Gets the elements of a `Subset` or `Ordered` check,
+    /// erroring if its value isn't an Array or Set.
+    fn elements(&self) -> Result<&'static [Tag], DeclaredCheckError> {
+        match self.value.value() {
+            TagValue::Array(elems) | TagValue::Set(elems) => Ok(elems),
+            _ => Err(DeclaredCheckError::NotACollection {
+                name: self.name,
+                kind: self.kind,
+                value: self.value,
+            }),
+        }
+    }
+}
+
+impl Display for DeclaredChecks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "DeclaredChecks: checks the constants declared with `#[sabi(check_*(..))]`:"
+        )?;
+        for check in self.checks.iter() {
+            writeln!(f, "    {}", check)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for DeclaredCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let attr = match self.kind {
+            DeclaredCheckKind::Const => "check_const",
+            DeclaredCheckKind::Subset => "check_subset",
+            DeclaredCheckKind::Ordered => "check_ordered",
+        };
+        write!(f, "{}({} = {})", attr, self.name, self.value)
+    }
+}
+
+unsafe impl ExtraChecks for DeclaredChecks {
+    fn type_layout(&self) -> &'static TypeLayout {
+        <Self as StableAbi>::LAYOUT
+    }
+
+    fn check_compatibility(
+        &self,
+        _layout_containing_self: &'static TypeLayout,
+        layout_containing_other: &'static TypeLayout,
+        checker: TypeCheckerMut<'_>,
+    ) -> RResult<(), ExtraChecksError> {
+        Self::downcast_with_layout(layout_containing_other, checker, |other, _| {
+            self.check_compatible(other)
+        })
+    }
+
+    fn nested_type_layouts(&self) -> RCow<'_, [&'static TypeLayout]> {
+        RCow::from_slice(&[])
+    }
+
+    fn combine(
+        &self,
+        other: ExtraChecksRef<'_>,
+        checker: TypeCheckerMut<'_>,
+    ) -> RResult<ROption<ExtraChecksBox>, ExtraChecksError> {
+        Self::downcast_with_object(other, checker, |other, _| {
+            self.combine_inner(other)
+                .map(|combined| RSome(ExtraChecksBox::from_value(combined, TD_Opaque)))
+        })
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// The error returned when two `DeclaredChecks` are incompatible.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeclaredCheckError {
+    /// The implementation doesn't have a check that the interface has.
+    MissingCheck { name: RStr<'static> },
+    /// The checks were declared with different attributes.
+    MismatchedKind {
+        name: RStr<'static>,
+        expected: DeclaredCheckKind,
+        found: DeclaredCheckKind,
+    },
+    /// The constants of a `check_const` aren't equal.
+    UnequalConst {
+        name: RStr<'static>,
+        expected: Tag,
+        found: Tag,
+    },
+    /// The implementation of a `check_subset` doesn't have an element of the interface.
+    MissingElement { name: RStr<'static>, element: Tag },
+    /// The value of a `check_subset` or `check_ordered` isn't an Array or Set.
+    NotACollection {
+        name: RStr<'static>,
+        kind: DeclaredCheckKind,
+        value: Tag,
+    },
+    /// The interface of a `check_ordered` isn't a prefix of the implementation.
+    NotAPrefix {
+        name: RStr<'static>,
+        expected: Tag,
+        found: Tag,
+    },
+}
+
+impl Display for DeclaredCheckError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeclaredCheckError::MissingCheck { name } => {
+                write!(f, "The implementation has no `{}` check.", name)
+            }
+            DeclaredCheckError::MismatchedKind {
+                name,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Mismatched kind of the `{}` check.\nExpected:\n    {:?}\nFound:\n    {:?}",
+                name, expected, found,
+            ),
+            DeclaredCheckError::UnequalConst {
+                name,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Expected the `{}` constant to be:\n{}\nFound:\n{}",
+                name, expected, found,
+            ),
+            DeclaredCheckError::MissingElement { name, element } => write!(
+                f,
+                "Expected `{}` in the implementation to contain:\n{}",
+                name, element,
+            ),
+            DeclaredCheckError::NotACollection { name, kind, value } => write!(
+                f,
+                "Expected the value of the `{}` {:?} check to be an array or a set,found:\n{}",
+                name, kind, value,
+            ),
+            DeclaredCheckError::NotAPrefix {
+                name,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Expected `{}` in the implementation to start with:\n{}\nFound:\n{}",
+                name, expected, found,
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DeclaredCheckError {}
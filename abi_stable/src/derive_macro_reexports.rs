@@ -1,7 +1,7 @@
 pub use crate::{
     abi_stability::{
         const_generics::{ConstGeneric, ConstGenericErasureHack, ConstGenericVTableFor},
-        extra_checks::{ExtraChecks_MV, StoredExtraChecks},
+        extra_checks::{DeclaredCheck, DeclaredChecks, ExtraChecks_MV, StoredExtraChecks},
         get_static_equivalent::{GetStaticEquivalent, GetStaticEquivalent_},
         stable_abi_trait::{
             GetTypeLayoutCtor, PrefixStableAbi, StableAbi, EXTERN_FN_LAYOUT,
@@ -27,7 +27,10 @@ pub use crate::{
     reflection::ModReflMode,
     sabi_trait::vtable::{RObjectVtable, RObjectVtable_Ref},
     sabi_types::{Constructor, MovePtr, RMut, RRef, VersionStrings},
-    std_types::{utypeid::new_utypeid, RErr, RNone, ROk, ROption, RResult, RSlice, RSome},
+    std_types::{
+        utypeid::new_utypeid, LocalRBoxFuture, RBoxFuture, RErr, RNone, ROk, ROption, RResult,
+        RSlice, RSome,
+    },
     type_layout::{
         CompTLFields, CompTLFunction, DiscriminantRepr, FieldAccessor, GenericTLData,
         GenericTLEnum, GenericTLPrefixType, IsExhaustive, LifetimeIndex, MakeTLNonExhaustive,
@@ -35,6 +38,7 @@ pub use crate::{
         SharedVars, StartLen, TLDiscriminants, TLFunction, TLFunctions, TLNonExhaustive, Tag,
         TypeLayout, _private_MonoTypeLayoutDerive, _private_TypeLayoutDerive,
     },
+    type_layout::tagging::FromLiteral,
     type_level::{
         downcasting::TD_Opaque,
         impl_enum::{ImplFrom, Implemented, Unimplemented},
@@ -45,6 +49,7 @@ pub use crate::{
 pub use std::{
     convert::{identity, From},
     fmt::{Debug, Formatter, Result as FmtResult},
+    marker::PhantomData,
     mem::ManuallyDrop,
     option::Option,
     ptr::NonNull,
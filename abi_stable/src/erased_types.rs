@@ -29,6 +29,8 @@ pub(crate) mod type_info;
 
 pub(crate) mod iterator;
 
+pub(crate) mod stream;
+
 pub(crate) mod dyn_trait;
 
 #[macro_use]
@@ -43,7 +45,7 @@ pub use self::{
     dyn_trait::{DynTraitBound, GetVWInterface, UneraseError},
     traits::{
         DeserializeDyn, ImplType, InterfaceType, IteratorItem, IteratorItemOrDefault,
-        SerializeImplType, SerializeProxyType,
+        SerializeImplType, SerializeProxyType, StreamItem, StreamItemOrDefault,
     },
     type_info::TypeInfo,
     vtable::{InterfaceBound, VTableDT},
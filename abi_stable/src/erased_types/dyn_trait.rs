@@ -4,8 +4,10 @@ use std::{
     fmt::{self, Write as fmtWrite},
     io,
     mem::ManuallyDrop,
+    pin::Pin,
     ptr,
     rc::Rc,
+    task::{Context, Poll},
 };
 
 use serde::{de, ser, Deserialize, Deserializer};
@@ -19,7 +21,7 @@ use crate::{
     },
     prefix_type::PrefixRef,
     sabi_types::{MovePtr, RMut, RRef},
-    std_types::{RBox, RIoError, RStr, RVec},
+    std_types::{future::stream::Stream, RBox, RContext, RIoError, RStr, RVec},
     type_level::{
         downcasting::{TD_CanDowncast, TD_Opaque},
         impl_enum::{Implemented, Unimplemented},
@@ -35,7 +37,7 @@ use super::{
     trait_objects::*,
     traits::{DeserializeDyn, GetSerializeProxyType, InterfaceFor},
     vtable::{GetVtable, VTable_Ref},
-    IteratorItemOrDefault, *,
+    IteratorItemOrDefault, StreamItemOrDefault, *,
 };
 
 // #[cfg(test)]
@@ -124,6 +126,10 @@ mod priv_ {
     ///
     /// - std::error::Error
     ///
+    /// - futures_core::Stream:
+    /// the wrapped stream must be `Unpin`,
+    /// and the `Stream` impl requires the "futures" feature.
+    ///
     /// - Default: Can be called as an inherent method.
     ///
     /// - Eq
@@ -2704,6 +2710,41 @@ where
 
 //////////////////////////////////////////////////////////////////
 
+/// Without the "futures" feature,
+/// this stream can be polled by converting it into an `RBoxStream`.
+impl<'borr, P, I, Item, EV> Stream for DynTrait<'borr, P, I, EV>
+where
+    P: AsMutPtr,
+    I: StreamItemOrDefault<'borr, Item = Item>,
+    I: InterfaceBound<Stream = Implemented<trait_marker::Stream>>,
+    Item: 'borr,
+{
+    type Item = Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Item>> {
+        unsafe {
+            // The wrapped stream is required to be `Unpin`,
+            // so this doesn't need to be pinned either.
+            let this = self.get_unchecked_mut();
+            let poll_next = this.sabi_vtable().stream().poll_next;
+            let value = this.sabi_erased_mut();
+            RContext::from_context(cx, |cx| poll_next(value, cx))
+                .map(IntoReprRust::into_rust)
+                .into_poll()
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        unsafe {
+            let vtable = self.sabi_vtable();
+            let tuple = (vtable.stream().size_hint)(self.sabi_erased_ref()).into_rust();
+            (tuple.0, tuple.1.into_rust())
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////
+
 unsafe impl<'borr, P, I, EV> Send for DynTrait<'borr, P, I, EV>
 where
     P: Send + GetPointerKind,
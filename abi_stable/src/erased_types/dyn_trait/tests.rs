@@ -13,7 +13,7 @@ use serde_json;
 
 #[allow(unused_imports)]
 use crate::{
-    erased_types::{DynTrait, ImplType, InterfaceType, IteratorItem},
+    erased_types::{DynTrait, ImplType, InterfaceType, IteratorItem, StreamItem},
     impl_get_type_info,
     std_types::{RArc, RBox, RBoxError, RCow, RNone, ROption, RSome, RStr, RString},
     traits::IntoReprC,
@@ -966,4 +966,100 @@ mod borrowing {
             assert_eq!(&out[..8], &[1, 2, 3, 4, 8, 9, 10, 7][..]);
         }
     }
+
+    ////////////////
+
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(impl_InterfaceType(Send, Stream))]
+    struct StreamInterface;
+
+    impl<'a> StreamItem<'a> for StreamInterface {
+        type Item = &'a str;
+    }
+
+    /// A stream of lines,which is pending every other time it's polled.
+    struct Lines<'a> {
+        lines: std::str::Lines<'a>,
+        pending: bool,
+    }
+
+    impl<'a> Stream for Lines<'a> {
+        type Item = &'a str;
+
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<&'a str>> {
+            self.pending = !self.pending;
+            if self.pending {
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            } else {
+                Poll::Ready(self.lines.next())
+            }
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            self.lines.size_hint()
+        }
+    }
+
+    fn stream_from_lines<'borr>(s: &'borr str) -> DynTrait<'borr, RBox<()>, StreamInterface> {
+        let lines = Lines {
+            lines: s.lines(),
+            pending: false,
+        };
+        DynTrait::from_borrowing_value(lines, StreamInterface)
+    }
+
+    fn collect_stream<S>(mut stream: S) -> Vec<S::Item>
+    where
+        S: Stream + Unpin,
+    {
+        let waker = crate::test_futures::noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        let mut list = Vec::new();
+        loop {
+            match Pin::new(&mut stream).poll_next(&mut cx) {
+                Poll::Ready(Some(x)) => list.push(x),
+                Poll::Ready(None) => return list,
+                Poll::Pending => {}
+            }
+        }
+    }
+
+    #[test]
+    fn stream_poll_next() {
+        let s = "line0\nline1\nline2".to_string();
+
+        assert_eq!(
+            collect_stream(stream_from_lines(&s)),
+            vec!["line0", "line1", "line2"],
+        );
+    }
+
+    #[test]
+    fn stream_size_hint() {
+        let s = "line0\nline1\nline2".to_string();
+
+        let wrapped = stream_from_lines(&s);
+        assert_eq!(Stream::size_hint(&wrapped), s.lines().size_hint());
+    }
+
+    #[test]
+    fn stream_into_rboxstream() {
+        use crate::std_types::RBoxStream;
+
+        let s = "line0\nline1\nline2".to_string();
+
+        let stream = RBoxStream::new(stream_from_lines(&s));
+        assert_eq!(collect_stream(stream), vec!["line0", "line1", "line2"]);
+
+        // Wrapping an `RBoxStream` in a `DynTrait`.
+        let stream = RBoxStream::new(Lines {
+            lines: s.lines(),
+            pending: false,
+        });
+        let wrapped = DynTrait::from_borrowing_value(stream, StreamInterface);
+        assert_eq!(collect_stream(wrapped), vec!["line0", "line1", "line2"]);
+    }
 }
@@ -110,3 +110,19 @@ impl<T> DEIteratorInterface<T> {
 impl<'a, T: 'a> IteratorItem<'a> for DEIteratorInterface<T> {
     type Item = T;
 }
+
+//////////////////////////////////////////////
+
+/// Implements `InterfaceType`, requiring `Send + Stream<Item = T>`
+#[repr(C)]
+#[derive(StableAbi)]
+#[sabi(impl_InterfaceType(Send, Stream))]
+pub struct StreamInterface<T>(PhantomData<T>);
+
+impl<T> StreamInterface<T> {
+    pub const NEW: Self = Self(PhantomData);
+}
+
+impl<'a, T: 'a> StreamItem<'a> for StreamInterface<T> {
+    type Item = T;
+}
@@ -0,0 +1,45 @@
+use crate::{
+    marker_type::{ErasedObject, NonOwningPhantom},
+    sabi_types::{RMut, RRef},
+    std_types::{
+        future::stream::{poll_next, size_hint, Stream},
+        RContext, ROption, RPoll, Tuple2,
+    },
+    utils::Transmuter,
+};
+
+///////////////////////////////////////////////////////////////////////////////////
+
+#[repr(C)]
+#[derive(StableAbi)]
+pub struct StreamFns<Item> {
+    pub(super) poll_next:
+        unsafe extern "C" fn(RMut<'_, ErasedObject>, RContext<'_>) -> RPoll<ROption<Item>>,
+    pub(super) size_hint:
+        unsafe extern "C" fn(RRef<'_, ErasedObject>) -> Tuple2<usize, ROption<usize>>,
+}
+
+impl<Item> Copy for StreamFns<Item> {}
+impl<Item> Clone for StreamFns<Item> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////
+
+pub struct MakeStreamFns<S>(NonOwningPhantom<S>);
+
+impl<S> MakeStreamFns<S>
+where
+    // Requiring `Unpin` because `DynTrait` can be converted back into
+    // the pointer to the stream,which would allow moving it after it's been pinned.
+    S: Stream + Unpin,
+{
+    const STREAM: StreamFns<S::Item> = StreamFns {
+        poll_next: poll_next::<S>,
+        size_hint: size_hint::<S>,
+    };
+
+    pub(super) const NEW: StreamFns<()> = unsafe { Transmuter { from: Self::STREAM }.to };
+}
@@ -157,6 +157,8 @@ declare_InterfaceType! {
     ///     // type IoBufRead = Unimplemented<trait_marker::IoBufRead>;
     ///
     ///     // type Error = Unimplemented<trait_marker::Error>;
+    ///
+    ///     // type Stream = Unimplemented<trait_marker::Stream>;
     /// }
     /// */
     ///
@@ -212,6 +214,8 @@ declare_InterfaceType! {
         type IoBufRead;
 
         type Error;
+
+        type Stream;
     ]
 
 
@@ -370,6 +374,47 @@ impl<'borr, I> IteratorItemOrDefaultHelper<'borr, Unimplemented<trait_marker::It
     type Item = ();
 }
 
+/////////////////////////////////////////////////////////////////////
+
+/// The way to specify the expected `Stream::Item` type for an `InterfaceType`.
+pub trait StreamItem<'a>: InterfaceType {
+    /// The stream item type.
+    type Item;
+}
+
+/// Gets the expected `Stream::Item` type for an `InterfaceType`,
+/// defaulting to `()` if it doesn't require `Stream` to be implemented.
+///
+/// Used by `DynTrait`'s vtable to give its stream methods a defaulted return type.
+pub trait StreamItemOrDefault<'borr>: InterfaceType {
+    /// The stream item type.
+    type Item;
+}
+
+impl<'borr, I, Item> StreamItemOrDefault<'borr> for I
+where
+    I: InterfaceType,
+    I: StreamItemOrDefaultHelper<'borr, <I as InterfaceType>::Stream, Item = Item>,
+{
+    type Item = Item;
+}
+
+#[doc(hidden)]
+pub trait StreamItemOrDefaultHelper<'borr, ImplIsRequired> {
+    type Item;
+}
+
+impl<'borr, I, Item> StreamItemOrDefaultHelper<'borr, Implemented<trait_marker::Stream>> for I
+where
+    I: StreamItem<'borr, Item = Item>,
+{
+    type Item = Item;
+}
+
+impl<'borr, I> StreamItemOrDefaultHelper<'borr, Unimplemented<trait_marker::Stream>> for I {
+    type Item = ();
+}
+
 //////////////////////////////////////////////////////////////////
 
 pub use self::interface_for::InterfaceFor;
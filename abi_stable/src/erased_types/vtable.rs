@@ -9,7 +9,11 @@ use std::{
 use super::{
     c_functions::*,
     iterator::{DoubleEndedIteratorFns, IteratorFns, MakeDoubleEndedIteratorFns, MakeIteratorFns},
-    traits::{GetSerializeProxyType, InterfaceFor, IteratorItemOrDefault, SerializeImplType},
+    stream::{MakeStreamFns, StreamFns},
+    traits::{
+        GetSerializeProxyType, InterfaceFor, IteratorItemOrDefault, SerializeImplType,
+        StreamItemOrDefault,
+    },
     *,
 };
 
@@ -18,7 +22,7 @@ use crate::{
     pointer_trait::{AsPtr, CanTransmuteElement, GetPointerKind},
     prefix_type::{panic_on_missing_fieldname, PrefixTypeTrait, WithMetadata},
     sabi_types::{RMut, RRef},
-    std_types::{RIoError, RNone, RSeekFrom, RSome},
+    std_types::{future::stream::Stream, RIoError, RNone, RSeekFrom, RSome},
     type_level::{
         impl_enum::{Implementability, Implemented, Unimplemented},
         trait_marker,
@@ -134,6 +138,8 @@ macro_rules! declare_meta_vtable {
             prefix_bound="I:InterfaceBound",
             bound="I:IteratorItemOrDefault<'borr>",
             bound="<I as IteratorItemOrDefault<'borr>>::Item:StableAbi",
+            bound="I:StreamItemOrDefault<'borr>",
+            bound="<I as StreamItemOrDefault<'borr>>::Item:StableAbi",
             bound="I: GetSerializeProxyType<'borr>",
             bound="<I as GetSerializeProxyType<'borr>>::ProxyType:StableAbi",
             // bound="I:for<'s> GetSerializeProxyType<'s>",
@@ -200,6 +206,21 @@ macro_rules! declare_meta_vtable {
                 }
             }
 
+            pub fn stream(
+                &self
+            )->StreamFns< <I as StreamItemOrDefault<'borr>>::Item >
+            where
+                $interf:InterfaceBound<Stream=Implemented<trait_marker::Stream>>,
+                $interf:StreamItemOrDefault<'borr>,
+            {
+                unsafe{
+                    std::mem::transmute::<
+                        StreamFns< () >,
+                        StreamFns< <I as StreamItemOrDefault<'borr>>::Item >
+                    >( self.erased_stream() )
+                }
+            }
+
             pub fn serialize<'s>(&self)->UnerasedSerializeFn<'s,I>
             where
                 I:InterfaceBound<Serialize=Implemented<trait_marker::Serialize>>,
@@ -755,6 +776,25 @@ declare_meta_vtable! {
             io_Seek_seek::<T>
         }
     ]
+    [
+        #[sabi(
+            unsafe_change_type=
+            "ROption<StreamFns< <I as StreamItemOrDefault<'borr>>::Item >>"
+        )]
+        #[sabi(accessible_if="<I as InterfaceBound>::Stream")]
+        erased_stream:StreamFns< () >;
+        priv _stream;
+        option=ROption,RSome,RNone;
+        field_index=field_index_for__stream;
+
+        impl[] VtableFieldValue<Stream>
+        where [
+            T:Stream+Unpin,
+            I:StreamItemOrDefault<'borr,Item=<T as Stream>::Item>,
+        ]{
+            MakeStreamFns::<T>::NEW
+        }
+    ]
 }
 
 //////////////
@@ -95,9 +95,36 @@ Enables impls which require using const generics,
 including implementing StableAbi for arrays of all lengths,
 requires Rust Rust 1.51.0 or higher.
 
+- "rust_1_75":
+Enables the tests of `#[sabi_trait]` traits with async methods,
+requires Rust 1.75.0 or higher.
+(async methods themselves only require that Rust version to be used,not the feature)
+
 - "rust_latest_stable":
 Enables the "rust_1_*" features for all the stable releases.
 
+These are crate features to manually enable optional crates:
+
+- "dyn_call":
+    Depends on `libffi` and `serde_json`,
+    allowing the functions of a module to be called by name with json arguments,
+    in `abi_stable::reflection::dyn_call`.
+
+- "ed25519":
+    Depends on `ed25519-compact`,
+    enabling `abi_stable::library::Ed25519Verifier`,
+    which verifies the signatures of libraries before they're loaded.
+
+- "futures":
+    Depends on `futures-core`,
+    implementing `futures_core::Stream` for `abi_stable::std_types::RBoxStream`,
+    and allowing `DynTrait` to wrap `Stream`s.
+
+- "tracing":
+    Depends on `tracing`,
+    emitting spans for each phase of loading a library,
+    the same phases that `abi_stable::library::LoadTimings` measures.
+
 # Glossary
 
 `interface crate`:the crate that declares the public functions, types, and traits that
@@ -254,6 +281,9 @@ mod test_macros;
 #[macro_use]
 pub mod test_utils;
 
+#[cfg(test)]
+mod test_futures;
+
 #[cfg(test)]
 mod misc_tests;
 
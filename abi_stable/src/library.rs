@@ -20,11 +20,27 @@
 //!
 //! All steps can return errors.
 //!
+//! # Reading metadata without loading
+//!
+//! Loading a library runs its static constructors,
+//! so to inspect an ELF library without loading it,
+//! [`lib_metadata_from_path`] reads its name,version,and abi_stable version
+//! from the section that `#[export_root_module]` emits,
+//! as well as the layout of its root module,
+//! if it was serialized by a build script with [`write_serialized_layout`].
+//!
+//! [`LibHeader::manifest`] reads the manifest declared with
+//! `#[export_root_module(manifest(...))]` (display name,description,authors,etc),
+//! which requires loading the library,but not initializing its root module.
+//!
 //! [`RawLibrary`]: ./struct.RawLibrary.html
 //! [`AbiHeaderRef`]: ./struct.AbiHeaderRef.html
 //! [`RootModule`]: ./trait.RootModule.html
 //! [`RootModule::initialize`]: ./trait.RootModule.html#method.initialization
 //! [`&'static LibHeader`]: ./struct.LibHeader.html
+//! [`lib_metadata_from_path`]: ./fn.lib_metadata_from_path.html
+//! [`write_serialized_layout`]: ./fn.write_serialized_layout.html
+//! [`LibHeader::manifest`]: ./struct.LibHeader.html#method.manifest
 
 use std::{
     convert::Infallible,
@@ -48,30 +64,49 @@ use crate::{
     type_layout::TypeLayout,
 };
 
+mod capabilities;
 pub mod c_abi_testing;
 pub mod development_utils;
 mod errors;
 mod lib_header;
+mod lib_manifest;
+mod lib_metadata;
+mod load_timings;
 
 #[cfg(test)]
 mod library_tests;
 
 mod raw_library;
 mod root_mod_trait;
+mod verification;
 
 #[doc(no_inline)]
 pub use self::c_abi_testing::{CAbiTestingFns, C_ABI_TESTING_FNS};
 
 pub use self::{
+    capabilities::{
+        granted_capabilities, lib_capabilities_from_raw_library, Capability, LibCapabilities,
+    },
     errors::{IntoRootModuleResult, LibraryError, RootModuleError},
     lib_header::{AbiHeader, AbiHeaderRef, LibHeader},
+    lib_manifest::{lib_manifest_from_raw_library, LibManifest},
+    lib_metadata::{
+        lib_metadata_from_bytes, lib_metadata_from_path, serialize_layout, write_serialized_layout,
+        LibMetadata, LibMetadataError, LibMetadataSection, LIB_LAYOUT_SECTION,
+        LIB_METADATA_SECTION, LIB_METADATA_STRING_CAPACITY, SERIALIZED_LAYOUT_FILE,
+    },
+    load_timings::{LoadPhase, LoadTimings},
     raw_library::RawLibrary,
     root_mod_trait::{
         abi_header_from_path, abi_header_from_raw_library, lib_header_from_path,
         lib_header_from_raw_library, ErasedRootModuleConsts, RootModule, RootModuleConsts,
     },
+    verification::VerifyLibrary,
 };
 
+#[cfg(feature = "ed25519")]
+pub use self::verification::{Ed25519Verifier, Ed25519VerifyError};
+
 ///////////////////////////////////////////////////////////////////////////////
 
 /// What naming convention to expect when loading a library from a directory.
@@ -97,6 +132,71 @@ pub enum LibraryPath<'a> {
 
 //////////////////////////////////////////////////////////////////////
 
+/// Optional checks done when loading a root module with
+/// [`RootModule::load_from_with_options`].
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use abi_stable::{
+///     library::{Capability, LibraryPath, LoadOptions, RootModule},
+///     for_examples::Module_Ref,
+/// };
+///
+/// use std::path::Path;
+///
+/// let granted = [Capability::FILESYSTEM, Capability::new("host:database")];
+///
+/// let options = LoadOptions::new().granted_capabilities(&granted);
+///
+/// let path = LibraryPath::FullPath(Path::new("plugins/libplugin.so"));
+/// let module = Module_Ref::load_from_with_options(path, options).unwrap();
+/// ```
+///
+/// [`RootModule::load_from_with_options`]:
+/// ./trait.RootModule.html#method.load_from_with_options
+#[derive(Copy, Clone, Default)]
+pub struct LoadOptions<'a> {
+    verifier: Option<&'a dyn VerifyLibrary>,
+    granted_capabilities: Option<&'a [Capability]>,
+}
+
+impl<'a> LoadOptions<'a> {
+    /// Constructs a `LoadOptions` which doesn't do any optional checks.
+    pub const fn new() -> Self {
+        Self {
+            verifier: None,
+            granted_capabilities: None,
+        }
+    }
+
+    /// Checks that the library is trusted by `verifier` before loading it,
+    /// returning a `LibraryError::UnverifiedLibrary` error if it's not.
+    pub fn verifier(mut self, verifier: &'a dyn VerifyLibrary) -> Self {
+        self.verifier = Some(verifier);
+        self
+    }
+
+    /// Refuses to initialize libraries that request capabilities not in `granted`,
+    /// returning a `LibraryError::UngrantedCapabilities` error.
+    ///
+    /// Libraries compiled with versions of abi_stable that don't declare
+    /// capabilities are refused with a `LibraryError::GetSymbolError`.
+    ///
+    /// The capabilities are read from the loaded library after its abi_stable version
+    /// and the layout of its root module are checked to be compatible,
+    /// and before the root module is initialized,
+    /// so its static constructors (if any) have already run when it's refused.
+    /// A refused library stays loaded,
+    /// later loads of the same root module check its capabilities again.
+    pub fn granted_capabilities(mut self, granted: &'a [Capability]) -> Self {
+        self.granted_capabilities = Some(granted);
+        self
+    }
+}
+
+//////////////////////////////////////////////////////////////////////
+
 /// Whether the ABI of a root module is checked.
 #[repr(u8)]
 #[derive(Debug, Copy, Clone, StableAbi)]
@@ -0,0 +1,205 @@
+//! Capabilities that a library declares it needs,which the host can grant or refuse.
+//!
+//! A library declares the capabilities it needs with the
+//! `capabilities(...)` parameter of the
+//! [`#[export_root_module]`](../attr.export_root_module.html) attribute,
+//! and the host grants capabilities with [`LoadOptions::granted_capabilities`].
+//!
+//! Capabilities are a declarative permission model,not a sandbox:
+//! libraries run native code in the host process,
+//! so they are trusted to only do what they declared.
+//!
+//! [`LoadOptions::granted_capabilities`]:
+//! ./struct.LoadOptions.html#method.granted_capabilities
+
+use super::{LibraryError, RawLibrary};
+
+use crate::{
+    sabi_types::LateStaticRef,
+    std_types::{RSlice, RStr, RVec},
+    utils::leak_value,
+};
+
+use abi_stable_shared::mangled_lib_capabilities_name;
+
+use std::{
+    fmt::{self, Display},
+    path::Path,
+};
+
+#[cfg(all(test, not(feature = "only_new_tests")))]
+mod tests;
+
+/// Something that a library needs to do,
+/// like accessing the filesystem,the network,or a service provided by the host.
+///
+/// # Example
+///
+/// ```rust
+/// use abi_stable::library::Capability;
+///
+/// // A service provided by the host application.
+/// const DATABASE: Capability = Capability::new("host:database");
+///
+/// assert_eq!(Capability::FILESYSTEM.name(), "filesystem");
+/// assert_eq!(DATABASE.name(), "host:database");
+/// assert_ne!(DATABASE, Capability::NETWORK);
+/// ```
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, StableAbi)]
+pub struct Capability(RStr<'static>);
+
+impl Capability {
+    /// Accessing the filesystem.
+    pub const FILESYSTEM: Self = Self::new("filesystem");
+    /// Accessing the network.
+    pub const NETWORK: Self = Self::new("network");
+    /// Spawning threads.
+    pub const THREADS: Self = Self::new("threads");
+
+    /// Constructs a `Capability` from its name.
+    ///
+    /// Services provided by the host can use any name that the host and
+    /// library agree on.
+    pub const fn new(name: &'static str) -> Self {
+        Self(RStr::from_str(name))
+    }
+
+    /// The name of this capability.
+    pub fn name(&self) -> &'static str {
+        self.0.as_str()
+    }
+}
+
+impl Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(self.name(), f)
+    }
+}
+
+//////////////////////////////////////////////////////////////////////
+
+/// The capabilities that a library requests,
+/// declared by the [`#[export_root_module]`](../attr.export_root_module.html) attribute.
+///
+/// This can be read before the root module is initialized,
+/// with [`lib_capabilities_from_raw_library`].
+///
+/// This is exported as a separate static,
+/// instead of being part of the `LibHeader` or `RootModuleConsts`,
+/// because changing their layout would make libraries compiled with
+/// earlier 0.10 versions of abi_stable incompatible.
+///
+/// [`lib_capabilities_from_raw_library`]: ./fn.lib_capabilities_from_raw_library.html
+#[repr(C)]
+#[derive(StableAbi)]
+pub struct LibCapabilities {
+    requested: RSlice<'static, Capability>,
+    grant: extern "C" fn(RSlice<'static, Capability>),
+}
+
+impl LibCapabilities {
+    /// Constructs a `LibCapabilities` which requests the `requested` capabilities.
+    pub const fn new(requested: RSlice<'static, Capability>) -> Self {
+        Self {
+            requested,
+            grant: grant_capabilities,
+        }
+    }
+
+    /// The capabilities that the library requests.
+    pub fn requested(&self) -> RSlice<'static, Capability> {
+        self.requested
+    }
+
+    /// Checks that every requested capability is in `granted`.
+    ///
+    /// This only reads the requested capabilities,without calling any code in the library.
+    ///
+    /// # Errors
+    ///
+    /// This returns a `LibraryError::UngrantedCapabilities` if any requested capability
+    /// is not in `granted`.
+    pub(super) fn check_granted(
+        &self,
+        library: &Path,
+        granted: &[Capability],
+    ) -> Result<(), LibraryError> {
+        let ungranted = self
+            .requested
+            .iter()
+            .filter(|cap| !granted.contains(cap))
+            .cloned()
+            .collect::<RVec<Capability>>();
+
+        if !ungranted.is_empty() {
+            return Err(LibraryError::UngrantedCapabilities {
+                library: library.to_owned(),
+                ungranted,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Passes the requested capabilities to the library,
+    /// so that it can query them with [`granted_capabilities`].
+    ///
+    /// This calls code in the library,
+    /// so it must only be called after the library was checked to be compatible,
+    /// and its requested capabilities were checked with `check_granted`.
+    ///
+    /// [`granted_capabilities`]: ./fn.granted_capabilities.html
+    pub(super) fn grant(&self) {
+        (self.grant)(self.requested);
+    }
+}
+
+static GRANTED: LateStaticRef<&'static RSlice<'static, Capability>> = LateStaticRef::new();
+
+extern "C" fn grant_capabilities(granted: RSlice<'static, Capability>) {
+    GRANTED.init(|| leak_value(granted));
+}
+
+/// Gets the capabilities that the host granted to the library this is called in.
+///
+/// This returns `None` if the library was loaded without checking capabilities,
+/// or if this is called from the executable.
+///
+/// The granted capabilities are the ones the library requested,
+/// the host refuses to load libraries that request capabilities it didn't grant.
+///
+/// # Example
+///
+/// ```rust
+/// use abi_stable::library::{granted_capabilities, Capability};
+///
+/// fn can_use_network() -> bool {
+///     granted_capabilities().map_or(false, |caps| caps.contains(&Capability::NETWORK))
+/// }
+///
+/// // Capabilities are only granted to dynamic libraries.
+/// assert!(!can_use_network());
+/// ```
+pub fn granted_capabilities() -> Option<RSlice<'static, Capability>> {
+    GRANTED.get().cloned()
+}
+
+/// Gets the capabilities that the library requests.
+///
+/// # Errors
+///
+/// This returns a `LibraryError::GetSymbolError` if the library doesn't
+/// export its capabilities,
+/// because it wasn't compiled with a version of abi_stable that declares them.
+///
+/// # Safety
+///
+/// The `LibCapabilities` is implicitly tied to the lifetime of the library,
+/// it will contain dangling `'static` references if the library is dropped before it does.
+pub unsafe fn lib_capabilities_from_raw_library(
+    raw_library: &RawLibrary,
+) -> Result<&'static LibCapabilities, LibraryError> {
+    let mangled = format!("{}\0", mangled_lib_capabilities_name());
+    Ok(*raw_library.get::<&'static LibCapabilities>(mangled.as_bytes())?)
+}
@@ -0,0 +1,50 @@
+use super::*;
+
+static REQUESTED: LibCapabilities = LibCapabilities::new(rslice![
+    Capability::FILESYSTEM,
+    Capability::new("host:database")
+]);
+
+#[test]
+fn checking_granted_capabilities() {
+    let library = Path::new("libplugin.so");
+
+    let err = REQUESTED
+        .check_granted(library, &[Capability::FILESYSTEM, Capability::NETWORK])
+        .err()
+        .unwrap();
+    match &err {
+        LibraryError::UngrantedCapabilities {
+            library: err_library,
+            ungranted,
+        } => {
+            assert_eq!(err_library, library);
+            assert_eq!(&ungranted[..], &[Capability::new("host:database")]);
+        }
+        _ => panic!("unexpected error: {}", err),
+    }
+    let message = err.to_string();
+    assert!(message.contains("libplugin.so"), "{}", message);
+    assert!(message.contains("\thost:database\n"), "{}", message);
+    assert!(!message.contains("filesystem"), "{}", message);
+
+    // Nothing is granted when any capability is refused.
+    assert_eq!(granted_capabilities(), None);
+
+    REQUESTED
+        .check_granted(
+            library,
+            &[
+                Capability::THREADS,
+                Capability::new("host:database"),
+                Capability::FILESYSTEM,
+            ],
+        )
+        .unwrap();
+
+    // Checking the capabilities doesn't pass them to the library.
+    assert_eq!(granted_capabilities(), None);
+
+    REQUESTED.grant();
+    assert_eq!(granted_capabilities(), Some(REQUESTED.requested()));
+}
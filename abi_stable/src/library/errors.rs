@@ -1,4 +1,4 @@
-use super::{lib_header::AbiHeader, root_mod_trait::RootModule};
+use super::{capabilities::Capability, lib_header::AbiHeader, root_mod_trait::RootModule};
 
 use crate::{
     sabi_types::{ParseVersionError, VersionNumber, VersionStrings},
@@ -54,6 +54,15 @@ pub enum LibraryError {
         expected: RBoxError,
         found: RBoxError,
     },
+    /// When the library was rejected by the `VerifyLibrary` passed to
+    /// `RootModule::load_from_verified`,before it was loaded.
+    UnverifiedLibrary { path: PathBuf, err: RBoxError },
+    /// When the library requested capabilities that weren't granted by the
+    /// `LoadOptions` passed to `RootModule::load_from_with_options`.
+    UngrantedCapabilities {
+        library: PathBuf,
+        ungranted: RVec<Capability>,
+    },
     /// There could have been 0 or more errors in the function.
     Many(RVec<Self>),
 }
@@ -129,6 +138,23 @@ impl Display for LibraryError {
                     expected=expected,
                 }
             }
+            LibraryError::UnverifiedLibrary { path, err } => writeln!(
+                f,
+                "Refused to load the library at:\n\t{}\nbecause it could not be verified:\n\t{}",
+                path.display(),
+                err
+            ),
+            LibraryError::UngrantedCapabilities { library, ungranted } => {
+                writeln!(
+                    f,
+                    "The library at:\n\t{}\nrequested capabilities that weren't granted:",
+                    library.display(),
+                )?;
+                for capability in ungranted {
+                    writeln!(f, "\t{}", capability)?;
+                }
+                Ok(())
+            }
             LibraryError::Many(list) => {
                 for e in list {
                     Display::fmt(e, f)?;
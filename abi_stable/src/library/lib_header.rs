@@ -77,6 +77,27 @@ impl LibHeader {
         self.root_mod_consts.layout().into_option()
     }
 
+    /// Gets the manifest that the library declares with
+    /// `#[export_root_module(manifest(...))]`,
+    /// without initializing the root module.
+    ///
+    /// The manifest is exported as a separate static
+    /// (since changing the layout of `LibHeader` would make libraries compiled with
+    /// earlier 0.10 versions of abi_stable incompatible),
+    /// which is looked up when the `LibHeader` is read from the library,
+    /// by [`lib_header_from_path`],[`lib_header_from_raw_library`],
+    /// or the `RootModule::load_*` functions.
+    ///
+    /// This returns None if this `LibHeader` wasn't read from a library that way,
+    /// or if the library doesn't export a manifest,
+    /// because it was compiled with a version of abi_stable that doesn't export them.
+    ///
+    /// [`lib_header_from_path`]: ./fn.lib_header_from_path.html
+    /// [`lib_header_from_raw_library`]: ./fn.lib_header_from_raw_library.html
+    pub fn manifest(&self) -> Option<&'static LibManifest> {
+        lib_manifest::get_manifest(self)
+    }
+
     pub(super) fn initialize_library_globals(&self, globals: &'static Globals) {
         (self.init_globals_with.0)(globals);
     }
@@ -140,8 +161,26 @@ impl LibHeader {
     where
         M: RootModule,
     {
+        self.init_root_module_timed::<M>().map(|(module, _)| module)
+    }
+
+    /// Checks that the library is compatible, returning the root module on success,
+    /// along with how long checking the layout and running the root module loader took.
+    ///
+    /// This is [`init_root_module`](#method.init_root_module),
+    /// with the same warnings and errors,
+    /// that also measures the time spent in each phase.
+    pub fn init_root_module_timed<M>(&self) -> Result<(M, LoadTimings), LibraryError>
+    where
+        M: RootModule,
+    {
+        let mut timings = LoadTimings::new();
         self.check_version::<M>()?;
-        self.check_layout::<M>()
+        timings.time(LoadPhase::LayoutCheck, || self.ensure_layout::<M>())?;
+        let module = timings
+            .time(LoadPhase::Loader, || unsafe { self.unchecked_layout() })
+            .map_err(RootModuleError::into_library_error::<M>)?;
+        Ok((module, timings))
     }
 
     /// Checks that the version number of the library is compatible,
@@ -371,6 +410,15 @@ impl AbiHeader {
         }
         value::ABI_HEADER
     };
+
+    pub(super) const fn from_parts(magic_string: [u8; 32], abi_major: u32, abi_minor: u32) -> Self {
+        Self {
+            magic_string,
+            abi_major,
+            abi_minor,
+            _priv: (),
+        }
+    }
 }
 
 impl AbiHeader {
@@ -401,6 +449,14 @@ impl AbiHeaderRef {
     /// - `LibraryError::InvalidCAbi`:
     /// If the C abi used by the library is not compatible.
     pub fn upgrade(self) -> Result<&'static LibHeader, LibraryError> {
+        self.upgrade_timed(&mut LoadTimings::new())
+    }
+
+    /// `upgrade`,adding the time spent in each phase to `timings`.
+    pub(super) fn upgrade_timed(
+        self,
+        timings: &mut LoadTimings,
+    ) -> Result<&'static LibHeader, LibraryError> {
         if !self.is_valid() {
             return Err(LibraryError::InvalidAbiHeader(*self));
         }
@@ -408,11 +464,15 @@ impl AbiHeaderRef {
         let lib_header: &'static LibHeader = unsafe { self.0.transmute_into_ref() };
 
         let c_abi_testing_fns = lib_header.root_mod_consts().c_abi_testing_fns();
-        crate::library::c_abi_testing::run_tests(c_abi_testing_fns)?;
+        timings.time(LoadPhase::CAbiTests, || {
+            crate::library::c_abi_testing::run_tests(c_abi_testing_fns)
+        })?;
 
-        let globals = globals::initialized_globals();
+        timings.time(LoadPhase::Header, || {
+            let globals = globals::initialized_globals();
 
-        lib_header.initialize_library_globals(globals);
+            lib_header.initialize_library_globals(globals);
+        });
 
         Ok(lib_header)
     }
@@ -0,0 +1,203 @@
+//! Descriptive metadata that a library embeds about itself,
+//! readable without initializing its root module.
+
+use super::{LibHeader, LibraryError, RawLibrary};
+
+use crate::{
+    sabi_types::LateStaticRef,
+    std_types::{ROption, RSlice, RStr, Tuple2},
+    utils::leak_value,
+};
+
+use abi_stable_shared::mangled_lib_manifest_name;
+
+use std::{
+    collections::HashMap,
+    sync::{Mutex, PoisonError},
+};
+
+#[cfg(all(test, not(feature = "only_new_tests")))]
+mod tests;
+
+/// Descriptive metadata about a library,
+/// declared with the `manifest(...)` parameter of the
+/// [`#[export_root_module]`](../attr.export_root_module.html) attribute.
+///
+/// This can be read without initializing the root module,
+/// with [`LibHeader::manifest`] or [`lib_manifest_from_raw_library`].
+///
+/// This is exported as a separate static,
+/// instead of being part of the `LibHeader` or `RootModuleConsts`,
+/// because changing their layout would make libraries compiled with
+/// earlier 0.10 versions of abi_stable incompatible.
+///
+/// # Example
+///
+/// ```rust
+/// use abi_stable::library::LibManifest;
+///
+/// static MANIFEST: LibManifest = LibManifest::new()
+///     .with_display_name("Text operations")
+///     .with_license("MIT OR Apache-2.0")
+///     .with_authors(abi_stable::rslice![abi_stable::rstr!("Jane Doe")]);
+///
+/// assert_eq!(MANIFEST.display_name(), Some("Text operations"));
+/// assert_eq!(MANIFEST.description(), None);
+/// assert_eq!(MANIFEST.license(), Some("MIT OR Apache-2.0"));
+/// assert_eq!(MANIFEST.authors().collect::<Vec<_>>(), ["Jane Doe"]);
+/// ```
+///
+/// [`LibHeader::manifest`]: ./struct.LibHeader.html#method.manifest
+/// [`lib_manifest_from_raw_library`]: ./fn.lib_manifest_from_raw_library.html
+#[repr(C)]
+#[derive(Debug, Copy, Clone, StableAbi)]
+pub struct LibManifest {
+    display_name: ROption<RStr<'static>>,
+    description: ROption<RStr<'static>>,
+    authors: RSlice<'static, RStr<'static>>,
+    license: ROption<RStr<'static>>,
+    homepage: ROption<RStr<'static>>,
+    custom: RSlice<'static, Tuple2<RStr<'static>, RStr<'static>>>,
+}
+
+impl LibManifest {
+    /// Constructs an empty `LibManifest`.
+    pub const fn new() -> Self {
+        Self {
+            display_name: ROption::RNone,
+            description: ROption::RNone,
+            authors: RSlice::EMPTY,
+            license: ROption::RNone,
+            homepage: ROption::RNone,
+            custom: RSlice::EMPTY,
+        }
+    }
+
+    /// Sets the human readable name of the library.
+    pub const fn with_display_name(mut self, display_name: &'static str) -> Self {
+        self.display_name = ROption::RSome(RStr::from_str(display_name));
+        self
+    }
+
+    /// Sets the description of the library.
+    pub const fn with_description(mut self, description: &'static str) -> Self {
+        self.description = ROption::RSome(RStr::from_str(description));
+        self
+    }
+
+    /// Sets the authors of the library.
+    pub const fn with_authors(mut self, authors: RSlice<'static, RStr<'static>>) -> Self {
+        self.authors = authors;
+        self
+    }
+
+    /// Sets the license of the library.
+    pub const fn with_license(mut self, license: &'static str) -> Self {
+        self.license = ROption::RSome(RStr::from_str(license));
+        self
+    }
+
+    /// Sets the url of the homepage of the library.
+    pub const fn with_homepage(mut self, homepage: &'static str) -> Self {
+        self.homepage = ROption::RSome(RStr::from_str(homepage));
+        self
+    }
+
+    /// Sets the custom key-value pairs of the library.
+    pub const fn with_custom(
+        mut self,
+        custom: RSlice<'static, Tuple2<RStr<'static>, RStr<'static>>>,
+    ) -> Self {
+        self.custom = custom;
+        self
+    }
+
+    /// The human readable name of the library.
+    pub fn display_name(&self) -> Option<&'static str> {
+        self.display_name.map(|x| x.as_str()).into_option()
+    }
+
+    /// The description of the library.
+    pub fn description(&self) -> Option<&'static str> {
+        self.description.map(|x| x.as_str()).into_option()
+    }
+
+    /// The authors of the library.
+    pub fn authors(&self) -> impl Iterator<Item = &'static str> + Clone {
+        self.authors.as_slice().iter().map(|x| x.as_str())
+    }
+
+    /// The license of the library.
+    pub fn license(&self) -> Option<&'static str> {
+        self.license.map(|x| x.as_str()).into_option()
+    }
+
+    /// The url of the homepage of the library.
+    pub fn homepage(&self) -> Option<&'static str> {
+        self.homepage.map(|x| x.as_str()).into_option()
+    }
+
+    /// The custom key-value pairs of the library.
+    pub fn custom(&self) -> impl Iterator<Item = (&'static str, &'static str)> + Clone {
+        self.custom
+            .as_slice()
+            .iter()
+            .map(|Tuple2(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Gets the value of the `key` custom key.
+    pub fn get_custom(&self, key: &str) -> Option<&'static str> {
+        self.custom().find(|&(k, _)| k == key).map(|(_, v)| v)
+    }
+}
+
+impl Default for LibManifest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Gets the manifest of a library.
+///
+/// # Errors
+///
+/// This returns a `LibraryError::GetSymbolError` if the library doesn't
+/// export a manifest,
+/// because it wasn't compiled with a version of abi_stable that exports them.
+///
+/// # Safety
+///
+/// The `LibManifest` is implicitly tied to the lifetime of the library,
+/// it will contain dangling `'static` references if the library is dropped before it does.
+pub unsafe fn lib_manifest_from_raw_library(
+    raw_library: &RawLibrary,
+) -> Result<&'static LibManifest, LibraryError> {
+    let mangled = format!("{}\0", mangled_lib_manifest_name());
+    Ok(*raw_library.get::<&'static LibManifest>(mangled.as_bytes())?)
+}
+
+/// The manifests of the libraries whose `LibHeader` was read from a `RawLibrary`,
+/// keyed by the address of the `LibHeader`.
+static MANIFESTS: LateStaticRef<&Mutex<HashMap<usize, &'static LibManifest>>> =
+    LateStaticRef::new();
+
+fn manifests() -> &'static Mutex<HashMap<usize, &'static LibManifest>> {
+    MANIFESTS.init(|| leak_value(Mutex::new(HashMap::new())))
+}
+
+/// Sets the manifest that `LibHeader::manifest` returns for `header`.
+pub(super) fn set_manifest(header: &LibHeader, manifest: Option<&'static LibManifest>) {
+    let key = header as *const LibHeader as usize;
+    let mut manifests = manifests().lock().unwrap_or_else(PoisonError::into_inner);
+    match manifest {
+        Some(manifest) => manifests.insert(key, manifest),
+        None => manifests.remove(&key),
+    };
+}
+
+/// Gets the manifest that was set for `header` with `set_manifest`.
+pub(super) fn get_manifest(header: &LibHeader) -> Option<&'static LibManifest> {
+    let key = header as *const LibHeader as usize;
+    let manifests = manifests().lock().unwrap_or_else(PoisonError::into_inner);
+    manifests.get(&key).cloned()
+}
@@ -0,0 +1,73 @@
+use super::*;
+
+use crate::{
+    for_examples::Module,
+    prefix_type::PrefixTypeTrait,
+    std_types::{RNone, RStr},
+};
+
+static MANIFEST: LibManifest = LibManifest::new()
+    .with_display_name("Text operations")
+    .with_description("Operations on text")
+    .with_authors(rslice![rstr!("Jane Doe"), rstr!("John Doe")])
+    .with_license("MIT")
+    .with_homepage("https://example.com")
+    .with_custom(rslice![
+        Tuple2(rstr!("repository"), rstr!("https://example.com/repo")),
+        Tuple2(rstr!("category"), rstr!("text")),
+    ]);
+
+#[test]
+fn manifest_accessors() {
+    assert_eq!(MANIFEST.display_name(), Some("Text operations"));
+    assert_eq!(MANIFEST.description(), Some("Operations on text"));
+    assert_eq!(
+        MANIFEST.authors().collect::<Vec<_>>(),
+        ["Jane Doe", "John Doe"]
+    );
+    assert_eq!(MANIFEST.license(), Some("MIT"));
+    assert_eq!(MANIFEST.homepage(), Some("https://example.com"));
+    assert_eq!(
+        MANIFEST.custom().collect::<Vec<_>>(),
+        [
+            ("repository", "https://example.com/repo"),
+            ("category", "text")
+        ]
+    );
+    assert_eq!(MANIFEST.get_custom("category"), Some("text"));
+    assert_eq!(MANIFEST.get_custom("Category"), None);
+
+    let empty = LibManifest::default();
+    assert_eq!(empty.display_name(), None);
+    assert_eq!(empty.description(), None);
+    assert_eq!(empty.authors().count(), 0);
+    assert_eq!(empty.license(), None);
+    assert_eq!(empty.homepage(), None);
+    assert_eq!(empty.custom().count(), 0);
+}
+
+#[test]
+fn manifest_of_header() {
+    let module = Module {
+        first: RNone,
+        second: RStr::from("hello"),
+        third: 100,
+    }
+    .leak_into_prefix();
+    let header: &'static LibHeader = leak_value(LibHeader::from_module(module));
+    let other: &'static LibHeader = leak_value(LibHeader::from_module(module));
+
+    // Headers that weren't read from a library have no manifest.
+    assert!(header.manifest().is_none());
+
+    set_manifest(header, Some(&MANIFEST));
+    assert_eq!(
+        header.manifest().and_then(LibManifest::display_name),
+        Some("Text operations")
+    );
+    assert!(other.manifest().is_none());
+
+    // Reading the header from a library without a manifest removes it.
+    set_manifest(header, None);
+    assert!(header.manifest().is_none());
+}
@@ -0,0 +1,637 @@
+//! Metadata about a library that is stored in an ELF section,
+//! so that it can be read without loading the library.
+//!
+//! Loading a library with `dlopen` runs its static constructors,
+//! which is undesirable when all you want is to inspect it
+//! (eg: in a package manager,or in CI).
+//!
+//! The [`#[export_root_module]`](../attr.export_root_module.html) attribute
+//! places a [`LibMetadataSection`] in the `.sabi_metadata` section of ELF libraries,
+//! which can be read with [`lib_metadata_from_path`].
+//!
+//! # Serialized layout
+//!
+//! The type layout of the root module can't be stored in `.sabi_metadata`,
+//! because it is made of pointers (and function pointers to construct nested layouts)
+//! that can't be followed at compile-time.
+//!
+//! Instead,a build script can serialize the layout with [`write_serialized_layout`],
+//! and `#[export_root_module(serialized_layout)]` embeds the serialized layout
+//! in the `.sabi_layout` section,
+//! which [`lib_metadata_from_path`] reads into [`LibMetadata::serialized_layout`].
+//!
+//! # Example
+//!
+//! The `build.rs` file of the crate that exports the root module,
+//! which must have the crate that declares the root module as a build-dependency:
+//!
+//! ```rust,ignore
+//! use abi_stable::{library::write_serialized_layout, StableAbi};
+//!
+//! use example_interface::ExampleMod_Ref;
+//!
+//! fn main() {
+//!     write_serialized_layout(<ExampleMod_Ref as StableAbi>::LAYOUT).unwrap();
+//! }
+//! ```
+//!
+//! The crate that exports the root module:
+//!
+//! ```rust,ignore
+//! use abi_stable::{export_root_module, prefix_type::PrefixTypeTrait};
+//!
+//! use example_interface::{ExampleMod, ExampleMod_Ref};
+//!
+//! #[export_root_module(serialized_layout)]
+//! pub fn get_library() -> ExampleMod_Ref {
+//!     ExampleMod {}.leak_into_prefix()
+//! }
+//! ```
+//!
+//! [`write_serialized_layout`]: ./fn.write_serialized_layout.html
+//! [`lib_metadata_from_path`]: ./fn.lib_metadata_from_path.html
+//! [`LibMetadata::serialized_layout`]: ./struct.LibMetadata.html#structfield.serialized_layout
+
+use super::lib_header::AbiHeader;
+
+use crate::{reflection::type_graph::TypeGraph, type_layout::TypeLayout};
+
+use std::{
+    fmt::{self, Display, Write},
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+#[cfg(all(test, not(feature = "only_new_tests")))]
+mod tests;
+
+/// The name of the ELF section that [`LibMetadataSection`] is stored in.
+///
+/// [`LibMetadataSection`]: ./struct.LibMetadataSection.html
+pub const LIB_METADATA_SECTION: &str = ".sabi_metadata";
+
+/// The name of the ELF section that the serialized layout of the root module is stored in,
+/// when it's exported with `#[export_root_module(serialized_layout)]`.
+pub const LIB_LAYOUT_SECTION: &str = ".sabi_layout";
+
+/// The name of the file (in the `OUT_DIR` of the build script)
+/// that [`write_serialized_layout`] writes the serialized layout to.
+///
+/// [`write_serialized_layout`]: ./fn.write_serialized_layout.html
+pub const SERIALIZED_LAYOUT_FILE: &str = "sabi_serialized_layout.txt";
+
+const MAGIC: [u8; 8] = *b"sabimeta";
+
+const FORMAT_VERSION: u32 = 0;
+
+/// The maximum length (in bytes) of the strings stored in a `LibMetadataSection`.
+pub const LIB_METADATA_STRING_CAPACITY: usize = 254;
+
+/// The metadata of a library,as stored in its `.sabi_metadata` ELF section.
+///
+/// This type only contains byte arrays,
+/// so that it can be read from the file of the library without relocating it.
+///
+/// This is declared by the
+/// [`#[export_root_module]`](../attr.export_root_module.html) attribute,
+/// use [`lib_metadata_from_path`] to read it.
+///
+/// [`lib_metadata_from_path`]: ./fn.lib_metadata_from_path.html
+#[doc(hidden)]
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct LibMetadataSection {
+    magic: [u8; 8],
+    format_version: [u8; 4],
+    abi_magic_string: [u8; 32],
+    abi_major: [u8; 4],
+    abi_minor: [u8; 4],
+    layout_checked: u8,
+    base_name: SectionStr,
+    name: SectionStr,
+    version: SectionStr,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct SectionStr {
+    len: [u8; 2],
+    bytes: [u8; LIB_METADATA_STRING_CAPACITY],
+}
+
+impl LibMetadataSection {
+    /// Constructs the metadata of a library.
+    ///
+    /// This fails to compile if any of the strings is longer than
+    /// `LIB_METADATA_STRING_CAPACITY` bytes.
+    ///
+    /// Use the [`__lib_metadata_section`] macro to construct this
+    /// from the constants of a root module.
+    ///
+    /// [`__lib_metadata_section`]: ../macro.__lib_metadata_section.html
+    pub const fn new(base_name: &str, name: &str, version: &str, is_layout_checked: bool) -> Self {
+        let abi_header = AbiHeader::VALUE;
+        Self {
+            magic: MAGIC,
+            format_version: FORMAT_VERSION.to_le_bytes(),
+            abi_magic_string: abi_header.magic_string,
+            abi_major: abi_header.abi_major.to_le_bytes(),
+            abi_minor: abi_header.abi_minor.to_le_bytes(),
+            layout_checked: is_layout_checked as u8,
+            base_name: SectionStr::new(base_name),
+            name: SectionStr::new(name),
+            version: SectionStr::new(version),
+        }
+    }
+
+    #[cfg(test)]
+    fn as_bytes(&self) -> &[u8] {
+        // Safety: this type only contains byte arrays,so it has no padding.
+        unsafe {
+            std::slice::from_raw_parts(
+                self as *const Self as *const u8,
+                std::mem::size_of::<Self>(),
+            )
+        }
+    }
+}
+
+impl SectionStr {
+    const fn new(s: &str) -> Self {
+        let s = s.as_bytes();
+        let mut bytes = [0; LIB_METADATA_STRING_CAPACITY];
+        let mut i = 0;
+        // Indexing out of bounds here causes a compile-time error in consts.
+        while i < s.len() {
+            bytes[i] = s[i];
+            i += 1;
+        }
+        Self {
+            len: (s.len() as u16).to_le_bytes(),
+            bytes,
+        }
+    }
+}
+
+/// Constructs a `LibMetadataSection` from an `ErasedRootModuleConsts` constant.
+///
+/// This is a macro because `RStr::as_str` can't be called in const fns,
+/// while reading union fields is allowed in the initializers of constants.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __lib_metadata_section {
+    ($consts:expr) => {{
+        const __CONSTS: $crate::library::ErasedRootModuleConsts = $consts;
+
+        type __RStrToStr =
+            $crate::utils::Transmuter<$crate::std_types::RStr<'static>, &'static str>;
+
+        unsafe {
+            $crate::library::LibMetadataSection::new(
+                __RStrToStr {
+                    from: __CONSTS.base_name(),
+                }
+                .to,
+                __RStrToStr {
+                    from: __CONSTS.name(),
+                }
+                .to,
+                __RStrToStr {
+                    from: __CONSTS.version_strings().version,
+                }
+                .to,
+                match __CONSTS.layout() {
+                    $crate::library::IsLayoutChecked::Yes(_) => true,
+                    $crate::library::IsLayoutChecked::No => false,
+                },
+            )
+        }
+    }};
+}
+
+//////////////////////////////////////////////////////////////////////
+
+/// Serializes `layout`,and the layout of every type that it references,
+/// in the same text format that the `sabi_extract layout` command outputs.
+///
+/// # Example
+///
+/// ```rust
+/// use abi_stable::{library::serialize_layout, std_types::RString, StableAbi};
+///
+/// let serialized = serialize_layout(<RString as StableAbi>::LAYOUT);
+///
+/// assert!(serialized.contains("RString"));
+/// assert!(serialized.contains("RVec<u8>"));
+/// ```
+pub fn serialize_layout(layout: &'static TypeLayout) -> String {
+    let mut serialized = String::new();
+    for node in TypeGraph::from_type_layout(layout).nodes() {
+        writeln!(serialized, "{}", node.layout()).expect("BUG:writing to a String failed");
+    }
+    serialized
+}
+
+/// Serializes `layout` with [`serialize_layout`],
+/// writing it to the [`SERIALIZED_LAYOUT_FILE`] file in the `OUT_DIR` directory.
+///
+/// This is meant to be called from the build script of a crate that exports a root module
+/// with `#[export_root_module(serialized_layout)]`,
+/// passing the layout of the root module.
+///
+/// Because the layout is computed on the machine that runs the build script,
+/// the sizes and alignments in it are those of the host,
+/// which may be different from those of the target when cross-compiling.
+///
+/// # Errors
+///
+/// This returns an error if the `OUT_DIR` environment variable isn't set,
+/// or if the file couldn't be written.
+///
+/// [`serialize_layout`]: ./fn.serialize_layout.html
+/// [`SERIALIZED_LAYOUT_FILE`]: ./constant.SERIALIZED_LAYOUT_FILE.html
+pub fn write_serialized_layout(layout: &'static TypeLayout) -> io::Result<()> {
+    let out_dir = std::env::var_os("OUT_DIR").ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            "The OUT_DIR environment variable isn't set,\
+             `write_serialized_layout` must be called in a build script.",
+        )
+    })?;
+    fs::write(
+        Path::new(&out_dir).join(SERIALIZED_LAYOUT_FILE),
+        serialize_layout(layout),
+    )
+}
+
+//////////////////////////////////////////////////////////////////////
+
+/// The metadata of a library,read from its file without loading it.
+///
+/// This is constructed with [`lib_metadata_from_path`].
+///
+/// [`lib_metadata_from_path`]: ./fn.lib_metadata_from_path.html
+#[derive(Debug, Clone)]
+pub struct LibMetadata {
+    /// The abi_stable version the library was compiled with.
+    pub abi_header: AbiHeader,
+    /// The name of the dynamic library,which is the same on all platforms.
+    pub base_name: String,
+    /// The name of the library used in error messages.
+    pub name: String,
+    /// The version string of the library.
+    pub version: String,
+    /// Whether the layout of the root module is checked when the library is loaded.
+    pub is_layout_checked: bool,
+    /// The layout of the root module,serialized with [`serialize_layout`].
+    ///
+    /// This is `None` unless the root module was exported with
+    /// `#[export_root_module(serialized_layout)]`.
+    ///
+    /// [`serialize_layout`]: ./fn.serialize_layout.html
+    pub serialized_layout: Option<String>,
+}
+
+/// Reads the metadata of the abi_stable library at `path`,without loading it.
+///
+/// This only supports ELF libraries (eg: on Linux and the BSDs).
+///
+/// # Errors
+///
+/// This returns a [`LibMetadataError`] if the file can't be read,
+/// it's not an ELF file,
+/// or doesn't contain valid metadata in its `.sabi_metadata` section.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use abi_stable::library::lib_metadata_from_path;
+///
+/// use std::path::Path;
+///
+/// let metadata = lib_metadata_from_path(Path::new("target/debug/libplugin.so")).unwrap();
+///
+/// assert!(metadata.abi_header.is_valid());
+/// println!("{} {}", metadata.name, metadata.version);
+/// ```
+///
+/// [`LibMetadataError`]: ./enum.LibMetadataError.html
+pub fn lib_metadata_from_path(path: &Path) -> Result<LibMetadata, LibMetadataError> {
+    let file = std::fs::read(path).map_err(|err| LibMetadataError::Io {
+        path: path.to_owned(),
+        err,
+    })?;
+    lib_metadata_from_bytes(&file)
+}
+
+/// Reads the metadata of an abi_stable library from the contents of its file.
+///
+/// # Errors
+///
+/// This returns the same errors as [`lib_metadata_from_path`],
+/// except for `LibMetadataError::Io`.
+///
+/// [`lib_metadata_from_path`]: ./fn.lib_metadata_from_path.html
+pub fn lib_metadata_from_bytes(file: &[u8]) -> Result<LibMetadata, LibMetadataError> {
+    let elf = Elf::new(file)?;
+    let section = elf
+        .section(LIB_METADATA_SECTION)?
+        .ok_or(LibMetadataError::MissingSection)?;
+    let mut metadata = decode_section(section)?;
+
+    if let Some(layout) = elf.section(LIB_LAYOUT_SECTION)? {
+        let layout =
+            std::str::from_utf8(layout).map_err(|_| LibMetadataError::InvalidLayoutSection)?;
+        metadata.serialized_layout = Some(layout.to_string());
+    }
+
+    Ok(metadata)
+}
+
+fn decode_section(section: &[u8]) -> Result<LibMetadata, LibMetadataError> {
+    let mut reader = SectionReader { section };
+
+    if reader.bytes(MAGIC.len())? != MAGIC {
+        return Err(LibMetadataError::InvalidSection {
+            what: "magic number",
+        });
+    }
+    let version = reader.u32()?;
+    if version != FORMAT_VERSION {
+        return Err(LibMetadataError::UnsupportedFormat { version });
+    }
+
+    let mut magic_string = [0; 32];
+    magic_string.copy_from_slice(reader.bytes(32)?);
+    let abi_major = reader.u32()?;
+    let abi_minor = reader.u32()?;
+    let abi_header = AbiHeader::from_parts(magic_string, abi_major, abi_minor);
+
+    let is_layout_checked = match reader.bytes(1)?[0] {
+        0 => false,
+        1 => true,
+        _ => {
+            return Err(LibMetadataError::InvalidSection {
+                what: "layout flag",
+            })
+        }
+    };
+
+    Ok(LibMetadata {
+        abi_header,
+        base_name: reader.string("base name")?,
+        name: reader.string("name")?,
+        version: reader.string("version")?,
+        is_layout_checked,
+        serialized_layout: None,
+    })
+}
+
+struct SectionReader<'a> {
+    section: &'a [u8],
+}
+
+impl<'a> SectionReader<'a> {
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], LibMetadataError> {
+        if self.section.len() < len {
+            return Err(LibMetadataError::InvalidSection {
+                what: "section size",
+            });
+        }
+        let (ret, rem) = self.section.split_at(len);
+        self.section = rem;
+        Ok(ret)
+    }
+
+    fn u32(&mut self) -> Result<u32, LibMetadataError> {
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(self.bytes(4)?);
+        Ok(u32::from_le_bytes(bytes))
+    }
+
+    fn string(&mut self, what: &'static str) -> Result<String, LibMetadataError> {
+        let len = self.bytes(2)?;
+        let len = u16::from_le_bytes([len[0], len[1]]) as usize;
+        let bytes = self.bytes(LIB_METADATA_STRING_CAPACITY)?;
+        bytes
+            .get(..len)
+            .and_then(|s| std::str::from_utf8(s).ok())
+            .map(String::from)
+            .ok_or(LibMetadataError::InvalidSection { what })
+    }
+}
+
+//////////////////////////////////////////////////////////////////////
+
+/// A minimal reader of ELF section headers.
+struct Elf<'a> {
+    file: &'a [u8],
+    is_64: bool,
+    big_endian: bool,
+}
+
+const SHT_NOBITS: u64 = 8;
+const SHN_XINDEX: u64 = 0xFFFF;
+
+impl<'a> Elf<'a> {
+    fn new(file: &'a [u8]) -> Result<Self, LibMetadataError> {
+        if file.get(..4) != Some(b"\x7FELF") {
+            return Err(LibMetadataError::NotElf);
+        }
+        let is_64 = match file.get(4) {
+            Some(1) => false,
+            Some(2) => true,
+            _ => return Err(LibMetadataError::MalformedElf { what: "class" }),
+        };
+        let big_endian = match file.get(5) {
+            Some(1) => false,
+            Some(2) => true,
+            _ => return Err(LibMetadataError::MalformedElf { what: "endianness" }),
+        };
+        Ok(Self {
+            file,
+            is_64,
+            big_endian,
+        })
+    }
+
+    fn uint(&self, offset: u64, size: usize) -> Result<u64, LibMetadataError> {
+        let bytes = self.slice(offset, size as u64, "file size")?;
+        let fold = |acc: u64, b: &u8| (acc << 8) | u64::from(*b);
+        Ok(if self.big_endian {
+            bytes.iter().fold(0, fold)
+        } else {
+            bytes.iter().rev().fold(0, fold)
+        })
+    }
+
+    /// Reads a field that is 4 bytes in ELF32 and 8 bytes in ELF64.
+    fn word(&self, offset32: u64, offset64: u64) -> Result<u64, LibMetadataError> {
+        if self.is_64 {
+            self.uint(offset64, 8)
+        } else {
+            self.uint(offset32, 4)
+        }
+    }
+
+    fn slice(
+        &self,
+        offset: u64,
+        len: u64,
+        what: &'static str,
+    ) -> Result<&'a [u8], LibMetadataError> {
+        let range = offset
+            .checked_add(len)
+            .filter(|&end| end <= self.file.len() as u64)
+            .map(|end| offset as usize..end as usize)
+            .ok_or(LibMetadataError::MalformedElf { what })?;
+        Ok(&self.file[range])
+    }
+
+    /// Finds the contents of the section named `name`.
+    fn section(&self, name: &str) -> Result<Option<&'a [u8]>, LibMetadataError> {
+        let (sh_off, sh_entsize, sh_num, sh_strndx) = if self.is_64 {
+            (0x28, 0x3A, 0x3C, 0x3E)
+        } else {
+            (0x20, 0x2E, 0x30, 0x32)
+        };
+        let table = self.word(sh_off, sh_off)?;
+        let entry_size = self.uint(sh_entsize, 2)?;
+        let mut count = self.uint(sh_num, 2)?;
+        let mut names_index = self.uint(sh_strndx, 2)?;
+
+        if table == 0 {
+            return Ok(None);
+        }
+        if entry_size < if self.is_64 { 0x40 } else { 0x28 } {
+            return Err(LibMetadataError::MalformedElf {
+                what: "section header size",
+            });
+        }
+
+        let header = |index: u64| -> Result<SectionHeader, LibMetadataError> {
+            let at = index
+                .checked_mul(entry_size)
+                .and_then(|x| x.checked_add(table))
+                .ok_or(LibMetadataError::MalformedElf {
+                    what: "section header offset",
+                })?;
+            Ok(SectionHeader {
+                name: self.uint(at, 4)?,
+                type_: self.uint(at + 4, 4)?,
+                link: self.uint(at + if self.is_64 { 0x28 } else { 0x18 }, 4)?,
+                offset: self.word(at + 0x10, at + 0x18)?,
+                size: self.word(at + 0x14, at + 0x20)?,
+            })
+        };
+
+        // Files with too many sections store the real values in the first section header.
+        if count == 0 {
+            count = header(0)?.size;
+        }
+        if names_index == SHN_XINDEX {
+            names_index = header(0)?.link;
+        }
+
+        let names = header(names_index)?;
+        let names = self.slice(names.offset, names.size, "section names")?;
+
+        for index in 0..count {
+            let header = header(index)?;
+            let section_name = names
+                .get(header.name as usize..)
+                .and_then(|s| s.split(|&b| b == 0).next())
+                .ok_or(LibMetadataError::MalformedElf {
+                    what: "section name",
+                })?;
+            if section_name == name.as_bytes() {
+                if header.type_ == SHT_NOBITS {
+                    return Ok(Some(&[]));
+                }
+                return self
+                    .slice(header.offset, header.size, "section contents")
+                    .map(Some);
+            }
+        }
+        Ok(None)
+    }
+}
+
+struct SectionHeader {
+    name: u64,
+    type_: u64,
+    link: u64,
+    offset: u64,
+    size: u64,
+}
+
+//////////////////////////////////////////////////////////////////////
+
+/// The errors that can happen when reading the metadata of a library
+/// with [`lib_metadata_from_path`].
+///
+/// [`lib_metadata_from_path`]: ./fn.lib_metadata_from_path.html
+#[derive(Debug)]
+pub enum LibMetadataError {
+    /// When the file could not be read.
+    Io { path: PathBuf, err: io::Error },
+    /// When the file is not an ELF file.
+    NotElf,
+    /// When the ELF file is malformed,`what` describes the part that's malformed.
+    MalformedElf { what: &'static str },
+    /// When the file does not have a `.sabi_metadata` section,
+    /// either because it's not an abi_stable library,
+    /// or because it was compiled with a version of abi_stable that didn't emit it.
+    MissingSection,
+    /// When the `.sabi_metadata` section does not contain valid metadata.
+    InvalidSection { what: &'static str },
+    /// When the `.sabi_layout` section is not valid UTF-8.
+    InvalidLayoutSection,
+    /// When the metadata was written in a format that this version of abi_stable
+    /// doesn't know how to read.
+    UnsupportedFormat { version: u32 },
+}
+
+impl Display for LibMetadataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LibMetadataError::Io { path, err } => write!(
+                f,
+                "Could not read library at:\n\t{}\nbecause:\n\t{}",
+                path.display(),
+                err
+            ),
+            LibMetadataError::NotElf => f.write_str("The library is not an ELF file"),
+            LibMetadataError::MalformedElf { what } => {
+                write!(f, "The ELF file is malformed,invalid {}", what)
+            }
+            LibMetadataError::MissingSection => write!(
+                f,
+                "The library does not have a `{}` section",
+                LIB_METADATA_SECTION
+            ),
+            LibMetadataError::InvalidSection { what } => write!(
+                f,
+                "The `{}` section is malformed,invalid {}",
+                LIB_METADATA_SECTION, what
+            ),
+            LibMetadataError::InvalidLayoutSection => {
+                write!(f, "The `{}` section is not valid UTF-8", LIB_LAYOUT_SECTION)
+            }
+            LibMetadataError::UnsupportedFormat { version } => write!(
+                f,
+                "Unsupported version of the library metadata format:{}",
+                version
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LibMetadataError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LibMetadataError::Io { err, .. } => Some(err),
+            _ => None,
+        }
+    }
+}
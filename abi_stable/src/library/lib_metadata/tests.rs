@@ -0,0 +1,213 @@
+use super::*;
+
+use core_extensions::matches;
+
+use crate::{for_examples::Module_Ref, library::RootModule, StableAbi};
+
+static SECTION: LibMetadataSection = crate::__lib_metadata_section!(Module_Ref::CONSTANTS.erased());
+
+static SECTION_NO_LAYOUT: LibMetadataSection =
+    crate::__lib_metadata_section!(Module_Ref::CONSTANTS_NO_ABI_INFO.erased());
+
+/// Builds an ELF file with an empty section,a section names section,
+/// and a section for each `(name, contents)` pair.
+fn elf_file(is_64: bool, big_endian: bool, sections: &[(&str, &[u8])]) -> Vec<u8> {
+    let (header_size, entry_size) = if is_64 { (0x40, 0x40) } else { (0x34, 0x28) };
+
+    let mut names = b"\0.shstrtab\0".to_vec();
+    let mut headers = Vec::new();
+    let mut contents = Vec::new();
+    for &(name, section) in sections {
+        headers.push((names.len(), 1, contents.len(), section.len()));
+        names.extend_from_slice(name.as_bytes());
+        names.push(0);
+        contents.extend_from_slice(section);
+    }
+
+    let names_offset = header_size;
+    let contents_offset = names_offset + names.len();
+    let table_offset = contents_offset + contents.len();
+    let section_count = sections.len() + 2;
+
+    let mut file = vec![0u8; table_offset + entry_size * section_count];
+    file[..4].copy_from_slice(b"\x7FELF");
+    file[4] = if is_64 { 2 } else { 1 };
+    file[5] = if big_endian { 2 } else { 1 };
+
+    let mut write = |offset: usize, size: usize, value: usize| {
+        let bytes = (value as u64).to_le_bytes();
+        let field = &mut file[offset..offset + size];
+        field.copy_from_slice(&bytes[..size]);
+        if big_endian {
+            field.reverse();
+        }
+    };
+    let word = if is_64 { 8 } else { 4 };
+
+    if is_64 {
+        write(0x28, 8, table_offset);
+        write(0x3A, 2, entry_size);
+        write(0x3C, 2, section_count);
+        write(0x3E, 2, 1);
+    } else {
+        write(0x20, 4, table_offset);
+        write(0x2E, 2, entry_size);
+        write(0x30, 2, section_count);
+        write(0x32, 2, 1);
+    }
+
+    let headers = std::iter::once((1, 3, names_offset, names.len())).chain(
+        headers
+            .into_iter()
+            .map(|(name, type_, offset, size)| (name, type_, contents_offset + offset, size)),
+    );
+    for (i, (name, type_, offset, size)) in headers.enumerate() {
+        let at = table_offset + entry_size * (i + 1);
+        write(at, 4, name);
+        write(at + 4, 4, type_);
+        let (offset_at, size_at) = if is_64 { (0x18, 0x20) } else { (0x10, 0x14) };
+        write(at + offset_at, word, offset);
+        write(at + size_at, word, size);
+    }
+
+    file[names_offset..contents_offset].copy_from_slice(&names);
+    file[contents_offset..table_offset].copy_from_slice(&contents);
+    file
+}
+
+fn metadata_file(section: &LibMetadataSection) -> Vec<u8> {
+    elf_file(true, false, &[(LIB_METADATA_SECTION, section.as_bytes())])
+}
+
+#[test]
+fn reading_metadata() {
+    for &is_64 in &[false, true] {
+        for &big_endian in &[false, true] {
+            let file = elf_file(
+                is_64,
+                big_endian,
+                &[(LIB_METADATA_SECTION, SECTION.as_bytes())],
+            );
+            let metadata = lib_metadata_from_bytes(&file).unwrap();
+
+            assert!(metadata.abi_header.is_valid());
+            assert_eq!(metadata.base_name, "example_root_module");
+            assert_eq!(metadata.name, "example_root_module");
+            assert_eq!(metadata.version, env!("CARGO_PKG_VERSION"));
+            assert!(metadata.is_layout_checked);
+            assert_eq!(metadata.serialized_layout, None);
+        }
+    }
+
+    let metadata = lib_metadata_from_bytes(&metadata_file(&SECTION_NO_LAYOUT)).unwrap();
+    assert!(!metadata.is_layout_checked);
+}
+
+#[test]
+fn serialized_layout() {
+    let layout = serialize_layout(<Module_Ref as StableAbi>::LAYOUT);
+    assert!(layout.contains("Module"), "{}", layout);
+    assert!(layout.contains("type:RStr<'a>"), "{}", layout);
+
+    for &is_64 in &[false, true] {
+        for &big_endian in &[false, true] {
+            let file = elf_file(
+                is_64,
+                big_endian,
+                &[
+                    (LIB_METADATA_SECTION, SECTION.as_bytes()),
+                    (LIB_LAYOUT_SECTION, layout.as_bytes()),
+                ],
+            );
+            let metadata = lib_metadata_from_bytes(&file).unwrap();
+
+            assert_eq!(metadata.name, "example_root_module");
+            assert_eq!(metadata.serialized_layout.as_ref(), Some(&layout));
+        }
+    }
+
+    let file = elf_file(
+        true,
+        false,
+        &[
+            (LIB_METADATA_SECTION, SECTION.as_bytes()),
+            (LIB_LAYOUT_SECTION, b"\xFF\xFE"),
+        ],
+    );
+    assert!(matches!(
+        lib_metadata_from_bytes(&file),
+        Err(LibMetadataError::InvalidLayoutSection)
+    ));
+}
+
+#[test]
+fn invalid_files() {
+    assert!(matches!(
+        lib_metadata_from_bytes(b"MZ\x90\0"),
+        Err(LibMetadataError::NotElf)
+    ));
+
+    let file = elf_file(true, false, &[(".text", SECTION.as_bytes())]);
+    assert!(matches!(
+        lib_metadata_from_bytes(&file),
+        Err(LibMetadataError::MissingSection)
+    ));
+
+    let mut file = metadata_file(&SECTION);
+    file.truncate(file.len() - 0x20);
+    assert!(matches!(
+        lib_metadata_from_bytes(&file),
+        Err(LibMetadataError::MalformedElf { .. })
+    ));
+
+    assert!(matches!(
+        lib_metadata_from_path(Path::new("/this/file/does/not/exist.so")),
+        Err(LibMetadataError::Io { .. })
+    ));
+}
+
+#[test]
+fn invalid_sections() {
+    let section = |f: &dyn Fn(&mut LibMetadataSection)| {
+        let mut section = SECTION;
+        f(&mut section);
+        lib_metadata_from_bytes(&metadata_file(&section))
+    };
+
+    assert!(matches!(
+        section(&|s| s.magic = *b"notmagic"),
+        Err(LibMetadataError::InvalidSection {
+            what: "magic number"
+        })
+    ));
+    assert!(matches!(
+        section(&|s| s.format_version = 1000u32.to_le_bytes()),
+        Err(LibMetadataError::UnsupportedFormat { version: 1000 })
+    ));
+    assert!(matches!(
+        section(&|s| s.layout_checked = 2),
+        Err(LibMetadataError::InvalidSection {
+            what: "layout flag"
+        })
+    ));
+    assert!(matches!(
+        section(&|s| s.name.len = 1000u16.to_le_bytes()),
+        Err(LibMetadataError::InvalidSection { what: "name" })
+    ));
+    assert!(matches!(
+        section(&|s| s.version.bytes[0] = 0xFF),
+        Err(LibMetadataError::InvalidSection { what: "version" })
+    ));
+
+    let file = elf_file(
+        true,
+        false,
+        &[(LIB_METADATA_SECTION, &SECTION.as_bytes()[..100])],
+    );
+    assert!(matches!(
+        lib_metadata_from_bytes(&file),
+        Err(LibMetadataError::InvalidSection {
+            what: "section size"
+        })
+    ));
+}
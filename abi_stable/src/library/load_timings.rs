@@ -0,0 +1,141 @@
+//! Measuring how long each phase of loading a root module takes.
+
+use std::{
+    fmt::{self, Display},
+    time::{Duration, Instant},
+};
+
+#[cfg(all(test, not(feature = "only_new_tests")))]
+mod tests;
+
+/// A phase of loading a root module,measured in [`LoadTimings`].
+///
+/// When the `tracing` feature is enabled,
+/// every phase is also recorded as a `"load_phase"` span,
+/// with the name of the phase in the `phase` field.
+///
+/// [`LoadTimings`]: ./struct.LoadTimings.html
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LoadPhase {
+    /// Verifying the library with the `VerifyLibrary` in the `LoadOptions`.
+    Verify,
+    /// Opening the dynamic library,which runs its static constructors.
+    Open,
+    /// Reading the header of the library and initializing its globals.
+    Header,
+    /// Running the tests of the C ABI, with `c_abi_testing::run_tests`.
+    CAbiTests,
+    /// Checking that the layout of the root module is compatible.
+    LayoutCheck,
+    /// Running the root module loader and `RootModule::initialization`.
+    Loader,
+}
+
+impl LoadPhase {
+    /// All the phases,in the order that they happen.
+    pub const ALL: [LoadPhase; 6] = [
+        LoadPhase::Verify,
+        LoadPhase::Open,
+        LoadPhase::Header,
+        LoadPhase::CAbiTests,
+        LoadPhase::LayoutCheck,
+        LoadPhase::Loader,
+    ];
+
+    /// The name of this phase,as used in the `tracing` spans.
+    pub fn name(self) -> &'static str {
+        match self {
+            LoadPhase::Verify => "verify",
+            LoadPhase::Open => "open",
+            LoadPhase::Header => "header",
+            LoadPhase::CAbiTests => "c_abi_tests",
+            LoadPhase::LayoutCheck => "layout_check",
+            LoadPhase::Loader => "loader",
+        }
+    }
+}
+
+impl Display for LoadPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// How long each phase of loading a root module took.
+///
+/// This is returned by [`RootModule::load_from_timed`] and
+/// [`LibHeader::init_root_module_timed`].
+///
+/// Phases that didn't run (eg:because the library was already open) take no time.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use abi_stable::{
+///     library::{LibraryPath, LoadOptions, LoadPhase, RootModule},
+///     for_examples::Module_Ref,
+/// };
+///
+/// use std::path::Path;
+///
+/// let path = LibraryPath::FullPath(Path::new("plugins/libplugin.so"));
+/// let (module, timings) = Module_Ref::load_from_timed(path, LoadOptions::new()).unwrap();
+///
+/// println!("loaded in {:?}:\n{}", timings.total(), timings);
+/// println!("the layout check took {:?}", timings.get(LoadPhase::LayoutCheck));
+/// ```
+///
+/// [`RootModule::load_from_timed`]: ./trait.RootModule.html#method.load_from_timed
+/// [`LibHeader::init_root_module_timed`]:
+/// ./struct.LibHeader.html#method.init_root_module_timed
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct LoadTimings {
+    durations: [Duration; 6],
+}
+
+impl LoadTimings {
+    /// Constructs a `LoadTimings` where every phase took no time.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The time spent in `phase`.
+    pub fn get(&self, phase: LoadPhase) -> Duration {
+        self.durations[phase as usize]
+    }
+
+    /// The time spent in all the phases.
+    pub fn total(&self) -> Duration {
+        self.durations.iter().sum()
+    }
+
+    /// Iterates over the phases,with the time spent in them.
+    pub fn iter(&self) -> impl Iterator<Item = (LoadPhase, Duration)> + '_ {
+        LoadPhase::ALL
+            .iter()
+            .map(move |&phase| (phase, self.get(phase)))
+    }
+
+    /// Runs `f`,adding the time it took to `phase`.
+    pub(crate) fn time<T, F>(&mut self, phase: LoadPhase, f: F) -> T
+    where
+        F: FnOnce() -> T,
+    {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("load_phase", phase = phase.name()).entered();
+
+        let start = Instant::now();
+        let ret = f();
+        self.durations[phase as usize] += start.elapsed();
+        ret
+    }
+}
+
+impl Display for LoadTimings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (phase, duration) in self.iter() {
+            writeln!(f, "{}: {:?}", phase, duration)?;
+        }
+        write!(f, "total: {:?}", self.total())
+    }
+}
@@ -0,0 +1,65 @@
+use super::*;
+
+use crate::{
+    for_examples::{Module, Module_Ref},
+    library::LibHeader,
+    prefix_type::PrefixTypeTrait,
+    std_types::{RNone, RStr},
+};
+
+#[test]
+fn timing_phases() {
+    let mut timings = LoadTimings::new();
+    assert_eq!(timings.total(), Duration::from_secs(0));
+
+    let ret = timings.time(LoadPhase::Open, || {
+        std::thread::sleep(Duration::from_millis(2));
+        100
+    });
+    assert_eq!(ret, 100);
+    timings.time(LoadPhase::Open, || {
+        std::thread::sleep(Duration::from_millis(1))
+    });
+    timings.time(LoadPhase::Loader, || ());
+
+    assert!(timings.get(LoadPhase::Open) >= Duration::from_millis(3));
+    assert_eq!(timings.get(LoadPhase::Verify), Duration::from_secs(0));
+    assert_eq!(
+        timings.total(),
+        timings.get(LoadPhase::Open) + timings.get(LoadPhase::Loader)
+    );
+
+    let phases = timings.iter().map(|(phase, _)| phase).collect::<Vec<_>>();
+    assert_eq!(phases, LoadPhase::ALL);
+
+    let formatted = timings.to_string();
+    let lines = formatted.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), LoadPhase::ALL.len() + 1);
+    assert!(lines[1].starts_with("open: "), "{}", formatted);
+    assert!(lines[3].starts_with("c_abi_tests: "), "{}", formatted);
+    assert!(lines[6].starts_with("total: "), "{}", formatted);
+}
+
+#[test]
+fn timed_root_module_init() {
+    let module = Module {
+        first: RNone,
+        second: RStr::from("hello"),
+        third: 100,
+    }
+    .leak_into_prefix();
+    let header = LibHeader::from_module(module);
+
+    let start = std::time::Instant::now();
+    let (loaded, timings) = header.init_root_module_timed::<Module_Ref>().unwrap();
+    let elapsed = start.elapsed();
+
+    assert_eq!(loaded.second(), "hello");
+    assert_eq!(timings.get(LoadPhase::Open), Duration::from_secs(0));
+    assert_eq!(timings.get(LoadPhase::Header), Duration::from_secs(0));
+    assert_eq!(
+        timings.total(),
+        timings.iter().map(|(_, time)| time).sum::<Duration>()
+    );
+    assert!(timings.total() <= elapsed);
+}
@@ -30,6 +30,11 @@ impl RawLibrary {
         directory.join(name)
     }
 
+    /// The path that the library was loaded from.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
     /// Loads the dynamic library at the `full_path` path.
     pub fn load_at(full_path: &Path) -> Result<Self, LibraryError> {
         // safety: not my problem if libraries have problematic static initializers
@@ -1,5 +1,7 @@
 use super::*;
 
+use super::verification::load_verified_library;
+
 use crate::{marker_type::NonOwningPhantom, prefix_type::PrefixRefTrait, utils::leak_value};
 
 /// The root module of a dynamic library,
@@ -165,30 +167,76 @@ pub trait RootModule: Sized + StableAbi + PrefixRefTrait + 'static {
     /// If the root module initializer returned an error or panicked.
     ///
     fn load_from(where_: LibraryPath<'_>) -> Result<Self, LibraryError> {
-        let statics = Self::root_module_statics();
-        statics.root_mod.try_init(|| {
-            let lib = statics.raw_lib.try_init(|| -> Result<_, LibraryError> {
-                let raw_library = load_raw_library::<Self>(where_)?;
-
-                // if the library isn't leaked
-                // it would cause any use of the module to be a use after free.
-                //
-                // By leaking the library
-                // this allows the root module loader to do anything that'd prevent
-                // sound library unloading.
-                Ok(leak_value(raw_library))
-            })?;
-            let items = unsafe { lib_header_from_raw_library(lib)? };
-
-            items.ensure_layout::<Self>()?;
-
-            // safety: the layout was checked in the code above,
-            unsafe {
-                items
-                    .init_root_module_with_unchecked_layout::<Self>()?
-                    .initialization()
-            }
-        })
+        Self::load_from_with_options(where_, LoadOptions::new())
+    }
+
+    /// Loads this module from the path specified by `where_`,
+    /// doing the optional checks in `options`,
+    /// first loading the dynamic library if it wasn't already loaded.
+    ///
+    /// Once the root module is loaded,
+    /// this will return the already loaded root module,
+    /// without doing the checks again.
+    ///
+    /// Warnings are detailed in [`load_from`](#method.load_from).
+    ///
+    /// # Errors
+    ///
+    /// This returns the same errors as [`load_from`](#method.load_from),
+    /// as well as the errors of the checks enabled in `options`,
+    /// detailed in [`LoadOptions`].
+    ///
+    /// [`LoadOptions`]: ./struct.LoadOptions.html
+    fn load_from_with_options(
+        where_: LibraryPath<'_>,
+        options: LoadOptions<'_>,
+    ) -> Result<Self, LibraryError> {
+        load_root_module::<Self>(where_, options, &mut LoadTimings::new())
+    }
+
+    /// Loads this module from the path specified by `where_`,
+    /// doing the optional checks in `options`,
+    /// returning how long each phase of loading the library took.
+    ///
+    /// If the root module was already loaded,
+    /// this returns it along with a `LoadTimings` where every phase took no time.
+    ///
+    /// Warnings and errors are detailed in
+    /// [`load_from_with_options`](#method.load_from_with_options).
+    fn load_from_timed(
+        where_: LibraryPath<'_>,
+        options: LoadOptions<'_>,
+    ) -> Result<(Self, LoadTimings), LibraryError> {
+        let mut timings = LoadTimings::new();
+        let module = load_root_module::<Self>(where_, options, &mut timings)?;
+        Ok((module, timings))
+    }
+
+    /// Loads this module from the path specified by `where_`,
+    /// first checking that the dynamic library is trusted by `verifier`,
+    /// then loading it if it wasn't already loaded.
+    ///
+    /// Once the root module is loaded,
+    /// this will return the already loaded root module,
+    /// without verifying the library again.
+    ///
+    /// Warnings are detailed in [`load_from`](#method.load_from).
+    ///
+    /// # Errors
+    ///
+    /// This returns the same errors as [`load_from`](#method.load_from),
+    /// as well as `LibraryError::UnverifiedLibrary`
+    /// if the library could not be read,`verifier` rejected it,
+    /// or the library was replaced after it was verified
+    /// (explained in [`VerifyLibrary`]).
+    ///
+    /// [`VerifyLibrary`]: ./trait.VerifyLibrary.html
+    ///
+    fn load_from_verified(
+        where_: LibraryPath<'_>,
+        verifier: &dyn VerifyLibrary,
+    ) -> Result<Self, LibraryError> {
+        Self::load_from_with_options(where_, LoadOptions::new().verifier(verifier))
     }
 
     /// Loads this module from the directory specified by `where_`,
@@ -226,8 +274,82 @@ pub trait RootModule: Sized + StableAbi + PrefixRefTrait + 'static {
     }
 }
 
-/// Loads the raw library at `where_`
-fn load_raw_library<M>(where_: LibraryPath<'_>) -> Result<RawLibrary, LibraryError>
+/// Loads the root module from the library at `where_`,
+/// doing the optional checks in `options`,
+/// adding the time spent in each phase to `timings`.
+fn load_root_module<M>(
+    where_: LibraryPath<'_>,
+    options: LoadOptions<'_>,
+    timings: &mut LoadTimings,
+) -> Result<M, LibraryError>
+where
+    M: RootModule,
+{
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!("load_root_module", library = M::NAME).entered();
+
+    let statics = M::root_module_statics();
+    statics.root_mod.try_init(|| {
+        let lib = statics.raw_lib.try_init(|| -> Result<_, LibraryError> {
+            let raw_library = load_raw_library::<M>(where_, options.verifier, timings)?;
+
+            // if the library isn't leaked
+            // it would cause any use of the module to be a use after free.
+            //
+            // By leaking the library
+            // this allows the root module loader to do anything that'd prevent
+            // sound library unloading.
+            Ok(leak_value(raw_library))
+        })?;
+        let items = timings
+            .time(LoadPhase::Header, || unsafe {
+                abi_header_from_raw_library(lib)
+            })?
+            .upgrade_timed(timings)?;
+        lib_manifest::set_manifest(items, unsafe { lib_manifest_from_raw_library(lib).ok() });
+
+        timings.time(LoadPhase::LayoutCheck, || items.ensure_layout::<M>())?;
+
+        // Checked after the library is known to be compatible,
+        // since this reads a static of the library.
+        let capabilities = check_capabilities(lib, options.granted_capabilities)?;
+
+        // safety: the layout was checked in the code above,
+        timings.time(LoadPhase::Loader, || unsafe {
+            if let Some(capabilities) = capabilities {
+                capabilities.grant();
+            }
+            items
+                .init_root_module_with_unchecked_layout::<M>()?
+                .initialization()
+        })
+    })
+}
+
+/// Checks that every capability that `lib` requests was granted,
+/// if `granted` is `Some`,
+/// returning the capabilities that the library requests.
+fn check_capabilities(
+    lib: &RawLibrary,
+    granted: Option<&[Capability]>,
+) -> Result<Option<&'static LibCapabilities>, LibraryError> {
+    match granted {
+        Some(granted) => {
+            let capabilities = unsafe { lib_capabilities_from_raw_library(lib)? };
+            capabilities.check_granted(lib.path(), granted)?;
+            Ok(Some(capabilities))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Loads the raw library at `where_`,
+/// verifying it with `verifier` before loading it.
+fn load_raw_library<M>(
+    where_: LibraryPath<'_>,
+    verifier: Option<&dyn VerifyLibrary>,
+    timings: &mut LoadTimings,
+) -> Result<RawLibrary, LibraryError>
 where
     M: RootModule,
 {
@@ -235,7 +357,10 @@ where
         LibraryPath::Directory(directory) => M::get_library_path(directory),
         LibraryPath::FullPath(full_path) => full_path.to_owned(),
     };
-    RawLibrary::load_at(&path)
+    match verifier {
+        Some(verifier) => load_verified_library(&path, verifier, timings),
+        None => timings.time(LoadPhase::Open, || RawLibrary::load_at(&path)),
+    }
 }
 
 /// Gets the LibHeader of a library.
@@ -259,7 +384,9 @@ where
 pub unsafe fn lib_header_from_raw_library(
     raw_library: &RawLibrary,
 ) -> Result<&'static LibHeader, LibraryError> {
-    abi_header_from_raw_library(raw_library)?.upgrade()
+    let header = abi_header_from_raw_library(raw_library)?.upgrade()?;
+    lib_manifest::set_manifest(header, lib_manifest_from_raw_library(raw_library).ok());
+    Ok(header)
 }
 
 /// Gets the AbiHeaderRef of a library.
@@ -292,6 +419,9 @@ pub unsafe fn abi_header_from_raw_library(
 /// if you need to do this without leaking you'll need to use
 /// `lib_header_from_raw_library` instead.
 ///
+/// The manifest that the library declares with `#[export_root_module(manifest(...))]`
+/// can be read with [`LibHeader::manifest`].
+///
 /// # Errors
 ///
 /// This will return these errors:
@@ -305,7 +435,7 @@ pub unsafe fn abi_header_from_raw_library(
 /// - `LibraryError::InvalidAbiHeader`:
 /// If the abi_stable version used by the library is not compatible.
 ///
-///
+/// [`LibHeader::manifest`]: ./struct.LibHeader.html#method.manifest
 pub fn lib_header_from_path(path: &Path) -> Result<&'static LibHeader, LibraryError> {
     let raw_lib = RawLibrary::load_at(path)?;
 
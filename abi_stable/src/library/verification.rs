@@ -0,0 +1,269 @@
+//! Verifying that dynamic libraries are trusted before loading them.
+
+use super::{LibraryError, LoadPhase, LoadTimings, RawLibrary};
+
+use crate::std_types::RBoxError;
+
+use std::{fs::File, io::Read, path::Path};
+
+#[cfg(feature = "ed25519")]
+use std::{
+    fmt::{self, Display},
+    io,
+    path::PathBuf,
+};
+
+#[cfg(all(test, not(feature = "only_new_tests")))]
+mod tests;
+
+/// Checks that a dynamic library is trusted before it's loaded,
+/// used by [`RootModule::load_from_verified`].
+///
+/// This is implemented for closures with the same signature as `verify_library`.
+///
+/// # Warning
+///
+/// The library is loaded from its path after it's verified.
+/// On unix systems this checks that the path still refers to the file that was verified,
+/// but the file can still be modified or replaced in between that check
+/// and the library being loaded,
+/// so make sure that only trusted users can write to the directory that contains it,
+/// and to the directories of the libraries that it depends on.
+///
+/// # Example
+///
+/// ```rust
+/// use abi_stable::{
+///     library::{LibraryError, LibraryPath, RootModule},
+///     std_types::RBoxError,
+///     for_examples::Module_Ref,
+/// };
+///
+/// use std::path::Path;
+///
+/// let verifier = |_: &Path, contents: &[u8]| -> Result<(), RBoxError> {
+///     if contents.starts_with(b"\x7FELF") {
+///         Ok(())
+///     } else {
+///         Err(RBoxError::from_fmt(&"not an ELF file"))
+///     }
+/// };
+///
+/// let path = std::env::current_dir().unwrap().join("Cargo.toml");
+/// let err = Module_Ref::load_from_verified(LibraryPath::FullPath(&path), &verifier)
+///     .err()
+///     .unwrap();
+///
+/// assert!(matches!(err, LibraryError::UnverifiedLibrary { .. }));
+/// ```
+///
+/// [`RootModule::load_from_verified`]:
+/// ./trait.RootModule.html#method.load_from_verified
+pub trait VerifyLibrary {
+    /// Checks whether the dynamic library at `path`,with `contents` as its contents,
+    /// is trusted.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error describing why the library isn't trusted.
+    fn verify_library(&self, path: &Path, contents: &[u8]) -> Result<(), RBoxError>;
+}
+
+impl<F> VerifyLibrary for F
+where
+    F: Fn(&Path, &[u8]) -> Result<(), RBoxError>,
+{
+    fn verify_library(&self, path: &Path, contents: &[u8]) -> Result<(), RBoxError> {
+        self(path, contents)
+    }
+}
+
+/// Reads the library at `path`,verifies it with `verifier`,
+/// then loads it if it wasn't replaced in between.
+pub(super) fn load_verified_library(
+    path: &Path,
+    verifier: &dyn VerifyLibrary,
+    timings: &mut LoadTimings,
+) -> Result<RawLibrary, LibraryError> {
+    timings
+        .time(LoadPhase::Verify, || -> Result<(), RBoxError> {
+            let mut file = File::open(path).map_err(RBoxError::new)?;
+            let mut contents = Vec::new();
+            file.read_to_end(&mut contents).map_err(RBoxError::new)?;
+            verifier.verify_library(path, &contents)?;
+            check_not_replaced(path, &file)
+        })
+        .map_err(|err| LibraryError::UnverifiedLibrary {
+            path: path.to_owned(),
+            err,
+        })?;
+
+    timings.time(LoadPhase::Open, || RawLibrary::load_at(path))
+}
+
+/// Checks that `path` still refers to `file`,the file that was verified.
+#[cfg(unix)]
+fn check_not_replaced(path: &Path, file: &File) -> Result<(), RBoxError> {
+    use std::os::unix::fs::MetadataExt;
+
+    let verified = file.metadata().map_err(RBoxError::new)?;
+    let current = std::fs::metadata(path).map_err(RBoxError::new)?;
+    if (verified.dev(), verified.ino()) == (current.dev(), current.ino()) {
+        Ok(())
+    } else {
+        Err(RBoxError::from_fmt(
+            &"the library was replaced after it was verified",
+        ))
+    }
+}
+
+#[cfg(not(unix))]
+fn check_not_replaced(_: &Path, _: &File) -> Result<(), RBoxError> {
+    Ok(())
+}
+
+//////////////////////////////////////////////////////////////////////
+
+/// Verifies that dynamic libraries were signed with a trusted Ed25519 key.
+///
+/// The signature of a library is expected in a file next to it,
+/// with `.sig` appended to its name (eg: `libplugin.so.sig`),
+/// containing the 64 byte Ed25519 signature of the contents of the library.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use abi_stable::{
+///     library::{Ed25519Verifier, LibraryPath, RootModule},
+///     for_examples::Module_Ref,
+/// };
+///
+/// use std::path::Path;
+///
+/// const VENDOR_KEY: [u8; 32] = [
+///     0x1a, 0x5b, 0x2c, 0x9d, 0x3e, 0x7f, 0x40, 0x81, 0x12, 0x53, 0x64, 0xa5, 0x36, 0x77, 0x08, 0x49,
+///     0x9a, 0xdb, 0x2c, 0x6d, 0xfe, 0x3f, 0x80, 0xc1, 0x52, 0x13, 0xa4, 0xe5, 0x76, 0x37, 0xc8, 0x09,
+/// ];
+///
+/// let verifier = Ed25519Verifier::new(vec![VENDOR_KEY]);
+///
+/// let path = Path::new("plugins/libplugin.so");
+/// let module = Module_Ref::load_from_verified(LibraryPath::FullPath(path), &verifier).unwrap();
+/// ```
+#[cfg(feature = "ed25519")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "ed25519")))]
+#[derive(Debug, Clone)]
+pub struct Ed25519Verifier {
+    trusted_keys: Vec<ed25519_compact::PublicKey>,
+}
+
+#[cfg(feature = "ed25519")]
+impl Ed25519Verifier {
+    /// Constructs an `Ed25519Verifier` which trusts libraries signed with any of
+    /// the `trusted_keys` public keys.
+    pub fn new<I>(trusted_keys: I) -> Self
+    where
+        I: IntoIterator<Item = [u8; 32]>,
+    {
+        Self {
+            trusted_keys: trusted_keys
+                .into_iter()
+                .map(ed25519_compact::PublicKey::new)
+                .collect(),
+        }
+    }
+
+    /// The path of the file that contains the signature of the library at `library`.
+    pub fn signature_path(library: &Path) -> PathBuf {
+        let mut path = library.as_os_str().to_owned();
+        path.push(".sig");
+        PathBuf::from(path)
+    }
+
+    /// Checks that `signature` is a signature of `contents`
+    /// made with one of the trusted keys.
+    ///
+    /// # Errors
+    ///
+    /// This returns `Ed25519VerifyError::MalformedSignature`
+    /// if `signature` isn't 64 bytes long,
+    /// and `Ed25519VerifyError::UntrustedSignature`
+    /// if it wasn't made with any of the trusted keys.
+    pub fn verify_signature(
+        &self,
+        contents: &[u8],
+        signature: &[u8],
+    ) -> Result<(), Ed25519VerifyError> {
+        let signature = ed25519_compact::Signature::from_slice(signature)
+            .map_err(|_| Ed25519VerifyError::MalformedSignature)?;
+
+        if self
+            .trusted_keys
+            .iter()
+            .any(|key| key.verify(contents, &signature).is_ok())
+        {
+            Ok(())
+        } else {
+            Err(Ed25519VerifyError::UntrustedSignature)
+        }
+    }
+}
+
+#[cfg(feature = "ed25519")]
+impl VerifyLibrary for Ed25519Verifier {
+    fn verify_library(&self, path: &Path, contents: &[u8]) -> Result<(), RBoxError> {
+        let signature_path = Self::signature_path(path);
+        std::fs::read(&signature_path)
+            .map_err(|err| Ed25519VerifyError::MissingSignature {
+                path: signature_path,
+                err,
+            })
+            .and_then(|signature| self.verify_signature(contents, &signature))
+            .map_err(RBoxError::new)
+    }
+}
+
+/// The reasons why an [`Ed25519Verifier`] can reject a library.
+///
+/// [`Ed25519Verifier`]: ./struct.Ed25519Verifier.html
+#[cfg(feature = "ed25519")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "ed25519")))]
+#[derive(Debug)]
+pub enum Ed25519VerifyError {
+    /// When the signature file could not be read.
+    MissingSignature { path: PathBuf, err: io::Error },
+    /// When the signature is not 64 bytes long.
+    MalformedSignature,
+    /// When the signature wasn't made with any of the trusted keys.
+    UntrustedSignature,
+}
+
+#[cfg(feature = "ed25519")]
+impl Display for Ed25519VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Ed25519VerifyError::MissingSignature { path, err } => write!(
+                f,
+                "Could not read the signature at:\n\t{}\nbecause:\n\t{}",
+                path.display(),
+                err
+            ),
+            Ed25519VerifyError::MalformedSignature => {
+                f.write_str("The signature is not a valid Ed25519 signature")
+            }
+            Ed25519VerifyError::UntrustedSignature => {
+                f.write_str("The library was not signed with any of the trusted keys")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "ed25519")]
+impl std::error::Error for Ed25519VerifyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Ed25519VerifyError::MissingSignature { err, .. } => Some(err),
+            _ => None,
+        }
+    }
+}
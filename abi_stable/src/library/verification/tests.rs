@@ -0,0 +1,151 @@
+use super::*;
+
+use core_extensions::matches;
+
+use crate::{
+    for_examples::Module_Ref,
+    library::{LibraryError, LibraryPath, RootModule},
+};
+
+use std::path::PathBuf;
+
+/// Writes `contents` to a file in the temporary directory,returning its path.
+fn temp_file(name: &str, contents: &[u8]) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "abi_stable_verification_{}_{}",
+        std::process::id(),
+        name
+    ));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn verified_before_loading() {
+    let path = temp_file("not_a_library", b"not a library");
+
+    let rejecting = |_: &Path, _: &[u8]| -> Result<(), RBoxError> {
+        Err(RBoxError::from_fmt(&"untrusted library"))
+    };
+    let err = Module_Ref::load_from_verified(LibraryPath::FullPath(&path), &rejecting)
+        .err()
+        .unwrap();
+    match &err {
+        LibraryError::UnverifiedLibrary {
+            path: err_path,
+            err,
+        } => {
+            assert_eq!(err_path, &path);
+            assert_eq!(err.to_string(), "untrusted library");
+        }
+        _ => panic!("unexpected error: {}", err),
+    }
+
+    // The library is only loaded after the verifier accepts it.
+    let accepting = |verified_path: &Path, contents: &[u8]| -> Result<(), RBoxError> {
+        assert_eq!(verified_path, path);
+        assert_eq!(contents, b"not a library");
+        Ok(())
+    };
+    let err = Module_Ref::load_from_verified(LibraryPath::FullPath(&path), &accepting)
+        .err()
+        .unwrap();
+    assert!(matches!(err, LibraryError::OpenError { .. }), "{}", err);
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+/// Replacing the library after it was verified is an error,
+/// instead of loading the replacement.
+#[cfg(unix)]
+#[test]
+fn replaced_after_verification() {
+    let path = temp_file("replaced.so", b"not a library");
+    let replacement = temp_file("replacement.so", b"not a library either");
+
+    let replacing = |_: &Path, contents: &[u8]| -> Result<(), RBoxError> {
+        assert_eq!(contents, b"not a library");
+        std::fs::rename(&replacement, &path).unwrap();
+        Ok(())
+    };
+    let err = Module_Ref::load_from_verified(LibraryPath::FullPath(&path), &replacing)
+        .err()
+        .unwrap();
+    match &err {
+        LibraryError::UnverifiedLibrary {
+            path: err_path,
+            err,
+        } => {
+            assert_eq!(err_path, &path);
+            assert_eq!(
+                err.to_string(),
+                "the library was replaced after it was verified"
+            );
+        }
+        _ => panic!("unexpected error: {}", err),
+    }
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn unreadable_library() {
+    let path = std::env::temp_dir().join("abi_stable_verification_missing_library");
+    let accepting = |_: &Path, _: &[u8]| -> Result<(), RBoxError> { Ok(()) };
+
+    let err = Module_Ref::load_from_verified(LibraryPath::FullPath(&path), &accepting)
+        .err()
+        .unwrap();
+    assert!(
+        matches!(err, LibraryError::UnverifiedLibrary { .. }),
+        "{}",
+        err
+    );
+}
+
+#[cfg(feature = "ed25519")]
+#[test]
+fn ed25519_signatures() {
+    use ed25519_compact::{KeyPair, Seed};
+
+    let vendor = KeyPair::from_seed(Seed::new([3; 32]));
+    let other = KeyPair::from_seed(Seed::new([5; 32]));
+
+    let contents = b"the contents of a library";
+    let signature = vendor.sk.sign(contents, None);
+
+    let verifier = Ed25519Verifier::new(vec![[0; 32], *vendor.pk]);
+
+    verifier.verify_signature(contents, &*signature).unwrap();
+    assert!(matches!(
+        verifier.verify_signature(b"modified contents", &*signature),
+        Err(Ed25519VerifyError::UntrustedSignature)
+    ));
+    assert!(matches!(
+        verifier.verify_signature(contents, &*other.sk.sign(contents, None)),
+        Err(Ed25519VerifyError::UntrustedSignature)
+    ));
+    assert!(matches!(
+        verifier.verify_signature(contents, &signature[..10]),
+        Err(Ed25519VerifyError::MalformedSignature)
+    ));
+
+    let path = temp_file("signed_library", contents);
+    let signature_path = Ed25519Verifier::signature_path(&path);
+    assert_eq!(
+        signature_path.file_name().unwrap(),
+        &*format!("{}.sig", path.file_name().unwrap().to_str().unwrap())
+    );
+
+    let err = verifier.verify_library(&path, contents).unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<Ed25519VerifyError>(),
+        Some(Ed25519VerifyError::MissingSignature { .. })
+    ));
+
+    std::fs::write(&signature_path, &*signature).unwrap();
+    verifier.verify_library(&path, contents).unwrap();
+
+    std::fs::remove_file(&path).unwrap();
+    std::fs::remove_file(&signature_path).unwrap();
+}
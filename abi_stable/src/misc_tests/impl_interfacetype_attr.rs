@@ -30,7 +30,8 @@ use crate::{
     IoSeek,
     IoRead,
     IoBufRead,
-    Error
+    Error,
+    Stream
 ))]
 pub struct AllTraitsImpld;
 
@@ -57,6 +58,7 @@ fn assert_all_traits_impld() {
     let _: <AllTraitsImpld as InterfaceType>::IoRead = Implemented::NEW;
     let _: <AllTraitsImpld as InterfaceType>::IoBufRead = Implemented::NEW;
     let _: <AllTraitsImpld as InterfaceType>::Error = Implemented::NEW;
+    let _: <AllTraitsImpld as InterfaceType>::Stream = Implemented::NEW;
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -90,6 +92,7 @@ fn assert_all_traits_unimpld() {
     let _: <NoTraitsImpld<()> as InterfaceType>::IoRead = Unimplemented::NEW;
     let _: <NoTraitsImpld<()> as InterfaceType>::IoBufRead = Unimplemented::NEW;
     let _: <NoTraitsImpld<()> as InterfaceType>::Error = Unimplemented::NEW;
+    let _: <NoTraitsImpld<()> as InterfaceType>::Stream = Unimplemented::NEW;
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -124,6 +127,7 @@ fn assert_fmt_traits_impld() {
     let _: <FmtInterface<()> as InterfaceType>::IoRead = Unimplemented::NEW;
     let _: <FmtInterface<()> as InterfaceType>::IoBufRead = Unimplemented::NEW;
     let _: <FmtInterface<()> as InterfaceType>::Error = Unimplemented::NEW;
+    let _: <FmtInterface<()> as InterfaceType>::Stream = Unimplemented::NEW;
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -158,6 +162,7 @@ fn assert_hash_ord_impld() {
     let _: <HashOrdInterface<()> as InterfaceType>::IoRead = Unimplemented::NEW;
     let _: <HashOrdInterface<()> as InterfaceType>::IoBufRead = Unimplemented::NEW;
     let _: <HashOrdInterface<()> as InterfaceType>::Error = Unimplemented::NEW;
+    let _: <HashOrdInterface<()> as InterfaceType>::Stream = Unimplemented::NEW;
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -190,6 +195,7 @@ fn assert_only_eq() {
     let _: <OnlyEq as InterfaceType>::IoRead = Unimplemented::NEW;
     let _: <OnlyEq as InterfaceType>::IoBufRead = Unimplemented::NEW;
     let _: <OnlyEq as InterfaceType>::Error = Unimplemented::NEW;
+    let _: <OnlyEq as InterfaceType>::Stream = Unimplemented::NEW;
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -222,6 +228,7 @@ fn assert_only_partial_ord() {
     let _: <OnlyPartialOrd as InterfaceType>::IoRead = Unimplemented::NEW;
     let _: <OnlyPartialOrd as InterfaceType>::IoBufRead = Unimplemented::NEW;
     let _: <OnlyPartialOrd as InterfaceType>::Error = Unimplemented::NEW;
+    let _: <OnlyPartialOrd as InterfaceType>::Stream = Unimplemented::NEW;
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -254,6 +261,7 @@ fn assert_only_error() {
     let _: <OnlyError as InterfaceType>::IoRead = Unimplemented::NEW;
     let _: <OnlyError as InterfaceType>::IoBufRead = Unimplemented::NEW;
     let _: <OnlyError as InterfaceType>::Error = Implemented::NEW;
+    let _: <OnlyError as InterfaceType>::Stream = Unimplemented::NEW;
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -286,6 +294,7 @@ fn assert_only_iter() {
     let _: <OnlyIter as InterfaceType>::IoRead = Unimplemented::NEW;
     let _: <OnlyIter as InterfaceType>::IoBufRead = Unimplemented::NEW;
     let _: <OnlyIter as InterfaceType>::Error = Unimplemented::NEW;
+    let _: <OnlyIter as InterfaceType>::Stream = Unimplemented::NEW;
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -318,4 +327,5 @@ fn assert_only_de_iter() {
     let _: <OnlyDEIter as InterfaceType>::IoRead = Unimplemented::NEW;
     let _: <OnlyDEIter as InterfaceType>::IoBufRead = Unimplemented::NEW;
     let _: <OnlyDEIter as InterfaceType>::Error = Unimplemented::NEW;
+    let _: <OnlyDEIter as InterfaceType>::Stream = Unimplemented::NEW;
 }
@@ -36,6 +36,7 @@ impl_InterfaceType! {
         type IoRead=True;
         type IoBufRead=True;
         type Error=True;
+        type Stream=True;
     }
 }
 
@@ -62,6 +63,7 @@ fn assert_all_traits_impld() {
     let _: <AllTraitsImpld as InterfaceType>::IoRead = Implemented::NEW;
     let _: <AllTraitsImpld as InterfaceType>::IoBufRead = Implemented::NEW;
     let _: <AllTraitsImpld as InterfaceType>::Error = Implemented::NEW;
+    let _: <AllTraitsImpld as InterfaceType>::Stream = Implemented::NEW;
 }
 
 #[repr(C)]
@@ -98,6 +100,7 @@ fn assert_all_traits_unimpld() {
     let _: <NoTraitsImpld<()> as InterfaceType>::IoRead = Unimplemented::NEW;
     let _: <NoTraitsImpld<()> as InterfaceType>::IoBufRead = Unimplemented::NEW;
     let _: <NoTraitsImpld<()> as InterfaceType>::Error = Unimplemented::NEW;
+    let _: <NoTraitsImpld<()> as InterfaceType>::Stream = Unimplemented::NEW;
 }
 
 #[repr(C)]
@@ -139,6 +142,7 @@ fn assert_fmt_traits_impld() {
     let _: <FmtInterface<()> as InterfaceType>::IoRead = Unimplemented::NEW;
     let _: <FmtInterface<()> as InterfaceType>::IoBufRead = Unimplemented::NEW;
     let _: <FmtInterface<()> as InterfaceType>::Error = Unimplemented::NEW;
+    let _: <FmtInterface<()> as InterfaceType>::Stream = Unimplemented::NEW;
 }
 
 #[repr(C)]
@@ -181,4 +185,5 @@ fn assert_hash_eq_impld() {
     let _: <HashEqInterface<()> as InterfaceType>::IoRead = Unimplemented::NEW;
     let _: <HashEqInterface<()> as InterfaceType>::IoBufRead = Unimplemented::NEW;
     let _: <HashEqInterface<()> as InterfaceType>::Error = Unimplemented::NEW;
+    let _: <HashEqInterface<()> as InterfaceType>::Stream = Unimplemented::NEW;
 }
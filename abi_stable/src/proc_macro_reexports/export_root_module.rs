@@ -87,14 +87,145 @@ This attribute should not be used unconditionally,
 it should be disabled in Continuous Integration so that the 
 binary compatibility of a dynamic library is checked at some point before releasing it.
 
+# Capabilities
+
+The capabilities that the library needs can be declared with the
+`capabilities(...)` parameter,which takes a list of [`Capability`] expressions.
+
+Hosts that load the library with [`LoadOptions::granted_capabilities`]
+refuse to initialize it unless every declared capability was granted,
+the library can query what was granted with [`granted_capabilities`].
+
+```rust
+use abi_stable::{library::Capability, prefix_type::PrefixTypeTrait};
+
+#[abi_stable::export_root_module(capabilities(
+    Capability::FILESYSTEM,
+    Capability::new("host:database"),
+))]
+pub fn get_hello_world_mod() -> TextOperationsMod_Ref {
+    TextOperationsMod { reverse_string }.leak_into_prefix()
+}
+
+# #[repr(C)]
+# #[derive(abi_stable::StableAbi)]
+# #[sabi(kind(Prefix(prefix_ref="TextOperationsMod_Ref")))]
+# #[sabi(missing_field(panic))]
+# pub struct TextOperationsMod {
+#     #[sabi(last_prefix_field)]
+#     pub reverse_string: extern "C" fn(),
+# }
+# 
+# extern "C" fn reverse_string() {}
+# 
+# impl abi_stable::library::RootModule for TextOperationsMod_Ref {
+#     abi_stable::declare_root_module_statics!{TextOperationsMod_Ref}
+#     const BASE_NAME: &'static str = "stuff";
+#     const NAME: &'static str = "stuff";
+#     const VERSION_STRINGS: abi_stable::sabi_types::VersionStrings =
+#           abi_stable::package_version_strings!();
+# }
+# 
+# fn main(){}
+```
+
+# Manifest
+
+Descriptive metadata about the library can be embedded with the `manifest(...)` parameter,
+which the host reads with [`LibHeader::manifest`] without initializing the root module.
+
+It takes these optional parameters,where every value is a `&'static str` expression:
+
+- `display_name = <expr>`: the human readable name of the library.
+
+- `description = <expr>`: a description of the library.
+
+- `authors(<expr>, ...)`: the authors of the library.
+
+- `license = <expr>`: the license of the library.
+
+- `homepage = <expr>`: the url of the homepage of the library.
+
+- `custom("key" = <expr>, ...)`: custom key-value pairs,where keys are string literals.
+
+```rust
+use abi_stable::prefix_type::PrefixTypeTrait;
+
+#[abi_stable::export_root_module(manifest(
+    display_name = "Text operations",
+    description = "Reverses strings",
+    authors(env!("CARGO_PKG_AUTHORS")),
+    license = "MIT OR Apache-2.0",
+    custom("category" = "text"),
+))]
+pub fn get_hello_world_mod() -> TextOperationsMod_Ref {
+    TextOperationsMod { reverse_string }.leak_into_prefix()
+}
+
+# #[repr(C)]
+# #[derive(abi_stable::StableAbi)]
+# #[sabi(kind(Prefix(prefix_ref="TextOperationsMod_Ref")))]
+# #[sabi(missing_field(panic))]
+# pub struct TextOperationsMod {
+#     #[sabi(last_prefix_field)]
+#     pub reverse_string: extern "C" fn(),
+# }
+# 
+# extern "C" fn reverse_string() {}
+# 
+# impl abi_stable::library::RootModule for TextOperationsMod_Ref {
+#     abi_stable::declare_root_module_statics!{TextOperationsMod_Ref}
+#     const BASE_NAME: &'static str = "stuff";
+#     const NAME: &'static str = "stuff";
+#     const VERSION_STRINGS: abi_stable::sabi_types::VersionStrings =
+#           abi_stable::package_version_strings!();
+# }
+# 
+# fn main(){}
+```
+
+# Serialized layout
+
+The `serialized_layout` parameter embeds the layout of the root module,
+serialized by the build script of the crate with [`write_serialized_layout`],
+in the `.sabi_layout` section of ELF libraries.
+This lets [`lib_metadata_from_path`] (and `sabi_extract layout --no-load`)
+read the layout without loading the library.
+
+The build script must have the crate that declares the root module as a build-dependency:
+
+```rust,ignore
+// build.rs
+fn main() {
+    abi_stable::library::write_serialized_layout(
+        <TextOperationsMod_Ref as abi_stable::StableAbi>::LAYOUT,
+    )
+    .unwrap();
+}
+```
+
+```rust,ignore
+#[abi_stable::export_root_module(serialized_layout)]
+pub fn get_library() -> TextOperationsMod_Ref {
+    TextOperationsMod { reverse_string }.leak_into_prefix()
+}
+```
+
 # More examples
 
 For a more detailed example look in the README in the repository for this crate.
 
 
 
+[`Capability`]: ./library/struct.Capability.html
+[`granted_capabilities`]: ./library/fn.granted_capabilities.html
 [`IntoRootModuleResult`]: ./library/trait.IntoRootModuleResult.html
+[`LoadOptions::granted_capabilities`]:
+./library/struct.LoadOptions.html#method.granted_capabilities
 [`LateStaticRef`]: ./sabi_types/struct.LateStaticRef.html
+[`LibHeader::manifest`]: ./library/struct.LibHeader.html#method.manifest
+[`lib_metadata_from_path`]: ./library/fn.lib_metadata_from_path.html
+[`write_serialized_layout`]: ./library/fn.write_serialized_layout.html
 [`LibHeader`]: ./library/struct.LibHeader.html
 
 */
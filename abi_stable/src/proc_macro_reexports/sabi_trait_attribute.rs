@@ -265,6 +265,101 @@ The only valid way to refer to associated types in the trait declaration is with
 Associated types in the trait object are transformed into type parameters 
 that come after those of the trait.
 
+# Async methods
+
+Methods can be `async fn`s, or return `impl Future<Output = T> + Send`,
+which requires Rust 1.75.0 or higher.
+
+In the vtable these methods return the future boxed,
+`async fn`s as a [`LocalRBoxFuture`], and `impl Future + Send` methods as an [`RBoxFuture`],
+which are also what the inherent methods of the trait object return.
+
+Methods returning futures must take `&self` or `&mut self`,
+and the future borrows everything that the method borrows for the lifetime of `&self`,
+so elided lifetimes in the parameters are the same lifetime as that of `&self`,
+and the method can't declare any other lifetime.
+
+*/
+#[cfg_attr(feature = "rust_1_75", doc = "```rust")]
+#[cfg_attr(not(feature = "rust_1_75"), doc = "```ignore")]
+/**
+use abi_stable::{
+    sabi_trait,
+    sabi_trait::prelude::TD_Opaque,
+    std_types::{RBoxFuture, RString},
+};
+
+use std::future::Future;
+
+#[sabi_trait]
+pub trait Handler {
+    /// This future is `!Send`,
+    /// it's a `LocalRBoxFuture<'_, RString>` in the vtable.
+    async fn handle(&self, request: RString) -> RString;
+
+    /// This future is `Send`,
+    /// it's an `RBoxFuture<'_, u32>` in the vtable.
+    fn count(&self) -> impl Future<Output = u32> + Send;
+}
+
+struct Echo;
+
+impl Handler for Echo {
+    async fn handle(&self, request: RString) -> RString {
+        request
+    }
+
+    fn count(&self) -> impl Future<Output = u32> + Send {
+        async { 1 }
+    }
+}
+
+# fn main() {
+let handler = Handler_TO::from_value(Echo, TD_Opaque);
+
+// Both of these are awaited like any other future.
+let response = handler.handle(RString::from("hello"));
+let count: RBoxFuture<'_, u32> = handler.count();
+# let _ = (response, count);
+# }
+```
+
+The future can't outlive the borrowed parameters:
+
+*/
+#[cfg_attr(feature = "rust_1_75", doc = "```compile_fail")]
+#[cfg_attr(not(feature = "rust_1_75"), doc = "```ignore")]
+/**
+use abi_stable::{
+    sabi_trait,
+    sabi_trait::prelude::TD_Opaque,
+    std_types::{RStr, RString},
+};
+
+#[sabi_trait]
+pub trait Measure {
+    async fn measure(&self, text: RStr<'_>) -> usize;
+}
+
+struct Len;
+
+impl Measure for Len {
+    async fn measure(&self, text: RStr<'_>) -> usize {
+        text.len()
+    }
+}
+
+# fn main() {
+let measurer = Measure_TO::from_value(Len, TD_Opaque);
+
+let future = {
+    let text = RString::from("hello");
+    measurer.measure(text.as_rstr())
+};
+# drop(future);
+# }
+```
+
 # Object safety
 
 Trait objects generated using this attribute have similar restrictions to built-in trait objects: 
@@ -488,6 +583,8 @@ assert!(!IS_CARD.contains(&'B'));
 [`RArc<()>`]: ./std_types/struct.RArc.html
 [`RRef<'_, ()>`]: ./sabi_types/struct.RRef.html
 [`RMut<'_, ()>`]: ./sabi_types/struct.RMut.html
+[`RBoxFuture`]: ./std_types/type.RBoxFuture.html
+[`LocalRBoxFuture`]: ./std_types/type.LocalRBoxFuture.html
 
 */
 #[doc(inline)]
@@ -112,6 +112,36 @@ which allows encoding and checking extra properties about a type.
 For examples of using this attribute
 [look here](./abi_stability/extra_checks/index.html#examples)
 
+###  `#[sabi(check_const( NAME = "<expression>" ))]` 
+
+Checks that the constant is the same in the interface and the implementation.
+
+`<expression>` can be anything that the [`tag`] macro accepts:
+bools, integers, strings, and `Tag`s.
+Integer and bool literals can be written without the quotes.
+
+###  `#[sabi(check_subset( NAME = "<expression>" ))]` 
+
+Checks that the elements in the interface are a subset of the elements
+in the implementation, for sets that can only grow in newer versions.
+
+`<expression>` can be an array literal (eg: `"[\"foo\", \"bar\"]"`),
+or a Set/Array `Tag` constant.
+
+###  `#[sabi(check_ordered( NAME = "<expression>" ))]` 
+
+Checks that the elements in the interface are a prefix of the elements
+in the implementation, for lists that can only be appended to in newer versions.
+
+`<expression>` can be an array literal, or an Array `Tag` constant.
+
+If the constant of a `check_subset` or `check_ordered` isn't an Array or a Set,
+the layout check fails.
+
+These three attributes can take multiple `NAME = "<expression>"` arguments,
+and can't be used with `#[sabi(extra_checks = "...")]`.
+For more details [look here](./abi_stability/extra_checks/struct.DeclaredChecks.html).
+
 ###  `#[sabi(debug_print)]` 
 
 Prints the generated code, stopping compilation.
@@ -132,6 +162,18 @@ Declares a struct with all the fields in the deriving type up to (and including)
 the field with the `#[sabi(last_prefix_field)]` attribute,
 named "<Identifier>".
 
+###  `#[sabi(with_prefix_extra)]` 
+
+Stores the doc comments of a prefix type and its fields,
+and what the accessor of each field does when the field is missing,
+in the layout of the prefix type.
+
+This information can then be read at runtime with
+[`TypeLayout::prefix_extra`](./type_layout/struct.TypeLayout.html#method.prefix_extra),
+and is used by module reflection and `sabi_extract`.
+
+This can only be used on prefix types.
+
 ###  `#[sabi(kind(WithNonExhaustive( .. ))]` 
 
 Declares this enum as being nonexhaustive,
@@ -210,6 +252,9 @@ and won't be usable afterwards.
 
 - `Error`: corresponds to `std::error::Error` .
 
+- `Stream`: corresponds to `futures_core::Stream`,
+    this type will also have to implement `abi_stable::erased_types::StreamItem`.
+
 <br>
 Examples:
 
@@ -539,6 +584,7 @@ For examples of using both `#[derive(GetStaticEquivalent)]` and `#[not_stableabi
 
 
 [`NonExhaustive`]: ./nonexhaustive_enum/struct.NonExhaustive.html
+[`tag`]: ./macro.tag.html
 
 */
 #[doc(inline)]
@@ -11,6 +11,20 @@ pub mod tests {
 #[doc(hidden)]
 pub mod export_module;
 
+/// Implementation details of the sabi_extract tool.
+#[doc(hidden)]
+pub mod export_layout;
+
+#[cfg(feature = "dyn_call")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "dyn_call")))]
+pub mod dyn_call;
+
+pub mod prefix_fields;
+
+pub mod type_graph;
+
+pub mod value_dump;
+
 /// Whether this is a module whose definition can be reflected on at runtime,
 ///
 /// Module reflection only allows accessing public fields.
@@ -0,0 +1,1002 @@
+//! Calling the functions of a module by name,
+//! passing the arguments and returning the result as `serde_json::Value`s.
+//!
+//! This is intended for tools that exercise the modules of a dynamic library
+//! without having to write Rust code for every function, eg: a REPL.
+//!
+//! # Supported types
+//!
+//! These are the types that can be passed to and returned from functions,
+//! along with how they are represented in json,
+//! which is the same as their `Serialize`/`Deserialize` impls:
+//!
+//! - `bool`: a json boolean.
+//!
+//! - integers and floats: a json number,integers are range checked.
+//!
+//! - `RStr`,`RString`: a json string.
+//!
+//! - `RVec<T>`: a json array. <br>
+//!   Vectors passed as arguments can only contain
+//!   `bool`s, integers, floats, `RStr`s, or `RString`s.
+//!
+//! - `ROption<T>`: `null` for `RNone`,the value itself for `RSome`.
+//!
+//! - `RResult<T, E>`: `{"Ok": value}` or `{"Err": value}`.
+//!
+//! - `#[repr(C)]` structs whose fields are all of supported types:
+//!   a json object with the fields by name.
+//!
+//! Functions that return `()` return `null`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use abi_stable::{
+//!     prefix_type::{PrefixTypeTrait, WithMetadata},
+//!     reflection::{dyn_call::DynModule, prefix_fields::PrefixFields},
+//!     std_types::{RString, RVec},
+//!     StableAbi,
+//! };
+//!
+//! use serde_json::json;
+//!
+//! #[repr(C)]
+//! #[derive(StableAbi)]
+//! #[sabi(kind(Prefix(prefix_ref = "MathMod_Ref")))]
+//! pub struct MathMod {
+//!     pub sum: extern "C" fn(RVec<u32>) -> u64,
+//!     #[sabi(last_prefix_field)]
+//!     pub describe: extern "C" fn(name: RString, value: f64) -> RString,
+//! }
+//!
+//! extern "C" fn sum(list: RVec<u32>) -> u64 {
+//!     list.iter().map(|&x| x as u64).sum()
+//! }
+//!
+//! extern "C" fn describe(name: RString, value: f64) -> RString {
+//!     format!("{} = {}", name, value).into()
+//! }
+//!
+//! const MODULE: &WithMetadata<MathMod> = &WithMetadata::new(
+//!     PrefixTypeTrait::METADATA,
+//!     MathMod { sum, describe },
+//! );
+//!
+//! let module = DynModule::new(PrefixFields::new(MODULE.static_as_prefix()).unwrap());
+//!
+//! unsafe {
+//!     assert_eq!(module.call("sum", &[json!([3, 5, 8])]).unwrap(), json!(16));
+//!
+//!     assert_eq!(
+//!         module.call("describe", &[json!("pi"), json!(3.5)]).unwrap(),
+//!         json!("pi = 3.5"),
+//!     );
+//!
+//!     assert!(module.call("sum", &[json!("hello")]).is_err());
+//! }
+//!
+//! ```
+
+use std::{
+    ffi::c_void,
+    fmt::{self, Display},
+    mem, ptr,
+};
+
+use libffi::{
+    middle::{Cif, CodePtr, Type},
+    raw::{ffi_arg, ffi_call},
+};
+
+use serde_json::{Map, Number, Value};
+
+use crate::{
+    abi_stability::PrefixStableAbi,
+    library::RootModule,
+    reflection::{
+        export_module::{MRFunction, MRItem},
+        prefix_fields::{PrefixField, PrefixFields},
+    },
+    std_types::{RStr, RString, RVec},
+    type_layout::{
+        DiscriminantRepr, ReprAttr, TLData, TLDiscriminant, TLFunction, TLPrimitive, TypeLayout,
+    },
+};
+
+#[cfg(all(test, not(feature = "only_new_tests")))]
+mod tests;
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// A module whose function pointer fields can be called by name.
+#[derive(Debug, Copy, Clone)]
+pub struct DynModule<'a> {
+    fields: PrefixFields<'a>,
+}
+
+/// A function pointer field of a `DynModule`.
+#[derive(Copy, Clone)]
+pub struct DynFunction {
+    name: &'static str,
+    function: TLFunction,
+    code: *const c_void,
+}
+
+/// An error returned when calling a function of a `DynModule`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DynCallError {
+    /// There is no field with that name.
+    UnknownFunction { name: String },
+    /// The field is not a function pointer.
+    NotAFunction { name: String },
+    /// The field is not accessible,
+    /// eg:the library was compiled with an older version of the module.
+    InaccessibleFunction { name: String },
+    /// The function takes or returns a type that isn't supported.
+    UnsupportedType { function: String, type_: String },
+    /// The function was passed the wrong amount of arguments.
+    ArgumentCount {
+        function: String,
+        expected: usize,
+        found: usize,
+    },
+    /// An argument couldn't be converted to the type of its parameter.
+    InvalidArgument {
+        function: String,
+        index: usize,
+        message: String,
+    },
+}
+
+impl<'a> DynModule<'a> {
+    /// Constructs a `DynModule` from the fields of a prefix type.
+    pub fn new(fields: PrefixFields<'a>) -> Self {
+        Self { fields }
+    }
+
+    /// Constructs a `DynModule` from a root module,eg:one loaded from a dynamic library.
+    ///
+    /// This returns None if the root module is not a prefix type.
+    pub fn from_root_module<M>(module: M) -> Option<Self>
+    where
+        M: RootModule,
+        M::PrefixFields: PrefixStableAbi,
+    {
+        PrefixFields::new(module.to_prefix_ref()).map(Self::new)
+    }
+
+    /// The fields of the module.
+    pub fn fields(&self) -> PrefixFields<'a> {
+        self.fields
+    }
+
+    /// A description of the module,used by the sabi_extract tool.
+    pub fn describe(&self) -> MRItem {
+        MRItem::from_type_layout(self.fields.layout())
+    }
+
+    /// Iterates over the accessible function pointer fields of the module.
+    pub fn functions(&self) -> impl Iterator<Item = DynFunction> + 'a {
+        self.fields
+            .iter()
+            .filter(|f| f.tl_field().is_function() && f.is_accessible())
+            .filter_map(|f| DynFunction::new(f).ok())
+    }
+
+    /// Gets the function pointer field named `name`.
+    pub fn function(&self, name: &str) -> Result<DynFunction, DynCallError> {
+        let field = self
+            .fields
+            .by_name(name)
+            .ok_or_else(|| DynCallError::UnknownFunction { name: name.into() })?;
+        DynFunction::new(field)
+    }
+
+    /// Calls the function pointer field named `name` with `args`.
+    ///
+    /// # Safety
+    ///
+    /// The function must be sound to call with any value of its parameter types.
+    pub unsafe fn call(&self, name: &str, args: &[Value]) -> Result<Value, DynCallError> {
+        self.function(name)?.call(args)
+    }
+}
+
+impl DynFunction {
+    fn new(field: PrefixField<'_>) -> Result<Self, DynCallError> {
+        let name = field.name();
+        let tl_field = field.tl_field();
+        if !tl_field.is_function() {
+            return Err(DynCallError::NotAFunction { name: name.into() });
+        }
+        let value = field
+            .get()
+            .ok_or_else(|| DynCallError::InaccessibleFunction { name: name.into() })?;
+        Ok(Self {
+            name,
+            function: tl_field.function_range().index(0),
+            code: unsafe { *(value.as_ptr() as *const *const c_void) },
+        })
+    }
+
+    /// The name of the field that contains the function pointer.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The signature of the function.
+    pub fn signature(&self) -> MRFunction {
+        MRFunction::from(&self.function)
+    }
+
+    /// The names of the parameters of the function.
+    pub fn param_names(&self) -> impl Iterator<Item = &'static str> {
+        self.function.get_params().map(|p| p.name())
+    }
+
+    /// Calls the function with `args`.
+    ///
+    /// # Safety
+    ///
+    /// The function must be sound to call with any value of its parameter types.
+    pub unsafe fn call(&self, args: &[Value]) -> Result<Value, DynCallError> {
+        let unsupported = |layout: &'static TypeLayout| DynCallError::UnsupportedType {
+            function: self.name.into(),
+            type_: layout.full_type().to_string(),
+        };
+        let new_type = |layout: &'static TypeLayout| {
+            DynType::new(layout).map_err(|e| DynCallError::UnsupportedType {
+                function: self.name.into(),
+                type_: e.full_type().to_string(),
+            })
+        };
+
+        let params = self
+            .function
+            .get_params()
+            .map(|p| match new_type(p.layout())? {
+                DynType {
+                    kind: DynKind::Unit,
+                    layout,
+                } => Err(unsupported(layout)),
+                param => Ok(param),
+            })
+            .collect::<Result<Vec<DynType>, DynCallError>>()?;
+        let ret = new_type(self.function.get_return().layout())?;
+
+        if params.len() != args.len() {
+            return Err(DynCallError::ArgumentCount {
+                function: self.name.into(),
+                expected: params.len(),
+                found: args.len(),
+            });
+        }
+
+        for (index, (param, arg)) in params.iter().zip(args).enumerate() {
+            param
+                .check_arg(arg)
+                .map_err(|message| DynCallError::InvalidArgument {
+                    function: self.name.into(),
+                    index,
+                    message,
+                })?;
+        }
+
+        let param_ffi_types = params
+            .iter()
+            .map(|p| p.ffi_type().ok_or_else(|| unsupported(p.layout)))
+            .collect::<Result<Vec<Type>, DynCallError>>()?;
+        let ret_ffi_type = ret.ffi_type().ok_or_else(|| unsupported(ret.layout))?;
+        let cif = Cif::new(param_ffi_types, ret_ffi_type);
+
+        // Nothing can fail from here on,
+        // so the arguments are only constructed once they are known to be valid.
+        let mut strings = Vec::<Box<str>>::new();
+        let mut buffers = params
+            .iter()
+            .zip(args)
+            .map(|(param, arg)| {
+                let mut buffer = ValueBuffer::new(param.layout.size());
+                param.write(arg, buffer.as_mut_ptr(), &mut strings);
+                buffer
+            })
+            .collect::<Vec<ValueBuffer>>();
+        let mut arg_ptrs = buffers
+            .iter_mut()
+            .map(|b| b.as_mut_ptr() as *mut c_void)
+            .collect::<Vec<*mut c_void>>();
+
+        let mut ret_buffer = ValueBuffer::new(ret.layout.size().max(mem::size_of::<ffi_arg>()));
+
+        ffi_call(
+            cif.as_raw_ptr(),
+            Some(*CodePtr::from_ptr(self.code).as_fun()),
+            ret_buffer.as_mut_ptr() as *mut c_void,
+            arg_ptrs.as_mut_ptr(),
+        );
+
+        // The arguments are owned by the called function,
+        // except for the strings that `RStr`s borrow.
+        drop(strings);
+
+        let ret_ptr = ret_buffer.as_mut_ptr();
+        ret.narrow_return(ret_ptr);
+        let value = ret.read(ret_ptr);
+        ret.drop_value(ret_ptr);
+        Ok(value)
+    }
+}
+
+impl Display for DynFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.name, self.signature())
+    }
+}
+
+impl fmt::Debug for DynFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DynFunction")
+            .field("name", &self.name)
+            .field("signature", &self.signature().to_string())
+            .finish()
+    }
+}
+
+impl Display for DynCallError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DynCallError::UnknownFunction { name } => {
+                write!(f, "There is no function named `{}` in the module.", name)
+            }
+            DynCallError::NotAFunction { name } => {
+                write!(f, "The `{}` field is not a function pointer.", name)
+            }
+            DynCallError::InaccessibleFunction { name } => write!(
+                f,
+                "The `{}` function is not accessible in the loaded module.",
+                name
+            ),
+            DynCallError::UnsupportedType { function, type_ } => write!(
+                f,
+                "The `{}` function uses the `{}` type,which can't be used dynamically.",
+                function, type_
+            ),
+            DynCallError::ArgumentCount {
+                function,
+                expected,
+                found,
+            } => write!(
+                f,
+                "The `{}` function takes {} arguments,but {} were passed.",
+                function, expected, found
+            ),
+            DynCallError::InvalidArgument {
+                function,
+                index,
+                message,
+            } => write!(
+                f,
+                "Invalid argument {} for the `{}` function:\n{}",
+                index, function, message
+            ),
+        }
+    }
+}
+
+impl ::std::error::Error for DynCallError {}
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// A zeroed buffer aligned to 8 bytes,the maximum alignment of supported types.
+struct ValueBuffer(Vec<u64>);
+
+impl ValueBuffer {
+    fn new(size: usize) -> Self {
+        ValueBuffer(vec![0; (size + 7) / 8])
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut u8 {
+        self.0.as_mut_ptr() as *mut u8
+    }
+}
+
+/// A type that can be converted from/to json,
+/// along with the layout information needed to do so.
+struct DynType {
+    layout: &'static TypeLayout,
+    kind: DynKind,
+}
+
+enum DynKind {
+    Unit,
+    Bool,
+    Int(TLPrimitive),
+    F32,
+    F64,
+    RStr,
+    RString,
+    RVec(Box<DynType>),
+    ROption(Vec<DynVariant>),
+    RResult(Vec<DynVariant>),
+    Struct(Vec<DynField>),
+}
+
+struct DynVariant {
+    /// The name of the variant in json,which is the same as its serde representation.
+    name: &'static str,
+    discriminant: u8,
+    /// The offset and type of the single field of the variant.
+    payload: Option<(usize, DynType)>,
+}
+
+struct DynField {
+    name: &'static str,
+    offset: usize,
+    type_: DynType,
+}
+
+impl DynType {
+    /// Constructs a `DynType`,returning the layout of the unsupported type on error.
+    fn new(layout: &'static TypeLayout) -> Result<Self, &'static TypeLayout> {
+        if layout.alignment() > mem::align_of::<u64>() {
+            return Err(layout);
+        }
+
+        let kind = if layout.package() == "abi_stable" {
+            Self::abi_stable_kind(layout)?
+        } else {
+            None
+        };
+
+        let kind = match kind {
+            Some(x) => x,
+            None if layout.size() == 0 => DynKind::Unit,
+            None => match layout.data() {
+                TLData::Primitive(prim) => match prim {
+                    TLPrimitive::Bool => DynKind::Bool,
+                    TLPrimitive::U8
+                    | TLPrimitive::I8
+                    | TLPrimitive::U16
+                    | TLPrimitive::I16
+                    | TLPrimitive::U32
+                    | TLPrimitive::I32
+                    | TLPrimitive::U64
+                    | TLPrimitive::I64
+                    | TLPrimitive::Usize
+                    | TLPrimitive::Isize => DynKind::Int(prim),
+                    _ => return Err(layout),
+                },
+                TLData::Opaque => match (layout.name(), layout.size()) {
+                    ("f32", 4) => DynKind::F32,
+                    ("f64", 8) => DynKind::F64,
+                    _ => return Err(layout),
+                },
+                TLData::Struct { fields } if layout.repr_attr() == ReprAttr::C => {
+                    let mut offset = 0;
+                    let fields = fields
+                        .iter()
+                        .map(|field| {
+                            let field_layout = field.layout();
+                            offset = align_up(offset, field_layout.alignment());
+                            let ret = DynField {
+                                name: field.name(),
+                                offset,
+                                type_: DynType::new(field_layout)?,
+                            };
+                            offset += field_layout.size();
+                            Ok(ret)
+                        })
+                        .collect::<Result<Vec<DynField>, _>>()?;
+                    DynKind::Struct(fields)
+                }
+                _ => return Err(layout),
+            },
+        };
+
+        Ok(DynType { layout, kind })
+    }
+
+    /// Gets the kind of the abi_stable types that have special support,
+    /// returning None for other types.
+    fn abi_stable_kind(
+        layout: &'static TypeLayout,
+    ) -> Result<Option<DynKind>, &'static TypeLayout> {
+        let word = mem::size_of::<usize>();
+        let kind = match (layout.name(), layout.data()) {
+            ("RStr", _) if layout.size() == 2 * word => DynKind::RStr,
+            ("RString", _) if layout.size() == 4 * word => DynKind::RString,
+            ("RVec", TLData::Struct { fields }) if layout.size() == 4 * word => {
+                // The type parameter of `RVec` is only stored in the type of its buffer.
+                let elem = fields
+                    .get(0)
+                    .and_then(|f| f.layout().generics().type_params().first().map(|x| x.get()))
+                    .ok_or(layout)?;
+                DynKind::RVec(Box::new(DynType::new(elem)?))
+            }
+            (name @ "ROption", TLData::Enum(enum_)) | (name @ "RResult", TLData::Enum(enum_))
+                if layout.repr_attr() == ReprAttr::Int(DiscriminantRepr::U8) =>
+            {
+                let mut fields = enum_.fields.iter();
+                let variants = enum_
+                    .variant_names_iter()
+                    .zip(enum_.field_count.iter())
+                    .enumerate()
+                    .map(|(i, (name, &field_count))| {
+                        let discriminant = match enum_.discriminants.get(i) {
+                            Some(TLDiscriminant::Unsigned(x)) if x <= 255 => x as u8,
+                            Some(TLDiscriminant::Signed(x)) if 0 <= x && x <= 255 => x as u8,
+                            _ => return Err(layout),
+                        };
+                        let payload = match field_count {
+                            0 => None,
+                            1 => {
+                                let field = fields.next().ok_or(layout)?.layout();
+                                Some((align_up(1, field.alignment()), DynType::new(field)?))
+                            }
+                            _ => return Err(layout),
+                        };
+                        Ok(DynVariant {
+                            name: name.strip_prefix('R').unwrap_or(name),
+                            discriminant,
+                            payload,
+                        })
+                    })
+                    .collect::<Result<Vec<DynVariant>, _>>()?;
+                if name == "ROption" {
+                    DynKind::ROption(variants)
+                } else {
+                    DynKind::RResult(variants)
+                }
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(kind))
+    }
+
+    /// The type used to pass this type to/from functions with libffi,
+    /// returning None if it can't be represented.
+    fn ffi_type(&self) -> Option<Type> {
+        Some(match &self.kind {
+            DynKind::Unit => Type::void(),
+            DynKind::Bool => Type::u8(),
+            DynKind::Int(prim) => match prim {
+                TLPrimitive::U8 => Type::u8(),
+                TLPrimitive::I8 => Type::i8(),
+                TLPrimitive::U16 => Type::u16(),
+                TLPrimitive::I16 => Type::i16(),
+                TLPrimitive::U32 => Type::u32(),
+                TLPrimitive::I32 => Type::i32(),
+                TLPrimitive::U64 => Type::u64(),
+                TLPrimitive::I64 => Type::i64(),
+                TLPrimitive::Usize => Type::usize(),
+                _ => Type::isize(),
+            },
+            DynKind::F32 => Type::f32(),
+            DynKind::F64 => Type::f64(),
+            DynKind::RStr => Type::structure(vec![Type::pointer(), Type::usize()]),
+            // The buffer,length,capacity,and vtable of the vector.
+            DynKind::RString | DynKind::RVec(_) => Type::structure(vec![
+                Type::pointer(),
+                Type::usize(),
+                Type::usize(),
+                Type::pointer(),
+            ]),
+            DynKind::Struct(fields) => Type::structure(
+                fields
+                    .iter()
+                    .filter(|f| f.type_.layout.size() != 0)
+                    .map(|f| f.type_.ffi_type())
+                    .collect::<Option<Vec<Type>>>()?,
+            ),
+            DynKind::ROption(variants) | DynKind::RResult(variants) => {
+                let mut payloads = variants.iter().filter_map(|v| v.payload.as_ref());
+                match (payloads.next(), payloads.next()) {
+                    (None, _) => Type::u8(),
+                    (Some((_, payload)), None) => {
+                        Type::structure(vec![Type::u8(), payload.ffi_type()?])
+                    }
+                    (Some((_, first)), Some((_, second)))
+                        if first.layout.get_utypeid() == second.layout.get_utypeid() =>
+                    {
+                        Type::structure(vec![Type::u8(), first.ffi_type()?])
+                    }
+                    // Enums with different payloads are passed as integers,
+                    // which is only how they are passed if they don't contain floats,
+                    // or if they're large enough to always be passed in memory.
+                    _ if !self.contains_float() || self.layout.size() > 16 => {
+                        let align = self.layout.alignment();
+                        let chunk = match align {
+                            1 => Type::u8,
+                            2 => Type::u16,
+                            4 => Type::u32,
+                            _ => Type::u64,
+                        };
+                        Type::structure((0..self.layout.size() / align).map(|_| chunk()))
+                    }
+                    _ => return None,
+                }
+            }
+        })
+    }
+
+    fn contains_float(&self) -> bool {
+        match &self.kind {
+            DynKind::F32 | DynKind::F64 => true,
+            DynKind::Struct(fields) => fields.iter().any(|f| f.type_.contains_float()),
+            DynKind::ROption(variants) | DynKind::RResult(variants) => variants
+                .iter()
+                .filter_map(|v| v.payload.as_ref())
+                .any(|(_, payload)| payload.contains_float()),
+            _ => false,
+        }
+    }
+
+    /// Checks that `value` can be written as an argument of this type.
+    fn check_arg(&self, value: &Value) -> Result<(), String> {
+        let mismatch = || {
+            format!(
+                "expected a value of type `{}`,found: {}",
+                self.layout.full_type(),
+                value
+            )
+        };
+        match &self.kind {
+            DynKind::Unit => {}
+            DynKind::Bool => {
+                value.as_bool().ok_or_else(mismatch)?;
+            }
+            DynKind::Int(prim) => {
+                int_from_json(value, *prim).ok_or_else(mismatch)?;
+            }
+            DynKind::F32 | DynKind::F64 => {
+                value.as_f64().ok_or_else(mismatch)?;
+            }
+            DynKind::RStr | DynKind::RString => {
+                value.as_str().ok_or_else(mismatch)?;
+            }
+            DynKind::RVec(elem) => {
+                if !matches!(
+                    elem.kind,
+                    DynKind::Bool
+                        | DynKind::Int(_)
+                        | DynKind::F32
+                        | DynKind::F64
+                        | DynKind::RStr
+                        | DynKind::RString
+                ) {
+                    return Err(format!(
+                        "`{}` can't be passed as an argument,\
+                         only vectors of bools,numbers,and strings can.",
+                        self.layout.full_type()
+                    ));
+                }
+                for elem_value in value.as_array().ok_or_else(mismatch)? {
+                    elem.check_arg(elem_value)?;
+                }
+            }
+            DynKind::ROption(_) | DynKind::RResult(_) => {
+                let (variant, payload) = self.json_variant(value).ok_or_else(mismatch)?;
+                if let Some((_, type_)) = &variant.payload {
+                    type_.check_arg(payload)?;
+                }
+            }
+            DynKind::Struct(fields) => {
+                let object = value.as_object().ok_or_else(mismatch)?;
+                for field in fields {
+                    match object.get(field.name) {
+                        Some(field_value) => field.type_.check_arg(field_value)?,
+                        None if field.type_.layout.size() == 0 => {}
+                        None => {
+                            return Err(format!(
+                                "missing the `{}` field of `{}`",
+                                field.name,
+                                self.layout.full_type()
+                            ))
+                        }
+                    }
+                }
+                if let Some(name) = object.keys().find(|k| fields.iter().all(|f| f.name != *k)) {
+                    return Err(format!(
+                        "`{}` has no `{}` field",
+                        self.layout.full_type(),
+                        name
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Gets the variant of an `ROption`/`RResult` that `value` represents,
+    /// along with the value of its payload.
+    fn json_variant<'v>(&self, value: &'v Value) -> Option<(&DynVariant, &'v Value)> {
+        const NULL: &Value = &Value::Null;
+        match &self.kind {
+            DynKind::ROption(variants) => {
+                let is_none = value.is_null();
+                let variant = variants.iter().find(|v| v.payload.is_none() == is_none)?;
+                Some((variant, value))
+            }
+            DynKind::RResult(variants) => {
+                let object = value.as_object().filter(|o| o.len() == 1)?;
+                let (name, payload) = object.iter().next()?;
+                let variant = variants.iter().find(|v| v.name == name)?;
+                Some((
+                    variant,
+                    if variant.payload.is_some() {
+                        payload
+                    } else {
+                        NULL
+                    },
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    /// Writes `value` to `ptr`.
+    ///
+    /// # Safety
+    ///
+    /// `value` must have been checked with `check_arg`,
+    /// and `ptr` must point to at least `self.layout.size()` writable bytes.
+    ///
+    /// The `RStr`s borrow strings that are pushed to `strings`.
+    unsafe fn write(&self, value: &Value, ptr: *mut u8, strings: &mut Vec<Box<str>>) {
+        match &self.kind {
+            DynKind::Unit => {}
+            DynKind::Bool => write_unaligned(ptr, value.as_bool() == Some(true)),
+            DynKind::Int(prim) => {
+                let x = int_from_json(value, *prim).unwrap_or(0);
+                match prim {
+                    TLPrimitive::U8 => write_unaligned(ptr, x as u8),
+                    TLPrimitive::I8 => write_unaligned(ptr, x as i8),
+                    TLPrimitive::U16 => write_unaligned(ptr, x as u16),
+                    TLPrimitive::I16 => write_unaligned(ptr, x as i16),
+                    TLPrimitive::U32 => write_unaligned(ptr, x as u32),
+                    TLPrimitive::I32 => write_unaligned(ptr, x as i32),
+                    TLPrimitive::U64 => write_unaligned(ptr, x as u64),
+                    TLPrimitive::I64 => write_unaligned(ptr, x as i64),
+                    TLPrimitive::Usize => write_unaligned(ptr, x as usize),
+                    _ => write_unaligned(ptr, x as isize),
+                }
+            }
+            DynKind::F32 => write_unaligned(ptr, value.as_f64().unwrap_or(0.0) as f32),
+            DynKind::F64 => write_unaligned(ptr, value.as_f64().unwrap_or(0.0)),
+            DynKind::RStr => {
+                let string: Box<str> = value.as_str().unwrap_or("").into();
+                write_unaligned(ptr, RStr::from_raw_parts(string.as_ptr(), string.len()));
+                strings.push(string);
+            }
+            DynKind::RString => write_unaligned(ptr, RString::from(value.as_str().unwrap_or(""))),
+            DynKind::RVec(elem) => {
+                let values = value.as_array().map_or(&[][..], |x| &x[..]);
+                match elem.kind {
+                    DynKind::Bool => write_vec::<bool>(ptr, elem, values, strings),
+                    DynKind::Int(TLPrimitive::U8) => write_vec::<u8>(ptr, elem, values, strings),
+                    DynKind::Int(TLPrimitive::I8) => write_vec::<i8>(ptr, elem, values, strings),
+                    DynKind::Int(TLPrimitive::U16) => write_vec::<u16>(ptr, elem, values, strings),
+                    DynKind::Int(TLPrimitive::I16) => write_vec::<i16>(ptr, elem, values, strings),
+                    DynKind::Int(TLPrimitive::U32) => write_vec::<u32>(ptr, elem, values, strings),
+                    DynKind::Int(TLPrimitive::I32) => write_vec::<i32>(ptr, elem, values, strings),
+                    DynKind::Int(TLPrimitive::U64) => write_vec::<u64>(ptr, elem, values, strings),
+                    DynKind::Int(TLPrimitive::I64) => write_vec::<i64>(ptr, elem, values, strings),
+                    DynKind::Int(TLPrimitive::Usize) => {
+                        write_vec::<usize>(ptr, elem, values, strings)
+                    }
+                    DynKind::Int(_) => write_vec::<isize>(ptr, elem, values, strings),
+                    DynKind::F32 => write_vec::<f32>(ptr, elem, values, strings),
+                    DynKind::F64 => write_vec::<f64>(ptr, elem, values, strings),
+                    DynKind::RStr => write_vec::<RStr<'static>>(ptr, elem, values, strings),
+                    _ => write_vec::<RString>(ptr, elem, values, strings),
+                }
+            }
+            DynKind::ROption(_) | DynKind::RResult(_) => {
+                if let Some((variant, payload)) = self.json_variant(value) {
+                    write_unaligned(ptr, variant.discriminant);
+                    if let Some((offset, type_)) = &variant.payload {
+                        type_.write(payload, ptr.add(*offset), strings);
+                    }
+                }
+            }
+            DynKind::Struct(fields) => {
+                for field in fields {
+                    if let Some(field_value) = value.get(field.name) {
+                        field
+                            .type_
+                            .write(field_value, ptr.add(field.offset), strings);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Converts an integer returned as an `ffi_arg` to this type,in place.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to a buffer that libffi wrote a return value of this type to.
+    unsafe fn narrow_return(&self, ptr: *mut u8) {
+        let is_int = matches!(self.kind, DynKind::Bool | DynKind::Int(_));
+        let size = self.layout.size();
+        if !is_int || size >= mem::size_of::<ffi_arg>() {
+            return;
+        }
+        let widened = ptr::read(ptr as *const ffi_arg);
+        match size {
+            1 => write_unaligned(ptr, widened as u8),
+            2 => write_unaligned(ptr, widened as u16),
+            _ => write_unaligned(ptr, widened as u32),
+        }
+    }
+
+    /// Reads a value of this type from `ptr` into json.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to a valid value of this type.
+    unsafe fn read(&self, ptr: *const u8) -> Value {
+        match &self.kind {
+            DynKind::Unit => Value::Null,
+            DynKind::Bool => Value::Bool(read_unaligned::<u8>(ptr) != 0),
+            DynKind::Int(prim) => match prim {
+                TLPrimitive::U8 => read_unaligned::<u8>(ptr).into(),
+                TLPrimitive::I8 => read_unaligned::<i8>(ptr).into(),
+                TLPrimitive::U16 => read_unaligned::<u16>(ptr).into(),
+                TLPrimitive::I16 => read_unaligned::<i16>(ptr).into(),
+                TLPrimitive::U32 => read_unaligned::<u32>(ptr).into(),
+                TLPrimitive::I32 => read_unaligned::<i32>(ptr).into(),
+                TLPrimitive::U64 => read_unaligned::<u64>(ptr).into(),
+                TLPrimitive::I64 => read_unaligned::<i64>(ptr).into(),
+                TLPrimitive::Usize => read_unaligned::<usize>(ptr).into(),
+                _ => read_unaligned::<isize>(ptr).into(),
+            },
+            DynKind::F32 => float_to_json(read_unaligned::<f32>(ptr) as f64),
+            DynKind::F64 => float_to_json(read_unaligned::<f64>(ptr)),
+            DynKind::RStr | DynKind::RString => {
+                let (buffer, len) = read_buffer(ptr);
+                let bytes = std::slice::from_raw_parts(buffer, len);
+                Value::String(String::from_utf8_lossy(bytes).into_owned())
+            }
+            DynKind::RVec(elem) => {
+                let (buffer, len) = read_buffer(ptr);
+                let elem_size = elem.layout.size();
+                (0..len)
+                    .map(|i| elem.read(buffer.add(i * elem_size)))
+                    .collect::<Vec<Value>>()
+                    .into()
+            }
+            DynKind::ROption(_) | DynKind::RResult(_) => {
+                let variant = match self.read_variant(ptr) {
+                    Some(x) => x,
+                    None => return Value::Null,
+                };
+                let payload = match &variant.payload {
+                    Some((offset, type_)) => type_.read(ptr.add(*offset)),
+                    None => Value::Null,
+                };
+                if let DynKind::RResult(_) = self.kind {
+                    let mut map = Map::new();
+                    map.insert(variant.name.into(), payload);
+                    Value::Object(map)
+                } else {
+                    payload
+                }
+            }
+            DynKind::Struct(fields) => fields
+                .iter()
+                .filter(|f| f.type_.layout.size() != 0)
+                .map(|f| (f.name.to_string(), f.type_.read(ptr.add(f.offset))))
+                .collect::<Map<String, Value>>()
+                .into(),
+        }
+    }
+
+    unsafe fn read_variant(&self, ptr: *const u8) -> Option<&DynVariant> {
+        let discriminant = read_unaligned::<u8>(ptr);
+        match &self.kind {
+            DynKind::ROption(variants) | DynKind::RResult(variants) => {
+                variants.iter().find(|v| v.discriminant == discriminant)
+            }
+            _ => None,
+        }
+    }
+
+    /// Drops a value of this type in place.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to a valid value of this type,which must not be used afterwards.
+    unsafe fn drop_value(&self, ptr: *mut u8) {
+        match &self.kind {
+            // `RString` is a wrapper around an `RVec<u8>`,
+            // and `RVec`s are dropped through their vtable,regardless of their element type.
+            DynKind::RString | DynKind::RVec(_) => ptr::drop_in_place(ptr as *mut RVec<u8>),
+            DynKind::ROption(_) | DynKind::RResult(_) => {
+                if let Some((offset, type_)) =
+                    self.read_variant(ptr).and_then(|v| v.payload.as_ref())
+                {
+                    type_.drop_value(ptr.add(*offset));
+                }
+            }
+            DynKind::Struct(fields) => {
+                for field in fields {
+                    field.type_.drop_value(ptr.add(field.offset));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Writes an `RVec<T>` to `ptr`,
+/// where `T` is the type that `elem` describes.
+unsafe fn write_vec<T>(
+    ptr: *mut u8,
+    elem: &DynType,
+    values: &[Value],
+    strings: &mut Vec<Box<str>>,
+) {
+    let mut vec = Vec::<T>::with_capacity(values.len());
+    for (i, value) in values.iter().enumerate() {
+        elem.write(value, vec.as_mut_ptr().add(i) as *mut u8, strings);
+    }
+    vec.set_len(values.len());
+    write_unaligned(ptr, RVec::from(vec));
+}
+
+/// Converts a json number to an integer of the `prim` type,
+/// returning None if it's not a number or it's out of range.
+fn int_from_json(value: &Value, prim: TLPrimitive) -> Option<i128> {
+    let x = match value {
+        Value::Number(n) => n
+            .as_u64()
+            .map(|x| x as i128)
+            .or_else(|| n.as_i64().map(|x| x as i128))?,
+        _ => return None,
+    };
+    let (min, max) = match prim {
+        TLPrimitive::U8 => (0, u8::max_value() as i128),
+        TLPrimitive::I8 => (i8::min_value() as i128, i8::max_value() as i128),
+        TLPrimitive::U16 => (0, u16::max_value() as i128),
+        TLPrimitive::I16 => (i16::min_value() as i128, i16::max_value() as i128),
+        TLPrimitive::U32 => (0, u32::max_value() as i128),
+        TLPrimitive::I32 => (i32::min_value() as i128, i32::max_value() as i128),
+        TLPrimitive::U64 => (0, u64::max_value() as i128),
+        TLPrimitive::I64 => (i64::min_value() as i128, i64::max_value() as i128),
+        TLPrimitive::Usize => (0, usize::max_value() as i128),
+        _ => (isize::min_value() as i128, isize::max_value() as i128),
+    };
+    if min <= x && x <= max {
+        Some(x)
+    } else {
+        None
+    }
+}
+
+fn float_to_json(x: f64) -> Value {
+    Number::from_f64(x).map_or(Value::Null, Value::Number)
+}
+
+/// Reads the buffer pointer and length,
+/// which are the first two fields of `RStr`,`RString`,and `RVec`.
+unsafe fn read_buffer(ptr: *const u8) -> (*const u8, usize) {
+    (
+        read_unaligned::<*const u8>(ptr),
+        read_unaligned::<usize>(ptr.add(mem::size_of::<usize>())),
+    )
+}
+
+unsafe fn read_unaligned<T>(ptr: *const u8) -> T {
+    ptr::read_unaligned(ptr as *const T)
+}
+
+unsafe fn write_unaligned<T>(ptr: *mut u8, value: T) {
+    ptr::write_unaligned(ptr as *mut T, value)
+}
+
+fn align_up(offset: usize, alignment: usize) -> usize {
+    let alignment = alignment.max(1);
+    (offset + alignment - 1) / alignment * alignment
+}
@@ -0,0 +1,267 @@
+use super::*;
+
+use crate::{
+    prefix_type::{PrefixTypeTrait, WithMetadata},
+    std_types::{RErr, RNone, ROk, ROption, RResult, RSome},
+    StableAbi,
+};
+
+use serde_json::json;
+
+#[repr(C)]
+#[derive(StableAbi, Debug, PartialEq)]
+pub struct Point {
+    x: i32,
+    y: f64,
+    label: RString,
+}
+
+#[repr(C)]
+#[derive(StableAbi)]
+#[sabi(kind(Prefix(prefix_ref = "Module_Ref", prefix_fields = "Module_Prefix")))]
+pub struct Module {
+    pub add: extern "C" fn(a: u8, b: i64) -> i64,
+    pub negate: extern "C" fn(x: bool) -> bool,
+    pub halve: extern "C" fn(x: f32) -> f32,
+    pub greet: extern "C" fn(name: RStr<'_>, punctuation: RString) -> RString,
+    pub lengths: extern "C" fn(words: RVec<RString>) -> RVec<u16>,
+    pub find: extern "C" fn(list: RVec<i32>, elem: i32) -> ROption<usize>,
+    pub parse: extern "C" fn(text: RString) -> RResult<u32, RString>,
+    pub move_point: extern "C" fn(point: Point, dx: i32) -> Point,
+    pub points: extern "C" fn(count: u32) -> RVec<Point>,
+    pub nothing: extern "C" fn(),
+    pub references: extern "C" fn(x: &u32) -> u32,
+    #[sabi(last_prefix_field)]
+    pub value: u32,
+}
+
+extern "C" fn add(a: u8, b: i64) -> i64 {
+    a as i64 + b
+}
+
+extern "C" fn negate(x: bool) -> bool {
+    !x
+}
+
+extern "C" fn halve(x: f32) -> f32 {
+    x / 2.0
+}
+
+extern "C" fn greet(name: RStr<'_>, punctuation: RString) -> RString {
+    format!("hello {}{}", name, punctuation).into()
+}
+
+extern "C" fn lengths(words: RVec<RString>) -> RVec<u16> {
+    words.iter().map(|w| w.len() as u16).collect()
+}
+
+extern "C" fn find(list: RVec<i32>, elem: i32) -> ROption<usize> {
+    list.iter().position(|&x| x == elem).into()
+}
+
+extern "C" fn parse(text: RString) -> RResult<u32, RString> {
+    match text.parse::<u32>() {
+        Ok(x) => ROk(x),
+        Err(e) => RErr(e.to_string().into()),
+    }
+}
+
+extern "C" fn move_point(point: Point, dx: i32) -> Point {
+    Point {
+        x: point.x + dx,
+        y: point.y * 2.0,
+        label: format!("{}!", point.label).into(),
+    }
+}
+
+extern "C" fn points(count: u32) -> RVec<Point> {
+    (0..count)
+        .map(|i| Point {
+            x: i as i32,
+            y: 0.5,
+            label: i.to_string().into(),
+        })
+        .collect()
+}
+
+extern "C" fn nothing() {}
+
+extern "C" fn references(x: &u32) -> u32 {
+    *x
+}
+
+const MOD_VAL: &WithMetadata<Module> = &WithMetadata::new(
+    PrefixTypeTrait::METADATA,
+    Module {
+        add,
+        negate,
+        halve,
+        greet,
+        lengths,
+        find,
+        parse,
+        move_point,
+        points,
+        nothing,
+        references,
+        value: 3,
+    },
+);
+
+fn module() -> DynModule<'static> {
+    DynModule::new(PrefixFields::new(MOD_VAL.static_as_prefix()).unwrap())
+}
+
+fn call(name: &str, args: &[Value]) -> Result<Value, DynCallError> {
+    unsafe { module().call(name, args) }
+}
+
+#[test]
+fn primitives() {
+    assert_eq!(call("add", &[json!(3), json!(-8)]).unwrap(), json!(-5));
+    assert_eq!(call("add", &[json!(255), json!(1)]).unwrap(), json!(256));
+    assert_eq!(call("negate", &[json!(true)]).unwrap(), json!(false));
+    assert_eq!(call("negate", &[json!(false)]).unwrap(), json!(true));
+    assert_eq!(call("halve", &[json!(5.0)]).unwrap(), json!(2.5));
+    assert_eq!(call("nothing", &[]).unwrap(), Value::Null);
+}
+
+#[test]
+fn strings_and_vecs() {
+    assert_eq!(
+        call("greet", &[json!("world"), json!("!")]).unwrap(),
+        json!("hello world!"),
+    );
+    assert_eq!(
+        call("lengths", &[json!(["a", "bcd", ""])]).unwrap(),
+        json!([1, 3, 0]),
+    );
+    assert_eq!(call("lengths", &[json!([])]).unwrap(), json!([]));
+}
+
+#[test]
+fn options_and_results() {
+    assert_eq!(
+        call("find", &[json!([5, 8, 13]), json!(8)]).unwrap(),
+        json!(1)
+    );
+    assert_eq!(
+        call("find", &[json!([5, 8, 13]), json!(21)]).unwrap(),
+        Value::Null,
+    );
+    assert_eq!(call("parse", &[json!("34")]).unwrap(), json!({"Ok": 34}));
+
+    let err = call("parse", &[json!("nope")]).unwrap();
+    assert!(err["Err"].is_string(), "{}", err);
+}
+
+#[test]
+fn json_matches_serde() {
+    assert_eq!(
+        serde_json::to_value(ROk::<u32, RString>(34)).unwrap(),
+        json!({"Ok": 34})
+    );
+    assert_eq!(serde_json::to_value(RSome(3)).unwrap(), json!(3));
+    assert_eq!(serde_json::to_value(RNone::<u32>).unwrap(), Value::Null);
+}
+
+#[test]
+fn structs() {
+    assert_eq!(
+        call(
+            "move_point",
+            &[json!({"x": 3, "y": 1.5, "label": "a"}), json!(10)]
+        )
+        .unwrap(),
+        json!({"x": 13, "y": 3.0, "label": "a!"}),
+    );
+    assert_eq!(
+        call("points", &[json!(2)]).unwrap(),
+        json!([
+            {"x": 0, "y": 0.5, "label": "0"},
+            {"x": 1, "y": 0.5, "label": "1"},
+        ]),
+    );
+}
+
+#[test]
+fn invalid_arguments() {
+    let invalid_index = |name: &str, args: &[Value]| match call(name, args) {
+        Err(DynCallError::InvalidArgument { index, .. }) => index,
+        x => panic!("expected an InvalidArgument error,found {:?}", x),
+    };
+
+    assert_eq!(invalid_index("add", &[json!(256), json!(0)]), 0);
+    assert_eq!(invalid_index("add", &[json!(-1), json!(0)]), 0);
+    assert_eq!(invalid_index("add", &[json!(0), json!(0.5)]), 1);
+    assert_eq!(invalid_index("negate", &[json!(1)]), 0);
+    assert_eq!(invalid_index("greet", &[json!("a"), json!(null)]), 1);
+    assert_eq!(invalid_index("lengths", &[json!(["a", 3])]), 0);
+    assert_eq!(invalid_index("parse", &[json!({"Ok": "a"})]), 0);
+    assert_eq!(
+        invalid_index("move_point", &[json!({"x": 3, "y": 1.5}), json!(0)]),
+        0
+    );
+    assert_eq!(
+        invalid_index(
+            "move_point",
+            &[json!({"x": 3, "y": 1.5, "label": "", "z": 0}), json!(0)]
+        ),
+        0
+    );
+
+    assert_eq!(
+        call("add", &[json!(3)]),
+        Err(DynCallError::ArgumentCount {
+            function: "add".into(),
+            expected: 2,
+            found: 1,
+        }),
+    );
+}
+
+#[test]
+fn lookup_errors() {
+    assert_eq!(
+        call("what", &[]),
+        Err(DynCallError::UnknownFunction {
+            name: "what".into()
+        }),
+    );
+    assert_eq!(
+        call("value", &[]),
+        Err(DynCallError::NotAFunction {
+            name: "value".into()
+        }),
+    );
+    match call("references", &[json!(3)]) {
+        Err(DynCallError::UnsupportedType { function, .. }) => assert_eq!(function, "references"),
+        x => panic!("expected an UnsupportedType error,found {:?}", x),
+    }
+}
+
+#[test]
+fn listing_functions() {
+    let module = module();
+    let names = module.functions().map(|f| f.name()).collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        [
+            "add",
+            "negate",
+            "halve",
+            "greet",
+            "lengths",
+            "find",
+            "parse",
+            "move_point",
+            "points",
+            "nothing",
+            "references",
+        ],
+    );
+
+    let add = module.function("add").unwrap();
+    assert_eq!(add.param_names().collect::<Vec<_>>(), ["a", "b"]);
+    assert!(add.to_string().starts_with("add: fn("), "{}", add);
+}
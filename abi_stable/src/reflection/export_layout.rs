@@ -0,0 +1,291 @@
+//! Data structures to export the `TypeLayout`s that a type references.
+
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+};
+
+use crate::{
+    reflection::type_graph::TypeGraph,
+    std_types::UTypeId,
+    type_layout::{tagging::TagTrait, *},
+};
+
+#[cfg(all(test, not(feature = "only_new_tests")))]
+mod tests;
+
+/// All the types transitively referenced by a type,including itself.
+///
+/// Types reference each other with their index in `types`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ELTypes {
+    /// The index of the type that this was constructed from.
+    root: usize,
+    types: Vec<ELType>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ELType {
+    name: String,
+    #[serde(flatten)]
+    summary: ELTypeSummary,
+    line: u32,
+    size: usize,
+    alignment: usize,
+    is_nonzero: bool,
+    repr: String,
+    generics: ELGenerics,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tag: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    phantom_fields: Vec<ELField>,
+    data: ELData,
+}
+
+/// A type and where it was declared.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ELTypeSummary {
+    full_type: String,
+    package: String,
+    version: String,
+    mod_path: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ELGenerics {
+    lifetimes: Vec<String>,
+    /// The indices of the type parameters.
+    types: Vec<usize>,
+    consts: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum ELData {
+    Primitive {
+        primitive: String,
+    },
+    Opaque,
+    Struct {
+        fields: Vec<ELField>,
+    },
+    Union {
+        fields: Vec<ELField>,
+    },
+    Enum {
+        nonexhaustive: bool,
+        variants: Vec<ELVariant>,
+    },
+    PrefixType {
+        first_suffix_field: u8,
+        fields: Vec<ELField>,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ELVariant {
+    name: String,
+    discriminant: String,
+    fields: Vec<ELField>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ELField {
+    name: String,
+    /// The index of the type of the field,None if the field is a function pointer.
+    type_: Option<usize>,
+    /// The function pointers in the type of the field.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    functions: Vec<ELFunction>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ELFunction {
+    signature: String,
+    params: Vec<ELParam>,
+    /// The index of the return type,None if it returns `()`.
+    returns: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ELParam {
+    name: String,
+    /// The index of the type of the parameter.
+    type_: usize,
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+impl ELTypes {
+    /// Exports every type in `graph`,
+    /// using the first one in the graph as the root.
+    pub fn from_type_graph(graph: &TypeGraph) -> Self {
+        let indices = graph
+            .nodes()
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (node.layout().get_utypeid(), i))
+            .collect::<HashMap<UTypeId, usize>>();
+
+        let index_of = |layout: &'static TypeLayout| indices[&layout.get_utypeid()];
+
+        let types = graph
+            .nodes()
+            .iter()
+            .map(|node| ELType::new(node.layout(), &index_of))
+            .collect();
+
+        Self { root: 0, types }
+    }
+}
+
+impl ELTypeSummary {
+    /// Summarizes every type in `graph`.
+    pub fn from_type_graph(graph: &TypeGraph) -> Vec<Self> {
+        graph
+            .nodes()
+            .iter()
+            .map(|node| Self::new(node.layout()))
+            .collect()
+    }
+
+    /// Summarizes the type with the `layout` layout.
+    pub fn new(layout: &'static TypeLayout) -> Self {
+        let (package, version) = layout.package_and_version();
+        Self {
+            full_type: layout.full_type().to_string(),
+            package: package.to_string(),
+            version: version.to_string(),
+            mod_path: layout.mod_path().to_string(),
+        }
+    }
+}
+
+impl Display for ELTypeSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} package:'{}' version:'{}' mod:{}",
+            self.full_type, self.package, self.version, self.mod_path
+        )
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+impl ELType {
+    fn new<F>(layout: &'static TypeLayout, index_of: &F) -> Self
+    where
+        F: Fn(&'static TypeLayout) -> usize,
+    {
+        let fields = |fields: TLFields| {
+            fields
+                .iter()
+                .map(|f| ELField::new(f, index_of))
+                .collect::<Vec<_>>()
+        };
+
+        let data = match layout.data() {
+            TLData::Primitive(prim) => ELData::Primitive {
+                primitive: format!("{:?}", prim),
+            },
+            TLData::Opaque => ELData::Opaque,
+            TLData::Struct { fields: f } => ELData::Struct { fields: fields(f) },
+            TLData::Union { fields: f } => ELData::Union { fields: fields(f) },
+            TLData::Enum(enum_) => {
+                let mut all_fields = enum_.fields.iter();
+                let variants = enum_
+                    .variant_names_iter()
+                    .zip(enum_.field_count.iter())
+                    .enumerate()
+                    .map(|(i, (name, &field_count))| ELVariant {
+                        name: name.to_string(),
+                        discriminant: enum_
+                            .discriminants
+                            .get(i)
+                            .map_or_else(String::new, |d| format!("{:?}", d)),
+                        fields: all_fields
+                            .by_ref()
+                            .take(field_count as usize)
+                            .map(|f| ELField::new(f, index_of))
+                            .collect(),
+                    })
+                    .collect();
+                ELData::Enum {
+                    nonexhaustive: enum_.exhaustiveness.is_nonexhaustive(),
+                    variants,
+                }
+            }
+            TLData::PrefixType(prefix) => ELData::PrefixType {
+                first_suffix_field: prefix.first_suffix_field,
+                fields: fields(prefix.fields),
+            },
+        };
+
+        let generics = layout.generics();
+        let tag = layout.tag();
+
+        Self {
+            name: layout.name().to_string(),
+            summary: ELTypeSummary::new(layout),
+            line: layout.item_info().line,
+            size: layout.size(),
+            alignment: layout.alignment(),
+            is_nonzero: layout.is_nonzero(),
+            repr: format!("{:?}", layout.repr_attr()),
+            generics: ELGenerics {
+                lifetimes: generics.lifetimes().map(String::from).collect(),
+                types: generics
+                    .type_params()
+                    .iter()
+                    .map(|ty| index_of(ty.get()))
+                    .collect(),
+                consts: generics
+                    .const_params()
+                    .iter()
+                    .map(|c| format!("{:?}", c))
+                    .collect(),
+            },
+            tag: if tag.is_null() {
+                None
+            } else {
+                Some(tag.to_string())
+            },
+            phantom_fields: fields(layout.phantom_fields()),
+            data,
+        }
+    }
+}
+
+impl ELField {
+    fn new<F>(field: TLField, index_of: &F) -> Self
+    where
+        F: Fn(&'static TypeLayout) -> usize,
+    {
+        let functions = field
+            .function_range()
+            .iter()
+            .map(|func| ELFunction {
+                signature: func.to_string(),
+                params: func
+                    .get_params()
+                    .map(|param| ELParam {
+                        name: param.name().to_string(),
+                        type_: index_of(param.layout()),
+                    })
+                    .collect(),
+                returns: func.return_type_layout.map(|ret| index_of(ret.get())),
+            })
+            .collect();
+
+        Self {
+            name: field.name().to_string(),
+            type_: if field.is_function() {
+                None
+            } else {
+                Some(index_of(field.layout()))
+            },
+            functions,
+        }
+    }
+}
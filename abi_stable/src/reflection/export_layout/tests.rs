@@ -0,0 +1,120 @@
+use super::*;
+
+use crate::{
+    std_types::{ROption, RString, RVec},
+    StableAbi,
+};
+
+use serde_json::{json, Value};
+
+#[repr(u8)]
+#[derive(StableAbi)]
+pub enum Shape {
+    Circle { radius: f32 },
+    Square(u32),
+    Nothing,
+}
+
+#[repr(C)]
+#[derive(StableAbi)]
+pub struct Canvas<'a, T> {
+    pub shapes: RVec<Shape>,
+    pub name: &'a RString,
+    pub extra: ROption<T>,
+    pub draw: extern "C" fn(&Shape, scale: u32) -> bool,
+}
+
+fn export() -> (TypeGraph, Value) {
+    let graph = TypeGraph::from_type_layout(<Canvas<'static, u64> as StableAbi>::LAYOUT);
+    let json = serde_json::to_value(ELTypes::from_type_graph(&graph)).unwrap();
+    (graph, json)
+}
+
+fn find_type<'a>(json: &'a Value, name: &str) -> (usize, &'a Value) {
+    json["types"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .enumerate()
+        .find(|(_, ty)| ty["name"] == name)
+        .unwrap_or_else(|| panic!("no type named {}", name))
+}
+
+#[test]
+fn root_type() {
+    let (_, json) = export();
+    assert_eq!(json["root"], 0);
+
+    let root = &json["types"][0];
+    assert_eq!(root["name"], "Canvas");
+    assert_eq!(root["package"], "abi_stable");
+    assert_eq!(root["version"], env!("CARGO_PKG_VERSION"));
+    assert!(root["mod_path"].as_str().unwrap().contains("export_layout"));
+    assert_eq!(root["repr"], "C");
+    assert_eq!(root["size"], std::mem::size_of::<Canvas<'static, u64>>());
+    assert_eq!(root["generics"]["lifetimes"], json!(["'a"]));
+
+    let (u64_index, _) = find_type(&json, "u64");
+    let extra = &root["data"]["fields"][2];
+    let (_, extra_type) = find_type(&json, "ROption");
+    assert_eq!(
+        json["types"][extra["type_"].as_u64().unwrap() as usize],
+        *extra_type
+    );
+    assert_eq!(
+        extra_type["data"]["variants"][0]["fields"][0]["type_"],
+        u64_index
+    );
+
+    let fields = root["data"]["fields"].as_array().unwrap();
+    assert_eq!(root["data"]["kind"], "Struct");
+    assert_eq!(fields.len(), 4);
+    assert_eq!(fields[0]["name"], "shapes");
+
+    let draw = &fields[3];
+    assert_eq!(draw["type_"], Value::Null);
+    let function = &draw["functions"][0];
+    assert_eq!(function["params"][1]["name"], "scale");
+    let (bool_index, _) = find_type(&json, "bool");
+    assert_eq!(function["returns"], bool_index);
+}
+
+#[test]
+fn enum_type() {
+    let (_, json) = export();
+    let (_, shape) = find_type(&json, "Shape");
+
+    assert_eq!(shape["data"]["kind"], "Enum");
+    assert_eq!(shape["data"]["nonexhaustive"], false);
+
+    let variants = shape["data"]["variants"].as_array().unwrap();
+    let names = variants
+        .iter()
+        .map(|v| {
+            (
+                v["name"].as_str().unwrap(),
+                v["fields"].as_array().unwrap().len(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(names, [("Circle", 1), ("Square", 1), ("Nothing", 0)]);
+    assert_eq!(variants[0]["fields"][0]["name"], "radius");
+}
+
+#[test]
+fn type_summaries() {
+    let (graph, json) = export();
+    let summaries = ELTypeSummary::from_type_graph(&graph);
+
+    assert_eq!(summaries.len(), json["types"].as_array().unwrap().len());
+    assert_eq!(summaries.len(), graph.nodes().len());
+
+    let listing = summaries.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+    assert!(
+        listing
+            .iter()
+            .any(|x| x.starts_with("Shape package:'abi_stable'")),
+        "{:#?}",
+        listing,
+    );
+}
@@ -4,13 +4,27 @@ use std::fmt::{self, Display};
 
 use core_extensions::SelfOps;
 
-use crate::{reflection::ModReflMode, type_layout::*};
+use crate::{
+    reflection::ModReflMode,
+    type_layout::{tagging::TagTrait, *},
+};
+
+pub mod markdown;
+
+#[cfg(all(test, not(feature = "only_new_tests")))]
+mod tests;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MRItem {
     item_name: String,
     type_: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    docs: String,
+    #[serde(flatten)]
+    type_info: MRTypeInfo,
     field_accessor: MRFieldAccessor,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    accessibility: Option<MRAccessibility>,
     #[serde(flatten)]
     variant: MRItemVariant,
 }
@@ -19,6 +33,47 @@ pub struct MRItem {
 pub struct MRNameType {
     name: String,
     type_: String,
+    #[serde(flatten)]
+    type_info: MRTypeInfo,
+}
+
+/// Where a type comes from,and its tag.
+///
+/// This is empty for function pointers.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct MRTypeInfo {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    package: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    version: String,
+    /// The `#[sabi(tag = ...)]` of the type,if it has one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tag: Option<String>,
+}
+
+/// Whether a field of a prefix type is accessible.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MRAccessibility {
+    /// Whether the field is in the prefix of the type,
+    /// which is the fields up to the one with the `#[sabi(last_prefix_field)]` attribute.
+    in_prefix: bool,
+    /// Whether the field has the `#[sabi(accessible_if = expression)]` attribute.
+    conditional: bool,
+    /// Whether the field is accessible in the binary that the layout comes from.
+    accessible: bool,
+    /// What the accessor does if the field is missing,
+    /// None if the field is always accessible.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    on_missing: Option<MROnMissingField>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum MROnMissingField {
+    ReturnOption,
+    Panic,
+    With,
+    Value,
+    Default,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -70,41 +125,79 @@ impl MRItem {
         Self {
             item_name: "root".into(),
             type_,
+            docs: Self::deref_target(layout)
+                .prefix_extra()
+                .map_or("", |extra| extra.type_docs())
+                .to_string(),
+            type_info: MRTypeInfo::from(Self::deref_target(layout)),
             field_accessor: MRFieldAccessor::Direct,
+            accessibility: None,
             variant,
         }
     }
 
+    /// Gets the layout of the type that `layout` delegates its module reflection to,
+    /// eg:the layout of the prefix type for `PrefixRef`s.
+    fn deref_target(layout: &'static TypeLayout) -> &'static TypeLayout {
+        match layout.mod_refl_mode() {
+            ModReflMode::DelegateDeref { layout_index } => {
+                let delegate_to = layout.shared_vars().type_layouts()[layout_index as usize];
+                Self::deref_target(delegate_to.get())
+            }
+            ModReflMode::Module | ModReflMode::Opaque => layout,
+        }
+    }
+
     fn get_item_variant(layout: &'static TypeLayout) -> MRItemVariant {
         match layout.mod_refl_mode() {
             ModReflMode::Module => {
-                let fields = match layout.data() {
-                    TLData::Struct { fields } => fields,
-                    TLData::PrefixType(prefix) => prefix.fields,
+                let (fields, prefix) = match layout.data() {
+                    TLData::Struct { fields } => (fields, None),
+                    TLData::PrefixType(prefix) => (prefix.fields, Some(prefix)),
                     TLData::Primitive { .. }
                     | TLData::Opaque { .. }
                     | TLData::Union { .. }
                     | TLData::Enum { .. } => return MRItemVariant::Static,
                 };
+                let extra = layout.prefix_extra();
 
                 let items = fields
                     .iter()
-                    .filter(|f| f.field_accessor() != FieldAccessor::Opaque)
-                    .map(|field| {
-                        let (type_, variant) = if field.is_function() {
+                    .enumerate()
+                    .filter(|(_, f)| f.field_accessor() != FieldAccessor::Opaque)
+                    .map(|(index, field)| {
+                        let (type_, type_info, variant) = if field.is_function() {
                             let func = MRFunction::from(&field.function_range().index(0));
-                            (func.to_string(), MRItemVariant::Function(func))
+                            (
+                                func.to_string(),
+                                MRTypeInfo::default(),
+                                MRItemVariant::Function(func),
+                            )
                         } else {
                             let layout = field.layout();
                             (
                                 layout.full_type().to_string(),
+                                MRTypeInfo::from(Self::deref_target(layout)),
                                 Self::get_item_variant(layout),
                             )
                         };
+                        let accessibility = prefix.map(|prefix| MRAccessibility {
+                            in_prefix: index < prefix.first_suffix_field as usize,
+                            conditional: prefix.conditional_prefix_fields.is_conditional(index),
+                            accessible: prefix.accessible_fields.is_accessible(index),
+                            on_missing: extra
+                                .and_then(|extra| extra.on_missing_field(index))
+                                .map(MROnMissingField::from),
+                        });
                         MRItem {
                             item_name: field.name().to_string(),
                             type_,
+                            docs: extra
+                                .map_or("", |extra| extra.field_docs(index))
+                                .to_string(),
+                            type_info,
                             field_accessor: field.field_accessor().into(),
+                            accessibility,
                             variant,
                         }
                     })
@@ -115,11 +208,7 @@ impl MRItem {
                 })
             }
             ModReflMode::Opaque => MRItemVariant::Static,
-            ModReflMode::DelegateDeref { layout_index } => {
-                let delegate_to = layout.shared_vars().type_layouts()[layout_index as usize];
-                let inner_layout = delegate_to.get();
-                Self::get_item_variant(inner_layout)
-            }
+            ModReflMode::DelegateDeref { .. } => Self::get_item_variant(Self::deref_target(layout)),
         }
     }
 }
@@ -160,13 +249,21 @@ impl Display for MRFunction {
 impl From<TLField> for MRNameType {
     fn from(field: TLField) -> Self {
         let name = field.name().to_string();
-        let type_ = if field.is_function() {
-            field.function_range().index(0).to_string()
+        let (type_, type_info) = if field.is_function() {
+            (
+                field.function_range().index(0).to_string(),
+                MRTypeInfo::default(),
+            )
         } else {
-            field.layout().full_type().to_string()
+            let layout = field.layout();
+            (layout.full_type().to_string(), MRTypeInfo::from(layout))
         };
 
-        Self { name, type_ }
+        Self {
+            name,
+            type_,
+            type_info,
+        }
     }
 }
 
@@ -178,6 +275,38 @@ impl Display for MRNameType {
 
 ///////////////////////////////////////////////////////////////////////////////
 
+impl From<&'static TypeLayout> for MRTypeInfo {
+    fn from(layout: &'static TypeLayout) -> Self {
+        let (package, version) = layout.package_and_version();
+        let tag = layout.tag();
+        Self {
+            package: package.to_string(),
+            version: version.to_string(),
+            tag: if tag.is_null() {
+                None
+            } else {
+                Some(tag.to_string())
+            },
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+impl From<OnMissingField> for MROnMissingField {
+    fn from(this: OnMissingField) -> Self {
+        match this {
+            OnMissingField::ReturnOption => MROnMissingField::ReturnOption,
+            OnMissingField::Panic => MROnMissingField::Panic,
+            OnMissingField::With => MROnMissingField::With,
+            OnMissingField::Value => MROnMissingField::Value,
+            OnMissingField::Default => MROnMissingField::Default,
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
 impl From<ModReflMode> for MRModReflMode {
     fn from(this: ModReflMode) -> Self {
         match this {
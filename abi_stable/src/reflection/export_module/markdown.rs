@@ -0,0 +1,301 @@
+//! Renders the module structure of a type as Markdown pages,one for each module.
+
+use std::{
+    collections::HashSet,
+    fmt::{self, Display, Write},
+};
+
+use super::*;
+
+use crate::{reflection::type_graph::TypeGraph, std_types::UTypeId};
+
+/// A Markdown page documenting a module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkdownPage {
+    /// The name of the file that the page is linked to by other pages.
+    pub file_name: String,
+    pub contents: String,
+}
+
+/// Renders the module structure of the type with the `layout` layout as Markdown,
+/// with one page for the type itself,and one page for every submodule.
+///
+/// The first page is the one for `layout`.
+pub fn markdown_pages(layout: &'static TypeLayout) -> Vec<MarkdownPage> {
+    let root = MRItem::from_type_layout(layout);
+    let name = MRItem::deref_target(layout).name();
+    let mut pages = Vec::new();
+    add_pages(&root, layout, name, &mut pages);
+    pages
+}
+
+fn add_pages(
+    item: &MRItem,
+    layout: &'static TypeLayout,
+    path: &str,
+    pages: &mut Vec<MarkdownPage>,
+) {
+    let module = match &item.variant {
+        MRItemVariant::Module(module) => module,
+        MRItemVariant::Function { .. } | MRItemVariant::Static => return,
+    };
+    let fields = module_fields(layout);
+    let field_of = |item: &MRItem| fields.iter().find(|f| f.name() == item.item_name).copied();
+
+    // Only prefix types get their own page,other types are documented as fields.
+    let is_submodule = |item: &MRItem| {
+        matches!(item.variant, MRItemVariant::Module { .. })
+            && field_of(item).map_or(false, |f| MRItem::deref_target(f.layout()).is_prefix_kind())
+    };
+
+    let submodules = module
+        .items
+        .iter()
+        .filter(|x| is_submodule(x))
+        .collect::<Vec<_>>();
+    let functions = module
+        .items
+        .iter()
+        .filter(|x| matches!(x.variant, MRItemVariant::Function { .. }))
+        .collect::<Vec<_>>();
+    let statics = module
+        .items
+        .iter()
+        .filter(|x| !is_submodule(x) && !matches!(x.variant, MRItemVariant::Function { .. }))
+        .collect::<Vec<_>>();
+
+    let nonexhaustive = nonexhaustive_enums(
+        functions
+            .iter()
+            .chain(&statics)
+            .filter_map(|item| field_of(item))
+            .flat_map(field_layouts),
+    );
+
+    let mut out = String::new();
+    let w = &mut out;
+
+    let _ = writeln!(w, "# `{}`\n", path);
+    if !item.docs.is_empty() {
+        let _ = writeln!(w, "{}\n", item.docs);
+    }
+    let _ = writeln!(w, "Type: `{}`\n", item.type_);
+    let _ = write!(w, "{}", Origin(&item.type_info));
+
+    if !submodules.is_empty() {
+        let _ = writeln!(w, "## Submodules\n");
+        for sub in &submodules {
+            let _ = write!(w, "- [`{}`]({}.{}.md)", sub.item_name, path, sub.item_name);
+            if let Some(line) = sub.docs.lines().next() {
+                let _ = write!(w, ": {}", line);
+            }
+            let _ = writeln!(w);
+        }
+        let _ = writeln!(w);
+    }
+
+    if !functions.is_empty() {
+        let _ = writeln!(w, "## Functions\n");
+        for func in &functions {
+            if let MRItemVariant::Function(signature) = &func.variant {
+                let _ = writeln!(w, "### `{}`\n", func.item_name);
+                let _ = writeln!(
+                    w,
+                    "```rust\n{}\n```\n",
+                    Signature(&func.item_name, signature)
+                );
+                write_item_body(w, func);
+            }
+        }
+    }
+
+    if !statics.is_empty() {
+        let _ = writeln!(w, "## Fields\n");
+        for static_ in &statics {
+            let _ = writeln!(w, "### `{}: {}`\n", static_.item_name, static_.type_);
+            let _ = write!(w, "{}", Origin(&static_.type_info));
+            write_item_body(w, static_);
+        }
+    }
+
+    if !nonexhaustive.is_empty() {
+        let _ = writeln!(w, "## Nonexhaustive enums\n");
+        for enum_layout in nonexhaustive {
+            write_nonexhaustive_enum(w, enum_layout);
+        }
+    }
+
+    pages.push(MarkdownPage {
+        file_name: format!("{}.md", path),
+        contents: out,
+    });
+
+    for sub in submodules {
+        if let Some(field) = field_of(sub) {
+            let sub_path = format!("{}.{}", path, sub.item_name);
+            add_pages(sub, field.layout(), &sub_path, pages);
+        }
+    }
+}
+
+fn write_item_body(w: &mut String, item: &MRItem) {
+    if let Some(accessibility) = &item.accessibility {
+        let _ = writeln!(w, "Availability: {}\n", Availability(accessibility));
+    }
+    if !item.docs.is_empty() {
+        let _ = writeln!(w, "{}\n", item.docs);
+    }
+}
+
+fn write_nonexhaustive_enum(w: &mut String, layout: &'static TypeLayout) {
+    let enum_ = match layout.data() {
+        TLData::Enum(enum_) => enum_,
+        _ => return,
+    };
+    let _ = writeln!(w, "### `{}`\n", layout.full_type());
+    let _ = write!(w, "{}", Origin(&MRTypeInfo::from(layout)));
+    let _ = writeln!(
+        w,
+        "Variants may be added in minor versions, \
+         so matching on this enum requires a fallback branch.\n"
+    );
+
+    let mut fields = enum_.fields.iter();
+    for (name, &field_count) in enum_.variant_names_iter().zip(enum_.field_count.iter()) {
+        let _ = write!(w, "- `{}`", name);
+        let variant_fields = fields.by_ref().take(field_count as usize);
+        let mut variant_fields = variant_fields.peekable();
+        if variant_fields.peek().is_some() {
+            let _ = write!(w, ":");
+            for (i, field) in variant_fields.enumerate() {
+                let sep = if i == 0 { " " } else { ", " };
+                let _ = write!(w, "{}`{}: {}`", sep, field.name(), field.full_type());
+            }
+        }
+        let _ = writeln!(w);
+    }
+    let _ = writeln!(w);
+}
+
+/// The fields of the module type with the `layout` layout.
+fn module_fields(layout: &'static TypeLayout) -> Vec<TLField> {
+    match MRItem::deref_target(layout).data() {
+        TLData::Struct { fields } => fields.iter().collect(),
+        TLData::PrefixType(prefix) => prefix.fields.iter().collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// The layouts of the types that `field` directly references,
+/// which for function pointers are the parameter and return types.
+fn field_layouts(field: TLField) -> Vec<&'static TypeLayout> {
+    if field.is_function() {
+        field
+            .function_range()
+            .iter()
+            .flat_map(|func| {
+                let params = func.get_params().map(|p| p.layout()).collect::<Vec<_>>();
+                params
+                    .into_iter()
+                    .chain(func.return_type_layout.map(|ret| ret.get()))
+            })
+            .collect()
+    } else {
+        vec![field.layout()]
+    }
+}
+
+/// Finds all the nonexhaustive enums that `layouts` transitively reference.
+fn nonexhaustive_enums<I>(layouts: I) -> Vec<&'static TypeLayout>
+where
+    I: IntoIterator<Item = &'static TypeLayout>,
+{
+    let mut visited = HashSet::<UTypeId>::new();
+    let mut enums = Vec::new();
+    for layout in layouts {
+        for node in TypeGraph::from_type_layout(layout).nodes() {
+            let layout = node.layout();
+            let is_nonexhaustive = match layout.data() {
+                TLData::Enum(enum_) => enum_.exhaustiveness.is_nonexhaustive(),
+                _ => false,
+            };
+            if is_nonexhaustive && visited.insert(layout.get_utypeid()) {
+                enums.push(layout);
+            }
+        }
+    }
+    enums
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+struct Signature<'a>(&'a str, &'a MRFunction);
+
+impl Display for Signature<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Signature(name, func) = *self;
+        write!(f, "fn {}(", name)?;
+        for (i, param) in func.params.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}: {}", param.name, param.type_)?;
+        }
+        write!(f, ")")?;
+        if func.returns.type_ != "()" {
+            write!(f, " -> {}", func.returns.type_)?;
+        }
+        Ok(())
+    }
+}
+
+struct Origin<'a>(&'a MRTypeInfo);
+
+impl Display for Origin<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let info = self.0;
+        if !info.package.is_empty() {
+            writeln!(
+                f,
+                "Declared in the `{}` package, version `{}`.\n",
+                info.package, info.version
+            )?;
+        }
+        Ok(())
+    }
+}
+
+struct Availability<'a>(&'a MRAccessibility);
+
+impl Display for Availability<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let this = self.0;
+        if !this.accessible {
+            return f.write_str("disabled in this library.");
+        }
+        if this.conditional {
+            return f.write_str(
+                "conditionally available, depending on the `#[sabi(accessible_if)]` attribute.",
+            );
+        }
+        if this.in_prefix {
+            return f.write_str("always available.");
+        }
+        f.write_str("added in a later version of the module, ")?;
+        f.write_str(match this.on_missing {
+            Some(MROnMissingField::ReturnOption) | None => {
+                "the accessor returns `None` if the library doesn't have it."
+            }
+            Some(MROnMissingField::Panic) => "the accessor panics if the library doesn't have it.",
+            Some(MROnMissingField::With) => {
+                "the accessor calls a fallback function if the library doesn't have it."
+            }
+            Some(MROnMissingField::Value) => {
+                "the accessor returns a fallback value if the library doesn't have it."
+            }
+            Some(MROnMissingField::Default) => {
+                "the accessor returns `Default::default()` if the library doesn't have it."
+            }
+        })
+    }
+}
@@ -0,0 +1,168 @@
+use super::*;
+
+use crate::{
+    for_examples::ValidTag_NE,
+    std_types::{RString, RVec},
+    type_layout::Tag,
+    StableAbi,
+};
+
+use serde_json::{json, Value};
+
+/// Some data.
+#[repr(C)]
+#[derive(StableAbi, Copy, Clone)]
+#[sabi(tag = r#"tag!{[ "data", 3 ]}"#)]
+pub struct Data {
+    pub value: u32,
+}
+
+/// The root module.
+#[repr(C)]
+#[derive(StableAbi)]
+#[sabi(kind(Prefix(prefix_ref = "Module_Ref", prefix_fields = "Module_Prefix")))]
+#[sabi(with_prefix_extra)]
+pub struct Module {
+    /// Gets some data.
+    #[sabi(last_prefix_field)]
+    pub data: Data,
+    /// Greets someone.
+    pub greet: extern "C" fn(name: RString) -> RString,
+    #[sabi(accessible_if = "false")]
+    pub disabled: u8,
+    #[sabi(missing_field(panic))]
+    pub panics: u16,
+}
+
+fn to_json(item: &MRItem) -> Value {
+    serde_json::to_value(item).unwrap()
+}
+
+#[test]
+fn module_items() {
+    let root = MRItem::from_type_layout(<Module_Ref as StableAbi>::LAYOUT);
+    let root = to_json(&root);
+
+    assert_eq!(root["docs"], "The root module.");
+    assert_eq!(root["package"], "abi_stable");
+    assert_eq!(root["version"], env!("CARGO_PKG_VERSION"));
+    assert_eq!(root["variant"], "Module");
+
+    let items = root["items"].as_array().unwrap();
+    let names = items
+        .iter()
+        .map(|x| x["item_name"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["data", "greet", "disabled", "panics"]);
+
+    let data = &items[0];
+    assert_eq!(data["docs"], "Gets some data.");
+    assert_eq!(data["package"], "abi_stable");
+    const TAG: Tag = tag!(["data", 3]);
+    assert_eq!(data["tag"], TAG.to_string());
+    assert_eq!(
+        data["accessibility"],
+        json!({"in_prefix": true, "conditional": false, "accessible": true}),
+    );
+
+    let greet = &items[1];
+    assert_eq!(greet["docs"], "Greets someone.");
+    assert_eq!(greet["variant"], "Function");
+    assert_eq!(greet.get("package"), None);
+    assert_eq!(greet["params"][0]["name"], "name");
+    assert_eq!(greet["params"][0]["package"], "abi_stable");
+    assert_eq!(
+        greet["accessibility"],
+        json!({
+            "in_prefix": false,
+            "conditional": false,
+            "accessible": true,
+            "on_missing": "ReturnOption",
+        }),
+    );
+
+    let disabled = &items[2];
+    assert_eq!(disabled.get("docs"), None);
+    assert_eq!(disabled["accessibility"]["accessible"], false);
+
+    let panics = &items[3];
+    assert_eq!(panics["accessibility"]["on_missing"], "Panic");
+}
+
+#[test]
+fn deserialize() {
+    let root = MRItem::from_type_layout(<Module_Ref as StableAbi>::LAYOUT);
+    let json = to_json(&root);
+    let deserialized = serde_json::from_value::<MRItem>(json.clone()).unwrap();
+    assert_eq!(to_json(&deserialized), json);
+}
+
+/// A plugin.
+#[repr(C)]
+#[derive(StableAbi)]
+#[sabi(kind(Prefix(prefix_ref = "Plugin_Ref", prefix_fields = "Plugin_Prefix")))]
+#[sabi(with_prefix_extra)]
+pub struct Plugin {
+    /// The root module.
+    pub module: Module_Ref,
+    #[sabi(last_prefix_field)]
+    pub tags: extern "C" fn(count: u32) -> RVec<ValidTag_NE>,
+}
+
+#[test]
+fn markdown() {
+    let pages = markdown::markdown_pages(<Plugin_Ref as StableAbi>::LAYOUT);
+    let names = pages.iter().map(|p| &*p.file_name).collect::<Vec<_>>();
+    assert_eq!(names, ["Plugin.md", "Plugin.module.md"]);
+
+    let plugin = &pages[0].contents;
+    assert!(
+        plugin.starts_with("# `Plugin`\n\nA plugin.\n"),
+        "{}",
+        plugin
+    );
+    assert!(
+        plugin.contains("- [`module`](Plugin.module.md): The root module.\n"),
+        "{}",
+        plugin
+    );
+    assert!(
+        plugin.contains(
+            "```rust\n\
+             fn tags(count: u32) -> \
+             RVec<NonExhaustive<OpaqueField, OpaqueField, OpaqueField, ValidTag>>\n\
+             ```\n"
+        ),
+        "{}",
+        plugin
+    );
+    assert!(plugin.contains("## Nonexhaustive enums"), "{}", plugin);
+    assert!(
+        plugin.contains("- `Tag`: `name: RString`, `tag: RString`\n"),
+        "{}",
+        plugin
+    );
+
+    let module = &pages[1].contents;
+    assert!(module.starts_with("# `Plugin.module`\n"), "{}", module);
+    assert!(
+        module.contains("```rust\nfn greet(name: RString) -> RString\n```\n"),
+        "{}",
+        module
+    );
+    assert!(
+        module.contains(
+            "### `panics: u16`\n\nDeclared in the `std` package, version `1.0.0`.\n\n\
+             Availability: added in a later version of the module, \
+             the accessor panics if the library doesn't have it.\n"
+        ),
+        "{}",
+        module
+    );
+    assert!(
+        module.contains("Availability: disabled in this library.\n"),
+        "{}",
+        module
+    );
+    assert!(!module.contains("Nonexhaustive"), "{}", module);
+}
@@ -0,0 +1,332 @@
+//! Runtime access to the fields of prefix types by name,
+//! using the `TypeLayout` of the prefix type to find them.
+
+use std::fmt;
+
+use crate::{
+    abi_stability::{PrefixStableAbi, StableAbi},
+    marker_type::ErasedObject,
+    prefix_type::{FieldAccessibility, IsAccessible, PrefixRef},
+    sabi_types::RRef,
+    type_layout::{
+        OnMissingField, ReprAttr, TLData, TLField, TLPrefixExtra, TLPrefixType, TypeLayout,
+    },
+};
+
+#[cfg(all(test, not(feature = "only_new_tests")))]
+mod tests;
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// The fields of a prefix type (eg:a module loaded from a dynamic library),
+/// which can be accessed by name at runtime.
+///
+/// The accessibility of each field is determined both by the layout of the prefix type
+/// in this binary, and by the metadata that the prefix type was constructed with,
+/// which is how the derive-generated accessors determine it.
+///
+/// # Example
+///
+/// ```rust
+/// use abi_stable::{
+///     for_examples::{Module, Module_Ref},
+///     prefix_type::{PrefixTypeTrait, WithMetadata},
+///     reflection::prefix_fields::PrefixFields,
+///     std_types::{RNone, RStr},
+/// };
+///
+/// const MOD_WM: &WithMetadata<Module> = &WithMetadata::new(
+///     PrefixTypeTrait::METADATA,
+///     Module {
+///         first: RNone,
+///         second: RStr::from_str("hello"),
+///         third: 100,
+///     },
+/// );
+///
+/// let module = Module_Ref(MOD_WM.static_as_prefix());
+///
+/// let fields = PrefixFields::new(module.0).unwrap();
+///
+/// let third = fields.by_name("third").unwrap();
+/// assert!(third.is_accessible());
+/// assert_eq!(third.layout().name(), "usize");
+/// assert_eq!(third.get_as::<usize>(), Some(&100));
+///
+/// let names = fields.iter().map(|f| f.name()).collect::<Vec<&str>>();
+/// assert_eq!(names, ["first", "second", "third"]);
+///
+/// ```
+#[derive(Copy, Clone)]
+pub struct PrefixFields<'a> {
+    layout: &'static TypeLayout,
+    prefix: TLPrefixType,
+    extra: Option<TLPrefixExtra>,
+    accessible: FieldAccessibility,
+    value: RRef<'a, ErasedObject>,
+}
+
+/// A field of a prefix type,gotten from `PrefixFields`.
+#[derive(Copy, Clone)]
+pub struct PrefixField<'a> {
+    index: usize,
+    field: TLField,
+    accessibility: IsAccessible,
+    in_prefix: bool,
+    is_conditional: bool,
+    extra: Option<TLPrefixExtra>,
+    value: Option<RRef<'a, ErasedObject>>,
+}
+
+impl<'a> PrefixFields<'a> {
+    /// Constructs a `PrefixFields` for the fields that `prefix` points to.
+    ///
+    /// This returns None if the layout of `P` is not that of a prefix type.
+    pub fn new<P>(prefix: PrefixRef<P>) -> Option<Self>
+    where
+        P: PrefixStableAbi,
+    {
+        let layout = <P as PrefixStableAbi>::LAYOUT;
+        let prefix_layout = match layout.data() {
+            TLData::PrefixType(x) => x,
+            _ => return None,
+        };
+
+        let accessible = FieldAccessibility::from_u64(
+            prefix.metadata().field_accessibility().bits() & prefix_layout.accessible_fields.bits(),
+        );
+
+        // The fields start where the prefix does,
+        // so the compiler computes their offset inside `WithMetadata_`.
+        let value = prefix.prefix() as *const P as *const ErasedObject;
+
+        unsafe {
+            Some(Self {
+                layout,
+                prefix: prefix_layout,
+                extra: layout.prefix_extra(),
+                accessible,
+                value: RRef::from_raw(value),
+            })
+        }
+    }
+
+    /// The layout of the prefix type.
+    pub fn layout(&self) -> &'static TypeLayout {
+        self.layout
+    }
+
+    /// The amount of fields the prefix type has in this binary,
+    /// including the inaccessible ones.
+    pub fn len(&self) -> usize {
+        self.prefix.fields.len()
+    }
+
+    /// Whether the prefix type has no fields.
+    pub fn is_empty(&self) -> bool {
+        self.prefix.fields.is_empty()
+    }
+
+    /// Gets the field at the `index` position.
+    pub fn get(&self, index: usize) -> Option<PrefixField<'a>> {
+        self.iter().nth(index)
+    }
+
+    /// Gets the field named `name`.
+    pub fn by_name(&self, name: &str) -> Option<PrefixField<'a>> {
+        self.iter().find(|f| f.name() == name)
+    }
+
+    /// An iterator over all the fields of the prefix type,
+    /// including the inaccessible ones.
+    pub fn iter(&self) -> PrefixFieldsIter<'a> {
+        PrefixFieldsIter {
+            fields: *self,
+            index: 0,
+            offset: 0,
+        }
+    }
+}
+
+impl<'a> IntoIterator for PrefixFields<'a> {
+    type Item = PrefixField<'a>;
+    type IntoIter = PrefixFieldsIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl fmt::Debug for PrefixFields<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PrefixFields")
+            .field("type", &self.layout.full_type())
+            .field("fields", &self.iter().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// An iterator over the fields of a prefix type,returned by `PrefixFields::iter`.
+#[derive(Clone)]
+pub struct PrefixFieldsIter<'a> {
+    fields: PrefixFields<'a>,
+    index: usize,
+    offset: usize,
+}
+
+impl<'a> Iterator for PrefixFieldsIter<'a> {
+    type Item = PrefixField<'a>;
+
+    fn next(&mut self) -> Option<PrefixField<'a>> {
+        let PrefixFields {
+            layout,
+            prefix,
+            extra,
+            accessible,
+            value,
+        } = self.fields;
+        let index = self.index;
+        let field = prefix.fields.get(index)?;
+        let field_layout = field.layout();
+
+        let mut align = field_layout.alignment();
+        if let ReprAttr::Packed {
+            alignment_power_of_two,
+        } = layout.repr_attr()
+        {
+            align = align.min(1 << alignment_power_of_two);
+        }
+        let offset = align_up(self.offset, align);
+
+        self.index += 1;
+        self.offset = offset + field_layout.size();
+
+        let accessibility = IsAccessible::new(accessible.is_accessible(index));
+        let value = if accessibility.is_accessible() {
+            // Accessible fields are always in bounds of the pointed-to value,
+            // since fields that the value doesn't have are inaccessible.
+            unsafe {
+                let ptr = (value.as_ptr() as *const u8).add(offset);
+                Some(RRef::from_raw(ptr as *const ErasedObject))
+            }
+        } else {
+            None
+        };
+
+        Some(PrefixField {
+            index,
+            field,
+            accessibility,
+            in_prefix: index < prefix.first_suffix_field as usize,
+            is_conditional: prefix.conditional_prefix_fields.is_conditional(index),
+            extra,
+            value,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.fields.len().saturating_sub(self.index);
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for PrefixFieldsIter<'_> {}
+
+///////////////////////////////////////////////////////////////////////////////
+
+impl<'a> PrefixField<'a> {
+    /// The name of the field.
+    pub fn name(&self) -> &'static str {
+        self.field.name()
+    }
+
+    /// The position of the field in the prefix type.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The layout of the type of the field.
+    pub fn layout(&self) -> &'static TypeLayout {
+        self.field.layout()
+    }
+
+    /// The full description of the field,
+    /// which includes the signature of function pointers.
+    pub fn tl_field(&self) -> TLField {
+        self.field
+    }
+
+    /// Whether the field can be accessed.
+    pub fn accessibility(&self) -> IsAccessible {
+        self.accessibility
+    }
+
+    /// Whether the field can be accessed.
+    pub fn is_accessible(&self) -> bool {
+        self.accessibility.is_accessible()
+    }
+
+    /// Whether the field is in the prefix of the type,
+    /// which is the fields up to the one with the `#[sabi(last_prefix_field)]` attribute.
+    pub fn in_prefix(&self) -> bool {
+        self.in_prefix
+    }
+
+    /// Whether the field has the `#[sabi(accessible_if = expression)]` attribute.
+    pub fn is_conditional(&self) -> bool {
+        self.is_conditional
+    }
+
+    /// What the accessor of the field does when the field is missing,
+    /// returning None if the field is always accessible.
+    pub fn on_missing_field(&self) -> Option<OnMissingField> {
+        self.extra?.on_missing_field(self.index)
+    }
+
+    /// The doc comments of the field.
+    pub fn docs(&self) -> &'static str {
+        self.extra.map_or("", |extra| extra.field_docs(self.index))
+    }
+
+    /// Gets a type-erased reference to the field,returning None if it's inaccessible.
+    pub fn get(&self) -> Option<RRef<'a, ErasedObject>> {
+        self.value
+    }
+
+    /// Gets a reference to the field,
+    /// returning None if it's inaccessible or its type is not `T`.
+    ///
+    /// Function pointer fields don't store the layout of their exact type,
+    /// so they must be accessed with `get`,
+    /// after checking their signature with `tl_field`.
+    pub fn get_as<T>(&self) -> Option<&'a T>
+    where
+        T: StableAbi,
+    {
+        if T::LAYOUT.get_utypeid() != self.layout().get_utypeid() {
+            return None;
+        }
+        self.value.map(|x| unsafe { x.transmute_into_ref::<T>() })
+    }
+}
+
+impl fmt::Debug for PrefixField<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PrefixField")
+            .field("name", &self.name())
+            .field("index", &self.index)
+            .field("type", &self.layout().full_type())
+            .field("accessibility", &self.accessibility)
+            .field("in_prefix", &self.in_prefix)
+            .field("is_conditional", &self.is_conditional)
+            .finish()
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+const fn align_up(offset: usize, align: usize) -> usize {
+    (offset + align - 1) & !(align - 1)
+}
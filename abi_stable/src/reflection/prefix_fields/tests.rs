@@ -0,0 +1,229 @@
+use super::*;
+
+use crate::{
+    prefix_type::{PrefixTypeTrait, WithMetadata},
+    std_types::{RStr, RString},
+};
+
+mod v0 {
+    use super::*;
+
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(kind(Prefix(prefix_ref = "Module_Ref", prefix_fields = "Module_Prefix")))]
+    pub struct Module {
+        pub byte: u8,
+        #[sabi(last_prefix_field)]
+        pub name: RStr<'static>,
+    }
+
+    pub const MOD_VAL: &WithMetadata<Module> = &WithMetadata::new(
+        PrefixTypeTrait::METADATA,
+        Module {
+            byte: 3,
+            name: RStr::from_str("v0"),
+        },
+    );
+}
+
+mod v1 {
+    use super::*;
+
+    /// The second version of the module.
+    ///
+    /// It has more fields.
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(kind(Prefix(prefix_ref = "Module_Ref", prefix_fields = "Module_Prefix")))]
+    #[sabi(with_prefix_extra)]
+    pub struct Module {
+        /// A byte.
+        pub byte: u8,
+        #[sabi(last_prefix_field)]
+        pub name: RStr<'static>,
+        #[sabi(missing_field(default))]
+        pub short: u16,
+        #[sabi(accessible_if = "false")]
+        pub hidden: u32,
+        /// Greets someone,
+        /// with multiple lines of docs.
+        #[sabi(missing_field(panic))]
+        pub greet: extern "C" fn(RStr<'_>) -> RString,
+        #[sabi(missing_field(value = "0"))]
+        pub long: u64,
+    }
+
+    pub extern "C" fn greet(name: RStr<'_>) -> RString {
+        format!("hello {}", name).into()
+    }
+
+    pub const MOD_VAL: &WithMetadata<Module> = &WithMetadata::new(
+        PrefixTypeTrait::METADATA,
+        Module {
+            byte: 5,
+            name: RStr::from_str("v1"),
+            short: 8,
+            hidden: 13,
+            greet,
+            long: 21,
+        },
+    );
+}
+
+#[test]
+fn field_values() {
+    let module = v1::Module_Ref(v1::MOD_VAL.static_as_prefix());
+    let fields = PrefixFields::new(module.0).unwrap();
+
+    assert_eq!(fields.len(), 6);
+    assert_eq!(fields.iter().len(), 6);
+
+    assert_eq!(fields.by_name("byte").unwrap().get_as::<u8>(), Some(&5));
+    assert_eq!(
+        fields.by_name("name").unwrap().get_as::<RStr<'static>>(),
+        Some(&RStr::from_str("v1")),
+    );
+    assert_eq!(fields.by_name("short").unwrap().get_as::<u16>(), Some(&8));
+    assert_eq!(fields.by_name("long").unwrap().get_as::<u64>(), Some(&21));
+
+    let greet = fields.by_name("greet").unwrap();
+    assert!(greet.tl_field().is_function());
+    assert_eq!(greet.tl_field().function_range().len(), 1);
+    let greet = unsafe {
+        *greet
+            .get()
+            .unwrap()
+            .transmute_into_ref::<extern "C" fn(RStr<'_>) -> RString>()
+    };
+    assert_eq!(greet(RStr::from_str("world")), "hello world");
+
+    // The erased reference points to the same place as the accessor.
+    let short = fields.get(2).unwrap().get().unwrap();
+    assert_eq!(
+        unsafe { *short.transmute_into_ref::<u16>() },
+        module.short()
+    );
+
+    assert!(fields.by_name("nonexistent").is_none());
+    assert!(fields.get(6).is_none());
+}
+
+#[test]
+fn field_metadata() {
+    let fields = PrefixFields::new(v1::MOD_VAL.static_as_prefix()).unwrap();
+
+    let list = fields
+        .iter()
+        .map(|f| (f.index(), f.name(), f.in_prefix(), f.is_conditional()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        list,
+        vec![
+            (0, "byte", true, false),
+            (1, "name", true, false),
+            (2, "short", false, false),
+            (3, "hidden", false, false),
+            (4, "greet", false, false),
+            (5, "long", false, false),
+        ],
+    );
+
+    assert_eq!(fields.get(2).unwrap().layout().name(), "u16");
+    assert_eq!(fields.get(5).unwrap().layout().name(), "u64");
+}
+
+#[test]
+fn wrong_type() {
+    let fields = PrefixFields::new(v1::MOD_VAL.static_as_prefix()).unwrap();
+    let long = fields.by_name("long").unwrap();
+
+    assert!(long.get().is_some());
+    assert_eq!(long.get_as::<u32>(), None);
+    assert_eq!(long.get_as::<i64>(), None);
+}
+
+#[test]
+fn disabled_field() {
+    let fields = PrefixFields::new(v1::MOD_VAL.static_as_prefix()).unwrap();
+    let hidden = fields.by_name("hidden").unwrap();
+
+    assert_eq!(hidden.accessibility(), IsAccessible::No);
+    assert!(hidden.get().is_none());
+    assert_eq!(hidden.get_as::<u32>(), None);
+}
+
+#[test]
+fn older_version() {
+    // Simulating a module from a library compiled with the older version of the type.
+    let prefix = unsafe { v0::MOD_VAL.static_as_prefix().cast::<v1::Module_Prefix>() };
+    let fields = PrefixFields::new(prefix).unwrap();
+
+    assert_eq!(fields.len(), 6);
+
+    let accessible = fields
+        .iter()
+        .map(|f| (f.name(), f.is_accessible()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        accessible,
+        vec![
+            ("byte", true),
+            ("name", true),
+            ("short", false),
+            ("hidden", false),
+            ("greet", false),
+            ("long", false),
+        ],
+    );
+
+    assert_eq!(fields.by_name("byte").unwrap().get_as::<u8>(), Some(&3));
+    assert_eq!(
+        fields.by_name("name").unwrap().get_as::<RStr<'static>>(),
+        Some(&RStr::from_str("v0")),
+    );
+    assert!(fields.by_name("greet").unwrap().get().is_none());
+}
+
+#[test]
+fn field_docs_and_missing_fields() {
+    let fields = PrefixFields::new(v1::MOD_VAL.static_as_prefix()).unwrap();
+
+    let extra = fields.layout().prefix_extra().unwrap();
+    assert_eq!(
+        extra.type_docs(),
+        "The second version of the module.\n\nIt has more fields."
+    );
+
+    let list = fields
+        .iter()
+        .map(|f| (f.name(), f.docs(), f.on_missing_field()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        list,
+        vec![
+            ("byte", "A byte.", None),
+            ("name", "", None),
+            ("short", "", Some(OnMissingField::Default)),
+            ("hidden", "", Some(OnMissingField::ReturnOption)),
+            (
+                "greet",
+                "Greets someone,\nwith multiple lines of docs.",
+                Some(OnMissingField::Panic)
+            ),
+            ("long", "", Some(OnMissingField::Value)),
+        ],
+    );
+}
+
+#[test]
+fn without_prefix_extra() {
+    let fields = PrefixFields::new(v0::MOD_VAL.static_as_prefix()).unwrap();
+
+    assert_eq!(fields.layout().prefix_extra(), None);
+    assert!(fields
+        .iter()
+        .all(|f| f.docs().is_empty() && f.on_missing_field().is_none()));
+}
@@ -0,0 +1,407 @@
+//! A graph of the types that a `TypeLayout` references,
+//! which can be outputted in the Graphviz DOT format.
+
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+};
+
+use crate::{std_types::UTypeId, type_layout::*};
+
+#[cfg(all(test, not(feature = "only_new_tests")))]
+mod tests;
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// The graph of all the types (transitively) referenced by a `TypeLayout`,
+/// with an edge for every field,function parameter,and generic parameter.
+///
+/// The `Display` impl of this type outputs the graph in the Graphviz DOT format.
+///
+/// # Example
+///
+/// ```rust
+/// use abi_stable::{
+///     reflection::type_graph::TypeGraph,
+///     std_types::RVec,
+///     StableAbi,
+/// };
+///
+/// #[repr(C)]
+/// #[derive(StableAbi)]
+/// pub struct Point {
+///     x: u32,
+///     y: u32,
+/// }
+///
+/// let graph = TypeGraph::from_type_layout(<RVec<Point> as StableAbi>::LAYOUT);
+///
+/// assert!(graph.nodes().iter().any(|n| n.name() == "Point"));
+///
+/// let dot = graph.to_string();
+/// assert!(dot.starts_with("digraph"));
+///
+/// ```
+#[derive(Debug, Clone)]
+pub struct TypeGraph {
+    nodes: Vec<TGNode>,
+    edges: Vec<TGEdge>,
+}
+
+/// A type in a `TypeGraph`.
+#[derive(Clone)]
+pub struct TGNode {
+    layout: &'static TypeLayout,
+    name: &'static str,
+    full_type: String,
+    package: &'static str,
+    version: &'static str,
+    size: usize,
+    alignment: usize,
+    kind: TGNodeKind,
+}
+
+/// What kind of type a `TGNode` is,used to highlight some types in the DOT output.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TGNodeKind {
+    /// Any type that doesn't fit in the other variants.
+    Regular,
+    /// A prefix type (a module or vtable that can be extended in minor versions).
+    PrefixType,
+    /// A `#[sabi(kind(WithNonExhaustive(...)))]` enum.
+    NonExhaustiveEnum,
+    /// The vtable of a `#[sabi_trait]` trait object.
+    SabiTraitVTable,
+}
+
+/// A reference from one type to another in a `TypeGraph`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TGEdge {
+    from: usize,
+    to: usize,
+    kind: TGEdgeKind,
+}
+
+/// How a type references another type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TGEdgeKind {
+    /// The type is the type of a field.
+    Field {
+        /// The name of the field.
+        name: &'static str,
+    },
+    /// The type is the type of a parameter of a function pointer in a field.
+    Param {
+        /// The name of the field that contains the function pointer.
+        function: &'static str,
+        /// The name of the parameter.
+        name: &'static str,
+    },
+    /// The type is the return type of a function pointer in a field.
+    Return {
+        /// The name of the field that contains the function pointer.
+        function: &'static str,
+    },
+    /// The type is a type parameter.
+    TypeParam {
+        /// The position of the type parameter.
+        index: usize,
+    },
+    /// The type is the type of a phantom field
+    /// (a field that is only used for layout checking).
+    PhantomField {
+        /// The name of the phantom field.
+        name: &'static str,
+    },
+}
+
+impl TypeGraph {
+    /// Constructs the graph of all the types transitively referenced by `layout`.
+    ///
+    /// The node for `layout` is always the first one.
+    pub fn from_type_layout(layout: &'static TypeLayout) -> Self {
+        let mut builder = GraphBuilder {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            indices: HashMap::new(),
+            pending: Vec::new(),
+        };
+
+        builder.node_index(layout);
+
+        while let Some((from, layout)) = builder.pending.pop() {
+            builder.add_edges(from, layout);
+        }
+
+        Self {
+            nodes: builder.nodes,
+            edges: builder.edges,
+        }
+    }
+
+    /// The types in this graph.
+    pub fn nodes(&self) -> &[TGNode] {
+        &self.nodes
+    }
+
+    /// The references between the types in this graph,
+    /// containing indices into `self.nodes()`.
+    pub fn edges(&self) -> &[TGEdge] {
+        &self.edges
+    }
+
+    /// Gets the types that directly reference the `index`th type.
+    pub fn dependents_of(&self, index: usize) -> impl Iterator<Item = &TGNode> + '_ {
+        self.edges
+            .iter()
+            .filter(move |e| e.to == index)
+            .map(move |e| &self.nodes[e.from])
+    }
+}
+
+struct GraphBuilder {
+    nodes: Vec<TGNode>,
+    edges: Vec<TGEdge>,
+    indices: HashMap<UTypeId, usize>,
+    pending: Vec<(usize, &'static TypeLayout)>,
+}
+
+impl GraphBuilder {
+    /// Gets the index of the node for `layout`,
+    /// adding the node and queuing its edges if it wasn't already in the graph.
+    fn node_index(&mut self, layout: &'static TypeLayout) -> usize {
+        let nodes = &mut self.nodes;
+        let pending = &mut self.pending;
+        *self.indices.entry(layout.get_utypeid()).or_insert_with(|| {
+            let index = nodes.len();
+            nodes.push(TGNode::new(layout));
+            pending.push((index, layout));
+            index
+        })
+    }
+
+    fn add_edge(&mut self, from: usize, to: &'static TypeLayout, kind: TGEdgeKind) {
+        let to = self.node_index(to);
+        self.edges.push(TGEdge { from, to, kind });
+    }
+
+    fn add_edges(&mut self, from: usize, layout: &'static TypeLayout) {
+        for (index, ty) in layout.generics().type_params().iter().enumerate() {
+            self.add_edge(from, ty.get(), TGEdgeKind::TypeParam { index });
+        }
+
+        for field in layout.get_fields().into_iter().flatten() {
+            self.add_field_edges(from, field, |name| TGEdgeKind::Field { name });
+        }
+
+        for field in layout.phantom_fields() {
+            self.add_field_edges(from, field, |name| TGEdgeKind::PhantomField { name });
+        }
+    }
+
+    fn add_field_edges<F>(&mut self, from: usize, field: TLField, field_edge: F)
+    where
+        F: FnOnce(&'static str) -> TGEdgeKind,
+    {
+        let function = field.name();
+        if !field.is_function() {
+            self.add_edge(from, field.layout(), field_edge(function));
+        }
+        for func in field.function_range() {
+            for param in func.get_params() {
+                let kind = TGEdgeKind::Param {
+                    function,
+                    name: param.name(),
+                };
+                self.add_edge(from, param.layout(), kind);
+            }
+            if let Some(ret) = func.return_type_layout {
+                self.add_edge(from, ret.get(), TGEdgeKind::Return { function });
+            }
+        }
+    }
+}
+
+impl TGNode {
+    fn new(layout: &'static TypeLayout) -> Self {
+        let (package, version) = layout.item_info().package_and_version();
+        Self {
+            layout,
+            name: layout.name(),
+            full_type: layout.full_type().to_string(),
+            package,
+            version,
+            size: layout.size(),
+            alignment: layout.alignment(),
+            kind: TGNodeKind::of_layout(layout),
+        }
+    }
+
+    /// The layout of the type.
+    pub fn layout(&self) -> &'static TypeLayout {
+        self.layout
+    }
+
+    /// The name of the type,without generic parameters.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The name of the type,with generic parameters.
+    pub fn full_type(&self) -> &str {
+        &self.full_type
+    }
+
+    /// The package where the type was declared.
+    pub fn package(&self) -> &'static str {
+        self.package
+    }
+
+    /// The version of the package where the type was declared.
+    pub fn version(&self) -> &'static str {
+        self.version
+    }
+
+    /// The module path where the type was declared.
+    pub fn mod_path(&self) -> ModPath {
+        self.layout.mod_path()
+    }
+
+    /// The size of the type.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The alignment of the type.
+    pub fn alignment(&self) -> usize {
+        self.alignment
+    }
+
+    /// What kind of type this is.
+    pub fn kind(&self) -> TGNodeKind {
+        self.kind
+    }
+}
+
+impl fmt::Debug for TGNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TGNode")
+            .field("full_type", &self.full_type)
+            .field("package", &self.package)
+            .field("version", &self.version)
+            .field("size", &self.size)
+            .field("alignment", &self.alignment)
+            .field("kind", &self.kind)
+            .finish()
+    }
+}
+
+impl TGNodeKind {
+    fn of_layout(layout: &'static TypeLayout) -> Self {
+        match layout.data() {
+            TLData::PrefixType(prefix) => {
+                let is_vtable = prefix.fields.iter().any(|f| f.name() == "_sabi_vtable");
+                if is_vtable {
+                    TGNodeKind::SabiTraitVTable
+                } else {
+                    TGNodeKind::PrefixType
+                }
+            }
+            TLData::Enum(enum_) if enum_.exhaustiveness.is_nonexhaustive() => {
+                TGNodeKind::NonExhaustiveEnum
+            }
+            _ => TGNodeKind::Regular,
+        }
+    }
+
+    /// The Graphviz fill color used for this kind of type.
+    fn fill_color(self) -> Option<&'static str> {
+        match self {
+            TGNodeKind::Regular => None,
+            TGNodeKind::PrefixType => Some("lightblue"),
+            TGNodeKind::NonExhaustiveEnum => Some("khaki"),
+            TGNodeKind::SabiTraitVTable => Some("lightpink"),
+        }
+    }
+}
+
+impl TGEdge {
+    /// The index of the type that references the other one.
+    pub fn from(&self) -> usize {
+        self.from
+    }
+
+    /// The index of the referenced type.
+    pub fn to(&self) -> usize {
+        self.to
+    }
+
+    /// How the `from` type references the `to` type.
+    pub fn kind(&self) -> &TGEdgeKind {
+        &self.kind
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+impl Display for TypeGraph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "digraph abi_stable {{")?;
+        writeln!(f, "    node [shape=box];")?;
+        for (i, node) in self.nodes.iter().enumerate() {
+            write!(
+                f,
+                "    n{} [label=\"{}\\n{}:{}\\nsize:{} align:{}\"",
+                i,
+                DotEscape(&node.full_type),
+                DotEscape(node.package),
+                DotEscape(node.version),
+                node.size,
+                node.alignment,
+            )?;
+            if let Some(color) = node.kind.fill_color() {
+                write!(f, ", style=filled, fillcolor={}", color)?;
+            }
+            writeln!(f, "];")?;
+        }
+        for edge in &self.edges {
+            writeln!(
+                f,
+                "    n{} -> n{} [label=\"{}\"];",
+                edge.from,
+                edge.to,
+                DotEscape(&edge.kind.to_string()),
+            )?;
+        }
+        writeln!(f, "}}")
+    }
+}
+
+impl Display for TGEdgeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TGEdgeKind::Field { name } => Display::fmt(name, f),
+            TGEdgeKind::Param { function, name } => write!(f, "{}({})", function, name),
+            TGEdgeKind::Return { function } => write!(f, "{}()->", function),
+            TGEdgeKind::TypeParam { index } => write!(f, "<{}>", index),
+            TGEdgeKind::PhantomField { name } => write!(f, "phantom {}", name),
+        }
+    }
+}
+
+/// Escapes a string to be used inside a double quoted DOT string.
+struct DotEscape<'a>(&'a str);
+
+impl Display for DotEscape<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for c in self.0.chars() {
+            match c {
+                '"' => f.write_str("\\\"")?,
+                '\\' => f.write_str("\\\\")?,
+                '\n' => f.write_str("\\n")?,
+                _ => fmt::Write::write_char(f, c)?,
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,104 @@
+use super::*;
+
+use crate::{
+    for_examples::ValidTag_NE,
+    sabi_trait::doc_examples::Doer_TO,
+    std_types::{RBox, ROption, RString, RVec},
+    StableAbi,
+};
+
+#[repr(C)]
+#[derive(StableAbi)]
+#[sabi(kind(Prefix))]
+pub struct Module {
+    pub tags: extern "C" fn() -> RVec<ValidTag_NE>,
+    pub doer: extern "C" fn() -> Doer_TO<'static, RBox<()>>,
+    #[sabi(last_prefix_field)]
+    pub find: extern "C" fn(name: RString, default: Leaf) -> ROption<Leaf>,
+}
+
+#[repr(C)]
+#[derive(StableAbi)]
+pub struct Leaf {
+    pub value: u32,
+}
+
+fn node_named<'a>(graph: &'a TypeGraph, name: &str) -> (usize, &'a TGNode) {
+    graph
+        .nodes()
+        .iter()
+        .enumerate()
+        .find(|(_, n)| n.name() == name)
+        .unwrap_or_else(|| panic!("expected a '{}' node in:\n{}", name, graph))
+}
+
+#[test]
+fn nodes_and_edges() {
+    let graph = TypeGraph::from_type_layout(<Module_Ref as StableAbi>::LAYOUT);
+
+    assert_eq!(graph.nodes()[0].name(), "PrefixRef");
+
+    let (module_i, module) = node_named(&graph, "Module");
+    assert_eq!(module.kind(), TGNodeKind::PrefixType);
+
+    let (_, tag) = node_named(&graph, "ValidTag");
+    assert_eq!(tag.kind(), TGNodeKind::NonExhaustiveEnum);
+
+    assert!(
+        graph
+            .nodes()
+            .iter()
+            .any(|n| n.kind() == TGNodeKind::SabiTraitVTable),
+        "{}",
+        graph,
+    );
+
+    let (leaf_i, _) = node_named(&graph, "Leaf");
+    let (rstring_i, _) = node_named(&graph, "RString");
+    let (u32_i, _) = node_named(&graph, "u32");
+
+    let has_edge = |from: usize, to: usize, kind: TGEdgeKind| {
+        graph
+            .edges()
+            .iter()
+            .any(|e| e.from() == from && e.to() == to && *e.kind() == kind)
+    };
+
+    assert!(has_edge(
+        module_i,
+        rstring_i,
+        TGEdgeKind::Param {
+            function: "find",
+            name: "name"
+        }
+    ));
+    assert!(has_edge(
+        module_i,
+        leaf_i,
+        TGEdgeKind::Param {
+            function: "find",
+            name: "default"
+        }
+    ));
+    assert!(has_edge(leaf_i, u32_i, TGEdgeKind::Field { name: "value" }));
+
+    assert!(graph.dependents_of(leaf_i).any(|n| n.name() == "ROption"));
+}
+
+#[test]
+fn dot_output() {
+    let graph = TypeGraph::from_type_layout(<Module_Ref as StableAbi>::LAYOUT);
+    let dot = graph.to_string();
+
+    assert!(dot.starts_with("digraph abi_stable {\n"), "{}", dot);
+    assert!(dot.ends_with("}\n"), "{}", dot);
+    assert!(dot.contains("fillcolor=lightblue"), "{}", dot);
+    assert!(dot.contains("fillcolor=khaki"), "{}", dot);
+    assert!(dot.contains("fillcolor=lightpink"), "{}", dot);
+    assert_eq!(dot.matches(" -> ").count(), graph.edges().len(), "{}", dot);
+}
+
+#[test]
+fn dot_escaping() {
+    assert_eq!(DotEscape("a\"b\\c\nd").to_string(), "a\\\"b\\\\c\\nd",);
+}
@@ -0,0 +1,646 @@
+//! Rendering values of any `StableAbi` type,using only its `TypeLayout`.
+//!
+//! This is useful to inspect values whose type is only known at runtime,
+//! like the private types of a dynamic library.
+
+use std::{
+    fmt::{self, Debug, Display},
+    mem,
+    os::raw::c_int,
+    ptr,
+};
+
+use crate::type_layout::*;
+
+#[cfg(all(test, not(feature = "only_new_tests")))]
+mod tests;
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// A structured representation of a value,
+/// constructed by reading it using its `TypeLayout`.
+///
+/// The `Display` impl outputs the value with Rust-like syntax,
+/// and with the alternate flag (`{:#}`) spreads it over multiple lines.
+///
+/// # Example
+///
+/// ```rust
+/// use abi_stable::{
+///     reflection::value_dump::{dump_value, ValueDump},
+///     std_types::{RString, RVec},
+///     StableAbi,
+/// };
+///
+/// #[repr(C)]
+/// #[derive(StableAbi)]
+/// pub struct Point {
+///     x: u32,
+///     y: RString,
+/// }
+///
+/// let points: RVec<Point> = vec![
+///     Point{x: 3, y: "5".into()},
+///     Point{x: 8, y: "13".into()},
+/// ].into();
+///
+/// let dump = unsafe{
+///     dump_value(&points as *const RVec<Point> as *const u8, <RVec<Point>>::LAYOUT)
+/// };
+///
+/// assert_eq!(
+///     dump.to_string(),
+///     r#"[Point { x: 3, y: "5" }, Point { x: 8, y: "13" }]"#,
+/// );
+///
+/// ```
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum ValueDump {
+    /// A `bool`.
+    Bool { value: bool },
+    /// An unsigned integer.
+    Unsigned { type_: String, value: u64 },
+    /// A signed integer.
+    Signed { type_: String, value: i64 },
+    /// A floating point number.
+    Float { type_: String, value: f64 },
+    /// A string,from an `RStr` or `RString`.
+    Str { type_: String, value: String },
+    /// A pointer that isn't followed,eg: references,raw pointers.
+    Pointer { type_: String, address: usize },
+    /// An array or the contents of a slice/vector.
+    Sequence {
+        type_: String,
+        elements: Vec<ValueDump>,
+        /// How many elements were not read,because of `ValueDumper::max_elements`.
+        omitted: usize,
+    },
+    /// A struct,or a prefix type.
+    Struct {
+        type_: String,
+        fields: Vec<DumpedField>,
+    },
+    /// An enum.
+    Enum {
+        type_: String,
+        variant: String,
+        fields: Vec<DumpedField>,
+    },
+    /// A type whose contents can't be inspected,stored as its bytes.
+    Opaque { type_: String, bytes: Vec<u8> },
+    /// A value that was not read because of `ValueDumper::max_depth`.
+    Elided { type_: String },
+    /// A value that is not valid for its type,
+    /// eg: an enum with an unknown discriminant.
+    Invalid { type_: String, reason: String },
+}
+
+/// A field of a struct or enum variant in a `ValueDump`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DumpedField {
+    pub name: String,
+    pub value: ValueDump,
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// Reads the value that `ptr` points to,using `layout` to interpret it.
+///
+/// This uses the default `ValueDumper` settings.
+///
+/// # Safety
+///
+/// `ptr` must point to a valid,initialized value of the type described by `layout`,
+/// including all the pointers that it contains to
+/// `RVec`/`RString`/`RSlice`/`RStr` buffers.
+pub unsafe fn dump_value(ptr: *const u8, layout: &'static TypeLayout) -> ValueDump {
+    ValueDumper::new().dump(ptr, layout)
+}
+
+/// Reads values using their `TypeLayout`,with configurable limits.
+///
+/// # Example
+///
+/// ```rust
+/// use abi_stable::{
+///     reflection::value_dump::{ValueDump, ValueDumper},
+///     std_types::RVec,
+///     StableAbi,
+/// };
+///
+/// let list: RVec<u8> = (0..100).collect();
+///
+/// let dump = unsafe{
+///     ValueDumper::new()
+///         .max_elements(2)
+///         .dump(&list as *const RVec<u8> as *const u8, <RVec<u8>>::LAYOUT)
+/// };
+///
+/// match dump {
+///     ValueDump::Sequence{elements, omitted, ..} => {
+///         assert_eq!(elements.len(), 2);
+///         assert_eq!(omitted, 98);
+///     }
+///     _ => unreachable!(),
+/// }
+///
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct ValueDumper {
+    max_elements: usize,
+    max_depth: usize,
+}
+
+impl Default for ValueDumper {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ValueDumper {
+    /// Constructs a `ValueDumper` which reads up to 256 elements of every sequence,
+    /// and up to 32 levels of nested values.
+    pub const fn new() -> Self {
+        Self {
+            max_elements: 256,
+            max_depth: 32,
+        }
+    }
+
+    /// Sets the maximum amount of elements read from arrays/slices/vectors.
+    pub const fn max_elements(mut self, max_elements: usize) -> Self {
+        self.max_elements = max_elements;
+        self
+    }
+
+    /// Sets the maximum nesting of values,
+    /// values nested deeper than this are outputted as `ValueDump::Elided`.
+    pub const fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Reads the value that `ptr` points to,using `layout` to interpret it.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to a valid,initialized value of the type described by `layout`,
+    /// including all the pointers that it contains to
+    /// `RVec`/`RString`/`RSlice`/`RStr` buffers.
+    pub unsafe fn dump(&self, ptr: *const u8, layout: &'static TypeLayout) -> ValueDump {
+        self.dump_inner(ptr, layout, 0)
+    }
+
+    unsafe fn dump_inner(
+        &self,
+        ptr: *const u8,
+        layout: &'static TypeLayout,
+        depth: usize,
+    ) -> ValueDump {
+        let type_ = || layout.full_type().to_string();
+
+        if depth > self.max_depth {
+            return ValueDump::Elided { type_: type_() };
+        }
+        let depth = depth + 1;
+
+        if layout.package() == "abi_stable" {
+            if let Some(x) = self.dump_abi_stable_type(ptr, layout, depth) {
+                return x;
+            }
+        }
+
+        match layout.data() {
+            TLData::Primitive(prim) => self.dump_primitive(ptr, layout, prim, depth),
+            TLData::Opaque => match (layout.name(), layout.size()) {
+                ("f32", 4) => ValueDump::Float {
+                    type_: type_(),
+                    value: read_unaligned::<f32>(ptr) as f64,
+                },
+                ("f64", 8) => ValueDump::Float {
+                    type_: type_(),
+                    value: read_unaligned::<f64>(ptr),
+                },
+                _ => opaque(ptr, layout),
+            },
+            TLData::Struct { fields } => ValueDump::Struct {
+                type_: type_(),
+                fields: self.dump_c_fields(ptr, layout, fields.iter(), 0, depth),
+            },
+            TLData::Union { .. } => opaque(ptr, layout),
+            TLData::Enum(enum_) => self.dump_enum(ptr, layout, enum_, depth),
+            TLData::PrefixType(prefix) => {
+                let accessible = prefix
+                    .accessible_fields
+                    .iter_count(prefix.fields.len())
+                    .take_while(|acc| acc.is_accessible())
+                    .count();
+                ValueDump::Struct {
+                    type_: type_(),
+                    fields: self.dump_c_fields(
+                        ptr,
+                        layout,
+                        prefix.fields.iter().take(accessible),
+                        0,
+                        depth,
+                    ),
+                }
+            }
+        }
+    }
+
+    unsafe fn dump_primitive(
+        &self,
+        ptr: *const u8,
+        layout: &'static TypeLayout,
+        prim: TLPrimitive,
+        depth: usize,
+    ) -> ValueDump {
+        use self::TLPrimitive as TLP;
+
+        let type_ = layout.full_type().to_string();
+        let unsigned = |value: u64| ValueDump::Unsigned {
+            type_: layout.name().into(),
+            value,
+        };
+        let signed = |value: i64| ValueDump::Signed {
+            type_: layout.name().into(),
+            value,
+        };
+
+        match prim {
+            TLP::U8 => unsigned(read_unaligned::<u8>(ptr) as u64),
+            TLP::I8 => signed(read_unaligned::<i8>(ptr) as i64),
+            TLP::U16 => unsigned(read_unaligned::<u16>(ptr) as u64),
+            TLP::I16 => signed(read_unaligned::<i16>(ptr) as i64),
+            TLP::U32 => unsigned(read_unaligned::<u32>(ptr) as u64),
+            TLP::I32 => signed(read_unaligned::<i32>(ptr) as i64),
+            TLP::U64 => unsigned(read_unaligned::<u64>(ptr)),
+            TLP::I64 => signed(read_unaligned::<i64>(ptr)),
+            TLP::Usize => unsigned(read_unaligned::<usize>(ptr) as u64),
+            TLP::Isize => signed(read_unaligned::<isize>(ptr) as i64),
+            TLP::Bool => match read_unaligned::<u8>(ptr) {
+                0 => ValueDump::Bool { value: false },
+                1 => ValueDump::Bool { value: true },
+                x => ValueDump::Invalid {
+                    type_,
+                    reason: format!("{} is not a valid bool", x),
+                },
+            },
+            TLP::SharedRef | TLP::MutRef | TLP::ConstPtr | TLP::MutPtr => ValueDump::Pointer {
+                type_,
+                address: read_unaligned::<usize>(ptr),
+            },
+            TLP::Array { len } => match layout.generics().type_params().first() {
+                Some(elem) => self.dump_sequence(ptr, elem.get(), len, type_, depth),
+                None => opaque(ptr, layout),
+            },
+        }
+    }
+
+    /// Handles types from abi_stable that need special handling,
+    /// returning None for all other types.
+    unsafe fn dump_abi_stable_type(
+        &self,
+        ptr: *const u8,
+        layout: &'static TypeLayout,
+        depth: usize,
+    ) -> Option<ValueDump> {
+        let type_ = || layout.full_type().to_string();
+        let fields = layout.get_fields()?;
+
+        let ret = match layout.name() {
+            "RVec" | "RSlice" => {
+                // Both of these store the pointer to the buffer and the length
+                // as their first two fields.
+                let elem = fields
+                    .get(0)?
+                    .layout()
+                    .generics()
+                    .type_params()
+                    .first()?
+                    .get();
+                let buffer = read_unaligned::<*const u8>(ptr);
+                let len = read_unaligned::<usize>(ptr.add(mem::size_of::<usize>()));
+                if buffer.is_null() {
+                    return Some(ValueDump::Invalid {
+                        type_: type_(),
+                        reason: "null buffer pointer".into(),
+                    });
+                }
+                self.dump_sequence(buffer, elem, len, type_(), depth)
+            }
+            "RStr" | "RString" => {
+                let buffer = read_unaligned::<*const u8>(ptr);
+                let len = read_unaligned::<usize>(ptr.add(mem::size_of::<usize>()));
+                if buffer.is_null() {
+                    return Some(ValueDump::Invalid {
+                        type_: type_(),
+                        reason: "null buffer pointer".into(),
+                    });
+                }
+                let bytes = std::slice::from_raw_parts(buffer, len);
+                match std::str::from_utf8(bytes) {
+                    Ok(value) => ValueDump::Str {
+                        type_: type_(),
+                        value: value.into(),
+                    },
+                    Err(e) => ValueDump::Invalid {
+                        type_: type_(),
+                        reason: e.to_string(),
+                    },
+                }
+            }
+            "NonExhaustive" => {
+                // The enum is stored at the start of the `NonExhaustive`,
+                // and its layout is the first phantom field.
+                let enum_layout = layout.phantom_fields().get(0)?.layout();
+                match enum_layout.data() {
+                    TLData::Enum(enum_) => self.dump_enum(ptr, enum_layout, enum_, depth),
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        };
+        Some(ret)
+    }
+
+    unsafe fn dump_sequence(
+        &self,
+        ptr: *const u8,
+        elem: &'static TypeLayout,
+        len: usize,
+        type_: String,
+        depth: usize,
+    ) -> ValueDump {
+        let read = len.min(self.max_elements);
+        let elements = (0..read)
+            .map(|i| self.dump_inner(ptr.add(i * elem.size()), elem, depth))
+            .collect();
+        ValueDump::Sequence {
+            type_,
+            elements,
+            omitted: len - read,
+        }
+    }
+
+    /// Reads fields laid out as in a `#[repr(C)]` struct starting at `ptr`,
+    /// where the first field is placed at or after `start_offset`.
+    unsafe fn dump_c_fields<I>(
+        &self,
+        ptr: *const u8,
+        layout: &'static TypeLayout,
+        fields: I,
+        start_offset: usize,
+        depth: usize,
+    ) -> Vec<DumpedField>
+    where
+        I: IntoIterator<Item = TLField>,
+    {
+        let max_align = match layout.repr_attr() {
+            ReprAttr::Packed {
+                alignment_power_of_two,
+            } => 1 << alignment_power_of_two,
+            _ => !0,
+        };
+
+        let mut offset = start_offset;
+        fields
+            .into_iter()
+            .map(|field| {
+                let field_layout = field.layout();
+                offset = align_up(offset, field_layout.alignment().min(max_align));
+                let value = self.dump_inner(ptr.add(offset), field_layout, depth);
+                offset += field_layout.size();
+                DumpedField {
+                    name: field.name().into(),
+                    value,
+                }
+            })
+            .collect()
+    }
+
+    unsafe fn dump_enum(
+        &self,
+        ptr: *const u8,
+        layout: &'static TypeLayout,
+        enum_: TLEnum,
+        depth: usize,
+    ) -> ValueDump {
+        let type_ = || layout.full_type().to_string();
+
+        let (discr_repr, union_offset) = match layout.repr_attr() {
+            ReprAttr::OptionNonZero => {
+                let is_none = std::slice::from_raw_parts(ptr, layout.size())
+                    .iter()
+                    .all(|&b| b == 0);
+                return if is_none {
+                    ValueDump::Enum {
+                        type_: type_(),
+                        variant: "None".into(),
+                        fields: Vec::new(),
+                    }
+                } else {
+                    let fields = self.dump_c_fields(ptr, layout, enum_.fields.iter(), 0, depth);
+                    ValueDump::Enum {
+                        type_: type_(),
+                        variant: "Some".into(),
+                        fields,
+                    }
+                };
+            }
+            // Each variant is laid out like a `#[repr(C)]` struct
+            // that starts with the discriminant.
+            ReprAttr::Int(repr) => (repr, None),
+            // The enum is laid out like a `#[repr(C)]` struct containing the discriminant
+            // followed by a union of the variants.
+            ReprAttr::C | ReprAttr::CAndInt(_) => {
+                let repr = match layout.repr_attr() {
+                    ReprAttr::CAndInt(repr) => repr,
+                    _ if mem::size_of::<c_int>() == 2 => DiscriminantRepr::I16,
+                    _ => DiscriminantRepr::I32,
+                };
+                let union_align = enum_
+                    .fields
+                    .iter()
+                    .map(|f| f.layout().alignment())
+                    .max()
+                    .unwrap_or(1);
+                (repr, Some(align_up(discr_size(repr), union_align)))
+            }
+            _ => return opaque(ptr, layout),
+        };
+
+        let discriminant = match read_discriminant(ptr, discr_repr) {
+            Some(x) => x,
+            None => return opaque(ptr, layout),
+        };
+
+        let variant = (0..enum_.discriminants.len())
+            .find(|&i| enum_.discriminants.get(i).map(discriminant_to_i128) == Some(discriminant));
+
+        let variant = match variant {
+            Some(x) => x,
+            None => {
+                return ValueDump::Invalid {
+                    type_: type_(),
+                    reason: format!("unknown discriminant: {}", discriminant),
+                }
+            }
+        };
+
+        let fields_start = enum_.field_count[..variant]
+            .iter()
+            .map(|&x| x as usize)
+            .sum::<usize>();
+        let variant_fields = enum_
+            .fields
+            .iter()
+            .skip(fields_start)
+            .take(enum_.field_count[variant] as usize);
+
+        // Without a union the fields are laid out right after the discriminant.
+        let fields_offset = union_offset.unwrap_or_else(|| discr_size(discr_repr));
+        let fields = self.dump_c_fields(ptr, layout, variant_fields, fields_offset, depth);
+
+        ValueDump::Enum {
+            type_: type_(),
+            variant: enum_
+                .variant_names_iter()
+                .nth(variant)
+                .unwrap_or("<unknown>")
+                .into(),
+            fields,
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+unsafe fn opaque(ptr: *const u8, layout: &'static TypeLayout) -> ValueDump {
+    ValueDump::Opaque {
+        type_: layout.full_type().to_string(),
+        bytes: std::slice::from_raw_parts(ptr, layout.size()).to_vec(),
+    }
+}
+
+unsafe fn read_unaligned<T: Copy>(ptr: *const u8) -> T {
+    ptr::read_unaligned(ptr as *const T)
+}
+
+fn align_up(offset: usize, alignment: usize) -> usize {
+    let alignment = alignment.max(1);
+    (offset + alignment - 1) / alignment * alignment
+}
+
+fn discr_size(repr: DiscriminantRepr) -> usize {
+    use self::DiscriminantRepr as DR;
+    match repr {
+        DR::U8 | DR::I8 => 1,
+        DR::U16 | DR::I16 => 2,
+        DR::U32 | DR::I32 => 4,
+        DR::U64 | DR::I64 => 8,
+        DR::U128 | DR::I128 => 16,
+        DR::Usize | DR::Isize => mem::size_of::<usize>(),
+    }
+}
+
+unsafe fn read_discriminant(ptr: *const u8, repr: DiscriminantRepr) -> Option<i128> {
+    use self::DiscriminantRepr as DR;
+    Some(match repr {
+        DR::U8 => read_unaligned::<u8>(ptr) as i128,
+        DR::I8 => read_unaligned::<i8>(ptr) as i128,
+        DR::U16 => read_unaligned::<u16>(ptr) as i128,
+        DR::I16 => read_unaligned::<i16>(ptr) as i128,
+        DR::U32 => read_unaligned::<u32>(ptr) as i128,
+        DR::I32 => read_unaligned::<i32>(ptr) as i128,
+        DR::U64 => read_unaligned::<u64>(ptr) as i128,
+        DR::I64 => read_unaligned::<i64>(ptr) as i128,
+        DR::Usize => read_unaligned::<usize>(ptr) as i128,
+        DR::Isize => read_unaligned::<isize>(ptr) as i128,
+        DR::U128 | DR::I128 => return None,
+    })
+}
+
+fn discriminant_to_i128(discr: TLDiscriminant) -> i128 {
+    match discr {
+        TLDiscriminant::Isize(x) => x as i128,
+        TLDiscriminant::Usize(x) => x as i128,
+        TLDiscriminant::Signed(x) => x as i128,
+        TLDiscriminant::Unsigned(x) => x as i128,
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+impl Debug for ValueDump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValueDump::Bool { value } => Display::fmt(value, f),
+            ValueDump::Unsigned { value, .. } => Display::fmt(value, f),
+            ValueDump::Signed { value, .. } => Display::fmt(value, f),
+            ValueDump::Float { value, .. } => Debug::fmt(value, f),
+            ValueDump::Str { value, .. } => Debug::fmt(value, f),
+            ValueDump::Pointer { address, .. } => write!(f, "{:#x}", address),
+            ValueDump::Sequence {
+                elements, omitted, ..
+            } => {
+                let mut list = f.debug_list();
+                list.entries(elements);
+                if *omitted != 0 {
+                    list.entry(&Omitted(*omitted));
+                }
+                list.finish()
+            }
+            ValueDump::Struct { type_, fields } => fmt_fields(f, type_, fields),
+            ValueDump::Enum {
+                variant, fields, ..
+            } => fmt_fields(f, variant, fields),
+            ValueDump::Opaque { type_, bytes } => write!(f, "<opaque {}: {:x?}>", type_, bytes),
+            ValueDump::Elided { type_ } => write!(f, "<elided {}>", type_),
+            ValueDump::Invalid { type_, reason } => write!(f, "<invalid {}: {}>", type_, reason),
+        }
+    }
+}
+
+impl Display for ValueDump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(self, f)
+    }
+}
+
+/// Formats the fields as a tuple struct if they're named after their position,
+/// otherwise as a braced struct.
+fn fmt_fields(f: &mut fmt::Formatter<'_>, name: &str, fields: &[DumpedField]) -> fmt::Result {
+    // Stripping the generic parameters of the type.
+    let name = name.split('<').next().unwrap_or(name);
+    let is_tuple = fields.iter().enumerate().all(|(i, field)| {
+        let name = field.name.strip_prefix("field_").unwrap_or(&field.name);
+        name == i.to_string()
+    });
+
+    if fields.is_empty() {
+        f.write_str(name)
+    } else if is_tuple {
+        let mut tuple = f.debug_tuple(name);
+        for field in fields {
+            tuple.field(&field.value);
+        }
+        tuple.finish()
+    } else {
+        let mut struct_ = f.debug_struct(name);
+        for field in fields {
+            struct_.field(&field.name, &field.value);
+        }
+        struct_.finish()
+    }
+}
+
+struct Omitted(usize);
+
+impl Debug for Omitted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "...{} more", self.0)
+    }
+}
@@ -0,0 +1,157 @@
+use super::*;
+
+use crate::{
+    for_examples::{ValidTag, ValidTag_NE},
+    std_types::{ROption, RSlice, RStr, RString, RVec, Tuple2},
+    StableAbi,
+};
+
+#[repr(C)]
+#[derive(StableAbi)]
+struct Mixed {
+    a: u8,
+    b: u32,
+    c: i16,
+    d: bool,
+    e: f64,
+    f: [u16; 3],
+    g: Tuple2<u8, u64>,
+}
+
+#[repr(u8)]
+#[derive(StableAbi)]
+#[allow(dead_code)]
+enum IntEnum {
+    Unit,
+    Tuple(u8, u32),
+    Braced { x: u16, y: RString },
+}
+
+#[repr(C)]
+#[derive(StableAbi)]
+#[allow(dead_code)]
+enum CEnum {
+    First(u8),
+    Second { x: u64, y: u8 },
+}
+
+fn dump<T: StableAbi>(value: &T) -> ValueDump {
+    unsafe { dump_value(value as *const T as *const u8, T::LAYOUT) }
+}
+
+#[test]
+fn structs() {
+    let value = Mixed {
+        a: 3,
+        b: 5,
+        c: -8,
+        d: true,
+        e: 0.5,
+        f: [13, 21, 34],
+        g: Tuple2(55, 89),
+    };
+
+    assert_eq!(
+        dump(&value).to_string(),
+        "Mixed { a: 3, b: 5, c: -8, d: true, e: 0.5, f: [13, 21, 34], g: Tuple2(55, 89) }",
+    );
+}
+
+#[test]
+fn enums() {
+    assert_eq!(dump(&IntEnum::Unit).to_string(), "Unit");
+    assert_eq!(dump(&IntEnum::Tuple(3, 5)).to_string(), "Tuple(3, 5)",);
+    assert_eq!(
+        dump(&IntEnum::Braced {
+            x: 8,
+            y: "hello".into()
+        })
+        .to_string(),
+        r#"Braced { x: 8, y: "hello" }"#,
+    );
+
+    assert_eq!(dump(&CEnum::First(13)).to_string(), "First(13)");
+    assert_eq!(
+        dump(&CEnum::Second { x: 21, y: 34 }).to_string(),
+        "Second { x: 21, y: 34 }",
+    );
+}
+
+#[test]
+fn std_types() {
+    assert_eq!(dump(&ROption::RSome(3u8)).to_string(), "RSome(3)");
+    assert_eq!(dump(&ROption::<u8>::RNone).to_string(), "RNone");
+    assert!(dump(&Some(&5u8)).to_string().starts_with("Some(0x"));
+    assert_eq!(dump(&None::<&u8>).to_string(), "None");
+
+    let list: RVec<RStr<'static>> = vec![RStr::from("foo"), RStr::from("bar")].into();
+    assert_eq!(dump(&list).to_string(), r#"["foo", "bar"]"#);
+
+    let slice: RSlice<'_, u16> = RSlice::from_slice(&[3, 5, 8]);
+    assert_eq!(dump(&slice).to_string(), "[3, 5, 8]");
+
+    let string = RString::from("what");
+    assert_eq!(
+        dump(&string),
+        ValueDump::Str {
+            type_: "RString".into(),
+            value: "what".into(),
+        },
+    );
+}
+
+#[test]
+fn nonexhaustive() {
+    let value = ValidTag_NE::new(ValidTag::Tag {
+        name: "foo".into(),
+        tag: "bar".into(),
+    });
+    assert_eq!(
+        dump(&value).to_string(),
+        r#"Tag { name: "foo", tag: "bar" }"#,
+    );
+}
+
+#[test]
+fn invalid_values() {
+    let invalid_bool = 2u8;
+    let dumped = unsafe { dump_value(&invalid_bool, <bool>::LAYOUT) };
+    assert!(matches!(dumped, ValueDump::Invalid { .. }), "{:?}", dumped);
+
+    let invalid_discr = [7u8; 8];
+    let dumped = unsafe { dump_value(invalid_discr.as_ptr(), <IntEnum>::LAYOUT) };
+    assert!(matches!(dumped, ValueDump::Invalid { .. }), "{:?}", dumped);
+}
+
+#[test]
+fn limits() {
+    let list: RVec<RVec<u8>> = vec![vec![1, 2, 3].into(), vec![4].into()].into();
+    let ptr = &list as *const RVec<RVec<u8>> as *const u8;
+    let layout = <RVec<RVec<u8>>>::LAYOUT;
+
+    unsafe {
+        let dumper = ValueDumper::new().max_elements(1);
+        assert_eq!(
+            dumper.dump(ptr, layout).to_string(),
+            "[[1, ...2 more], ...1 more]"
+        );
+
+        let dumper = ValueDumper::new().max_depth(1);
+        assert_eq!(
+            dumper.dump(ptr, layout).to_string(),
+            "[[<elided u8>, <elided u8>, <elided u8>], [<elided u8>]]",
+        );
+    }
+}
+
+#[test]
+fn pretty_and_serde() {
+    let value = IntEnum::Tuple(3, 5);
+    let dumped = dump(&value);
+
+    assert!(dumped.to_string().len() < format!("{:#}", dumped).len());
+
+    let json = serde_json::to_string(&dumped).unwrap();
+    let back = serde_json::from_str::<ValueDump>(&json).unwrap();
+    assert_eq!(dumped, back);
+}
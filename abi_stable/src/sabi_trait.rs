@@ -27,7 +27,7 @@ pub mod reexports {
                 },
             },
             sabi_types::{MovePtr, RMut, RRef},
-            std_types::RBox,
+            std_types::{LocalRBoxFuture, RBox, RBoxFuture},
             traits::IntoInner,
             utils::take_manuallydrop,
         };
@@ -66,6 +66,9 @@ pub mod tests;
 #[cfg(all(test, not(feature = "only_new_tests")))]
 pub mod test_supertraits;
 
+#[cfg(all(test, feature = "rust_1_75", not(feature = "only_new_tests")))]
+mod test_async_methods;
+
 use std::{
     fmt::{Debug, Display},
     marker::PhantomData,
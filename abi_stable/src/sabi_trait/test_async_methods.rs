@@ -0,0 +1,169 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use crate::{
+    abi_stability::abi_checking::check_layout_compatibility,
+    sabi_trait,
+    std_types::{RBox, RStr, RString, RVec},
+    test_futures::block_on,
+    type_level::downcasting::TD_Opaque,
+};
+
+/// A future that is pending on its first poll.
+struct YieldNow(bool);
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            Poll::Ready(())
+        } else {
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+#[sabi_trait]
+pub trait Greeter {
+    async fn greet(&self, name: RStr<'_>) -> RString;
+
+    async fn rename(&mut self, prefix: RString);
+
+    fn count(&self, n: u32) -> impl Future<Output = u32> + Send;
+
+    async fn shout(&self, name: RStr<'_>) -> RString {
+        let greeting = self.greet(name).await;
+        greeting.to_uppercase().into()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Polite {
+    prefix: RString,
+}
+
+impl Greeter for Polite {
+    async fn greet(&self, name: RStr<'_>) -> RString {
+        YieldNow(false).await;
+        format!("{} {}", self.prefix, name).into()
+    }
+
+    async fn rename(&mut self, prefix: RString) {
+        YieldNow(false).await;
+        self.prefix = prefix;
+    }
+
+    fn count(&self, n: u32) -> impl Future<Output = u32> + Send {
+        let len = self.prefix.len() as u32;
+        async move { len + n }
+    }
+}
+
+#[test]
+fn calling_async_methods() {
+    let mut object = Greeter_TO::from_value(
+        Polite {
+            prefix: "hello".into(),
+        },
+        TD_Opaque,
+    );
+
+    let name = RString::from("world");
+    assert_eq!(block_on(object.greet(name.as_rstr())), "hello world");
+    assert_eq!(block_on(object.shout(name.as_rstr())), "HELLO WORLD");
+
+    block_on(object.rename("goodbye".into()));
+    assert_eq!(block_on(object.greet(name.as_rstr())), "goodbye world");
+
+    assert_eq!(block_on(object.count(3)), 10);
+}
+
+#[test]
+fn async_methods_through_the_trait() {
+    async fn greet_twice<G: Greeter>(greeter: &G, name: &str) -> String {
+        let first = greeter.greet(name.into()).await;
+        let second = greeter.shout(name.into()).await;
+        format!("{},{}", first, second)
+    }
+
+    let object: Greeter_TO<'_, RBox<()>> = Greeter_TO::from_value(
+        Polite {
+            prefix: "hi".into(),
+        },
+        TD_Opaque,
+    );
+
+    assert_eq!(block_on(greet_twice(&object, "you")), "hi you,HI YOU");
+}
+
+#[test]
+fn send_futures() {
+    fn assert_send<T: Send>(x: T) -> T {
+        x
+    }
+
+    let object = Greeter_TO::from_value(
+        Polite {
+            prefix: "hey".into(),
+        },
+        TD_Opaque,
+    );
+
+    assert_eq!(block_on(assert_send(object.count(0))), 3);
+}
+
+#[test]
+fn async_methods_layout() {
+    type Object = Greeter_TO<'static, RBox<()>>;
+    check_layout_compatibility(
+        <Object as crate::StableAbi>::LAYOUT,
+        <Object as crate::StableAbi>::LAYOUT,
+    )
+    .unwrap();
+}
+
+#[sabi_trait]
+#[sabi(use_dyntrait)]
+pub trait DynCounter: Send + Sync {
+    fn add(&self, n: u32) -> impl Future<Output = u32> + Send;
+}
+
+impl DynCounter for u32 {
+    fn add(&self, n: u32) -> impl Future<Output = u32> + Send {
+        let this = *self;
+        async move { this + n }
+    }
+}
+
+#[test]
+fn dyntrait_async_methods() {
+    let object = DynCounter_TO::from_value(5u32, TD_Opaque);
+    assert_eq!(block_on(object.add(8)), 13);
+}
+
+#[sabi_trait]
+pub trait Finder<'a, T: 'a> {
+    async fn find(&self, elem: &'a T) -> bool;
+}
+
+impl<'a, T: PartialEq + 'a> Finder<'a, T> for RVec<T> {
+    async fn find(&self, elem: &'a T) -> bool {
+        YieldNow(false).await;
+        self.iter().any(|x| x == elem)
+    }
+}
+
+#[test]
+fn async_methods_with_trait_generics() {
+    let object = Finder_TO::from_value(RVec::from(vec![3u32, 5, 8]), TD_Opaque);
+
+    let elems = [5u32, 13];
+    assert!(block_on(object.find(&elems[0])));
+    assert!(!block_on(object.find(&elems[1])));
+}
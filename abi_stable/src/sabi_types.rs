@@ -9,6 +9,7 @@ mod nul_str;
 mod rmut;
 mod rref;
 pub mod rsmallbox;
+pub mod spawner;
 mod static_ref;
 pub mod version;
 
@@ -0,0 +1,122 @@
+//! An ffi-safe handle to the executor of the host,
+//! which dynamic libraries use to run tasks without bundling their own runtime.
+//!
+//! The host implements the [`Spawner`] trait for a handle to its executor,
+//! and passes it to the library as an [`RSpawner`].
+//!
+//! # Example
+//!
+//! A library that spawns a task which prints a message every second.
+//!
+//! ```rust
+//! use abi_stable::{
+//!     sabi_extern_fn,
+//!     sabi_types::spawner::RSpawner,
+//!     std_types::{RBoxFuture, RDuration},
+//! };
+//!
+//! #[sabi_extern_fn]
+//! pub fn start(spawner: RSpawner) {
+//!     let spawner2 = spawner.clone();
+//!     spawner.spawn(RBoxFuture::new(async move {
+//!         for i in 0.. {
+//!             spawner2.sleep(RDuration::from_secs(1)).await;
+//!             println!("tick {}", i);
+//!         }
+//!     }));
+//! }
+//! ```
+//!
+//! [`Spawner`]: ./trait.Spawner.html
+//! [`RSpawner`]: ./type.RSpawner.html
+
+use crate::{
+    sabi_trait,
+    std_types::{RArc, RBoxFuture, RBoxStream, RDuration},
+};
+
+#[cfg(all(test, not(feature = "only_new_tests")))]
+mod tests;
+
+/// A handle to an executor,which can spawn tasks and create timers.
+///
+/// This is implemented by the host for a handle to its runtime,
+/// and used by dynamic libraries through the [`RSpawner`] trait object.
+///
+/// Methods can only be added at the end of the trait,
+/// with default implementations,
+/// so that libraries compiled against older versions keep working.
+///
+/// [`RSpawner`]: ./type.RSpawner.html
+#[sabi_trait]
+pub trait Spawner: Send + Sync + Clone {
+    /// Spawns `future` as a new task,running it to completion in the background.
+    fn spawn(&self, future: RBoxFuture<'static, ()>);
+
+    /// Returns a future that completes once `duration` has passed.
+    fn sleep(&self, duration: RDuration) -> RBoxFuture<'static, ()>;
+
+    /// Returns a stream that yields every `period`,
+    /// starting once the first `period` has passed.
+    ///
+    /// The stream never ends.
+    #[sabi(last_prefix_field)]
+    fn interval(&self, period: RDuration) -> RBoxStream<'static, ()>;
+}
+
+/// An ffi-safe handle to the executor of the host.
+///
+/// Cloning an `RSpawner` is cheap,since it's reference counted.
+///
+/// # Example
+///
+/// Constructing an `RSpawner` from a [`Spawner`].
+///
+#[cfg_attr(feature = "futures", doc = "```rust")]
+#[cfg_attr(not(feature = "futures"), doc = "```ignore")]
+/**
+use abi_stable::{
+    sabi_types::spawner::{RSpawner, Spawner, Spawner_TO},
+    std_types::{RArc, RBoxFuture, RBoxStream, RDuration},
+    type_level::downcasting::TD_Opaque,
+};
+
+use futures_core::Stream;
+
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// A spawner that drops every task,and whose timers never wait.
+#[derive(Clone)]
+struct Impatient;
+
+impl Spawner for Impatient {
+    fn spawn(&self, _future: RBoxFuture<'static, ()>) {}
+
+    fn sleep(&self, _duration: RDuration) -> RBoxFuture<'static, ()> {
+        RBoxFuture::new(async {})
+    }
+
+    fn interval(&self, _period: RDuration) -> RBoxStream<'static, ()> {
+        RBoxStream::new(Ticks)
+    }
+}
+
+struct Ticks;
+
+impl Stream for Ticks {
+    type Item = ();
+
+    fn poll_next(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<()>> {
+        Poll::Ready(Some(()))
+    }
+}
+
+let spawner: RSpawner = Spawner_TO::from_ptr(RArc::new(Impatient), TD_Opaque);
+```
+*/
+///
+/// [`Spawner`]: ./trait.Spawner.html
+pub type RSpawner = Spawner_TO<'static, RArc<()>>;
@@ -0,0 +1,189 @@
+use super::*;
+
+use crate::{
+    std_types::future::stream::Stream, test_futures::block_on, type_level::downcasting::TD_Opaque,
+};
+
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Arc, Mutex,
+    },
+    task::{Context, Poll, Waker},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// A future that completes once `duration` has passed since it was first polled.
+struct Sleep {
+    duration: Duration,
+    state: Option<Arc<Mutex<SleepState>>>,
+}
+
+#[derive(Default)]
+struct SleepState {
+    done: bool,
+    waker: Option<Waker>,
+}
+
+impl Sleep {
+    fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            state: None,
+        }
+    }
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let duration = self.duration;
+        let state = self.state.get_or_insert_with(|| {
+            let state = Arc::new(Mutex::new(SleepState::default()));
+            let timer_state = state.clone();
+            thread::spawn(move || {
+                thread::sleep(duration);
+                let mut state = timer_state.lock().unwrap();
+                state.done = true;
+                if let Some(waker) = state.waker.take() {
+                    waker.wake();
+                }
+            });
+            state
+        });
+
+        let mut state = state.lock().unwrap();
+        if state.done {
+            Poll::Ready(())
+        } else {
+            state.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+struct Interval {
+    period: Duration,
+    sleep: Option<Sleep>,
+}
+
+impl Stream for Interval {
+    type Item = ();
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<()>> {
+        let period = self.period;
+        let sleep = self.sleep.get_or_insert_with(|| Sleep::new(period));
+        match Pin::new(sleep).poll(cx) {
+            Poll::Ready(()) => {
+                self.sleep = None;
+                Poll::Ready(Some(()))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// A spawner that runs every task in its own thread.
+#[derive(Clone, Default)]
+struct ThreadSpawner {
+    spawned: Arc<AtomicUsize>,
+}
+
+impl Spawner for ThreadSpawner {
+    fn spawn(&self, future: RBoxFuture<'static, ()>) {
+        self.spawned.fetch_add(1, Ordering::SeqCst);
+        thread::spawn(move || block_on(future));
+    }
+
+    fn sleep(&self, duration: RDuration) -> RBoxFuture<'static, ()> {
+        RBoxFuture::new(Sleep::new(duration.into()))
+    }
+
+    fn interval(&self, period: RDuration) -> RBoxStream<'static, ()> {
+        RBoxStream::new(Interval {
+            period: period.into(),
+            sleep: None,
+        })
+    }
+}
+
+fn new_spawner() -> (ThreadSpawner, RSpawner) {
+    let spawner = ThreadSpawner::default();
+    let rspawner = Spawner_TO::from_ptr(RArc::new(spawner.clone()), TD_Opaque);
+    (spawner, rspawner)
+}
+
+/// Waits for the next element of `stream`.
+fn next<T>(stream: &mut RBoxStream<'_, T>) -> Option<T> {
+    struct Next<'a, 'b, T>(&'a mut RBoxStream<'b, T>);
+
+    impl<T> Future for Next<'_, '_, T> {
+        type Output = Option<T>;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+            self.0.poll_next_unpin(cx)
+        }
+    }
+
+    block_on(Next(stream))
+}
+
+#[test]
+fn spawning_tasks() {
+    let (spawner, rspawner) = new_spawner();
+    let (sender, receiver) = mpsc::channel();
+
+    for i in 0..3 {
+        let sender = sender.clone();
+        let inner = rspawner.clone();
+        rspawner.spawn(RBoxFuture::new(async move {
+            inner.sleep(RDuration::from_millis(1)).await;
+            sender.send(i).unwrap();
+        }));
+    }
+
+    let mut list = (0..3)
+        .map(|_| receiver.recv_timeout(Duration::from_secs(10)).unwrap())
+        .collect::<Vec<u32>>();
+    list.sort_unstable();
+    assert_eq!(list, [0, 1, 2]);
+    assert_eq!(spawner.spawned.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn sleeping() {
+    let (_, rspawner) = new_spawner();
+
+    let start = Instant::now();
+    block_on(rspawner.sleep(RDuration::from_millis(20)));
+    assert!(start.elapsed() >= Duration::from_millis(20));
+}
+
+#[test]
+fn intervals() {
+    let (_, rspawner) = new_spawner();
+
+    let start = Instant::now();
+    let mut ticks = rspawner.interval(RDuration::from_millis(5));
+    for _ in 0..3 {
+        assert_eq!(next(&mut ticks), Some(()));
+    }
+    assert!(start.elapsed() >= Duration::from_millis(15));
+}
+
+#[test]
+fn cloning_shares_the_spawner() {
+    let (spawner, rspawner) = new_spawner();
+    let (sender, receiver) = mpsc::channel();
+
+    let cloned = rspawner.clone();
+    drop(rspawner);
+    cloned.spawn(RBoxFuture::new(async move { sender.send(()).unwrap() }));
+
+    receiver.recv_timeout(Duration::from_secs(10)).unwrap();
+    assert_eq!(spawner.spawned.load(Ordering::SeqCst), 1);
+}
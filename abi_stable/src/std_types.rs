@@ -6,12 +6,19 @@
 
 pub(crate) mod arc;
 pub(crate) mod boxed;
+pub mod btree_map;
+pub mod btree_set;
+pub(crate) mod c_str;
 pub(crate) mod cmp_ordering;
 pub mod cow;
+pub(crate) mod future;
 pub mod map;
 pub(crate) mod option;
+pub(crate) mod os_str;
+pub(crate) mod path;
 pub(crate) mod range;
 pub(crate) mod result;
+pub mod set;
 pub(crate) mod slice_mut;
 pub(crate) mod slices;
 pub(crate) mod std_error;
@@ -22,6 +29,7 @@ pub(crate) mod time;
 pub(crate) mod tuple;
 pub mod utypeid;
 pub mod vec;
+pub mod vec_deque;
 
 /// Some types from the `std::sync` module have ffi-safe equivalents in
 /// `abi_stable::external_types`.
@@ -38,19 +46,30 @@ pub mod sync {}
 pub use self::{
     arc::RArc,
     boxed::RBox,
+    btree_map::RBTreeMap,
+    btree_set::RBTreeSet,
+    c_str::{RCStr, RCString},
     cmp_ordering::RCmpOrdering,
     cow::RCow,
+    future::{
+        LocalRBoxFuture, LocalRBoxStream, RBoxFuture, RBoxFuture_, RBoxStream, RBoxStream_,
+        RContext, RPoll, RWaker,
+    },
     map::RHashMap,
     option::{RNone, ROption, RSome},
+    os_str::{ROsStr, ROsString},
+    path::{RPath, RPathBuf},
     result::{RErr, ROk, RResult},
+    set::RHashSet,
     slice_mut::RSliceMut,
     slices::RSlice,
     std_error::{RBoxError, RBoxError_, SendRBoxError, UnsyncRBoxError},
     std_io::{RIoError, RIoErrorKind, RSeekFrom},
     str::RStr,
     string::RString,
-    time::RDuration,
+    time::{RDuration, RInstant, RSystemTime},
     tuple::{Tuple1, Tuple2, Tuple3, Tuple4},
     utypeid::UTypeId,
     vec::RVec,
+    vec_deque::RVecDeque,
 };
@@ -0,0 +1,44 @@
+use super::*;
+
+// `MapKey` itself is shared with `RHashMap`, in `std_types::map::map_key`.
+
+impl<K> Ord for MapKey<K>
+where
+    K: Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (MapKey::Value(lhs), MapKey::Value(rhs)) => lhs.cmp(rhs),
+            (MapKey::Query(lhs), MapKey::Value(rhs)) => unsafe { lhs.as_ref().cmp_key(rhs) },
+            (MapKey::Value(lhs), MapKey::Query(rhs)) => unsafe {
+                rhs.as_ref().cmp_key(lhs).reverse()
+            },
+            // This happens when `BTreeMap::range` compares the bounds of the range.
+            (MapKey::Query(lhs), MapKey::Query(rhs)) => unsafe {
+                lhs.as_ref().cmp_query(rhs.as_ref())
+            },
+        }
+    }
+}
+
+impl<K> PartialOrd for MapKey<K>
+where
+    K: Ord,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K> Eq for MapKey<K> where K: Ord {}
+
+impl<K> PartialEq for MapKey<K>
+where
+    K: Ord,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
@@ -0,0 +1,110 @@
+use super::*;
+
+/// A trait object used in method that access map entries without replacing them.
+#[derive(StableAbi)]
+#[repr(C)]
+pub struct MapQuery<'a, K> {
+    _marker: NotCopyNotClone,
+    cmp_key: extern "C" fn(RRef<'_, ErasedObject>, &K) -> RCmpOrdering,
+    cmp_query: extern "C" fn(RRef<'_, ErasedObject>, RRef<'_, ErasedObject>) -> RCmpOrdering,
+    query: RRef<'a, ErasedObject>,
+}
+
+impl<'a, K> MapQuery<'a, K> {
+    #[inline]
+    pub(super) fn new<Q>(query: &'a &'a Q) -> Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + 'a + ?Sized,
+    {
+        MapQuery {
+            _marker: NotCopyNotClone,
+            cmp_key: cmp_key::<K, Q>,
+            cmp_query: cmp_query::<Q>,
+            query: unsafe { RRef::new(query).transmute() },
+        }
+    }
+
+    #[inline]
+    pub(super) unsafe fn as_static(&self) -> &MapQuery<'static, K> {
+        crate::utils::transmute_reference(self)
+    }
+}
+
+impl<'a, K> MapQuery<'a, K> {
+    /// Compares the query with `key`.
+    #[inline]
+    pub(super) fn cmp_key(&self, key: &K) -> Ordering {
+        (self.cmp_key)(self.query, key).into_ordering()
+    }
+
+    /// Compares the query with another one,
+    /// both of which must have been constructed from the same `Q` type.
+    #[inline]
+    pub(super) fn cmp_query(&self, other: &Self) -> Ordering {
+        (self.cmp_query)(self.query, other.query).into_ordering()
+    }
+
+    #[inline]
+    pub(super) unsafe fn as_mapkey(&self) -> MapKey<K> {
+        MapKey::Query(NonNull::from(self.as_static()))
+    }
+}
+
+extern "C" fn cmp_key<K, Q>(query: RRef<'_, ErasedObject>, key: &K) -> RCmpOrdering
+where
+    K: Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    extern_fn_panic_handling! {
+        let query = unsafe{ query.transmute_into_ref::<&Q>() };
+        (*query).cmp(key.borrow()).into()
+    }
+}
+
+extern "C" fn cmp_query<Q>(lhs: RRef<'_, ErasedObject>, rhs: RRef<'_, ErasedObject>) -> RCmpOrdering
+where
+    Q: Ord + ?Sized,
+{
+    extern_fn_panic_handling! {
+        let lhs = unsafe{ lhs.transmute_into_ref::<&Q>() };
+        let rhs = unsafe{ rhs.transmute_into_ref::<&Q>() };
+        (*lhs).cmp(*rhs).into()
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// One of the bounds of a range query.
+#[derive(StableAbi)]
+#[repr(C)]
+pub enum QueryBound<'a, K> {
+    Included(MapQuery<'a, K>),
+    Excluded(MapQuery<'a, K>),
+    Unbounded,
+}
+
+impl<'a, K> QueryBound<'a, K> {
+    pub(super) fn new<Q>(bound: &'a Bound<&'a Q>) -> Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + 'a + ?Sized,
+    {
+        match bound {
+            Bound::Included(query) => QueryBound::Included(MapQuery::new(query)),
+            Bound::Excluded(query) => QueryBound::Excluded(MapQuery::new(query)),
+            Bound::Unbounded => QueryBound::Unbounded,
+        }
+    }
+
+    /// # Safety
+    ///
+    /// The returned `Bound` must not be used after `self` is dropped.
+    pub(super) unsafe fn as_mapkey(&self) -> Bound<MapKey<K>> {
+        match self {
+            QueryBound::Included(query) => Bound::Included(query.as_mapkey()),
+            QueryBound::Excluded(query) => Bound::Excluded(query.as_mapkey()),
+            QueryBound::Unbounded => Bound::Unbounded,
+        }
+    }
+}
@@ -0,0 +1,260 @@
+use super::*;
+
+use crate::std_types::RString;
+
+fn new_stdmap() -> BTreeMap<u32, u32> {
+    vec![(90, 40), (10, 20), (88, 30), (77, 22)]
+        .into_iter()
+        .collect()
+}
+
+fn new_map() -> RBTreeMap<u32, u32> {
+    new_stdmap().into()
+}
+
+#[test]
+fn test_new_map() {
+    let mut map = RBTreeMap::new();
+    map.insert(10, 100);
+    assert_eq!(map.get(&10), Some(&100));
+}
+
+#[test]
+fn test_default() {
+    let default_ = RBTreeMap::<u32, u32>::default();
+    let new_ = RBTreeMap::<u32, u32>::new();
+
+    assert_eq!(default_.len(), 0);
+    assert_eq!(default_, new_);
+}
+
+#[test]
+fn test_get() {
+    let mut map = RBTreeMap::<RString, u32>::new();
+    map.insert("what".into(), 10);
+    map.insert("the".into(), 5);
+
+    assert_eq!(map.get("what"), Some(&10));
+    assert_eq!(map.get("the"), Some(&5));
+    assert_eq!(map.get("hello"), None);
+
+    *map.get_mut("the").unwrap() += 1;
+    assert_eq!(map["the"], 6);
+    map["what"] = 11;
+    assert_eq!(map.get_p(&RString::from("what")), Some(&11));
+
+    assert!(map.contains_key("the"));
+    assert!(!map.contains_key_p(&RString::from("hello")));
+}
+
+#[test]
+fn insert_remove() {
+    let mut map = RBTreeMap::<RString, u32>::new();
+
+    assert_eq!(map.insert("hello".into(), 3), RNone);
+    assert_eq!(map.insert("hello".into(), 4), RSome(3));
+    assert_eq!(map.insert("world".into(), 5), RNone);
+    assert_eq!(map.len(), 2);
+
+    assert_eq!(map.remove("hello"), RSome(4));
+    assert_eq!(map.remove("hello"), RNone);
+    assert_eq!(
+        map.remove_entry_p(&RString::from("world")),
+        RSome(Tuple2(RString::from("world"), 5))
+    );
+    assert!(map.is_empty());
+}
+
+#[test]
+fn clear() {
+    let mut map = new_map();
+    assert_eq!(map.len(), 4);
+
+    map.clear();
+
+    assert!(map.is_empty());
+    assert_eq!(map.get(&90), None);
+}
+
+#[test]
+fn ordered_iteration() {
+    let mut map = new_map();
+
+    assert_eq!(
+        map.iter().collect::<Vec<_>>(),
+        vec![
+            Tuple2(&10, &20),
+            Tuple2(&77, &22),
+            Tuple2(&88, &30),
+            Tuple2(&90, &40)
+        ],
+    );
+    assert_eq!(
+        map.keys().rev().collect::<Vec<_>>(),
+        vec![&90, &88, &77, &10]
+    );
+    assert_eq!(map.values().collect::<Vec<_>>(), vec![&20, &22, &30, &40]);
+
+    for Tuple2(_, v) in map.iter_mut() {
+        *v += 1;
+    }
+    assert_eq!(map.values().collect::<Vec<_>>(), vec![&21, &23, &31, &41]);
+
+    assert_eq!(map.first_key_value(), RSome(Tuple2(&10, &21)));
+    assert_eq!(map.last_key_value(), RSome(Tuple2(&90, &41)));
+
+    let mut iter = map.into_iter();
+    assert_eq!(iter.next(), Some(Tuple2(10, 21)));
+    assert_eq!(iter.next_back(), Some(Tuple2(90, 41)));
+    assert_eq!(
+        iter.collect::<Vec<_>>(),
+        vec![Tuple2(77, 23), Tuple2(88, 31)]
+    );
+}
+
+#[test]
+fn ranges() {
+    let map = new_map();
+
+    fn keys<'a, I>(range: I) -> Vec<u32>
+    where
+        I: Iterator<Item = Tuple2<&'a u32, &'a u32>>,
+    {
+        range.map(|x| *x.0).collect()
+    }
+
+    assert_eq!(keys(map.range(..)), vec![10, 77, 88, 90]);
+    assert_eq!(keys(map.range(77..90)), vec![77, 88]);
+    assert_eq!(keys(map.range(77..=90)), vec![77, 88, 90]);
+    assert_eq!(keys(map.range(78..)), vec![88, 90]);
+    assert_eq!(keys(map.range(..88)), vec![10, 77]);
+    assert_eq!(keys(map.range(0..5)), Vec::<u32>::new());
+    assert_eq!(keys(map.range(5..5)), Vec::<u32>::new());
+    assert_eq!(
+        keys(map.range((Bound::Excluded(77), Bound::Unbounded))),
+        vec![88, 90]
+    );
+    assert_eq!(keys(map.range(..).rev()), vec![90, 88, 77, 10]);
+
+    let strings = vec!["a", "b", "c", "d"]
+        .into_iter()
+        .map(|s| (RString::from(s), ()))
+        .collect::<RBTreeMap<RString, ()>>();
+
+    assert_eq!(
+        strings
+            .range::<str, _>((Bound::Included("b"), Bound::Excluded("d")))
+            .map(|x| x.0.as_str())
+            .collect::<Vec<_>>(),
+        vec!["b", "c"],
+    );
+}
+
+#[test]
+fn range_mut() {
+    let mut map = new_map();
+
+    for Tuple2(_, v) in map.range_mut(77..=88) {
+        *v = 0;
+    }
+
+    assert_eq!(map.values().collect::<Vec<_>>(), vec![&20, &0, &0, &40]);
+}
+
+#[test]
+#[should_panic]
+#[allow(clippy::reversed_empty_ranges)]
+fn decreasing_range() {
+    let map = new_map();
+    let _ = map.range(80..70);
+}
+
+#[test]
+#[should_panic]
+fn empty_excluded_range() {
+    let map = new_map();
+    let _ = map.range((Bound::Excluded(80), Bound::Excluded(80)));
+}
+
+#[test]
+fn from_btreemap() {
+    let stdmap = new_stdmap();
+
+    let map: RBTreeMap<u32, u32> = stdmap.clone().into();
+
+    assert_eq!(map.len(), 4);
+
+    let list = map.iter().map(|x| (*x.0, *x.1)).collect::<Vec<_>>();
+    assert_eq!(list, stdmap.into_iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn into_btreemap() {
+    let stdmap = new_stdmap();
+
+    let map: RBTreeMap<u32, u32> = stdmap.clone().into();
+
+    let stdmap2: BTreeMap<_, _> = map.into();
+
+    assert_eq!(stdmap2, stdmap);
+}
+
+#[test]
+fn clone_and_compare() {
+    let map = new_map();
+    let clone = map.clone();
+
+    assert_eq!(map, clone);
+
+    let mut other = map.clone();
+    other.insert(1, 1);
+    assert_ne!(map, other);
+    assert!(other < map);
+}
+
+#[test]
+fn entries() {
+    let mut map = RBTreeMap::<RString, u32>::new();
+
+    assert_eq!(map.entry("foo".into()).get(), None);
+    assert_eq!(map.entry("foo".into()).or_insert(3), &mut 3);
+    assert_eq!(map.entry("foo".into()).or_insert(4), &mut 3);
+
+    *map.entry("bar".into()).or_default() += 10;
+    map.entry("bar".into()).and_modify(|x| *x *= 2);
+    assert_eq!(map["bar"], 20);
+
+    match map.entry("foo".into()) {
+        REntry::Occupied(entry) => assert_eq!(entry.remove(), 3),
+        REntry::Vacant(_) => unreachable!(),
+    }
+
+    match map.entry("baz".into()) {
+        REntry::Occupied(_) => unreachable!(),
+        REntry::Vacant(entry) => assert_eq!(entry.insert(5), &mut 5),
+    }
+
+    assert_eq!(
+        map.keys().map(RString::as_str).collect::<Vec<_>>(),
+        vec!["bar", "baz"]
+    );
+}
+
+#[test]
+fn test_serde() {
+    let mut map = RBTreeMap::<String, RString>::new();
+
+    map.insert("90".into(), "40".into());
+    map.insert("10".into(), "20".into());
+    map.insert("88".into(), "30".into());
+
+    let json = r##"{"10":"20","88":"30","90":"40"}"##;
+
+    let deserialized = serde_json::from_str::<RBTreeMap<String, RString>>(json).unwrap();
+
+    assert_eq!(deserialized, map);
+
+    let serialized = serde_json::to_string(&map).unwrap();
+
+    assert_eq!(serialized, json);
+}
@@ -0,0 +1,929 @@
+//! Contains the ffi-safe equivalent of `std::collections::BTreeSet`, and related items.
+
+use std::{
+    borrow::Borrow,
+    cmp::{Eq, Ord, Ordering, PartialEq, PartialOrd},
+    collections::BTreeSet,
+    fmt::{self, Debug},
+    iter::{FromIterator, Peekable},
+    ops::RangeBounds,
+};
+
+use crate::{
+    marker_type::NonOwningPhantom,
+    std_types::{
+        btree_map::{self, Keys},
+        RBTreeMap, ROption, Tuple2,
+    },
+    StableAbi,
+};
+
+#[cfg(all(test, not(feature = "only_new_tests")))]
+mod test;
+
+/// An ffi-safe ordered set, which wraps `std::collections::BTreeSet<T>`,
+/// only requiring the `T: Ord` bound when constructing it.
+///
+/// This is implemented on top of an `RBTreeMap<T, ()>`,
+/// so it is type-erased and checked for layout compatibility the same way.
+///
+/// # Example
+///
+/// ```
+/// use abi_stable::std_types::{RBTreeSet, RString};
+///
+/// let mut set = RBTreeSet::<RString>::new();
+///
+/// assert!(set.insert("cheese".into()));
+/// assert!(set.insert("bread".into()));
+/// assert!(!set.insert("bread".into()));
+///
+/// assert_eq!(
+///     set.iter().map(RString::as_str).collect::<Vec<_>>(),
+///     vec!["bread", "cheese"],
+/// );
+///
+/// assert!(set.remove("cheese"));
+/// assert!(!set.contains("cheese"));
+///
+/// ```
+///
+#[derive(StableAbi)]
+#[repr(C)]
+pub struct RBTreeSet<T> {
+    map: RBTreeMap<T, ()>,
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+impl<T> RBTreeSet<T> {
+    /// Constructs an empty RBTreeSet.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RBTreeSet, RString};
+    ///
+    /// let mut set = RBTreeSet::<RString>::new();
+    /// assert!(set.is_empty());
+    /// set.insert("Hello".into());
+    /// assert_eq!(set.is_empty(), false);
+    ///
+    /// ```
+    #[inline]
+    pub fn new() -> RBTreeSet<T>
+    where
+        T: Ord,
+    {
+        RBTreeSet {
+            map: RBTreeMap::new(),
+        }
+    }
+
+    /// Returns whether the set contains the value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RBTreeSet, RString};
+    ///
+    /// let mut set = RBTreeSet::<RString>::new();
+    /// assert_eq!(set.contains("boo"), false);
+    /// set.insert("boo".into());
+    /// assert_eq!(set.contains("boo"), true);
+    ///
+    /// ```
+    pub fn contains<Q>(&self, query: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.contains_key(query)
+    }
+
+    /// Removes the value from the set,returning whether it was in the set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RBTreeSet;
+    ///
+    /// let mut set = vec![0, 3].into_iter().collect::<RBTreeSet<u32>>();
+    ///
+    /// assert_eq!(set.remove(&0), true);
+    /// assert_eq!(set.remove(&0), false);
+    ///
+    /// ```
+    pub fn remove<Q>(&mut self, query: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.remove(query).is_some()
+    }
+
+    /// Removes the value from the set,returning it if it was in the set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RBTreeSet, RNone, RSome, RString};
+    ///
+    /// let mut set = RBTreeSet::<RString>::new();
+    /// set.insert("foo".into());
+    ///
+    /// assert_eq!(set.take("foo"), RSome(RString::from("foo")));
+    /// assert_eq!(set.take("foo"), RNone);
+    ///
+    /// ```
+    pub fn take<Q>(&mut self, query: &Q) -> ROption<T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.remove_entry(query).map(|Tuple2(k, ())| k)
+    }
+
+    /// Iterates over the values in the `range`,in ascending order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than the end of the range,
+    /// or if both are equal and excluded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RBTreeSet;
+    ///
+    /// let set = vec![0, 3, 5, 8].into_iter().collect::<RBTreeSet<u32>>();
+    ///
+    /// assert_eq!(set.range(3..8).collect::<Vec<_>>(), vec![&3, &5]);
+    /// assert_eq!(set.range(4..).rev().collect::<Vec<_>>(), vec![&8, &5]);
+    ///
+    /// ```
+    pub fn range<Q, R>(&self, range: R) -> Range<'_, T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        Range {
+            inner: self.map.range(range),
+        }
+    }
+}
+
+impl<T> RBTreeSet<T> {
+    /// Returns whether the set contains the value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RBTreeSet;
+    ///
+    /// let mut set = RBTreeSet::<u32>::new();
+    /// assert_eq!(set.contains_p(&11), false);
+    /// set.insert(11);
+    /// assert_eq!(set.contains_p(&11), true);
+    ///
+    /// ```
+    pub fn contains_p(&self, value: &T) -> bool {
+        self.map.contains_key_p(value)
+    }
+
+    /// Removes the value from the set,returning whether it was in the set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RBTreeSet;
+    ///
+    /// let mut set = vec![0, 3].into_iter().collect::<RBTreeSet<u32>>();
+    ///
+    /// assert_eq!(set.remove_p(&0), true);
+    /// assert_eq!(set.remove_p(&0), false);
+    ///
+    /// ```
+    pub fn remove_p(&mut self, value: &T) -> bool {
+        self.map.remove_p(value).is_some()
+    }
+
+    /// Removes the value from the set,returning it if it was in the set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RBTreeSet, RNone, RSome};
+    ///
+    /// let mut set = vec![0, 3].into_iter().collect::<RBTreeSet<u32>>();
+    ///
+    /// assert_eq!(set.take_p(&0), RSome(0));
+    /// assert_eq!(set.take_p(&0), RNone);
+    ///
+    /// ```
+    pub fn take_p(&mut self, value: &T) -> ROption<T> {
+        self.map.remove_entry_p(value).map(|Tuple2(k, ())| k)
+    }
+
+    /// Adds the value to the set,
+    /// returning whether the value was not in the set before.
+    ///
+    /// If the value was already in the set,the set is not modified.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RBTreeSet;
+    ///
+    /// let mut set = RBTreeSet::<u32>::new();
+    ///
+    /// assert_eq!(set.insert(0), true);
+    /// assert_eq!(set.insert(0), false);
+    /// assert_eq!(set.len(), 1);
+    ///
+    /// ```
+    pub fn insert(&mut self, value: T) -> bool {
+        self.map.insert(value, ()).is_none()
+    }
+
+    /// Removes all the elements in the set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RBTreeSet;
+    ///
+    /// let mut set = vec![0, 3].into_iter().collect::<RBTreeSet<u32>>();
+    ///
+    /// set.clear();
+    ///
+    /// assert_eq!(set.contains(&0), false);
+    /// assert_eq!(set.contains(&3), false);
+    ///
+    /// ```
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    /// Returns the amount of elements in the set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RBTreeSet;
+    ///
+    /// let mut set = RBTreeSet::<u32>::new();
+    /// assert_eq!(set.len(), 0);
+    /// set.insert(0);
+    /// assert_eq!(set.len(), 1);
+    ///
+    /// ```
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns whether the set contains any elements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RBTreeSet;
+    ///
+    /// let mut set = RBTreeSet::<u32>::new();
+    /// assert!(set.is_empty());
+    /// set.insert(0);
+    /// assert!(!set.is_empty());
+    ///
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Returns the smallest element in the set,if it isn't empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RBTreeSet, RNone, RSome};
+    ///
+    /// let mut set = RBTreeSet::<u32>::new();
+    /// assert_eq!(set.first(), RNone);
+    ///
+    /// set.insert(5);
+    /// set.insert(3);
+    /// assert_eq!(set.first(), RSome(&3));
+    ///
+    /// ```
+    pub fn first(&self) -> ROption<&T> {
+        self.map.first_key_value().map(|Tuple2(k, _)| k)
+    }
+
+    /// Returns the largest element in the set,if it isn't empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RBTreeSet, RNone, RSome};
+    ///
+    /// let mut set = RBTreeSet::<u32>::new();
+    /// assert_eq!(set.last(), RNone);
+    ///
+    /// set.insert(5);
+    /// set.insert(3);
+    /// assert_eq!(set.last(), RSome(&5));
+    ///
+    /// ```
+    pub fn last(&self) -> ROption<&T> {
+        self.map.last_key_value().map(|Tuple2(k, _)| k)
+    }
+
+    /// Iterates over the elements in the set,in ascending order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RBTreeSet;
+    ///
+    /// let set = vec![3, 0].into_iter().collect::<RBTreeSet<u32>>();
+    ///
+    /// assert_eq!(set.iter().collect::<Vec<_>>(), vec![&0, &3]);
+    ///
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            inner: self.map.keys(),
+        }
+    }
+
+    /// Iterates over the elements that are in `self`, `other`, or both,
+    /// in ascending order and without duplicates.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RBTreeSet;
+    ///
+    /// let a = vec![0, 1, 2].into_iter().collect::<RBTreeSet<u32>>();
+    /// let b = vec![1, 2, 3].into_iter().collect::<RBTreeSet<u32>>();
+    ///
+    /// assert_eq!(a.union(&b).collect::<Vec<_>>(), vec![&0, &1, &2, &3]);
+    ///
+    /// ```
+    pub fn union<'a>(&'a self, other: &'a RBTreeSet<T>) -> Union<'a, T> {
+        Union {
+            a: self.iter().peekable(),
+            b: other.iter().peekable(),
+        }
+    }
+
+    /// Iterates over the elements that are in both `self` and `other`,
+    /// in ascending order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RBTreeSet;
+    ///
+    /// let a = vec![0, 1, 2].into_iter().collect::<RBTreeSet<u32>>();
+    /// let b = vec![1, 2, 3].into_iter().collect::<RBTreeSet<u32>>();
+    ///
+    /// assert_eq!(a.intersection(&b).collect::<Vec<_>>(), vec![&1, &2]);
+    ///
+    /// ```
+    pub fn intersection<'a>(&'a self, other: &'a RBTreeSet<T>) -> Intersection<'a, T> {
+        // Iterating over the smaller set,since every element is looked up in the other one.
+        let (iter, other) = if self.len() <= other.len() {
+            (self.iter(), other)
+        } else {
+            (other.iter(), self)
+        };
+        Intersection { iter, other }
+    }
+
+    /// Iterates over the elements that are in `self` but not in `other`,
+    /// in ascending order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RBTreeSet;
+    ///
+    /// let a = vec![0, 1, 2].into_iter().collect::<RBTreeSet<u32>>();
+    /// let b = vec![1, 2, 3].into_iter().collect::<RBTreeSet<u32>>();
+    ///
+    /// assert_eq!(a.difference(&b).collect::<Vec<_>>(), vec![&0]);
+    /// assert_eq!(b.difference(&a).collect::<Vec<_>>(), vec![&3]);
+    ///
+    /// ```
+    pub fn difference<'a>(&'a self, other: &'a RBTreeSet<T>) -> Difference<'a, T> {
+        Difference {
+            iter: self.iter(),
+            other,
+        }
+    }
+
+    /// Iterates over the elements that are in either `self` or `other`,
+    /// but not in both,in ascending order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RBTreeSet;
+    ///
+    /// let a = vec![0, 1, 2].into_iter().collect::<RBTreeSet<u32>>();
+    /// let b = vec![1, 2, 3].into_iter().collect::<RBTreeSet<u32>>();
+    ///
+    /// assert_eq!(a.symmetric_difference(&b).collect::<Vec<_>>(), vec![&0, &3]);
+    ///
+    /// ```
+    pub fn symmetric_difference<'a>(
+        &'a self,
+        other: &'a RBTreeSet<T>,
+    ) -> SymmetricDifference<'a, T> {
+        SymmetricDifference {
+            a: self.iter().peekable(),
+            b: other.iter().peekable(),
+        }
+    }
+
+    /// Returns whether `self` and `other` have no elements in common.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RBTreeSet;
+    ///
+    /// let a = vec![0, 1].into_iter().collect::<RBTreeSet<u32>>();
+    /// let b = vec![1, 2].into_iter().collect::<RBTreeSet<u32>>();
+    /// let c = vec![2, 3].into_iter().collect::<RBTreeSet<u32>>();
+    ///
+    /// assert!(!a.is_disjoint(&b));
+    /// assert!(a.is_disjoint(&c));
+    ///
+    /// ```
+    pub fn is_disjoint(&self, other: &RBTreeSet<T>) -> bool {
+        self.intersection(other).next().is_none()
+    }
+
+    /// Returns whether all the elements in `self` are in `other`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RBTreeSet;
+    ///
+    /// let a = vec![0, 1].into_iter().collect::<RBTreeSet<u32>>();
+    /// let b = vec![0, 1, 2].into_iter().collect::<RBTreeSet<u32>>();
+    ///
+    /// assert!(a.is_subset(&b));
+    /// assert!(!b.is_subset(&a));
+    ///
+    /// ```
+    pub fn is_subset(&self, other: &RBTreeSet<T>) -> bool {
+        self.len() <= other.len() && self.iter().all(|v| other.contains_p(v))
+    }
+
+    /// Returns whether all the elements in `other` are in `self`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RBTreeSet;
+    ///
+    /// let a = vec![0, 1, 2].into_iter().collect::<RBTreeSet<u32>>();
+    /// let b = vec![0, 1].into_iter().collect::<RBTreeSet<u32>>();
+    ///
+    /// assert!(a.is_superset(&b));
+    /// assert!(!b.is_superset(&a));
+    ///
+    /// ```
+    pub fn is_superset(&self, other: &RBTreeSet<T>) -> bool {
+        other.is_subset(self)
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// An iterator over the elements of an `RBTreeSet`,in ascending order.
+///
+/// This `struct` is created by the [`iter`] method on [`RBTreeSet`].
+///
+/// [`iter`]: RBTreeSet::iter
+#[repr(C)]
+#[derive(StableAbi)]
+pub struct Iter<'a, T> {
+    inner: Keys<'a, T, ()>,
+}
+
+impl<T> Clone for Iter<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Iter {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T: Debug> Debug for Iter<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        self.inner.next()
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a T> {
+        self.inner.next_back()
+    }
+}
+
+/// An iterator over a range of elements of an `RBTreeSet`,in ascending order.
+///
+/// This `struct` is created by the [`range`] method on [`RBTreeSet`].
+///
+/// [`range`]: RBTreeSet::range
+#[repr(C)]
+#[derive(StableAbi)]
+pub struct Range<'a, T: 'a> {
+    inner: btree_map::Range<'a, T, ()>,
+}
+
+impl<'a, T> Iterator for Range<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        self.inner.next().map(|tuple| tuple.0)
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Range<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a T> {
+        self.inner.next_back().map(|tuple| tuple.0)
+    }
+}
+
+/// An iterator over the elements of an `RBTreeSet`,in ascending order,
+/// which takes them by value.
+///
+/// This `struct` is created by the `into_iter` method on [`RBTreeSet`].
+#[repr(C)]
+#[derive(StableAbi)]
+pub struct IntoIter<T> {
+    inner: btree_map::IntoIter<T, ()>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.inner.next().map(|Tuple2(k, ())| k)
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        self.inner.next_back().map(|Tuple2(k, ())| k)
+    }
+}
+
+/// An iterator over the union of two `RBTreeSet`s,in ascending order.
+///
+/// This `struct` is created by the [`union`] method on [`RBTreeSet`].
+///
+/// [`union`]: RBTreeSet::union
+pub struct Union<'a, T> {
+    a: Peekable<Iter<'a, T>>,
+    b: Peekable<Iter<'a, T>>,
+}
+
+impl<'a, T: Ord> Iterator for Union<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        match (self.a.peek(), self.b.peek()) {
+            (Some(a), Some(b)) => match a.cmp(b) {
+                Ordering::Less => self.a.next(),
+                Ordering::Greater => self.b.next(),
+                Ordering::Equal => {
+                    self.b.next();
+                    self.a.next()
+                }
+            },
+            (Some(_), None) => self.a.next(),
+            (None, _) => self.b.next(),
+        }
+    }
+}
+
+/// An iterator over the intersection of two `RBTreeSet`s,in ascending order.
+///
+/// This `struct` is created by the [`intersection`] method on [`RBTreeSet`].
+///
+/// [`intersection`]: RBTreeSet::intersection
+pub struct Intersection<'a, T> {
+    iter: Iter<'a, T>,
+    other: &'a RBTreeSet<T>,
+}
+
+impl<'a, T> Iterator for Intersection<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let other = self.other;
+        self.iter.find(|v| other.contains_p(v))
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+/// An iterator over the difference of two `RBTreeSet`s,in ascending order.
+///
+/// This `struct` is created by the [`difference`] method on [`RBTreeSet`].
+///
+/// [`difference`]: RBTreeSet::difference
+pub struct Difference<'a, T> {
+    iter: Iter<'a, T>,
+    other: &'a RBTreeSet<T>,
+}
+
+impl<'a, T> Iterator for Difference<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let other = self.other;
+        self.iter.find(|v| !other.contains_p(v))
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+/// An iterator over the symmetric difference of two `RBTreeSet`s,in ascending order.
+///
+/// This `struct` is created by the [`symmetric_difference`] method on [`RBTreeSet`].
+///
+/// [`symmetric_difference`]: RBTreeSet::symmetric_difference
+pub struct SymmetricDifference<'a, T> {
+    a: Peekable<Iter<'a, T>>,
+    b: Peekable<Iter<'a, T>>,
+}
+
+impl<'a, T: Ord> Iterator for SymmetricDifference<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        loop {
+            match (self.a.peek(), self.b.peek()) {
+                (Some(a), Some(b)) => match a.cmp(b) {
+                    Ordering::Less => return self.a.next(),
+                    Ordering::Greater => return self.b.next(),
+                    Ordering::Equal => {
+                        self.a.next();
+                        self.b.next();
+                    }
+                },
+                (Some(_), None) => return self.a.next(),
+                (None, _) => return self.b.next(),
+            }
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// This returns an `DoubleEndedIterator<Item= T >+!Send+!Sync`
+impl<T> IntoIterator for RBTreeSet<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter {
+            inner: self.map.into_iter(),
+        }
+    }
+}
+
+/// This returns an `DoubleEndedIterator<Item= &T > + !Send + !Sync + Clone`
+impl<'a, T> IntoIterator for &'a RBTreeSet<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T> From<BTreeSet<T>> for RBTreeSet<T>
+where
+    T: Ord,
+{
+    fn from(set: BTreeSet<T>) -> Self {
+        set.into_iter().collect()
+    }
+}
+
+impl<T> From<RBTreeSet<T>> for BTreeSet<T>
+where
+    T: Ord,
+{
+    fn from(this: RBTreeSet<T>) -> BTreeSet<T> {
+        this.into_iter().collect()
+    }
+}
+
+impl<T> FromIterator<T> for RBTreeSet<T>
+where
+    T: Ord,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T> Extend<T> for RBTreeSet<T> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl<'a, T> Extend<&'a T> for RBTreeSet<T>
+where
+    T: Copy + 'a,
+{
+    #[inline]
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a T>,
+    {
+        self.extend(iter.into_iter().cloned());
+    }
+}
+
+impl<T> Default for RBTreeSet<T>
+where
+    T: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for RBTreeSet<T>
+where
+    T: Clone + Ord,
+{
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T> Debug for RBTreeSet<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<T> Eq for RBTreeSet<T> where T: Eq {}
+
+impl<T> PartialEq for RBTreeSet<T>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T> Ord for RBTreeSet<T>
+where
+    T: Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T> PartialOrd for RBTreeSet<T>
+where
+    T: PartialOrd,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+mod serde {
+    use super::*;
+
+    use ::serde::{
+        de::{SeqAccess, Visitor},
+        ser::SerializeSeq,
+        Deserialize, Deserializer, Serialize, Serializer,
+    };
+
+    struct RBTreeSetVisitor<T> {
+        _marker: NonOwningPhantom<RBTreeSet<T>>,
+    }
+
+    impl<T> RBTreeSetVisitor<T> {
+        fn new() -> Self {
+            RBTreeSetVisitor {
+                _marker: NonOwningPhantom::NEW,
+            }
+        }
+    }
+
+    impl<'de, T> Visitor<'de> for RBTreeSetVisitor<T>
+    where
+        T: Deserialize<'de> + Ord,
+    {
+        type Value = RBTreeSet<T>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("an RBTreeSet")
+        }
+
+        fn visit_seq<A>(self, mut seq_access: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let mut set = RBTreeSet::new();
+
+            while let Some(value) = seq_access.next_element()? {
+                set.insert(value);
+            }
+
+            Ok(set)
+        }
+    }
+
+    impl<'de, T> Deserialize<'de> for RBTreeSet<T>
+    where
+        T: Deserialize<'de> + Ord,
+    {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_seq(RBTreeSetVisitor::new())
+        }
+    }
+
+    impl<T> Serialize for RBTreeSet<T>
+    where
+        T: Serialize,
+    {
+        fn serialize<Z>(&self, serializer: Z) -> Result<Z::Ok, Z::Error>
+        where
+            Z: Serializer,
+        {
+            let mut seq = serializer.serialize_seq(Some(self.len()))?;
+            for value in self.iter() {
+                seq.serialize_element(value)?;
+            }
+            seq.end()
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use super::*;
+
+use crate::std_types::{RNone, RSome, RString};
+
+fn new_stdset() -> BTreeSet<u32> {
+    vec![90, 10, 88, 77].into_iter().collect()
+}
+
+fn new_set(list: &[u32]) -> RBTreeSet<u32> {
+    list.iter().cloned().collect()
+}
+
+fn to_vec<'a, I>(iter: I) -> Vec<u32>
+where
+    I: IntoIterator<Item = &'a u32>,
+{
+    iter.into_iter().cloned().collect()
+}
+
+#[test]
+fn test_new_set() {
+    let mut set = RBTreeSet::new();
+    set.insert(10);
+    assert!(set.contains(&10));
+}
+
+#[test]
+fn test_default() {
+    let default_ = RBTreeSet::<u32>::default();
+    let new_ = RBTreeSet::<u32>::new();
+
+    assert_eq!(default_.len(), 0);
+    assert_eq!(default_, new_);
+}
+
+#[test]
+fn insert_remove() {
+    let mut set = RBTreeSet::<RString>::new();
+
+    assert!(set.insert("hello".into()));
+    assert!(set.insert("world".into()));
+    assert!(!set.insert("hello".into()));
+    assert_eq!(set.len(), 2);
+
+    assert!(set.remove("hello"));
+    assert!(!set.remove("hello"));
+    assert!(!set.contains("hello"));
+
+    assert_eq!(set.take("world"), RSome(RString::from("world")));
+    assert_eq!(set.take("world"), RNone);
+    assert!(set.is_empty());
+
+    set.insert("what".into());
+    assert!(set.contains_p(&RString::from("what")));
+    assert!(set.remove_p(&RString::from("what")));
+    assert!(!set.contains_p(&RString::from("what")));
+}
+
+#[test]
+fn ordered_iteration() {
+    let set: RBTreeSet<u32> = new_stdset().into();
+
+    assert_eq!(to_vec(&set), vec![10, 77, 88, 90]);
+    assert_eq!(to_vec(set.iter().rev()), vec![90, 88, 77, 10]);
+    assert_eq!(to_vec(set.range(20..90)), vec![77, 88]);
+    assert_eq!(to_vec(set.range(..=77).rev()), vec![77, 10]);
+
+    assert_eq!(set.first(), RSome(&10));
+    assert_eq!(set.last(), RSome(&90));
+
+    let mut iter = set.into_iter();
+    assert_eq!(iter.next_back(), Some(90));
+    assert_eq!(iter.collect::<Vec<_>>(), vec![10, 77, 88]);
+}
+
+#[test]
+fn from_btreeset() {
+    let stdset = new_stdset();
+
+    let set: RBTreeSet<u32> = stdset.clone().into();
+    assert_eq!(set.len(), 4);
+    assert_eq!(to_vec(&set), to_vec(&stdset));
+
+    let stdset2: BTreeSet<_> = set.into();
+    assert_eq!(stdset2, stdset);
+}
+
+#[test]
+fn extend() {
+    let mut set = RBTreeSet::<u32>::new();
+
+    set.extend(vec![2, 1, 0]);
+    set.extend(&[3, 2]);
+
+    assert_eq!(to_vec(&set), vec![0, 1, 2, 3]);
+}
+
+#[test]
+fn set_operations() {
+    let a = new_set(&[0, 1, 2, 3, 8]);
+    let b = new_set(&[2, 3, 4, 5]);
+    let empty = RBTreeSet::<u32>::new();
+
+    assert_eq!(to_vec(a.union(&b)), vec![0, 1, 2, 3, 4, 5, 8]);
+    assert_eq!(to_vec(b.union(&a)), vec![0, 1, 2, 3, 4, 5, 8]);
+    assert_eq!(to_vec(a.intersection(&b)), vec![2, 3]);
+    assert_eq!(to_vec(b.intersection(&a)), vec![2, 3]);
+    assert_eq!(to_vec(a.difference(&b)), vec![0, 1, 8]);
+    assert_eq!(to_vec(b.difference(&a)), vec![4, 5]);
+    assert_eq!(to_vec(a.symmetric_difference(&b)), vec![0, 1, 4, 5, 8]);
+
+    assert_eq!(to_vec(a.union(&empty)), vec![0, 1, 2, 3, 8]);
+    assert_eq!(to_vec(a.intersection(&empty)), Vec::<u32>::new());
+    assert_eq!(to_vec(empty.difference(&a)), Vec::<u32>::new());
+    assert_eq!(to_vec(empty.symmetric_difference(&a)), vec![0, 1, 2, 3, 8]);
+}
+
+#[test]
+fn set_relations() {
+    let a = new_set(&[0, 1, 2, 3]);
+    let b = new_set(&[1, 2]);
+    let c = new_set(&[4, 5]);
+
+    assert!(b.is_subset(&a));
+    assert!(!a.is_subset(&b));
+    assert!(a.is_superset(&b));
+    assert!(!b.is_superset(&a));
+
+    assert!(a.is_disjoint(&c));
+    assert!(!a.is_disjoint(&b));
+
+    assert!(b > a);
+    assert_eq!(a.clone(), a);
+}
+
+#[test]
+fn test_serde() {
+    let set = vec!["90", "10", "88"]
+        .into_iter()
+        .map(RString::from)
+        .collect::<RBTreeSet<RString>>();
+
+    let json = r##"["10","88","90"]"##;
+
+    let deserialized = serde_json::from_str::<RBTreeSet<RString>>(json).unwrap();
+
+    assert_eq!(deserialized, set);
+
+    let serialized = serde_json::to_string(&set).unwrap();
+
+    assert_eq!(serialized, json);
+}
@@ -0,0 +1,681 @@
+//! Contains ffi-safe equivalents of `&'a CStr` and `CString`.
+
+use std::{
+    borrow::{Borrow, Cow},
+    cmp::Ordering,
+    ffi::{CStr, CString, FromBytesWithNulError, NulError},
+    fmt::{self, Debug},
+    hash::{Hash, Hasher},
+    ops::Deref,
+    os::raw::c_char,
+    str::{self, Utf8Error},
+};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    sabi_types::NulStr,
+    std_types::{RSlice, RVec},
+};
+
+#[cfg(all(test, not(feature = "only_new_tests")))]
+mod test;
+
+/// Ffi-safe equivalent of `&'a CStr`.
+///
+/// Unlike [`NulStr`](../sabi_types/struct.NulStr.html),
+/// this stores the length of the string (including the nul terminator),
+/// so converting it to a `&CStr` or a slice doesn't require searching for the nul byte.
+///
+/// # Example
+///
+/// ```
+/// use abi_stable::{sabi_extern_fn, std_types::RCStr};
+///
+/// use std::ffi::CStr;
+///
+/// #[sabi_extern_fn]
+/// fn count_spaces(string: RCStr<'_>) -> usize {
+///     string.to_bytes().iter().filter(|&&b| b == b' ').count()
+/// }
+///
+/// let string = CStr::from_bytes_with_nul(b"hello world, bye world\0").unwrap();
+///
+/// assert_eq!(count_spaces(string.into()), 3);
+///
+/// ```
+#[repr(C)]
+#[derive(Copy, Clone, StableAbi)]
+pub struct RCStr<'a> {
+    // Includes the nul terminator.
+    inner: RSlice<'a, u8>,
+}
+
+impl<'a> RCStr<'a> {
+    /// Converts a `&'a CStr` to an `RCStr<'a>`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RCStr;
+    ///
+    /// use std::ffi::CStr;
+    ///
+    /// let cstr = CStr::from_bytes_with_nul(b"hello\0").unwrap();
+    ///
+    /// assert_eq!(RCStr::from_c_str(cstr).as_c_str(), cstr);
+    ///
+    /// ```
+    pub fn from_c_str(cstr: &'a CStr) -> Self {
+        Self {
+            inner: RSlice::from_slice(cstr.to_bytes_with_nul()),
+        }
+    }
+
+    /// Constructs an `RCStr<'a>` from a nul-terminated byte slice.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if the slice doesn't end with a nul byte,
+    /// or if it contains a nul byte before the end.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RCStr;
+    ///
+    /// assert_eq!(RCStr::from_bytes_with_nul(b"hello\0").unwrap().to_bytes(), b"hello");
+    ///
+    /// assert!(RCStr::from_bytes_with_nul(b"hello").is_err());
+    /// assert!(RCStr::from_bytes_with_nul(b"hel\0lo\0").is_err());
+    ///
+    /// ```
+    pub fn from_bytes_with_nul(bytes: &'a [u8]) -> Result<Self, FromBytesWithNulError> {
+        CStr::from_bytes_with_nul(bytes).map(Self::from_c_str)
+    }
+
+    /// Converts this `RCStr<'a>` to a `&'a CStr`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RCStr;
+    ///
+    /// use std::ffi::CStr;
+    ///
+    /// let cstr = CStr::from_bytes_with_nul(b"world\0").unwrap();
+    ///
+    /// assert_eq!(RCStr::from(cstr).as_c_str(), cstr);
+    ///
+    /// ```
+    pub fn as_c_str(&self) -> &'a CStr {
+        unsafe { CStr::from_bytes_with_nul_unchecked(self.inner.as_slice()) }
+    }
+
+    /// Gets a pointer to the start of this nul-terminated string,
+    /// which can be passed to C functions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RCStr;
+    ///
+    /// let bytes = b"hello\0";
+    /// let string = RCStr::from_bytes_with_nul(bytes).unwrap();
+    ///
+    /// assert_eq!(string.as_ptr() as *const u8, bytes.as_ptr());
+    ///
+    /// ```
+    pub fn as_ptr(&self) -> *const c_char {
+        self.inner.as_ptr() as *const c_char
+    }
+
+    /// Gets the bytes of this string, without the nul terminator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RCStr;
+    ///
+    /// let string = RCStr::from_bytes_with_nul(b"hello\0").unwrap();
+    ///
+    /// assert_eq!(string.to_bytes(), b"hello");
+    ///
+    /// ```
+    pub fn to_bytes(&self) -> &'a [u8] {
+        let bytes = self.inner.as_slice();
+        &bytes[..bytes.len() - 1]
+    }
+
+    /// Gets the bytes of this string, including the nul terminator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RCStr;
+    ///
+    /// let string = RCStr::from_bytes_with_nul(b"hello\0").unwrap();
+    ///
+    /// assert_eq!(string.to_bytes_with_nul(), b"hello\0");
+    ///
+    /// ```
+    pub fn to_bytes_with_nul(&self) -> &'a [u8] {
+        self.inner.as_slice()
+    }
+
+    /// Gets the length of the string, not including the nul terminator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RCStr;
+    ///
+    /// assert_eq!(RCStr::from_bytes_with_nul(b"\0").unwrap().len(), 0);
+    /// assert_eq!(RCStr::from_bytes_with_nul(b"hello\0").unwrap().len(), 5);
+    ///
+    /// ```
+    pub const fn len(&self) -> usize {
+        self.inner.len() - 1
+    }
+
+    /// Queries whether the string is empty (it only contains the nul terminator).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RCStr;
+    ///
+    /// assert!(RCStr::from_bytes_with_nul(b"\0").unwrap().is_empty());
+    /// assert!(!RCStr::from_bytes_with_nul(b"hello\0").unwrap().is_empty());
+    ///
+    /// ```
+    pub const fn is_empty(&self) -> bool {
+        self.inner.len() == 1
+    }
+
+    /// Gets the string as a `&'a str` (not including the nul terminator),
+    /// if it's valid utf8.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RCStr;
+    ///
+    /// let string = RCStr::from_bytes_with_nul(b"hello\0").unwrap();
+    /// assert_eq!(string.to_str(), Ok("hello"));
+    ///
+    /// let string = RCStr::from_bytes_with_nul(b"\xFF\0").unwrap();
+    /// assert!(string.to_str().is_err());
+    ///
+    /// ```
+    pub fn to_str(&self) -> Result<&'a str, Utf8Error> {
+        str::from_utf8(self.to_bytes())
+    }
+
+    /// Converts the string to a `Cow<'a, str>` (not including the nul terminator),
+    /// replacing invalid utf8 with `U+FFFD REPLACEMENT CHARACTER`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RCStr;
+    ///
+    /// let string = RCStr::from_bytes_with_nul(b"hello\xFF\0").unwrap();
+    ///
+    /// assert_eq!(string.to_string_lossy(), "hello\u{FFFD}");
+    ///
+    /// ```
+    pub fn to_string_lossy(&self) -> Cow<'a, str> {
+        String::from_utf8_lossy(self.to_bytes())
+    }
+
+    /// Converts this `RCStr<'a>` to a `NulStr<'a>`.
+    ///
+    /// This only needs to check that the string is valid utf8,
+    /// since the position of the nul terminator is already known.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RCStr;
+    ///
+    /// let string = RCStr::from_bytes_with_nul(b"hello\0").unwrap();
+    /// assert_eq!(string.to_nul_str().unwrap(), "hello");
+    ///
+    /// let string = RCStr::from_bytes_with_nul(b"\xFF\0").unwrap();
+    /// assert!(string.to_nul_str().is_err());
+    ///
+    /// ```
+    pub fn to_nul_str(&self) -> Result<NulStr<'a>, Utf8Error> {
+        self.to_str()
+            .map(|_| unsafe { NulStr::from_ptr(self.inner.as_ptr()) })
+    }
+
+    /// Converts this `RCStr<'a>` to a `NulStr<'a>`, without checking that it's valid utf8.
+    ///
+    /// # Safety
+    ///
+    /// The string must be valid utf8.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RCStr;
+    ///
+    /// let string = RCStr::from_bytes_with_nul(b"hello\0").unwrap();
+    ///
+    /// assert_eq!(unsafe { string.to_nul_str_unchecked() }, "hello");
+    ///
+    /// ```
+    pub unsafe fn to_nul_str_unchecked(&self) -> NulStr<'a> {
+        NulStr::from_ptr(self.inner.as_ptr())
+    }
+
+    /// Copies this `RCStr<'a>` into an `RCString`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RCStr, RCString};
+    ///
+    /// let string = RCStr::from_bytes_with_nul(b"hello\0").unwrap();
+    ///
+    /// assert_eq!(string.to_rc_string(), RCString::new("hello").unwrap());
+    ///
+    /// ```
+    pub fn to_rc_string(&self) -> RCString {
+        RCString {
+            inner: self.inner.to_rvec(),
+        }
+    }
+}
+
+unsafe impl<'a> Send for RCStr<'a> {}
+unsafe impl<'a> Sync for RCStr<'a> {}
+
+impl<'a> Default for RCStr<'a> {
+    fn default() -> Self {
+        Self {
+            inner: RSlice::from_slice(&[0]),
+        }
+    }
+}
+
+impl<'a> Deref for RCStr<'a> {
+    type Target = CStr;
+
+    #[inline]
+    fn deref(&self) -> &CStr {
+        self.as_c_str()
+    }
+}
+
+impl AsRef<CStr> for RCStr<'_> {
+    fn as_ref(&self) -> &CStr {
+        self.as_c_str()
+    }
+}
+
+impl_from_rust_repr! {
+    impl['a] From<&'a CStr> for RCStr<'a> {
+        fn(this){
+            RCStr::from_c_str(this)
+        }
+    }
+}
+
+impl_into_rust_repr! {
+    impl['a] Into<&'a CStr> for RCStr<'a> {
+        fn(this){
+            this.as_c_str()
+        }
+    }
+}
+
+impl<'a> From<NulStr<'a>> for RCStr<'a> {
+    /// This requires searching for the nul terminator of the `NulStr`.
+    fn from(this: NulStr<'a>) -> Self {
+        Self {
+            inner: RSlice::from_slice(this.to_str_with_nul().as_bytes()),
+        }
+    }
+}
+
+impl<'a> From<RCStr<'a>> for CString {
+    fn from(this: RCStr<'a>) -> CString {
+        this.as_c_str().into()
+    }
+}
+
+impl Debug for RCStr<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(self.as_c_str(), f)
+    }
+}
+
+impl PartialEq for RCStr<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_bytes() == other.to_bytes()
+    }
+}
+
+impl Eq for RCStr<'_> {}
+
+impl PartialOrd for RCStr<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for RCStr<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.to_bytes().cmp(other.to_bytes())
+    }
+}
+
+impl Hash for RCStr<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_c_str().hash(state)
+    }
+}
+
+impl Serialize for RCStr<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.as_c_str().serialize(serializer)
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// Ffi-safe equivalent of `CString`.
+///
+/// This stores the string with its nul terminator,
+/// so it can be passed to C functions with `as_ptr`,
+/// and converted to a `&CStr`, an `RCStr<'_>`, or a `NulStr<'_>`
+/// without searching for the nul byte.
+///
+/// # Example
+///
+/// ```
+/// use abi_stable::{
+///     sabi_extern_fn,
+///     sabi_types::NulStr,
+///     std_types::RCString,
+/// };
+///
+/// #[sabi_extern_fn]
+/// fn greeting(name: NulStr<'_>) -> RCString {
+///     RCString::new(format!("hello, {}", name)).unwrap()
+/// }
+///
+/// let greeting = greeting(NulStr::from_str("world\0"));
+///
+/// assert_eq!(greeting.to_nul_str().unwrap(), "hello, world");
+///
+/// ```
+#[repr(C)]
+#[derive(Clone, StableAbi)]
+pub struct RCString {
+    // Includes the nul terminator.
+    inner: RVec<u8>,
+}
+
+impl RCString {
+    /// Constructs an `RCString` from a byte container,
+    /// appending the nul terminator.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if the bytes contain a nul byte.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RCString;
+    ///
+    /// assert_eq!(RCString::new("hello").unwrap().to_bytes(), b"hello");
+    ///
+    /// assert_eq!(RCString::new(b"hel\0lo".to_vec()).unwrap_err().nul_position(), 3);
+    ///
+    /// ```
+    pub fn new<T>(bytes: T) -> Result<Self, NulError>
+    where
+        T: Into<Vec<u8>>,
+    {
+        CString::new(bytes).map(Self::from)
+    }
+
+    /// Creates an `RCStr<'_>` borrowing from this `RCString`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RCStr, RCString};
+    ///
+    /// let string = RCString::new("hello").unwrap();
+    ///
+    /// assert_eq!(string.as_rc_str(), RCStr::from_bytes_with_nul(b"hello\0").unwrap());
+    ///
+    /// ```
+    pub fn as_rc_str(&self) -> RCStr<'_> {
+        RCStr {
+            inner: self.inner.as_rslice(),
+        }
+    }
+
+    /// Gets a `&CStr` borrowing from this `RCString`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RCString;
+    ///
+    /// use std::ffi::CStr;
+    ///
+    /// let string = RCString::new("hello").unwrap();
+    ///
+    /// assert_eq!(string.as_c_str(), CStr::from_bytes_with_nul(b"hello\0").unwrap());
+    ///
+    /// ```
+    pub fn as_c_str(&self) -> &CStr {
+        self.as_rc_str().as_c_str()
+    }
+
+    /// Converts this `RCString` to a `NulStr<'_>`.
+    ///
+    /// This only needs to check that the string is valid utf8,
+    /// since the position of the nul terminator is already known.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RCString;
+    ///
+    /// let string = RCString::new("hello").unwrap();
+    /// assert_eq!(string.to_nul_str().unwrap(), "hello");
+    ///
+    /// let string = RCString::new(b"\xFF".to_vec()).unwrap();
+    /// assert!(string.to_nul_str().is_err());
+    ///
+    /// ```
+    pub fn to_nul_str(&self) -> Result<NulStr<'_>, Utf8Error> {
+        self.as_rc_str().to_nul_str()
+    }
+
+    /// Converts this `RCString` into a `CString`.
+    ///
+    /// # Allocation
+    ///
+    /// This only allocates if it's invoked outside of
+    /// the dynamic library/binary that created the `RCString`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RCString;
+    ///
+    /// use std::ffi::CString;
+    ///
+    /// assert_eq!(
+    ///     RCString::new("hello").unwrap().into_c_string(),
+    ///     CString::new("hello").unwrap(),
+    /// );
+    ///
+    /// ```
+    pub fn into_c_string(self) -> CString {
+        let mut bytes = self.inner.into_vec();
+        bytes.pop();
+        // Reuses the capacity that the nul terminator was using.
+        unsafe { CString::from_vec_unchecked(bytes) }
+    }
+
+    /// Converts this `RCString` into its bytes, without the nul terminator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RCString;
+    ///
+    /// assert_eq!(RCString::new("hello").unwrap().into_bytes(), b"hello"[..]);
+    ///
+    /// ```
+    pub fn into_bytes(self) -> RVec<u8> {
+        let mut bytes = self.inner;
+        bytes.pop();
+        bytes
+    }
+
+    /// Converts this `RCString` into its bytes, including the nul terminator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RCString;
+    ///
+    /// assert_eq!(
+    ///     RCString::new("hello").unwrap().into_bytes_with_nul(),
+    ///     b"hello\0"[..],
+    /// );
+    ///
+    /// ```
+    pub fn into_bytes_with_nul(self) -> RVec<u8> {
+        self.inner
+    }
+}
+
+impl Default for RCString {
+    fn default() -> Self {
+        CString::default().into()
+    }
+}
+
+impl Deref for RCString {
+    type Target = CStr;
+
+    #[inline]
+    fn deref(&self) -> &CStr {
+        self.as_c_str()
+    }
+}
+
+impl AsRef<CStr> for RCString {
+    fn as_ref(&self) -> &CStr {
+        self.as_c_str()
+    }
+}
+
+impl Borrow<CStr> for RCString {
+    fn borrow(&self) -> &CStr {
+        self.as_c_str()
+    }
+}
+
+impl_from_rust_repr! {
+    impl From<CString> for RCString {
+        fn(this){
+            RCString {
+                inner: this.into_bytes_with_nul().into(),
+            }
+        }
+    }
+}
+
+impl_into_rust_repr! {
+    impl Into<CString> for RCString {
+        fn(this){
+            this.into_c_string()
+        }
+    }
+}
+
+impl From<&CStr> for RCString {
+    fn from(this: &CStr) -> Self {
+        RCStr::from_c_str(this).to_rc_string()
+    }
+}
+
+impl From<RCStr<'_>> for RCString {
+    fn from(this: RCStr<'_>) -> Self {
+        this.to_rc_string()
+    }
+}
+
+impl From<NulStr<'_>> for RCString {
+    /// This requires searching for the nul terminator of the `NulStr`.
+    fn from(this: NulStr<'_>) -> Self {
+        RCStr::from(this).to_rc_string()
+    }
+}
+
+impl Debug for RCString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(self.as_c_str(), f)
+    }
+}
+
+impl PartialEq for RCString {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_rc_str() == other.as_rc_str()
+    }
+}
+
+impl Eq for RCString {}
+
+impl PartialOrd for RCString {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for RCString {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_rc_str().cmp(&other.as_rc_str())
+    }
+}
+
+impl Hash for RCString {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_c_str().hash(state)
+    }
+}
+
+impl Serialize for RCString {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.as_c_str().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for RCString {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        CString::deserialize(deserializer).map(Self::from)
+    }
+}
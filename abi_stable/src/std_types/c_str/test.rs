@@ -0,0 +1,118 @@
+use super::*;
+
+use std::collections::hash_map::DefaultHasher;
+
+use crate::std_types::RString;
+
+fn hash<T: Hash + ?Sized>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn c_str_conversions() {
+    for &bytes in &[&b"\0"[..], b"hello\0", b"\xFFworld\0"] {
+        let cstr = CStr::from_bytes_with_nul(bytes).unwrap();
+
+        let rcstr = RCStr::from(cstr);
+        assert_eq!(rcstr.as_c_str(), cstr);
+        assert_eq!(&*rcstr, cstr);
+        assert_eq!(rcstr.as_ptr(), cstr.as_ptr());
+        assert_eq!(rcstr.to_bytes(), cstr.to_bytes());
+        assert_eq!(rcstr.to_bytes_with_nul(), bytes);
+        assert_eq!(rcstr.len(), bytes.len() - 1);
+        assert_eq!(rcstr.is_empty(), bytes.len() == 1);
+        assert_eq!(rcstr.to_string_lossy(), cstr.to_string_lossy());
+        assert_eq!(rcstr, RCStr::from_bytes_with_nul(bytes).unwrap());
+        assert_eq!(format!("{:?}", rcstr), format!("{:?}", cstr));
+        assert_eq!(hash(&rcstr), hash(cstr));
+        assert_eq!(CString::from(rcstr), cstr.to_owned());
+    }
+}
+
+#[test]
+fn c_string_conversions() {
+    for &s in &["", "hello", "ñ😀"] {
+        let cstring = CString::new(s).unwrap();
+
+        let rcstring = RCString::from(cstring.clone());
+        assert_eq!(rcstring.as_c_str(), &*cstring);
+        assert_eq!(rcstring.to_str(), Ok(s));
+        assert_eq!(rcstring.as_rc_str().to_str(), Ok(s));
+        assert_eq!(rcstring, RCString::new(s).unwrap());
+        assert_eq!(rcstring, RCString::from(&*cstring));
+        assert_eq!(rcstring, rcstring.as_rc_str().to_rc_string());
+        assert_eq!(hash(&rcstring), hash(&cstring));
+        assert_eq!(rcstring.clone().into_bytes(), s.as_bytes());
+        assert_eq!(
+            rcstring.clone().into_bytes_with_nul(),
+            cstring.as_bytes_with_nul()
+        );
+        assert_eq!(rcstring.into_c_string(), cstring);
+    }
+
+    assert_eq!(RCString::default(), RCString::new("").unwrap());
+    assert_eq!(RCStr::default(), RCString::default().as_rc_str());
+}
+
+#[test]
+fn invalid_strings() {
+    assert!(RCStr::from_bytes_with_nul(b"").is_err());
+    assert!(RCStr::from_bytes_with_nul(b"hello").is_err());
+    assert!(RCStr::from_bytes_with_nul(b"hel\0lo\0").is_err());
+
+    assert_eq!(RCString::new("hel\0lo").unwrap_err().nul_position(), 3);
+}
+
+#[test]
+fn nul_str_conversions() {
+    let rcstring = RCString::new("hello").unwrap();
+
+    let nul_str = rcstring.to_nul_str().unwrap();
+    assert_eq!(nul_str.as_ptr(), rcstring.as_ptr() as *const u8);
+    assert_eq!(nul_str, "hello");
+    assert_eq!(
+        unsafe { rcstring.as_rc_str().to_nul_str_unchecked() },
+        "hello"
+    );
+
+    assert_eq!(RCStr::from(nul_str), rcstring.as_rc_str());
+    assert_eq!(RCString::from(NulStr::from_str("hello\0")), rcstring);
+
+    let invalid = RCString::new(b"\xFF".to_vec()).unwrap();
+    assert!(invalid.to_nul_str().is_err());
+    assert!(invalid.as_rc_str().to_nul_str().is_err());
+}
+
+#[test]
+fn ordering() {
+    let a = RCString::new("aaa").unwrap();
+    let b = RCString::new("aab").unwrap();
+    let c = RCString::new("aa").unwrap();
+
+    assert!(a < b);
+    assert!(c < a);
+    assert_eq!(a.cmp(&a.clone()), Ordering::Equal);
+    assert_eq!(a.as_rc_str().cmp(&b.as_rc_str()), Ordering::Less);
+}
+
+#[test]
+fn test_serde() {
+    let rcstring = RCString::new("hello").unwrap();
+    let cstring = CString::new("hello").unwrap();
+
+    let json = serde_json::to_string(&cstring).unwrap();
+
+    assert_eq!(serde_json::to_string(&rcstring).unwrap(), json);
+    assert_eq!(serde_json::to_string(&rcstring.as_rc_str()).unwrap(), json);
+
+    let deserialized = serde_json::from_str::<RCString>(&json).unwrap();
+
+    assert_eq!(deserialized, rcstring);
+
+    let from_str = serde_json::from_str::<RCString>(r##""hello""##).unwrap();
+
+    assert_eq!(from_str, rcstring);
+    assert_eq!(from_str.into_bytes(), RString::from("hello").into_bytes());
+}
@@ -0,0 +1,275 @@
+//! Contains ffi-safe equivalents of `Pin<Box<dyn Future>>` and `Pin<Box<dyn Stream>>`,
+//! and of the types used to poll them.
+
+use std::{
+    fmt::{self, Debug},
+    future::Future,
+    marker::PhantomData,
+    mem::{self, ManuallyDrop},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use crate::{
+    marker_type::{ErasedObject, UnsyncSend, UnsyncUnsend},
+    pointer_trait::AsMutPtr,
+    prefix_type::{PrefixTypeTrait, WithMetadata},
+    sabi_types::RMut,
+    std_types::{RBox, RStr},
+};
+
+pub(crate) mod stream;
+mod waker;
+
+#[cfg(all(test, not(feature = "only_new_tests")))]
+mod test;
+
+pub use self::{
+    stream::{LocalRBoxStream, RBoxStream, RBoxStream_},
+    waker::{RContext, RWaker},
+};
+
+/// Ffi-safe equivalent of `std::task::Poll`.
+///
+/// # Example
+///
+/// ```rust
+/// use abi_stable::std_types::RPoll;
+///
+/// use std::task::Poll;
+///
+/// assert_eq!(RPoll::from(Poll::Ready(10)), RPoll::Ready(10));
+/// assert_eq!(RPoll::<u32>::Pending.into_poll(), Poll::Pending);
+/// assert!(RPoll::Ready(()).is_ready());
+/// ```
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, StableAbi)]
+pub enum RPoll<T> {
+    /// The value is ready.
+    Ready(T),
+    /// The value is not ready yet.
+    Pending,
+}
+
+impl<T> RPoll<T> {
+    /// Whether this is `RPoll::Ready`.
+    pub fn is_ready(&self) -> bool {
+        matches!(self, RPoll::Ready { .. })
+    }
+
+    /// Whether this is `RPoll::Pending`.
+    pub fn is_pending(&self) -> bool {
+        !self.is_ready()
+    }
+
+    /// Maps the value of an `RPoll::Ready`.
+    pub fn map<U, F>(self, f: F) -> RPoll<U>
+    where
+        F: FnOnce(T) -> U,
+    {
+        match self {
+            RPoll::Ready(x) => RPoll::Ready(f(x)),
+            RPoll::Pending => RPoll::Pending,
+        }
+    }
+
+    /// Converts this into a `std::task::Poll`.
+    pub fn into_poll(self) -> Poll<T> {
+        match self {
+            RPoll::Ready(x) => Poll::Ready(x),
+            RPoll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl<T> From<Poll<T>> for RPoll<T> {
+    fn from(poll: Poll<T>) -> Self {
+        match poll {
+            Poll::Ready(x) => RPoll::Ready(x),
+            Poll::Pending => RPoll::Pending,
+        }
+    }
+}
+
+impl<T> From<RPoll<T>> for Poll<T> {
+    fn from(poll: RPoll<T>) -> Self {
+        poll.into_poll()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Ffi-safe equivalent of `Pin<Box<dyn Future<Output = T> + 'a>>`,
+/// whose `Send`ness is determined by the `M` type parameter.
+///
+/// The future is polled through a vtable,
+/// so it can be awaited on the other side of an ffi boundary
+/// regardless of which executor it's awaited in,
+/// since wakers are passed as [`RWaker`]s.
+///
+/// # Example
+///
+/// An ffi-safe function returning a future.
+///
+/// ```rust
+/// use abi_stable::{
+///     sabi_extern_fn,
+///     std_types::{RBoxFuture, RString},
+/// };
+///
+/// #[sabi_extern_fn]
+/// pub fn greet(name: RString) -> RBoxFuture<'static, RString> {
+///     RBoxFuture::new(async move { format!("hello, {}", name).into() })
+/// }
+///
+/// # fn block_on<F: std::future::Future>(fut: F) -> F::Output {
+/// #     use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+/// #     fn clone(_: *const ()) -> RawWaker { RawWaker::new(std::ptr::null(), &VTABLE) }
+/// #     fn noop(_: *const ()) {}
+/// #     static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+/// #     let waker = unsafe { Waker::from_raw(RawWaker::new(std::ptr::null(), &VTABLE)) };
+/// #     let mut fut = Box::pin(fut);
+/// #     loop {
+/// #         if let Poll::Ready(x) = fut.as_mut().poll(&mut Context::from_waker(&waker)) {
+/// #             return x;
+/// #         }
+/// #     }
+/// # }
+/// #
+/// assert_eq!(block_on(greet("world".into())), "hello, world");
+/// ```
+///
+/// [`RWaker`]: ./struct.RWaker.html
+#[repr(C)]
+#[derive(StableAbi)]
+pub struct RBoxFuture_<'a, T, M> {
+    value: ManuallyDrop<RBox<ErasedObject>>,
+    vtable: FutureVTable_Ref<T>,
+    _marker: PhantomData<(M, RStr<'a>)>,
+}
+
+/// Ffi-safe equivalent of `Pin<Box<dyn Future<Output = T> + Send + 'a>>`.
+pub type RBoxFuture<'a, T> = RBoxFuture_<'a, T, UnsyncSend>;
+
+/// Ffi-safe equivalent of `Pin<Box<dyn Future<Output = T> + 'a>>`.
+pub type LocalRBoxFuture<'a, T> = RBoxFuture_<'a, T, UnsyncUnsend>;
+
+impl<'a, T> RBoxFuture_<'a, T, UnsyncSend> {
+    /// Constructs a `Send` `RBoxFuture` from a future.
+    pub fn new<F>(future: F) -> Self
+    where
+        F: Future<Output = T> + Send + 'a,
+    {
+        unsafe { Self::new_inner(future) }
+    }
+
+    /// Converts this into a `LocalRBoxFuture`.
+    pub fn into_local(self) -> LocalRBoxFuture<'a, T> {
+        let this = ManuallyDrop::new(self);
+        unsafe {
+            RBoxFuture_ {
+                value: mem::transmute_copy(&this.value),
+                vtable: this.vtable,
+                _marker: PhantomData,
+            }
+        }
+    }
+}
+
+impl<'a, T> RBoxFuture_<'a, T, UnsyncUnsend> {
+    /// Constructs a `!Send` `LocalRBoxFuture` from a future.
+    pub fn new<F>(future: F) -> Self
+    where
+        F: Future<Output = T> + 'a,
+    {
+        unsafe { Self::new_inner(future) }
+    }
+}
+
+impl<'a, T, M> RBoxFuture_<'a, T, M> {
+    unsafe fn new_inner<F>(future: F) -> Self
+    where
+        F: Future<Output = T>,
+    {
+        let value = mem::transmute::<RBox<F>, RBox<ErasedObject>>(RBox::new(future));
+        Self {
+            value: ManuallyDrop::new(value),
+            vtable: MakeFutureVTable::<F, T>::LIB_VTABLE,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> From<RBoxFuture<'a, T>> for LocalRBoxFuture<'a, T> {
+    fn from(future: RBoxFuture<'a, T>) -> Self {
+        future.into_local()
+    }
+}
+
+impl<'a, T, M> Future for RBoxFuture_<'a, T, M> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let this = &mut *self;
+        let poll = this.vtable.poll();
+        let value = this.value.as_rmut();
+        RContext::from_context(cx, |cx| unsafe { poll(value, cx) }).into_poll()
+    }
+}
+
+// The future is boxed,so moving the `RBoxFuture_` doesn't move it.
+impl<'a, T, M> Unpin for RBoxFuture_<'a, T, M> {}
+
+impl<'a, T, M> Drop for RBoxFuture_<'a, T, M> {
+    fn drop(&mut self) {
+        // Dropping it here instead of relying on drop glue,
+        // so that the borrowck requires `'a` to be alive while the future is dropped.
+        unsafe { ManuallyDrop::drop(&mut self.value) }
+    }
+}
+
+impl<'a, T, M> Debug for RBoxFuture_<'a, T, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RBoxFuture_ { .. }")
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[repr(C)]
+#[derive(StableAbi)]
+#[sabi(kind(Prefix))]
+#[sabi(missing_field(panic))]
+struct FutureVTable<T> {
+    #[sabi(last_prefix_field)]
+    poll: unsafe extern "C" fn(RMut<'_, ErasedObject>, RContext<'_>) -> RPoll<T>,
+}
+
+struct MakeFutureVTable<F, T>(F, T);
+
+impl<F, T> MakeFutureVTable<F, T>
+where
+    F: Future<Output = T>,
+{
+    staticref! {
+        const WM_VTABLE: WithMetadata<FutureVTable<T>> = WithMetadata::new(
+            PrefixTypeTrait::METADATA,
+            FutureVTable {
+                poll: poll_future::<F, T>,
+            },
+        )
+    }
+
+    const LIB_VTABLE: FutureVTable_Ref<T> = FutureVTable_Ref(Self::WM_VTABLE.as_prefix());
+}
+
+unsafe extern "C" fn poll_future<F, T>(this: RMut<'_, ErasedObject>, cx: RContext<'_>) -> RPoll<T>
+where
+    F: Future<Output = T>,
+{
+    extern_fn_panic_handling! {
+        // The future is never moved out of its `RBox`.
+        let this = Pin::new_unchecked(this.transmute_into_mut::<F>());
+        cx.with_context(|cx| this.poll(cx)).into()
+    }
+}
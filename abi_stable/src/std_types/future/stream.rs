@@ -0,0 +1,300 @@
+//! Contains the ffi-safe equivalent of `Pin<Box<dyn Stream>>`.
+
+use std::{
+    fmt::{self, Debug},
+    marker::PhantomData,
+    mem::{self, ManuallyDrop},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use super::{RContext, RPoll};
+
+use crate::{
+    marker_type::{ErasedObject, UnsyncSend, UnsyncUnsend},
+    pointer_trait::{AsMutPtr, AsPtr},
+    prefix_type::{PrefixTypeTrait, WithMetadata},
+    sabi_types::{RMut, RRef},
+    std_types::{RBox, ROption, RStr, Tuple2},
+    traits::{IntoReprC, IntoReprRust},
+};
+
+#[cfg(feature = "futures")]
+pub use futures_core::Stream;
+
+#[cfg(not(feature = "futures"))]
+pub use self::no_futures::Stream;
+
+#[cfg(not(feature = "futures"))]
+mod no_futures {
+    use super::*;
+
+    /// A copy of the `futures_core::Stream` trait,
+    /// used when the "futures" feature is disabled.
+    ///
+    /// The only implementors of this trait are
+    /// `RBoxStream_` and `DynTrait`s that wrap a stream.
+    pub trait Stream {
+        type Item;
+
+        fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>>;
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (0, None)
+        }
+    }
+}
+
+/// Ffi-safe equivalent of `Pin<Box<dyn Stream<Item = T> + 'a>>`,
+/// whose `Send`ness is determined by the `M` type parameter.
+///
+/// Streams are polled through a vtable,with wakers passed as [`RWaker`]s,
+/// so that they can be consumed on the other side of an ffi boundary.
+///
+/// With the "futures" feature,
+/// this implements the `futures_core::Stream` trait,
+/// and can be constructed from any `Stream` with `RBoxStream::new`.
+/// Without it,the stream can only be polled with `poll_next_unpin`.
+///
+/// # Example
+///
+#[cfg_attr(feature = "futures", doc = "```rust")]
+#[cfg_attr(not(feature = "futures"), doc = "```ignore")]
+/**
+use abi_stable::{
+    sabi_extern_fn,
+    std_types::{RBoxStream, RPoll},
+};
+
+use futures_core::Stream;
+
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Counts down from `n`.
+struct Countdown(u32);
+
+impl Stream for Countdown {
+    type Item = u32;
+
+    fn poll_next(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<u32>> {
+        if self.0 == 0 {
+            Poll::Ready(None)
+        } else {
+            self.0 -= 1;
+            Poll::Ready(Some(self.0))
+        }
+    }
+}
+
+#[sabi_extern_fn]
+pub fn countdown(n: u32) -> RBoxStream<'static, u32> {
+    RBoxStream::new(Countdown(n))
+}
+
+# use std::task::{RawWaker, RawWakerVTable, Waker};
+# fn clone(_: *const ()) -> RawWaker { RawWaker::new(std::ptr::null(), &VTABLE) }
+# fn noop(_: *const ()) {}
+# static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+# let waker = unsafe { Waker::from_raw(RawWaker::new(std::ptr::null(), &VTABLE)) };
+# let mut cx = Context::from_waker(&waker);
+#
+let mut stream = countdown(3);
+assert_eq!(stream.size_hint(), (0, None));
+
+let mut list = Vec::new();
+while let Poll::Ready(Some(x)) = stream.poll_next_unpin(&mut cx) {
+    list.push(x);
+}
+assert_eq!(list, [2, 1, 0]);
+```
+*/
+///
+/// [`RWaker`]: ./struct.RWaker.html
+#[repr(C)]
+#[derive(StableAbi)]
+pub struct RBoxStream_<'a, T, M> {
+    value: ManuallyDrop<RBox<ErasedObject>>,
+    vtable: StreamVTable_Ref<T>,
+    _marker: PhantomData<(M, RStr<'a>)>,
+}
+
+/// Ffi-safe equivalent of `Pin<Box<dyn Stream<Item = T> + Send + 'a>>`.
+pub type RBoxStream<'a, T> = RBoxStream_<'a, T, UnsyncSend>;
+
+/// Ffi-safe equivalent of `Pin<Box<dyn Stream<Item = T> + 'a>>`.
+pub type LocalRBoxStream<'a, T> = RBoxStream_<'a, T, UnsyncUnsend>;
+
+impl<'a, T> RBoxStream_<'a, T, UnsyncSend> {
+    /// Constructs a `Send` `RBoxStream` from a stream.
+    pub fn new<S>(stream: S) -> Self
+    where
+        S: Stream<Item = T> + Send + 'a,
+    {
+        Self::new_inner(stream)
+    }
+
+    /// Converts this into a `LocalRBoxStream`.
+    pub fn into_local(self) -> LocalRBoxStream<'a, T> {
+        let this = ManuallyDrop::new(self);
+        unsafe {
+            RBoxStream_ {
+                value: mem::transmute_copy(&this.value),
+                vtable: this.vtable,
+                _marker: PhantomData,
+            }
+        }
+    }
+}
+
+impl<'a, T> RBoxStream_<'a, T, UnsyncUnsend> {
+    /// Constructs a `!Send` `LocalRBoxStream` from a stream.
+    pub fn new<S>(stream: S) -> Self
+    where
+        S: Stream<Item = T> + 'a,
+    {
+        Self::new_inner(stream)
+    }
+}
+
+impl<'a, T, M> RBoxStream_<'a, T, M> {
+    fn new_inner<S>(stream: S) -> Self
+    where
+        S: Stream<Item = T> + 'a,
+    {
+        let value = unsafe { mem::transmute::<RBox<S>, RBox<ErasedObject>>(RBox::new(stream)) };
+        Self {
+            value: ManuallyDrop::new(value),
+            vtable: MakeStreamVTable::<S, T>::LIB_VTABLE,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Polls for the next element of the stream.
+    ///
+    /// This is the same as `Stream::poll_next`,
+    /// usable without pinning the `RBoxStream_` or enabling the "futures" feature.
+    pub fn poll_next_unpin(&mut self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let poll_next = self.vtable.poll_next();
+        let value = self.value.as_rmut();
+        RContext::from_context(cx, |cx| unsafe { poll_next(value, cx) })
+            .map(IntoReprRust::into_rust)
+            .into_poll()
+    }
+
+    /// Returns the bounds on the remaining length of the stream,
+    /// as returned by `Stream::size_hint`.
+    pub fn size_hint(&self) -> (usize, Option<usize>) {
+        unsafe {
+            let tuple = (self.vtable.size_hint())(self.value.as_rref()).into_rust();
+            (tuple.0, tuple.1.into_rust())
+        }
+    }
+}
+
+impl<'a, T> From<RBoxStream<'a, T>> for LocalRBoxStream<'a, T> {
+    fn from(stream: RBoxStream<'a, T>) -> Self {
+        stream.into_local()
+    }
+}
+
+impl<'a, T, M> Stream for RBoxStream_<'a, T, M> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        self.poll_next_unpin(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        RBoxStream_::size_hint(self)
+    }
+}
+
+// The stream is boxed,so moving the `RBoxStream_` doesn't move it.
+impl<'a, T, M> Unpin for RBoxStream_<'a, T, M> {}
+
+impl<'a, T, M> Drop for RBoxStream_<'a, T, M> {
+    fn drop(&mut self) {
+        // Dropping it here instead of relying on drop glue,
+        // so that the borrowck requires `'a` to be alive while the stream is dropped.
+        unsafe { ManuallyDrop::drop(&mut self.value) }
+    }
+}
+
+impl<'a, T, M> Debug for RBoxStream_<'a, T, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RBoxStream_ { .. }")
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[repr(C)]
+#[derive(StableAbi)]
+#[sabi(kind(Prefix))]
+#[sabi(missing_field(panic))]
+struct StreamVTable<T> {
+    poll_next: unsafe extern "C" fn(RMut<'_, ErasedObject>, RContext<'_>) -> RPoll<ROption<T>>,
+    #[sabi(last_prefix_field)]
+    size_hint: unsafe extern "C" fn(RRef<'_, ErasedObject>) -> Tuple2<usize, ROption<usize>>,
+}
+
+struct MakeStreamVTable<S, T>(S, T);
+
+impl<S, T> MakeStreamVTable<S, T>
+where
+    S: Stream<Item = T>,
+{
+    staticref! {
+        const WM_VTABLE: WithMetadata<StreamVTable<T>> = WithMetadata::new(
+            PrefixTypeTrait::METADATA,
+            StreamVTable {
+                poll_next: poll_next::<S>,
+                size_hint: size_hint::<S>,
+            },
+        )
+    }
+
+    const LIB_VTABLE: StreamVTable_Ref<T> = StreamVTable_Ref(Self::WM_VTABLE.as_prefix());
+}
+
+/// Polls the `S` stream that `this` points to.
+///
+/// # Safety
+///
+/// `this` must point to an `S` that is never moved afterwards,
+/// unless `S: Unpin`.
+pub(crate) unsafe extern "C" fn poll_next<S>(
+    this: RMut<'_, ErasedObject>,
+    cx: RContext<'_>,
+) -> RPoll<ROption<S::Item>>
+where
+    S: Stream,
+{
+    extern_fn_panic_handling! {
+        let this = Pin::new_unchecked(this.transmute_into_mut::<S>());
+        cx.with_context(|cx| this.poll_next(cx))
+            .map(IntoReprC::into_c)
+            .into()
+    }
+}
+
+/// Gets the size hint of the `S` stream that `this` points to.
+///
+/// # Safety
+///
+/// `this` must point to an `S`.
+pub(crate) unsafe extern "C" fn size_hint<S>(
+    this: RRef<'_, ErasedObject>,
+) -> Tuple2<usize, ROption<usize>>
+where
+    S: Stream,
+{
+    extern_fn_panic_handling! {
+        let this = this.transmute_into_ref::<S>();
+        let (low, high) = this.size_hint();
+        Tuple2(low, high.into_c())
+    }
+}
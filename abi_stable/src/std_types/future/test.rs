@@ -0,0 +1,356 @@
+use super::{stream::Stream, *};
+
+use std::{
+    cell::Cell,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    task::{RawWaker, RawWakerVTable, Waker},
+    thread,
+};
+
+/// A `Waker` that counts how many times it was woken,
+/// and how many clones of it are alive.
+#[derive(Default)]
+struct CountingWaker {
+    wakes: AtomicUsize,
+    clones: AtomicUsize,
+}
+
+impl CountingWaker {
+    fn waker(self: &Arc<Self>) -> Waker {
+        self.clones.fetch_add(1, Ordering::SeqCst);
+        let data = Arc::into_raw(self.clone()) as *const ();
+        unsafe { Waker::from_raw(RawWaker::new(data, &COUNTING_VTABLE)) }
+    }
+
+    fn wakes(&self) -> usize {
+        self.wakes.load(Ordering::SeqCst)
+    }
+
+    fn clones(&self) -> usize {
+        self.clones.load(Ordering::SeqCst)
+    }
+}
+
+static COUNTING_VTABLE: RawWakerVTable = RawWakerVTable::new(
+    counting_clone,
+    counting_wake,
+    counting_wake_by_ref,
+    counting_drop,
+);
+
+unsafe fn counting_clone(data: *const ()) -> RawWaker {
+    let this = ManuallyDrop::new(Arc::from_raw(data as *const CountingWaker));
+    this.clones.fetch_add(1, Ordering::SeqCst);
+    let data = Arc::into_raw(Arc::clone(&this)) as *const ();
+    RawWaker::new(data, &COUNTING_VTABLE)
+}
+
+unsafe fn counting_wake(data: *const ()) {
+    counting_wake_by_ref(data);
+    counting_drop(data);
+}
+
+unsafe fn counting_wake_by_ref(data: *const ()) {
+    let this = &*(data as *const CountingWaker);
+    this.wakes.fetch_add(1, Ordering::SeqCst);
+}
+
+unsafe fn counting_drop(data: *const ()) {
+    let this = Arc::from_raw(data as *const CountingWaker);
+    this.clones.fetch_sub(1, Ordering::SeqCst);
+}
+
+fn poll_once<F>(fut: &mut F, waker: &Waker) -> Poll<F::Output>
+where
+    F: Future + Unpin,
+{
+    Pin::new(fut).poll(&mut Context::from_waker(waker))
+}
+
+/// A future that returns `Pending` until `ready` is set,
+/// storing the waker of the last poll.
+#[derive(Default)]
+struct Flag {
+    ready: bool,
+    waker: Option<Waker>,
+}
+
+struct WaitFlag(Arc<Mutex<Flag>>);
+
+impl Future for WaitFlag {
+    type Output = u32;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<u32> {
+        let mut flag = self.0.lock().unwrap();
+        if flag.ready {
+            Poll::Ready(100)
+        } else {
+            flag.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+#[test]
+fn rpoll_conversions() {
+    assert_eq!(RPoll::from(Poll::Ready(3)), RPoll::Ready(3));
+    assert_eq!(RPoll::<u8>::from(Poll::Pending), RPoll::Pending);
+    assert_eq!(Poll::from(RPoll::Ready("hi")), Poll::Ready("hi"));
+    assert_eq!(RPoll::<u8>::Pending.into_poll(), Poll::Pending);
+
+    assert!(RPoll::Ready(0).is_ready());
+    assert!(!RPoll::Ready(0).is_pending());
+    assert!(RPoll::<()>::Pending.is_pending());
+    assert_eq!(RPoll::Ready(3).map(|x| x * 2), RPoll::Ready(6));
+    assert_eq!(RPoll::<u8>::Pending.map(|x| x * 2), RPoll::Pending);
+}
+
+#[test]
+fn rwaker_wakes_the_waker() {
+    let counter = Arc::new(CountingWaker::default());
+    {
+        let rwaker = RWaker::new(counter.waker());
+        rwaker.wake_by_ref();
+        assert_eq!(counter.wakes(), 1);
+
+        let cloned = rwaker.clone();
+        assert_eq!(counter.clones(), 2);
+        cloned.wake();
+        assert_eq!(counter.wakes(), 2);
+        assert_eq!(counter.clones(), 1);
+
+        let waker = rwaker.clone().into_waker();
+        waker.wake_by_ref();
+        let waker2 = waker.clone();
+        assert_eq!(counter.clones(), 3);
+        waker2.wake();
+        drop(waker);
+        assert_eq!(counter.wakes(), 4);
+        assert_eq!(counter.clones(), 1);
+    }
+    assert_eq!(counter.clones(), 0);
+    assert_eq!(Arc::strong_count(&counter), 1);
+}
+
+#[test]
+fn rcontext_conversions() {
+    let counter = Arc::new(CountingWaker::default());
+    let waker = counter.waker();
+    let mut cx = Context::from_waker(&waker);
+
+    // Borrowing the `Waker` as an `RWaker` doesn't clone it.
+    RContext::from_context(&mut cx, |rcx| {
+        assert_eq!(counter.clones(), 1);
+        rcx.waker().wake_by_ref();
+
+        let owned = rcx.waker().clone();
+        assert_eq!(counter.clones(), 2);
+        drop(owned);
+
+        rcx.with_context(|cx| {
+            cx.waker().wake_by_ref();
+            let cloned = cx.waker().clone();
+            assert_eq!(counter.clones(), 2);
+            cloned.wake();
+        });
+    });
+    assert_eq!(counter.wakes(), 3);
+    assert_eq!(counter.clones(), 1);
+
+    drop(waker);
+    assert_eq!(Arc::strong_count(&counter), 1);
+}
+
+#[test]
+fn polling_rboxfuture() {
+    let counter = Arc::new(CountingWaker::default());
+    let waker = counter.waker();
+
+    let mut fut = RBoxFuture::new(async { 10 });
+    assert_eq!(poll_once(&mut fut, &waker), Poll::Ready(10));
+
+    let flag = Arc::new(Mutex::new(Flag::default()));
+    let mut fut = RBoxFuture::new(WaitFlag(flag.clone()));
+    assert_eq!(poll_once(&mut fut, &waker), Poll::Pending);
+    assert_eq!(poll_once(&mut fut, &waker), Poll::Pending);
+    assert_eq!(counter.wakes(), 0);
+
+    // Waking the task from another thread,through the waker stored by the future.
+    let flag2 = flag.clone();
+    thread::spawn(move || {
+        let mut flag = flag2.lock().unwrap();
+        flag.ready = true;
+        flag.waker.take().unwrap().wake();
+    })
+    .join()
+    .unwrap();
+    assert_eq!(counter.wakes(), 1);
+
+    assert_eq!(poll_once(&mut fut, &waker), Poll::Ready(100));
+
+    drop(fut);
+    drop(flag);
+    drop(waker);
+    assert_eq!(counter.clones(), 0);
+}
+
+#[test]
+fn local_rboxfuture() {
+    let counter = Arc::new(CountingWaker::default());
+    let waker = counter.waker();
+
+    let rc = Rc::new(Cell::new(3));
+    let mut fut = LocalRBoxFuture::new({
+        let rc = rc.clone();
+        async move {
+            rc.set(rc.get() + 1);
+            rc
+        }
+    });
+    let out = match poll_once(&mut fut, &waker) {
+        Poll::Ready(x) => x,
+        Poll::Pending => panic!("the future should be ready"),
+    };
+    assert_eq!(out.get(), 4);
+
+    let mut fut: LocalRBoxFuture<'_, u32> = RBoxFuture::new(async { 5 }).into();
+    assert_eq!(poll_once(&mut fut, &waker), Poll::Ready(5));
+}
+
+#[test]
+fn borrowing_rboxfuture() {
+    let counter = Arc::new(CountingWaker::default());
+    let waker = counter.waker();
+
+    let text = String::from("hello");
+    let mut fut = RBoxFuture::new(async { text.len() });
+    assert_eq!(poll_once(&mut fut, &waker), Poll::Ready(5));
+}
+
+#[test]
+fn dropping_rboxfuture() {
+    let arc = Arc::new(());
+    let fut = RBoxFuture::new({
+        let arc = arc.clone();
+        async move { drop(arc) }
+    });
+    assert_eq!(Arc::strong_count(&arc), 2);
+    drop(fut.into_local());
+    assert_eq!(Arc::strong_count(&arc), 1);
+}
+
+/// A stream that counts down to 0,
+/// which is pending every other time it's polled.
+struct Countdown<T> {
+    count: u32,
+    pending: bool,
+    value: T,
+}
+
+impl<T: Clone> Stream for Countdown<T> {
+    type Item = (u32, T);
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<(u32, T)>> {
+        // Doesn't move any field.
+        let this = unsafe { self.get_unchecked_mut() };
+        this.pending = !this.pending;
+        if this.pending {
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        } else if this.count == 0 {
+            Poll::Ready(None)
+        } else {
+            this.count -= 1;
+            Poll::Ready(Some((this.count, this.value.clone())))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let count = self.count as usize;
+        (count, Some(count))
+    }
+}
+
+fn countdown<T>(count: u32, value: T) -> Countdown<T> {
+    Countdown {
+        count,
+        pending: false,
+        value,
+    }
+}
+
+#[test]
+fn polling_rboxstream() {
+    let counter = Arc::new(CountingWaker::default());
+    let waker = counter.waker();
+    let mut cx = Context::from_waker(&waker);
+
+    let mut stream = RBoxStream::new(countdown(2, "hi"));
+    assert_eq!(stream.size_hint(), (2, Some(2)));
+
+    assert_eq!(stream.poll_next_unpin(&mut cx), Poll::Pending);
+    assert_eq!(counter.wakes(), 1);
+    assert_eq!(
+        stream.poll_next_unpin(&mut cx),
+        Poll::Ready(Some((1, "hi")))
+    );
+    assert_eq!(stream.size_hint(), (1, Some(1)));
+
+    assert_eq!(Pin::new(&mut stream).poll_next(&mut cx), Poll::Pending);
+    assert_eq!(
+        Pin::new(&mut stream).poll_next(&mut cx),
+        Poll::Ready(Some((0, "hi")))
+    );
+    assert_eq!(Stream::size_hint(&stream), (0, Some(0)));
+
+    assert_eq!(stream.poll_next_unpin(&mut cx), Poll::Pending);
+    assert_eq!(stream.poll_next_unpin(&mut cx), Poll::Ready(None));
+    assert_eq!(counter.wakes(), 3);
+
+    drop(stream);
+    drop(waker);
+    assert_eq!(counter.clones(), 0);
+}
+
+#[test]
+fn local_rboxstream() {
+    let counter = Arc::new(CountingWaker::default());
+    let waker = counter.waker();
+    let mut cx = Context::from_waker(&waker);
+
+    let rc = Rc::new(3);
+    let mut stream = LocalRBoxStream::new(countdown(1, rc.clone()));
+    assert_eq!(stream.poll_next_unpin(&mut cx), Poll::Pending);
+    match stream.poll_next_unpin(&mut cx) {
+        Poll::Ready(Some((0, x))) => assert!(Rc::ptr_eq(&x, &rc)),
+        _ => panic!("the stream should have yielded an element"),
+    }
+
+    let mut stream: LocalRBoxStream<'_, (u32, u8)> = RBoxStream::new(countdown(1, 5)).into();
+    assert_eq!(stream.poll_next_unpin(&mut cx), Poll::Pending);
+    assert_eq!(stream.poll_next_unpin(&mut cx), Poll::Ready(Some((0, 5))));
+}
+
+#[test]
+fn dropping_rboxstream() {
+    let arc = Arc::new(());
+    let stream = RBoxStream::new(countdown(1, arc.clone()));
+    assert_eq!(Arc::strong_count(&arc), 2);
+    drop(stream.into_local());
+    assert_eq!(Arc::strong_count(&arc), 1);
+}
+
+#[test]
+fn send_sync() {
+    fn assert_send<T: Send>() {}
+    fn assert_sync<T: Sync>() {}
+
+    assert_send::<RWaker>();
+    assert_sync::<RWaker>();
+    assert_send::<RBoxFuture<'static, ()>>();
+    assert_send::<RBoxStream<'static, ()>>();
+}
@@ -0,0 +1,321 @@
+//! Contains the ffi-safe equivalents of `std::task::{Waker, Context}`.
+
+use std::{
+    fmt::{self, Debug},
+    marker::PhantomData,
+    mem::{self, ManuallyDrop},
+    task::{Context, RawWaker, RawWakerVTable, Waker},
+};
+
+use crate::prefix_type::{PrefixTypeTrait, WithMetadata};
+
+/// Ffi-safe equivalent of `std::task::Waker`.
+///
+/// This can be constructed from a `Waker` with `RWaker::new`,
+/// and converted back into a `Waker` with `RWaker::into_waker`,
+/// waking the `RWaker` wakes the task that the original `Waker` belongs to.
+///
+/// # Example
+///
+/// ```rust
+/// use abi_stable::std_types::RWaker;
+///
+/// use std::{
+///     sync::atomic::{AtomicUsize, Ordering},
+///     task::{RawWaker, RawWakerVTable, Waker},
+/// };
+///
+/// static WAKES: AtomicUsize = AtomicUsize::new(0);
+///
+/// fn clone(_: *const ()) -> RawWaker {
+///     RawWaker::new(std::ptr::null(), &VTABLE)
+/// }
+/// fn wake(_: *const ()) {
+///     WAKES.fetch_add(1, Ordering::SeqCst);
+/// }
+/// fn drop(_: *const ()) {}
+///
+/// static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, wake, wake, drop);
+///
+/// let waker = unsafe { Waker::from_raw(RawWaker::new(std::ptr::null(), &VTABLE)) };
+///
+/// let rwaker = RWaker::new(waker);
+/// rwaker.wake_by_ref();
+/// rwaker.clone().wake();
+///
+/// // Converting it back into a `Waker`,which wakes through the `RWaker`.
+/// rwaker.into_waker().wake();
+///
+/// assert_eq!(WAKES.load(Ordering::SeqCst), 3);
+/// ```
+#[repr(C)]
+#[derive(StableAbi)]
+pub struct RWaker {
+    data: *const (),
+    vtable: RWakerVTable_Ref,
+}
+
+// Wakers are required to be thread-safe by `std::task::RawWakerVTable`.
+unsafe impl Send for RWaker {}
+unsafe impl Sync for RWaker {}
+
+impl RWaker {
+    /// Constructs an `RWaker` that wakes up the task that `waker` belongs to.
+    pub fn new(waker: Waker) -> Self {
+        Self {
+            data: Box::into_raw(Box::new(waker)) as *const (),
+            vtable: OWNED_VTABLE,
+        }
+    }
+
+    /// Wakes up the task associated with this `RWaker`.
+    pub fn wake(self) {
+        let this = ManuallyDrop::new(self);
+        unsafe { (this.vtable.wake())(this.data) }
+    }
+
+    /// Wakes up the task associated with this `RWaker`,without consuming it.
+    pub fn wake_by_ref(&self) {
+        unsafe { (self.vtable.wake_by_ref())(self.data) }
+    }
+
+    /// Converts this `RWaker` into a `std::task::Waker`,
+    /// which wakes the task by waking this `RWaker`.
+    pub fn into_waker(self) -> Waker {
+        let raw = RawWaker::new(Box::into_raw(Box::new(self)) as *const (), &STD_VTABLE);
+        unsafe { Waker::from_raw(raw) }
+    }
+
+    /// Borrows `waker` as an `RWaker`,without cloning it.
+    ///
+    /// Cloning the returned `RWaker` clones the `Waker`.
+    fn from_ref(waker: &Waker) -> RWakerRef<'_> {
+        RWakerRef {
+            waker: ManuallyDrop::new(Self {
+                data: waker as *const Waker as *const (),
+                vtable: BORROWED_VTABLE,
+            }),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Borrows this `RWaker` as a `std::task::Waker`,
+    /// which must never be dropped.
+    fn as_std_waker(&self) -> ManuallyDrop<Waker> {
+        let raw = RawWaker::new(self as *const Self as *const (), &STD_VTABLE);
+        ManuallyDrop::new(unsafe { Waker::from_raw(raw) })
+    }
+}
+
+impl Clone for RWaker {
+    fn clone(&self) -> Self {
+        unsafe { (self.vtable.clone_())(self.data) }
+    }
+}
+
+impl Drop for RWaker {
+    fn drop(&mut self) {
+        unsafe { (self.vtable.drop_())(self.data) }
+    }
+}
+
+impl Debug for RWaker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RWaker").field("data", &self.data).finish()
+    }
+}
+
+impl From<Waker> for RWaker {
+    fn from(waker: Waker) -> Self {
+        Self::new(waker)
+    }
+}
+
+impl From<RWaker> for Waker {
+    fn from(waker: RWaker) -> Self {
+        waker.into_waker()
+    }
+}
+
+/// An `RWaker` that borrows a `Waker`.
+struct RWakerRef<'a> {
+    waker: ManuallyDrop<RWaker>,
+    _marker: PhantomData<&'a Waker>,
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Ffi-safe equivalent of `std::task::Context`.
+///
+/// This is passed to the `poll` function of [`RBoxFuture`]s,
+/// and can be converted to and from a `std::task::Context`
+/// with `RContext::with_context` and `RContext::from_context`.
+///
+/// [`RBoxFuture`]: ./type.RBoxFuture.html
+///
+/// # Example
+///
+/// ```rust
+/// use abi_stable::std_types::RContext;
+///
+/// use std::{future::Future, pin::Pin, task::{Context, Poll}};
+///
+/// // An ffi-safe poll function.
+/// extern "C" fn poll_ready(cx: RContext<'_>) -> bool {
+///     cx.with_context(|cx| {
+///         let mut fut = Box::pin(async {});
+///         fut.as_mut().poll(cx).is_ready()
+///     })
+/// }
+///
+/// struct CallsPollReady;
+///
+/// impl Future for CallsPollReady {
+///     type Output = ();
+///
+///     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+///         if RContext::from_context(cx, |cx| poll_ready(cx)) {
+///             Poll::Ready(())
+///         } else {
+///             Poll::Pending
+///         }
+///     }
+/// }
+/// ```
+#[repr(C)]
+#[derive(Copy, Clone, StableAbi)]
+pub struct RContext<'a> {
+    waker: &'a RWaker,
+}
+
+impl<'a> RContext<'a> {
+    /// Constructs an `RContext` from the `RWaker` of the current task.
+    pub const fn from_waker(waker: &'a RWaker) -> Self {
+        Self { waker }
+    }
+
+    /// The `RWaker` of the current task.
+    pub const fn waker(&self) -> &'a RWaker {
+        self.waker
+    }
+
+    /// Calls `f` with the `std::task::Context` equivalent of this `RContext`.
+    pub fn with_context<R, F>(self, f: F) -> R
+    where
+        F: FnOnce(&mut Context<'_>) -> R,
+    {
+        let waker = self.waker.as_std_waker();
+        f(&mut Context::from_waker(&waker))
+    }
+
+    /// Calls `f` with the `RContext` equivalent of `cx`.
+    ///
+    /// This doesn't allocate,the `Waker` of `cx` is only cloned if the `RWaker` is.
+    pub fn from_context<R, F>(cx: &mut Context<'_>, f: F) -> R
+    where
+        F: FnOnce(RContext<'_>) -> R,
+    {
+        let waker = RWaker::from_ref(cx.waker());
+        f(RContext::from_waker(&waker.waker))
+    }
+}
+
+impl Debug for RContext<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RContext")
+            .field("waker", &self.waker)
+            .finish()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[repr(C)]
+#[derive(StableAbi)]
+#[sabi(kind(Prefix))]
+#[sabi(missing_field(panic))]
+struct RWakerVTable {
+    clone_: unsafe extern "C" fn(*const ()) -> RWaker,
+    wake: unsafe extern "C" fn(*const ()),
+    wake_by_ref: unsafe extern "C" fn(*const ()),
+    #[sabi(last_prefix_field)]
+    drop_: unsafe extern "C" fn(*const ()),
+}
+
+/// The vtable of `RWaker`s that own a `Box<Waker>`.
+const OWNED_VTABLE: RWakerVTable_Ref = {
+    const WM: &WithMetadata<RWakerVTable> = &WithMetadata::new(
+        PrefixTypeTrait::METADATA,
+        RWakerVTable {
+            clone_: clone_waker,
+            wake: wake_owned,
+            wake_by_ref,
+            drop_: drop_owned,
+        },
+    );
+    RWakerVTable_Ref(WM.static_as_prefix())
+};
+
+/// The vtable of `RWaker`s that borrow a `Waker`.
+const BORROWED_VTABLE: RWakerVTable_Ref = {
+    const WM: &WithMetadata<RWakerVTable> = &WithMetadata::new(
+        PrefixTypeTrait::METADATA,
+        RWakerVTable {
+            clone_: clone_waker,
+            wake: wake_by_ref,
+            wake_by_ref,
+            drop_: drop_borrowed,
+        },
+    );
+    RWakerVTable_Ref(WM.static_as_prefix())
+};
+
+unsafe extern "C" fn clone_waker(data: *const ()) -> RWaker {
+    extern_fn_panic_handling! {
+        RWaker::new((*(data as *const Waker)).clone())
+    }
+}
+
+unsafe extern "C" fn wake_owned(data: *const ()) {
+    extern_fn_panic_handling! {
+        Box::from_raw(data as *mut Waker).wake();
+    }
+}
+
+unsafe extern "C" fn wake_by_ref(data: *const ()) {
+    extern_fn_panic_handling! {
+        (*(data as *const Waker)).wake_by_ref();
+    }
+}
+
+unsafe extern "C" fn drop_owned(data: *const ()) {
+    extern_fn_panic_handling! {
+        mem::drop(Box::from_raw(data as *mut Waker));
+    }
+}
+
+unsafe extern "C" fn drop_borrowed(_: *const ()) {}
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// The vtable of `Waker`s that wrap an `RWaker`,
+/// with a pointer to the `RWaker` as the data.
+static STD_VTABLE: RawWakerVTable =
+    RawWakerVTable::new(std_clone, std_wake, std_wake_by_ref, std_drop);
+
+unsafe fn std_clone(data: *const ()) -> RawWaker {
+    let waker = (*(data as *const RWaker)).clone();
+    RawWaker::new(Box::into_raw(Box::new(waker)) as *const (), &STD_VTABLE)
+}
+
+unsafe fn std_wake(data: *const ()) {
+    Box::from_raw(data as *mut RWaker).wake();
+}
+
+unsafe fn std_wake_by_ref(data: *const ()) {
+    (*(data as *const RWaker)).wake_by_ref();
+}
+
+unsafe fn std_drop(data: *const ()) {
+    mem::drop(Box::from_raw(data as *mut RWaker));
+}
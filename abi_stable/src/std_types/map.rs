@@ -27,16 +27,19 @@ use crate::{
     DynTrait, StableAbi,
 };
 
-mod entry;
+// `entry` and `map_key` are shared with `RBTreeMap`.
+pub(super) mod entry;
 mod extern_fns;
 mod iterator_stuff;
-mod map_key;
+pub(super) mod map_key;
 mod map_query;
 
 #[cfg(all(test, not(feature = "only_new_tests")))]
 mod test;
 
-use self::{entry::BoxedREntry, map_key::MapKey, map_query::MapQuery};
+use self::{entry::HashMapEntry, map_query::MapQuery};
+
+type MapKey<K> = map_key::MapKey<K, MapQuery<'static, K>>;
 
 pub use self::{
     entry::{REntry, ROccupiedEntry, RVacantEntry},
@@ -107,7 +110,7 @@ pub struct RHashMap<K, V, S = RandomState> {
 
 struct BoxedHashMap<'a, K, V, S> {
     map: HashMap<MapKey<K>, V, S>,
-    entry: Option<BoxedREntry<'a, K, V>>,
+    entry: Option<HashMapEntry<'a, K, V>>,
 }
 
 /// An RHashMap iterator,
@@ -1,7 +1,10 @@
 use super::*;
 
 use std::{
-    collections::hash_map::{Entry, OccupiedEntry, VacantEntry},
+    collections::{
+        btree_map,
+        hash_map::{self, OccupiedEntry, VacantEntry},
+    },
     mem::ManuallyDrop,
     ptr,
 };
@@ -10,12 +13,14 @@ use crate::{
     marker_type::UnsafeIgnoredType,
     prefix_type::{PrefixTypeTrait, WithMetadata},
     sabi_types::{RMut, RRef},
+    std_types::btree_map::MapKey as BTreeMapKey,
 };
 
-/// The enum stored alongside the unerased HashMap.
-pub(super) enum BoxedREntry<'a, K, V> {
-    Occupied(UnerasedOccupiedEntry<'a, K, V>),
-    Vacant(UnerasedVacantEntry<'a, K, V>),
+/// The enum stored alongside the unerased map,
+/// which is either a `HashMapEntry` or a `BTreeMapEntry`.
+pub(in crate::std_types) enum BoxedREntry<O, Va> {
+    Occupied(ManuallyDrop<O>),
+    Vacant(ManuallyDrop<Va>),
 }
 
 /// A handle into an entry in a map, which is either vacant or occupied.
@@ -37,46 +42,6 @@ struct ErasedOccupiedEntry<K, V>(PhantomData<(K, V)>);
 #[repr(C)]
 struct ErasedVacantEntry<K, V>(PhantomData<(K, V)>);
 
-type UnerasedOccupiedEntry<'a, K, V> = ManuallyDrop<OccupiedEntry<'a, MapKey<K>, V>>;
-
-type UnerasedVacantEntry<'a, K, V> = ManuallyDrop<VacantEntry<'a, MapKey<K>, V>>;
-
-impl<'a, K: 'a, V: 'a> ErasedType<'a> for ErasedOccupiedEntry<K, V> {
-    type Unerased = UnerasedOccupiedEntry<'a, K, V>;
-}
-
-impl<'a, K: 'a, V: 'a> ErasedType<'a> for ErasedVacantEntry<K, V> {
-    type Unerased = UnerasedVacantEntry<'a, K, V>;
-}
-
-/////////////////////////////////////////////////////////////////////////////////////////////
-
-impl<'a, K, V> From<Entry<'a, MapKey<K>, V>> for BoxedREntry<'a, K, V>
-where
-    K: Eq + Hash,
-{
-    fn from(entry: Entry<'a, MapKey<K>, V>) -> Self {
-        match entry {
-            Entry::Occupied(entry) => entry.piped(ManuallyDrop::new).piped(BoxedREntry::Occupied),
-            Entry::Vacant(entry) => entry.piped(ManuallyDrop::new).piped(BoxedREntry::Vacant),
-        }
-    }
-}
-
-impl<'a, K, V> REntry<'a, K, V>
-where
-    K: Eq + Hash,
-{
-    pub(super) unsafe fn new(entry: &'a mut BoxedREntry<'a, K, V>) -> Self {
-        match entry {
-            BoxedREntry::Occupied(entry) => {
-                entry.piped(ROccupiedEntry::new).piped(REntry::Occupied)
-            }
-            BoxedREntry::Vacant(entry) => entry.piped(RVacantEntry::new).piped(REntry::Vacant),
-        }
-    }
-}
-
 /////////////////////////////////////////////////////////////////////////////////////////////
 
 impl<'a, K, V> REntry<'a, K, V> {
@@ -300,13 +265,11 @@ impl<'a, K, V> ROccupiedEntry<'a, K, V> {
         unsafe { ((&mut this.entry) as *mut RMut<'a, ErasedOccupiedEntry<K, V>>).read() }
     }
 
-    pub(super) fn new(entry: &'a mut UnerasedOccupiedEntry<'a, K, V>) -> Self {
-        unsafe {
-            Self {
-                entry: ErasedOccupiedEntry::from_unerased(entry),
-                vtable: OccupiedVTable::VTABLE_REF,
-                _marker: UnsafeIgnoredType::DEFAULT,
-            }
+    fn new(entry: RMut<'a, ErasedOccupiedEntry<K, V>>, vtable: OccupiedVTable_Ref<K, V>) -> Self {
+        Self {
+            entry,
+            vtable,
+            _marker: UnsafeIgnoredType::DEFAULT,
         }
     }
 
@@ -512,16 +475,11 @@ impl<'a, K, V> RVacantEntry<'a, K, V> {
         unsafe { ((&mut this.entry) as *mut RMut<'a, ErasedVacantEntry<K, V>>).read() }
     }
 
-    pub(super) fn new(entry: &'a mut UnerasedVacantEntry<'a, K, V>) -> Self
-    where
-        K: Eq + Hash,
-    {
-        unsafe {
-            Self {
-                entry: ErasedVacantEntry::from_unerased(entry),
-                vtable: VacantVTable::VTABLE_REF,
-                _marker: UnsafeIgnoredType::DEFAULT,
-            }
+    fn new(entry: RMut<'a, ErasedVacantEntry<K, V>>, vtable: VacantVTable_Ref<K, V>) -> Self {
+        Self {
+            entry,
+            vtable,
+            _marker: UnsafeIgnoredType::DEFAULT,
         }
     }
 
@@ -641,95 +599,6 @@ pub struct OccupiedVTable<K, V> {
     remove: extern "C" fn(ROccupiedEntry<'_, K, V>) -> V,
 }
 
-impl<K, V> OccupiedVTable<K, V> {
-    const VTABLE_REF: OccupiedVTable_Ref<K, V> = OccupiedVTable_Ref(Self::WM_VTABLE.as_prefix());
-
-    staticref! {
-        const WM_VTABLE: WithMetadata<OccupiedVTable<K, V>> =
-            WithMetadata::new(PrefixTypeTrait::METADATA, Self::VTABLE)
-    }
-
-    const VTABLE: OccupiedVTable<K, V> = OccupiedVTable {
-        drop_entry: ErasedOccupiedEntry::drop_entry,
-        key: ErasedOccupiedEntry::key,
-        get_elem: ErasedOccupiedEntry::get_elem,
-        get_mut_elem: ErasedOccupiedEntry::get_mut_elem,
-        fn_into_mut_elem: ErasedOccupiedEntry::fn_into_mut_elem,
-        insert_elem: ErasedOccupiedEntry::insert_elem,
-        remove: ErasedOccupiedEntry::remove,
-    };
-}
-
-impl<K, V> ErasedOccupiedEntry<K, V> {
-    unsafe extern "C" fn drop_entry(this: RMut<'_, Self>) {
-        extern_fn_panic_handling! {
-            Self::run_downcast_as_mut(this, |this|{
-                ManuallyDrop::drop(this);
-            })
-        }
-    }
-    extern "C" fn key(this: RRef<'_, Self>) -> &K {
-        unsafe {
-            extern_fn_panic_handling! {
-                Self::run_downcast_as(
-                    this,
-                    |this| this.key().as_ref()
-                )
-            }
-        }
-    }
-    extern "C" fn get_elem(this: RRef<'_, Self>) -> &V {
-        unsafe {
-            extern_fn_panic_handling! {
-                Self::run_downcast_as(
-                    this,
-                    |this| this.get()
-                )
-            }
-        }
-    }
-    extern "C" fn get_mut_elem(this: RMut<'_, Self>) -> &mut V {
-        unsafe {
-            extern_fn_panic_handling! {
-                Self::run_downcast_as_mut(
-                    this,
-                    |this| this.get_mut()
-                )
-            }
-        }
-    }
-    extern "C" fn fn_into_mut_elem(this: ROccupiedEntry<'_, K, V>) -> &'_ mut V {
-        unsafe {
-            extern_fn_panic_handling! {
-                Self::run_downcast_as_mut(
-                    this.into_inner(),
-                    |this| take_manuallydrop(this).into_mut()
-                )
-            }
-        }
-    }
-    extern "C" fn insert_elem(this: RMut<'_, Self>, elem: V) -> V {
-        unsafe {
-            extern_fn_panic_handling! {
-                Self::run_downcast_as_mut(
-                    this,
-                    |this| this.insert(elem)
-                )
-            }
-        }
-    }
-    extern "C" fn remove(this: ROccupiedEntry<'_, K, V>) -> V {
-        unsafe {
-            extern_fn_panic_handling! {
-                Self::run_downcast_as_mut(
-                    this.into_inner(),
-                    |this| take_manuallydrop(this).remove()
-                )
-            }
-        }
-    }
-}
-
 /////////////////////////////////////////////////////////////////////////////////////////////
 
 #[derive(StableAbi)]
@@ -742,60 +611,205 @@ pub struct VacantVTable<K, V> {
     insert_elem: extern "C" fn(RVacantEntry<'_, K, V>, V) -> &'_ mut V,
 }
 
-impl<K, V> VacantVTable<K, V> {
-    const VTABLE_REF: VacantVTable_Ref<K, V> = VacantVTable_Ref(Self::WM_VTABLE.as_prefix());
+/////////////////////////////////////////////////////////////////////////////////////////////
 
-    staticref! {
-        const WM_VTABLE: WithMetadata<VacantVTable<K, V>> =
-            WithMetadata::new(PrefixTypeTrait::METADATA, Self::VTABLE)
-    }
+/// Declares the `BoxedREntry` alias for the entries of one of the std maps,
+/// along with the vtables that `ROccupiedEntry`/`RVacantEntry` use to access them.
+///
+/// The vtables are declared in an inherent impl of the alias,
+/// so that each map gets its own vtables for the same `REntry` type.
+macro_rules! declare_boxed_entry {
+    (
+        $(#[$attr:meta])*
+        type $alias:ident = $std_module:ident::Entry<$key:ty>
+        where [$($bounds:tt)*];
+    ) => {
+        $(#[$attr])*
+        pub(in crate::std_types) type $alias<'a, K, V> = BoxedREntry<
+            $std_module::OccupiedEntry<'a, $key, V>,
+            $std_module::VacantEntry<'a, $key, V>,
+        >;
+
+        impl<'a, K, V> From<$std_module::Entry<'a, $key, V>> for $alias<'a, K, V>
+        where
+            $($bounds)*
+        {
+            fn from(entry: $std_module::Entry<'a, $key, V>) -> Self {
+                match entry {
+                    $std_module::Entry::Occupied(entry) => {
+                        entry.piped(ManuallyDrop::new).piped(BoxedREntry::Occupied)
+                    }
+                    $std_module::Entry::Vacant(entry) => {
+                        entry.piped(ManuallyDrop::new).piped(BoxedREntry::Vacant)
+                    }
+                }
+            }
+        }
 
-    const VTABLE: VacantVTable<K, V> = VacantVTable {
-        drop_entry: ErasedVacantEntry::drop_entry,
-        key: ErasedVacantEntry::key,
-        fn_into_key: ErasedVacantEntry::fn_into_key,
-        insert_elem: ErasedVacantEntry::insert_elem,
-    };
-}
+        impl<'a, K, V> $alias<'a, K, V>
+        where
+            $($bounds)*
+        {
+            pub(in crate::std_types) fn as_rentry(&'a mut self) -> REntry<'a, K, V> {
+                unsafe {
+                    match self {
+                        BoxedREntry::Occupied(entry) => ROccupiedEntry::new(
+                            RMut::new(entry).transmute(),
+                            Self::OCCUPIED_VTABLE_REF,
+                        )
+                        .piped(REntry::Occupied),
+                        BoxedREntry::Vacant(entry) => {
+                            RVacantEntry::new(RMut::new(entry).transmute(), Self::VACANT_VTABLE_REF)
+                                .piped(REntry::Vacant)
+                        }
+                    }
+                }
+            }
 
-impl<K, V> ErasedVacantEntry<K, V> {
-    unsafe extern "C" fn drop_entry(this: RMut<'_, Self>) {
-        extern_fn_panic_handling! {
-            Self::run_downcast_as_mut(this, |this|{
-                ManuallyDrop::drop(this);
-            })
-        }
-    }
-    extern "C" fn key(this: RRef<'_, Self>) -> &K {
-        unsafe {
-            extern_fn_panic_handling! {
-                Self::run_downcast_as(
-                    this,
-                    |this| this.key().as_ref()
-                )
+            const OCCUPIED_VTABLE_REF: OccupiedVTable_Ref<K, V> =
+                OccupiedVTable_Ref(Self::WM_OCCUPIED_VTABLE.as_prefix());
+
+            staticref! {
+                const WM_OCCUPIED_VTABLE: WithMetadata<OccupiedVTable<K, V>> =
+                    WithMetadata::new(PrefixTypeTrait::METADATA, Self::OCCUPIED_VTABLE)
             }
-        }
-    }
-    extern "C" fn fn_into_key(this: RVacantEntry<'_, K, V>) -> K {
-        unsafe {
-            extern_fn_panic_handling! {
-                Self::run_downcast_as_mut(
-                    this.into_inner(),
-                    |this| take_manuallydrop(this).into_key().into_inner()
-                )
+
+            const OCCUPIED_VTABLE: OccupiedVTable<K, V> = OccupiedVTable {
+                drop_entry: Self::drop_occupied,
+                key: Self::occupied_key,
+                get_elem: Self::get_elem,
+                get_mut_elem: Self::get_mut_elem,
+                fn_into_mut_elem: Self::fn_into_mut_elem,
+                insert_elem: Self::occupied_insert_elem,
+                remove: Self::remove,
+            };
+
+            const VACANT_VTABLE_REF: VacantVTable_Ref<K, V> =
+                VacantVTable_Ref(Self::WM_VACANT_VTABLE.as_prefix());
+
+            staticref! {
+                const WM_VACANT_VTABLE: WithMetadata<VacantVTable<K, V>> =
+                    WithMetadata::new(PrefixTypeTrait::METADATA, Self::VACANT_VTABLE)
             }
-        }
-    }
-    extern "C" fn insert_elem(this: RVacantEntry<'_, K, V>, elem: V) -> &'_ mut V {
-        unsafe {
-            extern_fn_panic_handling! {
-                Self::run_downcast_as_mut(
-                    this.into_inner(),
-                    |this| take_manuallydrop(this).insert(elem)
-                )
+
+            const VACANT_VTABLE: VacantVTable<K, V> = VacantVTable {
+                drop_entry: Self::drop_vacant,
+                key: Self::vacant_key,
+                fn_into_key: Self::fn_into_key,
+                insert_elem: Self::vacant_insert_elem,
+            };
+
+            unsafe extern "C" fn drop_occupied(this: RMut<'_, ErasedOccupiedEntry<K, V>>) {
+                extern_fn_panic_handling! {
+                    let this = this
+                        .transmute_into_mut::<ManuallyDrop<$std_module::OccupiedEntry<'_, $key, V>>>();
+                    ManuallyDrop::drop(this);
+                }
+            }
+            extern "C" fn occupied_key(this: RRef<'_, ErasedOccupiedEntry<K, V>>) -> &K {
+                unsafe {
+                    extern_fn_panic_handling! {
+                        this.transmute_into_ref::<$std_module::OccupiedEntry<'_, $key, V>>()
+                            .key()
+                            .as_ref()
+                    }
+                }
+            }
+            extern "C" fn get_elem(this: RRef<'_, ErasedOccupiedEntry<K, V>>) -> &V {
+                unsafe {
+                    extern_fn_panic_handling! {
+                        this.transmute_into_ref::<$std_module::OccupiedEntry<'_, $key, V>>()
+                            .get()
+                    }
+                }
+            }
+            extern "C" fn get_mut_elem(this: RMut<'_, ErasedOccupiedEntry<K, V>>) -> &mut V {
+                unsafe {
+                    extern_fn_panic_handling! {
+                        this.transmute_into_mut::<$std_module::OccupiedEntry<'_, $key, V>>()
+                            .get_mut()
+                    }
+                }
+            }
+            extern "C" fn fn_into_mut_elem(this: ROccupiedEntry<'_, K, V>) -> &'_ mut V {
+                unsafe {
+                    extern_fn_panic_handling! {
+                        let this = this.into_inner().transmute_into_mut();
+                        take_manuallydrop::<$std_module::OccupiedEntry<'_, $key, V>>(this)
+                            .into_mut()
+                    }
+                }
+            }
+            extern "C" fn occupied_insert_elem(
+                this: RMut<'_, ErasedOccupiedEntry<K, V>>,
+                elem: V,
+            ) -> V {
+                unsafe {
+                    extern_fn_panic_handling! {
+                        this.transmute_into_mut::<$std_module::OccupiedEntry<'_, $key, V>>()
+                            .insert(elem)
+                    }
+                }
+            }
+            extern "C" fn remove(this: ROccupiedEntry<'_, K, V>) -> V {
+                unsafe {
+                    extern_fn_panic_handling! {
+                        let this = this.into_inner().transmute_into_mut();
+                        take_manuallydrop::<$std_module::OccupiedEntry<'_, $key, V>>(this)
+                            .remove()
+                    }
+                }
+            }
+
+            unsafe extern "C" fn drop_vacant(this: RMut<'_, ErasedVacantEntry<K, V>>) {
+                extern_fn_panic_handling! {
+                    let this = this
+                        .transmute_into_mut::<ManuallyDrop<$std_module::VacantEntry<'_, $key, V>>>();
+                    ManuallyDrop::drop(this);
+                }
+            }
+            extern "C" fn vacant_key(this: RRef<'_, ErasedVacantEntry<K, V>>) -> &K {
+                unsafe {
+                    extern_fn_panic_handling! {
+                        this.transmute_into_ref::<$std_module::VacantEntry<'_, $key, V>>()
+                            .key()
+                            .as_ref()
+                    }
+                }
+            }
+            extern "C" fn fn_into_key(this: RVacantEntry<'_, K, V>) -> K {
+                unsafe {
+                    extern_fn_panic_handling! {
+                        let this = this.into_inner().transmute_into_mut();
+                        take_manuallydrop::<$std_module::VacantEntry<'_, $key, V>>(this)
+                            .into_key()
+                            .into_inner()
+                    }
+                }
+            }
+            extern "C" fn vacant_insert_elem(this: RVacantEntry<'_, K, V>, elem: V) -> &'_ mut V {
+                unsafe {
+                    extern_fn_panic_handling! {
+                        let this = this.into_inner().transmute_into_mut();
+                        take_manuallydrop::<$std_module::VacantEntry<'_, $key, V>>(this)
+                            .insert(elem)
+                    }
+                }
             }
         }
-    }
+    };
+}
+
+declare_boxed_entry! {
+    /// The entries of the `HashMap` that `RHashMap` wraps.
+    type HashMapEntry = hash_map::Entry<MapKey<K>>
+    where [K: Eq + Hash];
+}
+
+declare_boxed_entry! {
+    /// The entries of the `BTreeMap` that `RBTreeMap` wraps.
+    type BTreeMapEntry = btree_map::Entry<BTreeMapKey<K>>
+    where [K: Ord];
 }
 
 /////////////////////////////////////////////////////////////////////////////////////////////
@@ -155,9 +155,9 @@ where
             let map = &mut this.map;
             let entry_mut = this
                 .entry
-                .get_or_insert_with(|| { map }.entry(MapKey::Value(key)).piped(BoxedREntry::from));
+                .get_or_insert_with(|| { map }.entry(MapKey::Value(key)).piped(HashMapEntry::from));
 
-            unsafe { REntry::new(entry_mut) }
+            entry_mut.as_rentry()
         })
     }
 }
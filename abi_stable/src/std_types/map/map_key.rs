@@ -1,17 +1,25 @@
 use super::*;
 
-pub enum MapKey<K> {
+/// The key type of the std maps that `RHashMap` and `RBTreeMap` wrap,
+/// where `Q` is the `MapQuery` type of the map.
+///
+/// Only `MapKey::Value` is ever stored in the map,
+/// `MapKey::Query` is only constructed to look up keys.
+pub enum MapKey<K, Q> {
     Value(K),
-    /// This is a horrible hack.
-    Query(NonNull<MapQuery<'static, K>>),
+    /// A pointer to the query of a lookup,
+    /// which only lives for the duration of the call to the vtable function.
+    Query(NonNull<Q>),
 }
 
-impl<K> MapKey<K> {
+const QUERY_MSG: &str = "a `MapKey::Query` was used as a key stored in the map";
+
+impl<K, Q> MapKey<K, Q> {
     #[inline]
     pub fn into_inner(self) -> K {
         match self {
             MapKey::Value(v) => v,
-            _ => unreachable!("This is a BUG!!!!!!!!!!!!!!!!!!!!"),
+            MapKey::Query(_) => unreachable!("{}", QUERY_MSG),
         }
     }
 
@@ -19,33 +27,38 @@ impl<K> MapKey<K> {
     pub fn as_ref(&self) -> &K {
         match self {
             MapKey::Value(v) => v,
-            _ => unreachable!("This is a BUG!!!!!!!!!!!!!!!!!!!!"),
+            MapKey::Query(_) => unreachable!("{}", QUERY_MSG),
         }
     }
 }
 
-impl<K> From<K> for MapKey<K> {
+impl<K, Q> From<K> for MapKey<K, Q> {
     #[inline]
     fn from(value: K) -> Self {
         MapKey::Value(value)
     }
 }
 
-impl<K> Debug for MapKey<K>
+impl<K, Q> Debug for MapKey<K, Q>
 where
     K: Debug,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            MapKey::Value(v) => Debug::fmt(v, f),
-            _ => unreachable!("This is a BUG!!!!!!!!!!!!!!!!!!!!"),
-        }
+        Debug::fmt(self.as_ref(), f)
+    }
+}
+
+impl<K, Q> Borrow<K> for MapKey<K, Q> {
+    fn borrow(&self) -> &K {
+        self.as_ref()
     }
 }
 
-impl<K> Eq for MapKey<K> where K: Eq {}
+///////////////////////////////////////////////////////////////////////////////
+
+impl<K> Eq for MapKey<K, MapQuery<'static, K>> where K: Eq {}
 
-impl<K> PartialEq for MapKey<K>
+impl<K> PartialEq for MapKey<K, MapQuery<'static, K>>
 where
     K: PartialEq,
 {
@@ -55,14 +68,14 @@ where
                 rhs.as_ref().is_equal(lhs)
             },
             (MapKey::Value(lhs), MapKey::Value(rhs)) => lhs == rhs,
-            _ => {
-                unreachable!("This is a BUG!!!!!!!!!!!!!!!!!!!!!!!!");
+            (MapKey::Query(_), MapKey::Query(_)) => {
+                unreachable!("`HashMap` compared two `MapKey::Query`s")
             }
         }
     }
 }
 
-impl<K> Hash for MapKey<K>
+impl<K> Hash for MapKey<K, MapQuery<'static, K>>
 where
     K: Hash,
 {
@@ -80,9 +93,3 @@ where
         }
     }
 }
-
-impl<K> Borrow<K> for MapKey<K> {
-    fn borrow(&self) -> &K {
-        self.as_ref()
-    }
-}
@@ -0,0 +1,689 @@
+//! Contains ffi-safe equivalents of `&'a OsStr` and `OsString`.
+
+use std::{
+    borrow::Cow,
+    cmp::Ordering,
+    ffi::{OsStr, OsString},
+    fmt::{self, Debug},
+    hash::{Hash, Hasher},
+    str,
+};
+
+#[cfg(unix)]
+use std::{ops::Deref, os::unix::ffi::OsStrExt};
+
+#[cfg(any(unix, windows))]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::std_types::{RSlice, RString, RVec};
+
+mod encoding;
+
+#[cfg(all(test, not(feature = "only_new_tests")))]
+mod test;
+
+/// Ffi-safe equivalent of `&'a OsStr`.
+///
+/// # Encoding
+///
+/// On Unix this stores the bytes of the `OsStr` as-is,
+/// so converting to and from `&OsStr` is free,
+/// and this type dereferences to `OsStr`.
+///
+/// On Windows this stores the string as WTF-8 (which is UTF-8 for valid unicode),
+/// so converting from an `OsStr` that contains unpaired surrogates requires
+/// re-encoding it.
+///
+/// # Example
+///
+/// ```
+/// use abi_stable::{
+///     sabi_extern_fn,
+///     std_types::{ROsStr, ROsString},
+/// };
+///
+/// #[sabi_extern_fn]
+/// fn with_extension(name: ROsStr<'_>, extension: ROsStr<'_>) -> ROsString {
+///     let mut ret = name.to_os_string();
+///     ret.push(".");
+///     ret.push(extension.to_os_string());
+///     ret.into()
+/// }
+///
+/// assert_eq!(
+///     with_extension("hello".into(), "txt".into()).to_str(),
+///     Some("hello.txt"),
+/// );
+///
+/// ```
+#[repr(C)]
+#[derive(Copy, Clone, StableAbi)]
+pub struct ROsStr<'a> {
+    inner: RSlice<'a, u8>,
+}
+
+impl<'a> ROsStr<'a> {
+    /// Converts `&'a str` to an `ROsStr<'a>`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::ROsStr;
+    ///
+    /// const NAME: ROsStr<'static> = ROsStr::from_str("hello");
+    ///
+    /// assert_eq!(NAME.to_str(), Some("hello"));
+    ///
+    /// ```
+    pub const fn from_str(s: &'a str) -> Self {
+        Self {
+            inner: RSlice::from_slice(s.as_bytes()),
+        }
+    }
+
+    /// Converts `&'a OsStr` to an `ROsStr<'a>`, without copying.
+    ///
+    /// This is only available on Unix.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(unix)] {
+    /// use abi_stable::std_types::ROsStr;
+    ///
+    /// use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+    ///
+    /// let name = OsStr::from_bytes(b"hello\xFF");
+    ///
+    /// assert_eq!(ROsStr::from_os_str(name).as_os_str(), name);
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    pub fn from_os_str(s: &'a OsStr) -> Self {
+        Self {
+            inner: RSlice::from_slice(s.as_bytes()),
+        }
+    }
+
+    /// Gets the `&'a OsStr` this is equivalent to.
+    ///
+    /// This is only available on Unix,
+    /// use `to_os_string` on other platforms.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(unix)] {
+    /// use abi_stable::std_types::ROsStr;
+    ///
+    /// use std::ffi::OsStr;
+    ///
+    /// assert_eq!(ROsStr::from("hello").as_os_str(), OsStr::new("hello"));
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    pub fn as_os_str(&self) -> &'a OsStr {
+        OsStr::from_bytes(self.inner.as_slice())
+    }
+
+    /// Converts this `ROsStr<'a>` to an `OsString`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::ROsStr;
+    ///
+    /// use std::ffi::OsString;
+    ///
+    /// assert_eq!(ROsStr::from("hello").to_os_string(), OsString::from("hello"));
+    ///
+    /// ```
+    pub fn to_os_string(&self) -> OsString {
+        self.as_os_cow().into_owned()
+    }
+
+    pub(crate) fn as_os_cow(&self) -> Cow<'a, OsStr> {
+        encoding::bytes_to_os_str(self.inner.as_slice())
+    }
+
+    /// Gets the string as a `&'a str` if it's valid unicode.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::ROsStr;
+    ///
+    /// assert_eq!(ROsStr::from("hello").to_str(), Some("hello"));
+    ///
+    /// ```
+    pub fn to_str(&self) -> Option<&'a str> {
+        str::from_utf8(self.inner.as_slice()).ok()
+    }
+
+    /// Converts the string to a `Cow<'a, str>`,
+    /// replacing the non-unicode parts with `U+FFFD REPLACEMENT CHARACTER`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::ROsStr;
+    ///
+    /// assert_eq!(ROsStr::from("hello").to_string_lossy(), "hello");
+    ///
+    /// ```
+    pub fn to_string_lossy(&self) -> Cow<'a, str> {
+        match self.to_str() {
+            Some(x) => Cow::Borrowed(x),
+            None => Cow::Owned(self.as_os_cow().to_string_lossy().into_owned()),
+        }
+    }
+
+    /// Gets the encoded bytes of this string.
+    ///
+    /// The encoding is platform-specific,
+    /// the [type-level docs](#encoding) describe it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::ROsStr;
+    ///
+    /// assert_eq!(ROsStr::from("hello").as_encoded_bytes(), b"hello");
+    ///
+    /// ```
+    pub fn as_encoded_bytes(&self) -> &'a [u8] {
+        self.inner.as_slice()
+    }
+
+    /// Gets the length (in encoded bytes) of this `ROsStr<'a>`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::ROsStr;
+    ///
+    /// assert_eq!(ROsStr::from("").len(), 0);
+    /// assert_eq!(ROsStr::from("What").len(), 4);
+    ///
+    /// ```
+    pub const fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Queries whether this `ROsStr<'a>` is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::ROsStr;
+    ///
+    /// assert!(ROsStr::from("").is_empty());
+    /// assert!(!ROsStr::from("What").is_empty());
+    ///
+    /// ```
+    pub const fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Copies this `ROsStr<'a>` into an `ROsString`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{ROsStr, ROsString};
+    ///
+    /// assert_eq!(ROsStr::from("hello").to_ros_string(), ROsString::from("hello"));
+    ///
+    /// ```
+    pub fn to_ros_string(&self) -> ROsString {
+        ROsString {
+            inner: self.inner.to_rvec(),
+        }
+    }
+}
+
+unsafe impl<'a> Send for ROsStr<'a> {}
+unsafe impl<'a> Sync for ROsStr<'a> {}
+
+impl<'a> Default for ROsStr<'a> {
+    fn default() -> Self {
+        Self::from_str("")
+    }
+}
+
+#[cfg(unix)]
+impl<'a> Deref for ROsStr<'a> {
+    type Target = OsStr;
+
+    #[inline]
+    fn deref(&self) -> &OsStr {
+        self.as_os_str()
+    }
+}
+
+#[cfg(unix)]
+impl AsRef<OsStr> for ROsStr<'_> {
+    fn as_ref(&self) -> &OsStr {
+        self.as_os_str()
+    }
+}
+
+impl<'a> From<&'a str> for ROsStr<'a> {
+    fn from(this: &'a str) -> Self {
+        Self::from_str(this)
+    }
+}
+
+#[cfg(unix)]
+impl_from_rust_repr! {
+    impl['a] From<&'a OsStr> for ROsStr<'a> {
+        fn(this){
+            ROsStr::from_os_str(this)
+        }
+    }
+}
+
+#[cfg(unix)]
+impl_into_rust_repr! {
+    impl['a] Into<&'a OsStr> for ROsStr<'a> {
+        fn(this){
+            this.as_os_str()
+        }
+    }
+}
+
+impl<'a> From<ROsStr<'a>> for OsString {
+    fn from(this: ROsStr<'a>) -> OsString {
+        this.to_os_string()
+    }
+}
+
+impl Debug for ROsStr<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&*self.as_os_cow(), f)
+    }
+}
+
+impl PartialEq for ROsStr<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_encoded_bytes() == other.as_encoded_bytes()
+    }
+}
+
+impl Eq for ROsStr<'_> {}
+
+impl PartialOrd for ROsStr<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ROsStr<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_encoded_bytes().cmp(other.as_encoded_bytes())
+    }
+}
+
+impl Hash for ROsStr<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_encoded_bytes().hash(state)
+    }
+}
+
+#[cfg(any(unix, windows))]
+impl Serialize for ROsStr<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.as_os_cow().serialize(serializer)
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// Ffi-safe equivalent of `OsString`.
+///
+/// This uses the same [encoding](./struct.ROsStr.html#encoding) as `ROsStr`.
+///
+/// # Example
+///
+/// ```
+/// use abi_stable::{
+///     sabi_extern_fn,
+///     std_types::{ROsString, RVec},
+/// };
+///
+/// #[sabi_extern_fn]
+/// fn variable_names() -> RVec<ROsString> {
+///     std::env::vars_os().map(|(name, _)| ROsString::from(name)).collect()
+/// }
+///
+/// assert!(variable_names().iter().any(|name| name.to_str().is_some()));
+///
+/// ```
+#[repr(C)]
+#[derive(Clone, Default, StableAbi)]
+pub struct ROsString {
+    inner: RVec<u8>,
+}
+
+impl ROsString {
+    /// Creates a new, empty `ROsString`.
+    ///
+    /// This function does not allocate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::ROsString;
+    ///
+    /// let string = ROsString::new();
+    ///
+    /// assert!(string.is_empty());
+    ///
+    /// ```
+    pub const fn new() -> Self {
+        Self { inner: RVec::new() }
+    }
+
+    /// Creates an `ROsStr<'_>` borrowing from this `ROsString`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{ROsStr, ROsString};
+    ///
+    /// let string = ROsString::from("hello");
+    ///
+    /// assert_eq!(string.as_ros_str(), ROsStr::from("hello"));
+    ///
+    /// ```
+    pub fn as_ros_str(&self) -> ROsStr<'_> {
+        ROsStr {
+            inner: self.inner.as_rslice(),
+        }
+    }
+
+    /// Gets an `&OsStr` borrowing from this `ROsString`.
+    ///
+    /// This is only available on Unix,
+    /// use `to_os_string` on other platforms.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(unix)] {
+    /// use abi_stable::std_types::ROsString;
+    ///
+    /// use std::ffi::OsStr;
+    ///
+    /// assert_eq!(ROsString::from("hello").as_os_str(), OsStr::new("hello"));
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    pub fn as_os_str(&self) -> &OsStr {
+        OsStr::from_bytes(self.inner.as_slice())
+    }
+
+    /// Converts this `ROsString` into an `OsString`.
+    ///
+    /// # Allocation
+    ///
+    /// On Unix, this only allocates if it's invoked outside of
+    /// the dynamic library/binary that created the `ROsString`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::ROsString;
+    ///
+    /// use std::ffi::OsString;
+    ///
+    /// assert_eq!(
+    ///     ROsString::from("hello").into_os_string(),
+    ///     OsString::from("hello"),
+    /// );
+    ///
+    /// ```
+    pub fn into_os_string(self) -> OsString {
+        encoding::bytes_into_os_string(self.inner.into_vec())
+    }
+
+    /// Copies this `ROsString` into an `OsString`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::ROsString;
+    ///
+    /// use std::ffi::OsString;
+    ///
+    /// assert_eq!(ROsString::from("hello").to_os_string(), OsString::from("hello"));
+    ///
+    /// ```
+    pub fn to_os_string(&self) -> OsString {
+        self.as_ros_str().to_os_string()
+    }
+
+    /// Converts this `ROsString` into an `RString` if it's valid unicode,
+    /// otherwise returning it back.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{ROsString, RString};
+    ///
+    /// assert_eq!(
+    ///     ROsString::from("hello").into_string(),
+    ///     Ok(RString::from("hello")),
+    /// );
+    ///
+    /// ```
+    pub fn into_string(self) -> Result<RString, ROsString> {
+        RString::from_utf8(self.inner).map_err(|e| ROsString {
+            inner: e.into_bytes(),
+        })
+    }
+
+    /// Gets the string as a `&str` if it's valid unicode.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::ROsString;
+    ///
+    /// assert_eq!(ROsString::from("hello").to_str(), Some("hello"));
+    ///
+    /// ```
+    pub fn to_str(&self) -> Option<&str> {
+        self.as_ros_str().to_str()
+    }
+
+    /// Converts the string to a `Cow<'_, str>`,
+    /// replacing the non-unicode parts with `U+FFFD REPLACEMENT CHARACTER`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::ROsString;
+    ///
+    /// assert_eq!(ROsString::from("hello").to_string_lossy(), "hello");
+    ///
+    /// ```
+    pub fn to_string_lossy(&self) -> Cow<'_, str> {
+        self.as_ros_str().to_string_lossy()
+    }
+
+    /// Gets the encoded bytes of this string.
+    ///
+    /// The encoding is platform-specific,
+    /// the [`ROsStr` docs](./struct.ROsStr.html#encoding) describe it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::ROsString;
+    ///
+    /// assert_eq!(ROsString::from("hello").as_encoded_bytes(), b"hello");
+    ///
+    /// ```
+    pub fn as_encoded_bytes(&self) -> &[u8] {
+        self.inner.as_slice()
+    }
+
+    /// Gets the length (in encoded bytes) of this `ROsString`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::ROsString;
+    ///
+    /// assert_eq!(ROsString::new().len(), 0);
+    /// assert_eq!(ROsString::from("What").len(), 4);
+    ///
+    /// ```
+    pub const fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Queries whether this `ROsString` is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::ROsString;
+    ///
+    /// assert!(ROsString::new().is_empty());
+    /// assert!(!ROsString::from("What").is_empty());
+    ///
+    /// ```
+    pub const fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
+#[cfg(unix)]
+impl Deref for ROsString {
+    type Target = OsStr;
+
+    #[inline]
+    fn deref(&self) -> &OsStr {
+        self.as_os_str()
+    }
+}
+
+#[cfg(unix)]
+impl AsRef<OsStr> for ROsString {
+    fn as_ref(&self) -> &OsStr {
+        self.as_os_str()
+    }
+}
+
+impl_from_rust_repr! {
+    impl From<OsString> for ROsString {
+        fn(this){
+            ROsString {
+                inner: encoding::os_string_into_bytes(this).into(),
+            }
+        }
+    }
+}
+
+impl_into_rust_repr! {
+    impl Into<OsString> for ROsString {
+        fn(this){
+            this.into_os_string()
+        }
+    }
+}
+
+impl From<&OsStr> for ROsString {
+    fn from(this: &OsStr) -> Self {
+        ROsString {
+            inner: encoding::os_str_to_bytes(this).into_owned().into(),
+        }
+    }
+}
+
+impl From<ROsStr<'_>> for ROsString {
+    fn from(this: ROsStr<'_>) -> Self {
+        this.to_ros_string()
+    }
+}
+
+impl From<&str> for ROsString {
+    fn from(this: &str) -> Self {
+        ROsString {
+            inner: this.as_bytes().into(),
+        }
+    }
+}
+
+impl From<String> for ROsString {
+    fn from(this: String) -> Self {
+        ROsString {
+            inner: this.into_bytes().into(),
+        }
+    }
+}
+
+impl From<RString> for ROsString {
+    fn from(this: RString) -> Self {
+        ROsString {
+            inner: this.into_bytes(),
+        }
+    }
+}
+
+impl Debug for ROsString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.as_ros_str(), f)
+    }
+}
+
+impl PartialEq for ROsString {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_encoded_bytes() == other.as_encoded_bytes()
+    }
+}
+
+impl Eq for ROsString {}
+
+impl PartialOrd for ROsString {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ROsString {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_encoded_bytes().cmp(other.as_encoded_bytes())
+    }
+}
+
+impl Hash for ROsString {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_encoded_bytes().hash(state)
+    }
+}
+
+#[cfg(any(unix, windows))]
+impl Serialize for ROsString {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.as_ros_str().serialize(serializer)
+    }
+}
+
+#[cfg(any(unix, windows))]
+impl<'de> Deserialize<'de> for ROsString {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        OsString::deserialize(deserializer).map(Self::from)
+    }
+}
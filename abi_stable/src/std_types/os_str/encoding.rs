@@ -0,0 +1,162 @@
+//! Conversions between `OsStr`s and the bytes stored in `ROsStr`/`ROsString`.
+//!
+//! On Unix the bytes are the ones the OS uses.
+//!
+//! On Windows the bytes are WTF-8,
+//! a superset of UTF-8 that can also encode unpaired surrogates.
+//!
+//! On every other platform the bytes are UTF-8,
+//! and non-unicode strings are converted lossily.
+
+pub(super) use self::imp::*;
+
+#[cfg(unix)]
+mod imp {
+    use std::{
+        borrow::Cow,
+        ffi::{OsStr, OsString},
+        os::unix::ffi::{OsStrExt, OsStringExt},
+    };
+
+    pub(crate) fn os_str_to_bytes(this: &OsStr) -> Cow<'_, [u8]> {
+        Cow::Borrowed(this.as_bytes())
+    }
+
+    pub(crate) fn os_string_into_bytes(this: OsString) -> Vec<u8> {
+        this.into_vec()
+    }
+
+    pub(crate) fn bytes_to_os_str(bytes: &[u8]) -> Cow<'_, OsStr> {
+        Cow::Borrowed(OsStr::from_bytes(bytes))
+    }
+
+    pub(crate) fn bytes_into_os_string(bytes: Vec<u8>) -> OsString {
+        OsString::from_vec(bytes)
+    }
+}
+
+#[cfg(windows)]
+mod imp {
+    use std::{
+        borrow::Cow,
+        char,
+        ffi::{OsStr, OsString},
+        os::windows::ffi::{OsStrExt, OsStringExt},
+        str,
+    };
+
+    pub(crate) fn os_str_to_bytes(this: &OsStr) -> Cow<'_, [u8]> {
+        if let Some(x) = this.to_str() {
+            return Cow::Borrowed(x.as_bytes());
+        }
+
+        let mut bytes = Vec::with_capacity(this.len());
+        for elem in char::decode_utf16(this.encode_wide()) {
+            match elem {
+                Ok(c) => {
+                    let mut buffer = [0; 4];
+                    bytes.extend_from_slice(c.encode_utf8(&mut buffer).as_bytes());
+                }
+                Err(e) => {
+                    // Unpaired surrogates are encoded like any other code point below 0x10000
+                    let s = e.unpaired_surrogate();
+                    bytes.extend_from_slice(&[
+                        0xE0 | (s >> 12) as u8,
+                        0x80 | ((s >> 6) & 0x3F) as u8,
+                        0x80 | (s & 0x3F) as u8,
+                    ]);
+                }
+            }
+        }
+        Cow::Owned(bytes)
+    }
+
+    pub(crate) fn os_string_into_bytes(this: OsString) -> Vec<u8> {
+        match this.into_string() {
+            Ok(x) => x.into_bytes(),
+            Err(x) => os_str_to_bytes(&x).into_owned(),
+        }
+    }
+
+    pub(crate) fn bytes_to_os_str(bytes: &[u8]) -> Cow<'_, OsStr> {
+        if let Ok(x) = str::from_utf8(bytes) {
+            return Cow::Borrowed(OsStr::new(x));
+        }
+
+        let continuation = |i: usize| (bytes[i] & 0x3F) as u32;
+
+        let mut wide = Vec::<u16>::with_capacity(bytes.len());
+        let mut i = 0;
+        while i < bytes.len() {
+            let first = bytes[i] as u32;
+            let (code_point, width) = if first < 0x80 {
+                (first, 1)
+            } else if first < 0xE0 {
+                (((first & 0x1F) << 6) | continuation(i + 1), 2)
+            } else if first < 0xF0 {
+                let code_point =
+                    ((first & 0x0F) << 12) | (continuation(i + 1) << 6) | continuation(i + 2);
+                (code_point, 3)
+            } else {
+                let code_point = ((first & 0x07) << 18)
+                    | (continuation(i + 1) << 12)
+                    | (continuation(i + 2) << 6)
+                    | continuation(i + 3);
+                (code_point, 4)
+            };
+            i += width;
+
+            if code_point < 0x1_0000 {
+                wide.push(code_point as u16);
+            } else {
+                let offset = code_point - 0x1_0000;
+                wide.push(0xD800 | (offset >> 10) as u16);
+                wide.push(0xDC00 | (offset & 0x3FF) as u16);
+            }
+        }
+        Cow::Owned(OsString::from_wide(&wide))
+    }
+
+    pub(crate) fn bytes_into_os_string(bytes: Vec<u8>) -> OsString {
+        match String::from_utf8(bytes) {
+            Ok(x) => x.into(),
+            Err(e) => bytes_to_os_str(e.as_bytes()).into_owned(),
+        }
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+mod imp {
+    use std::{
+        borrow::Cow,
+        ffi::{OsStr, OsString},
+    };
+
+    pub(crate) fn os_str_to_bytes(this: &OsStr) -> Cow<'_, [u8]> {
+        match this.to_string_lossy() {
+            Cow::Borrowed(x) => Cow::Borrowed(x.as_bytes()),
+            Cow::Owned(x) => Cow::Owned(x.into_bytes()),
+        }
+    }
+
+    pub(crate) fn os_string_into_bytes(this: OsString) -> Vec<u8> {
+        match this.into_string() {
+            Ok(x) => x.into_bytes(),
+            Err(x) => x.to_string_lossy().into_owned().into_bytes(),
+        }
+    }
+
+    pub(crate) fn bytes_to_os_str(bytes: &[u8]) -> Cow<'_, OsStr> {
+        match String::from_utf8_lossy(bytes) {
+            Cow::Borrowed(x) => Cow::Borrowed(OsStr::new(x)),
+            Cow::Owned(x) => Cow::Owned(x.into()),
+        }
+    }
+
+    pub(crate) fn bytes_into_os_string(bytes: Vec<u8>) -> OsString {
+        match String::from_utf8(bytes) {
+            Ok(x) => x.into(),
+            Err(e) => String::from_utf8_lossy(e.as_bytes()).into_owned().into(),
+        }
+    }
+}
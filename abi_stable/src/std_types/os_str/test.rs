@@ -0,0 +1,133 @@
+use super::*;
+
+#[cfg(unix)]
+use std::os::unix::ffi::OsStringExt;
+
+#[cfg(windows)]
+use std::os::windows::ffi::{OsStrExt, OsStringExt};
+
+/// Returns strings that aren't valid unicode, paired with valid ones.
+fn non_unicode() -> Vec<OsString> {
+    #[cfg(unix)]
+    let list = vec![
+        OsString::from_vec(b"hello\xFF".to_vec()),
+        OsString::from_vec(b"\xC3world".to_vec()),
+        OsString::from_vec(b"\x80".to_vec()),
+    ];
+
+    #[cfg(windows)]
+    let list = vec![
+        OsString::from_wide(&[0x68, 0xD800]),
+        OsString::from_wide(&[0xDC00, 0x77]),
+        OsString::from_wide(&[0xDBFF, 0x61, 0xD83D, 0xDE00, 0xDFFF]),
+    ];
+
+    #[cfg(not(any(unix, windows)))]
+    let list = Vec::new();
+
+    list
+}
+
+#[test]
+fn unicode_roundtrip() {
+    for &s in &["", "hello", "ñúñí", "😀 emoji"] {
+        let os = OsString::from(s);
+
+        let ros = ROsString::from(os.clone());
+        assert_eq!(ros.to_str(), Some(s));
+        assert_eq!(ros.as_encoded_bytes(), s.as_bytes());
+        assert_eq!(ros.to_os_string(), os);
+        assert_eq!(ros.as_ros_str(), ROsStr::from(s));
+        assert_eq!(ros.clone().into_string(), Ok(RString::from(s)));
+        assert_eq!(ros.into_os_string(), os);
+
+        assert_eq!(ROsString::from(&*os), ROsString::from(s));
+        assert_eq!(ROsStr::from(s).to_os_string(), os);
+    }
+}
+
+#[test]
+fn non_unicode_roundtrip() {
+    for os in non_unicode() {
+        let ros = ROsString::from(os.clone());
+
+        assert_eq!(ros.to_str(), None);
+        assert_eq!(ros.to_string_lossy(), os.to_string_lossy());
+        assert_eq!(ros.as_ros_str().to_os_string(), os);
+        assert_eq!(ROsString::from(&*os), ros);
+        assert_eq!(format!("{:?}", ros), format!("{:?}", os));
+
+        let ros = ros.into_string().unwrap_err();
+        assert_eq!(ros.into_os_string(), os);
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn unix_deref() {
+    for os in non_unicode() {
+        let ros = ROsString::from(os.clone());
+        assert_eq!(&*ros, &*os);
+        assert_eq!(ros.as_encoded_bytes(), os.as_bytes());
+
+        let ros_str = ROsStr::from(&*os);
+        assert_eq!(&*ros_str, &*os);
+        assert_eq!(ros_str.as_os_str().len(), os.len());
+    }
+}
+
+#[cfg(windows)]
+#[test]
+fn windows_encoding() {
+    let os = OsString::from_wide(&[0x68, 0xD800, 0xD83D, 0xDE00]);
+    let ros = ROsString::from(os.clone());
+
+    assert_eq!(
+        ros.as_encoded_bytes(),
+        &[0x68, 0xED, 0xA0, 0x80, 0xF0, 0x9F, 0x98, 0x80][..]
+    );
+    assert_eq!(
+        ros.to_os_string().encode_wide().collect::<Vec<u16>>(),
+        [0x68, 0xD800, 0xD83D, 0xDE00]
+    );
+}
+
+#[test]
+fn cmp_and_hash() {
+    use std::collections::hash_map::DefaultHasher;
+
+    fn hash<T: Hash>(value: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    let a = ROsString::from("aaa");
+    let b = ROsString::from("bbb");
+
+    assert!(a < b);
+    assert_eq!(a, a.as_ros_str().to_ros_string());
+    assert_ne!(a, b);
+    assert_eq!(hash(&a), hash(&a.clone()));
+    assert_eq!(hash(&a.as_ros_str()), hash(&ROsStr::from("aaa")));
+}
+
+#[cfg(any(unix, windows))]
+#[test]
+fn test_serde() {
+    let list = vec![OsString::from("hello")]
+        .into_iter()
+        .chain(non_unicode())
+        .collect::<Vec<OsString>>();
+
+    for os in list {
+        let ros = ROsString::from(os.clone());
+
+        let json = serde_json::to_string(&os).unwrap();
+        assert_eq!(serde_json::to_string(&ros).unwrap(), json);
+        assert_eq!(serde_json::to_string(&ros.as_ros_str()).unwrap(), json);
+
+        let deserialized = serde_json::from_str::<ROsString>(&json).unwrap();
+        assert_eq!(deserialized, ros);
+    }
+}
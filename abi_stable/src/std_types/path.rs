@@ -0,0 +1,614 @@
+//! Contains ffi-safe equivalents of `&'a Path` and `PathBuf`.
+
+use std::{
+    borrow::Cow,
+    cmp::Ordering,
+    ffi::OsString,
+    fmt::{self, Debug},
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+};
+
+#[cfg(unix)]
+use std::ops::Deref;
+
+use serde::{ser::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::std_types::{ROsStr, ROsString, RString};
+
+#[cfg(all(test, not(feature = "only_new_tests")))]
+mod test;
+
+/// Ffi-safe equivalent of `&'a Path`.
+///
+/// This stores the path as an [`ROsStr<'a>`](./struct.ROsStr.html),
+/// so it's only free to convert to and from `&Path` on Unix,
+/// where this type dereferences to `Path`.
+///
+/// Comparisons, hashing and `Debug` formatting behave like those of `Path`,
+/// so `a/b` equals `a//b`.
+///
+/// # Example
+///
+/// ```
+/// use abi_stable::{
+///     sabi_extern_fn,
+///     std_types::{RPath, RString},
+/// };
+///
+/// #[sabi_extern_fn]
+/// fn file_stem(path: RPath<'_>) -> RString {
+///     path.to_path_buf()
+///         .file_stem()
+///         .map_or(RString::new(), |x| x.to_string_lossy().into())
+/// }
+///
+/// assert_eq!(file_stem("plugins/hello.so".into()), "hello");
+///
+/// ```
+#[repr(C)]
+#[derive(Copy, Clone, StableAbi)]
+pub struct RPath<'a> {
+    inner: ROsStr<'a>,
+}
+
+impl<'a> RPath<'a> {
+    /// Converts `&'a str` to an `RPath<'a>`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RPath;
+    ///
+    /// const CONFIG: RPath<'static> = RPath::from_str("config/plugins.toml");
+    ///
+    /// assert_eq!(CONFIG.to_str(), Some("config/plugins.toml"));
+    ///
+    /// ```
+    pub const fn from_str(s: &'a str) -> Self {
+        Self {
+            inner: ROsStr::from_str(s),
+        }
+    }
+
+    /// Converts `&'a Path` to an `RPath<'a>`, without copying.
+    ///
+    /// This is only available on Unix.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(unix)] {
+    /// use abi_stable::std_types::RPath;
+    ///
+    /// use std::path::Path;
+    ///
+    /// let path = Path::new("foo/bar");
+    ///
+    /// assert_eq!(RPath::from_path(path).as_path(), path);
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    pub fn from_path(path: &'a Path) -> Self {
+        Self {
+            inner: ROsStr::from_os_str(path.as_os_str()),
+        }
+    }
+
+    /// Gets the `&'a Path` this is equivalent to.
+    ///
+    /// This is only available on Unix,
+    /// use `to_path_buf` on other platforms.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(unix)] {
+    /// use abi_stable::std_types::RPath;
+    ///
+    /// use std::path::Path;
+    ///
+    /// assert_eq!(RPath::from("foo/bar").as_path(), Path::new("foo/bar"));
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    pub fn as_path(&self) -> &'a Path {
+        Path::new(self.inner.as_os_str())
+    }
+
+    /// Converts this `RPath<'a>` to a `PathBuf`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RPath;
+    ///
+    /// use std::path::PathBuf;
+    ///
+    /// assert_eq!(RPath::from("foo/bar").to_path_buf(), PathBuf::from("foo/bar"));
+    ///
+    /// ```
+    pub fn to_path_buf(&self) -> PathBuf {
+        self.as_path_cow().into_owned()
+    }
+
+    fn as_path_cow(&self) -> Cow<'a, Path> {
+        match self.inner.as_os_cow() {
+            Cow::Borrowed(x) => Cow::Borrowed(Path::new(x)),
+            Cow::Owned(x) => Cow::Owned(x.into()),
+        }
+    }
+
+    /// Gets the path as an `ROsStr<'a>`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{ROsStr, RPath};
+    ///
+    /// assert_eq!(RPath::from("foo/bar").as_ros_str(), ROsStr::from("foo/bar"));
+    ///
+    /// ```
+    pub const fn as_ros_str(&self) -> ROsStr<'a> {
+        self.inner
+    }
+
+    /// Gets the path as a `&'a str` if it's valid unicode.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RPath;
+    ///
+    /// assert_eq!(RPath::from("foo/bar").to_str(), Some("foo/bar"));
+    ///
+    /// ```
+    pub fn to_str(&self) -> Option<&'a str> {
+        self.inner.to_str()
+    }
+
+    /// Converts the path to a `Cow<'a, str>`,
+    /// replacing the non-unicode parts with `U+FFFD REPLACEMENT CHARACTER`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RPath;
+    ///
+    /// assert_eq!(RPath::from("foo/bar").to_string_lossy(), "foo/bar");
+    ///
+    /// ```
+    pub fn to_string_lossy(&self) -> Cow<'a, str> {
+        self.inner.to_string_lossy()
+    }
+
+    /// Copies this `RPath<'a>` into an `RPathBuf`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RPath, RPathBuf};
+    ///
+    /// assert_eq!(RPath::from("foo/bar").to_rpath_buf(), RPathBuf::from("foo/bar"));
+    ///
+    /// ```
+    pub fn to_rpath_buf(&self) -> RPathBuf {
+        RPathBuf {
+            inner: self.inner.to_ros_string(),
+        }
+    }
+}
+
+impl<'a> Default for RPath<'a> {
+    fn default() -> Self {
+        Self::from_str("")
+    }
+}
+
+#[cfg(unix)]
+impl<'a> Deref for RPath<'a> {
+    type Target = Path;
+
+    #[inline]
+    fn deref(&self) -> &Path {
+        self.as_path()
+    }
+}
+
+#[cfg(unix)]
+impl AsRef<Path> for RPath<'_> {
+    fn as_ref(&self) -> &Path {
+        self.as_path()
+    }
+}
+
+impl<'a> From<&'a str> for RPath<'a> {
+    fn from(this: &'a str) -> Self {
+        Self::from_str(this)
+    }
+}
+
+impl<'a> From<ROsStr<'a>> for RPath<'a> {
+    fn from(this: ROsStr<'a>) -> Self {
+        Self { inner: this }
+    }
+}
+
+#[cfg(unix)]
+impl_from_rust_repr! {
+    impl['a] From<&'a Path> for RPath<'a> {
+        fn(this){
+            RPath::from_path(this)
+        }
+    }
+}
+
+#[cfg(unix)]
+impl_into_rust_repr! {
+    impl['a] Into<&'a Path> for RPath<'a> {
+        fn(this){
+            this.as_path()
+        }
+    }
+}
+
+impl<'a> From<RPath<'a>> for PathBuf {
+    fn from(this: RPath<'a>) -> PathBuf {
+        this.to_path_buf()
+    }
+}
+
+impl Debug for RPath<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&*self.as_path_cow(), f)
+    }
+}
+
+impl PartialEq for RPath<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_path_cow() == other.as_path_cow()
+    }
+}
+
+impl Eq for RPath<'_> {}
+
+impl PartialOrd for RPath<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for RPath<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_path_cow().cmp(&other.as_path_cow())
+    }
+}
+
+impl Hash for RPath<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_path_cow().hash(state)
+    }
+}
+
+impl Serialize for RPath<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.to_str() {
+            Some(x) => x.serialize(serializer),
+            None => Err(S::Error::custom("path contains invalid UTF-8 characters")),
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// Ffi-safe equivalent of `PathBuf`.
+///
+/// This stores the path as an [`ROsString`](./struct.ROsString.html),
+/// and dereferences to `Path` on Unix.
+///
+/// Comparisons, hashing and `Debug` formatting behave like those of `Path`.
+///
+/// # Example
+///
+/// ```
+/// use abi_stable::{
+///     sabi_extern_fn,
+///     std_types::{RPathBuf, RVec},
+/// };
+///
+/// #[sabi_extern_fn]
+/// fn plugin_paths(dir: RPathBuf, names: RVec<RPathBuf>) -> RVec<RPathBuf> {
+///     let dir = dir.into_path_buf();
+///     names.into_iter().map(|name| dir.join(name.into_path_buf()).into()).collect()
+/// }
+///
+/// let paths = plugin_paths("plugins".into(), vec!["a.so".into(), "b.so".into()].into());
+///
+/// assert_eq!(paths[0].to_path_buf(), std::path::Path::new("plugins").join("a.so"));
+/// assert_eq!(paths[1].to_path_buf(), std::path::Path::new("plugins").join("b.so"));
+///
+/// ```
+#[repr(C)]
+#[derive(Clone, Default, StableAbi)]
+pub struct RPathBuf {
+    inner: ROsString,
+}
+
+impl RPathBuf {
+    /// Creates a new, empty `RPathBuf`.
+    ///
+    /// This function does not allocate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RPathBuf;
+    ///
+    /// let path = RPathBuf::new();
+    ///
+    /// assert_eq!(path.to_str(), Some(""));
+    ///
+    /// ```
+    pub const fn new() -> Self {
+        Self {
+            inner: ROsString::new(),
+        }
+    }
+
+    /// Creates an `RPath<'_>` borrowing from this `RPathBuf`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RPath, RPathBuf};
+    ///
+    /// let path = RPathBuf::from("foo/bar");
+    ///
+    /// assert_eq!(path.as_rpath(), RPath::from("foo/bar"));
+    ///
+    /// ```
+    pub fn as_rpath(&self) -> RPath<'_> {
+        RPath {
+            inner: self.inner.as_ros_str(),
+        }
+    }
+
+    /// Gets a `&Path` borrowing from this `RPathBuf`.
+    ///
+    /// This is only available on Unix,
+    /// use `to_path_buf` on other platforms.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(unix)] {
+    /// use abi_stable::std_types::RPathBuf;
+    ///
+    /// use std::path::Path;
+    ///
+    /// assert_eq!(RPathBuf::from("foo/bar").as_path(), Path::new("foo/bar"));
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    pub fn as_path(&self) -> &Path {
+        Path::new(self.inner.as_os_str())
+    }
+
+    /// Converts this `RPathBuf` into a `PathBuf`.
+    ///
+    /// # Allocation
+    ///
+    /// On Unix, this only allocates if it's invoked outside of
+    /// the dynamic library/binary that created the `RPathBuf`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RPathBuf;
+    ///
+    /// use std::path::PathBuf;
+    ///
+    /// assert_eq!(
+    ///     RPathBuf::from("foo/bar").into_path_buf(),
+    ///     PathBuf::from("foo/bar"),
+    /// );
+    ///
+    /// ```
+    pub fn into_path_buf(self) -> PathBuf {
+        self.inner.into_os_string().into()
+    }
+
+    /// Copies this `RPathBuf` into a `PathBuf`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RPathBuf;
+    ///
+    /// use std::path::PathBuf;
+    ///
+    /// assert_eq!(RPathBuf::from("foo/bar").to_path_buf(), PathBuf::from("foo/bar"));
+    ///
+    /// ```
+    pub fn to_path_buf(&self) -> PathBuf {
+        self.as_rpath().to_path_buf()
+    }
+
+    /// Converts this `RPathBuf` into an `ROsString`, without copying.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{ROsString, RPathBuf};
+    ///
+    /// assert_eq!(
+    ///     RPathBuf::from("foo/bar").into_ros_string(),
+    ///     ROsString::from("foo/bar"),
+    /// );
+    ///
+    /// ```
+    pub fn into_ros_string(self) -> ROsString {
+        self.inner
+    }
+
+    /// Gets the path as a `&str` if it's valid unicode.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RPathBuf;
+    ///
+    /// assert_eq!(RPathBuf::from("foo/bar").to_str(), Some("foo/bar"));
+    ///
+    /// ```
+    pub fn to_str(&self) -> Option<&str> {
+        self.inner.to_str()
+    }
+
+    /// Converts the path to a `Cow<'_, str>`,
+    /// replacing the non-unicode parts with `U+FFFD REPLACEMENT CHARACTER`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RPathBuf;
+    ///
+    /// assert_eq!(RPathBuf::from("foo/bar").to_string_lossy(), "foo/bar");
+    ///
+    /// ```
+    pub fn to_string_lossy(&self) -> Cow<'_, str> {
+        self.inner.to_string_lossy()
+    }
+}
+
+#[cfg(unix)]
+impl Deref for RPathBuf {
+    type Target = Path;
+
+    #[inline]
+    fn deref(&self) -> &Path {
+        self.as_path()
+    }
+}
+
+#[cfg(unix)]
+impl AsRef<Path> for RPathBuf {
+    fn as_ref(&self) -> &Path {
+        self.as_path()
+    }
+}
+
+impl_from_rust_repr! {
+    impl From<PathBuf> for RPathBuf {
+        fn(this){
+            RPathBuf {
+                inner: this.into_os_string().into(),
+            }
+        }
+    }
+}
+
+impl_into_rust_repr! {
+    impl Into<PathBuf> for RPathBuf {
+        fn(this){
+            this.into_path_buf()
+        }
+    }
+}
+
+impl From<&Path> for RPathBuf {
+    fn from(this: &Path) -> Self {
+        RPathBuf {
+            inner: this.as_os_str().into(),
+        }
+    }
+}
+
+impl From<RPath<'_>> for RPathBuf {
+    fn from(this: RPath<'_>) -> Self {
+        this.to_rpath_buf()
+    }
+}
+
+impl From<ROsString> for RPathBuf {
+    fn from(this: ROsString) -> Self {
+        RPathBuf { inner: this }
+    }
+}
+
+impl From<OsString> for RPathBuf {
+    fn from(this: OsString) -> Self {
+        RPathBuf { inner: this.into() }
+    }
+}
+
+impl From<&str> for RPathBuf {
+    fn from(this: &str) -> Self {
+        RPathBuf { inner: this.into() }
+    }
+}
+
+impl From<String> for RPathBuf {
+    fn from(this: String) -> Self {
+        RPathBuf { inner: this.into() }
+    }
+}
+
+impl From<RString> for RPathBuf {
+    fn from(this: RString) -> Self {
+        RPathBuf { inner: this.into() }
+    }
+}
+
+impl Debug for RPathBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.as_rpath(), f)
+    }
+}
+
+impl PartialEq for RPathBuf {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_rpath() == other.as_rpath()
+    }
+}
+
+impl Eq for RPathBuf {}
+
+impl PartialOrd for RPathBuf {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for RPathBuf {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_rpath().cmp(&other.as_rpath())
+    }
+}
+
+impl Hash for RPathBuf {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_rpath().hash(state)
+    }
+}
+
+impl Serialize for RPathBuf {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.as_rpath().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for RPathBuf {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        PathBuf::deserialize(deserializer).map(Self::from)
+    }
+}
@@ -0,0 +1,80 @@
+use super::*;
+
+use std::collections::hash_map::DefaultHasher;
+
+fn hash<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn conversions() {
+    for &s in &["", "foo", "foo/bar.txt", "/ñ/😀"] {
+        let path = PathBuf::from(s);
+
+        let rpath_buf = RPathBuf::from(path.clone());
+        assert_eq!(rpath_buf.to_str(), Some(s));
+        assert_eq!(rpath_buf.to_path_buf(), path);
+        assert_eq!(rpath_buf.as_rpath(), RPath::from(s));
+        assert_eq!(rpath_buf.as_rpath().to_path_buf(), path);
+        assert_eq!(RPathBuf::from(&*path), rpath_buf);
+        assert_eq!(RPathBuf::from(s.to_string()), rpath_buf);
+        assert_eq!(rpath_buf.clone().into_ros_string(), ROsString::from(s));
+        assert_eq!(rpath_buf.into_path_buf(), path);
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn non_unicode() {
+    use std::os::unix::ffi::OsStringExt;
+
+    let path = PathBuf::from(OsString::from_vec(b"foo/\xFFbar".to_vec()));
+
+    let rpath_buf = RPathBuf::from(path.clone());
+    assert_eq!(rpath_buf.to_str(), None);
+    assert_eq!(rpath_buf.to_string_lossy(), path.to_string_lossy());
+    assert_eq!(&*rpath_buf, &*path);
+    assert_eq!(RPath::from(&*path).as_path(), &*path);
+    assert_eq!(format!("{:?}", rpath_buf), format!("{:?}", path));
+    assert_eq!(rpath_buf.into_path_buf(), path);
+}
+
+#[test]
+fn cmp_like_path() {
+    let a = RPathBuf::from("foo/bar");
+    let b = RPathBuf::from("foo//bar/");
+    let c = RPathBuf::from("foo/baz");
+
+    assert_eq!(a, b);
+    assert_eq!(hash(&a), hash(&b));
+    assert_eq!(hash(&a.as_rpath()), hash(&Path::new("foo/bar")));
+    assert_ne!(a, c);
+    assert!(a < c);
+    assert_eq!(a.cmp(&b), Ordering::Equal);
+}
+
+#[test]
+fn test_serde() {
+    let path = RPathBuf::from("foo/bar.txt");
+
+    let json = r##""foo/bar.txt""##;
+
+    let deserialized = serde_json::from_str::<RPathBuf>(json).unwrap();
+
+    assert_eq!(deserialized, path);
+
+    assert_eq!(serde_json::to_string(&path).unwrap(), json);
+    assert_eq!(serde_json::to_string(&path.as_rpath()).unwrap(), json);
+}
+
+#[cfg(unix)]
+#[test]
+fn serde_non_unicode() {
+    use std::os::unix::ffi::OsStringExt;
+
+    let path = RPathBuf::from(OsString::from_vec(b"\xFF".to_vec()));
+
+    assert!(serde_json::to_string(&path).is_err());
+}
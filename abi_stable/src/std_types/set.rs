@@ -0,0 +1,947 @@
+//! Contains the ffi-safe equivalent of `std::collections::HashSet`, and related items.
+
+use std::{
+    borrow::Borrow,
+    cmp::{Eq, PartialEq},
+    collections::{hash_map::RandomState, HashSet},
+    fmt::{self, Debug},
+    hash::{BuildHasher, Hash},
+    iter::{Chain, FromIterator},
+};
+
+use crate::{
+    marker_type::NonOwningPhantom,
+    std_types::{
+        map::{self, Keys},
+        RHashMap, ROption, Tuple2,
+    },
+    StableAbi,
+};
+
+#[cfg(all(test, not(feature = "only_new_tests")))]
+mod test;
+
+/// An ffi-safe hashset, which wraps `std::collections::HashSet<T, S>`,
+/// only requiring the `T: Eq + Hash` bounds when constructing it.
+///
+/// This is implemented on top of an `RHashMap<T, (), S>`,
+/// so it is type-erased and checked for layout compatibility the same way.
+///
+/// # Example
+///
+/// ```
+/// use abi_stable::std_types::{RHashSet, RString};
+///
+/// let mut set = RHashSet::<RString>::new();
+///
+/// assert!(set.insert("bread".into()));
+/// assert!(set.insert("cheese".into()));
+/// assert!(!set.insert("bread".into()));
+///
+/// assert!(set.contains("cheese"));
+/// assert!(set.remove("cheese"));
+/// assert!(!set.contains("cheese"));
+///
+/// assert_eq!(set.len(), 1);
+///
+/// ```
+///
+#[derive(StableAbi)]
+#[repr(C)]
+#[sabi(
+    // The hasher doesn't matter
+    unsafe_unconstrained(S),
+)]
+pub struct RHashSet<T, S = RandomState> {
+    map: RHashMap<T, (), S>,
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+impl<T> RHashSet<T, RandomState> {
+    /// Constructs an empty RHashSet.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RHashSet, RString};
+    ///
+    /// let mut set = RHashSet::<RString>::new();
+    /// assert!(set.is_empty());
+    /// set.insert("Hello".into());
+    /// assert_eq!(set.is_empty(), false);
+    ///
+    /// ```
+    #[inline]
+    pub fn new() -> RHashSet<T>
+    where
+        Self: Default,
+    {
+        Self::default()
+    }
+
+    /// Constructs an empty RHashSet with at least the passed capacity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RHashSet, RString};
+    ///
+    /// let set = RHashSet::<RString>::with_capacity(10);
+    /// assert!(set.capacity() >= 10);
+    ///
+    /// ```
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> RHashSet<T>
+    where
+        Self: Default,
+    {
+        let mut this = Self::default();
+        this.reserve(capacity);
+        this
+    }
+}
+
+impl<T, S> RHashSet<T, S> {
+    /// Constructs an empty RHashSet with the passed `hash_builder` to hash the elements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RHashSet, RString};
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let s = RandomState::new();
+    /// let mut set = RHashSet::<RString, _>::with_hasher(s);
+    /// assert!(set.is_empty());
+    /// set.insert("Hello".into());
+    /// assert_eq!(set.is_empty(), false);
+    ///
+    /// ```
+    #[inline]
+    pub fn with_hasher(hash_builder: S) -> RHashSet<T, S>
+    where
+        T: Eq + Hash,
+        S: BuildHasher + Default,
+    {
+        Self::with_capacity_and_hasher(0, hash_builder)
+    }
+
+    /// Constructs an empty RHashSet with at least the passed capacity,
+    /// and the passed `hash_builder` to hash the elements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RHashSet, RString};
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let s = RandomState::new();
+    /// let set = RHashSet::<RString, _>::with_capacity_and_hasher(10, s);
+    /// assert!(set.capacity() >= 10);
+    ///
+    /// ```
+    pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> RHashSet<T, S>
+    where
+        T: Eq + Hash,
+        S: BuildHasher + Default,
+    {
+        RHashSet {
+            map: RHashMap::with_capacity_and_hasher(capacity, hash_builder),
+        }
+    }
+}
+
+impl<T, S> RHashSet<T, S> {
+    /// Returns whether the set contains the value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RHashSet, RString};
+    ///
+    /// let mut set = RHashSet::<RString>::new();
+    /// assert_eq!(set.contains("boo"), false);
+    /// set.insert("boo".into());
+    /// assert_eq!(set.contains("boo"), true);
+    ///
+    /// ```
+    pub fn contains<Q>(&self, query: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.contains_key(query)
+    }
+
+    /// Removes the value from the set,returning whether it was in the set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RHashSet;
+    ///
+    /// let mut set = vec![0, 3].into_iter().collect::<RHashSet<u32>>();
+    ///
+    /// assert_eq!(set.remove(&0), true);
+    /// assert_eq!(set.remove(&0), false);
+    ///
+    /// ```
+    pub fn remove<Q>(&mut self, query: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.remove(query).is_some()
+    }
+
+    /// Removes the value from the set,returning it if it was in the set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RHashSet, RNone, RSome, RString};
+    ///
+    /// let mut set = RHashSet::<RString>::new();
+    /// set.insert("foo".into());
+    ///
+    /// assert_eq!(set.take("foo"), RSome(RString::from("foo")));
+    /// assert_eq!(set.take("foo"), RNone);
+    ///
+    /// ```
+    pub fn take<Q>(&mut self, query: &Q) -> ROption<T>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.remove_entry(query).map(|Tuple2(k, ())| k)
+    }
+}
+
+impl<T, S> RHashSet<T, S> {
+    /// Returns whether the set contains the value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RHashSet;
+    ///
+    /// let mut set = RHashSet::<u32>::new();
+    /// assert_eq!(set.contains_p(&11), false);
+    /// set.insert(11);
+    /// assert_eq!(set.contains_p(&11), true);
+    ///
+    /// ```
+    pub fn contains_p(&self, value: &T) -> bool {
+        self.map.contains_key_p(value)
+    }
+
+    /// Removes the value from the set,returning whether it was in the set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RHashSet;
+    ///
+    /// let mut set = vec![0, 3].into_iter().collect::<RHashSet<u32>>();
+    ///
+    /// assert_eq!(set.remove_p(&0), true);
+    /// assert_eq!(set.remove_p(&0), false);
+    ///
+    /// ```
+    pub fn remove_p(&mut self, value: &T) -> bool {
+        self.map.remove_p(value).is_some()
+    }
+
+    /// Removes the value from the set,returning it if it was in the set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RHashSet, RNone, RSome};
+    ///
+    /// let mut set = vec![0, 3].into_iter().collect::<RHashSet<u32>>();
+    ///
+    /// assert_eq!(set.take_p(&0), RSome(0));
+    /// assert_eq!(set.take_p(&0), RNone);
+    ///
+    /// ```
+    pub fn take_p(&mut self, value: &T) -> ROption<T> {
+        self.map.remove_entry_p(value).map(|Tuple2(k, ())| k)
+    }
+
+    /// Adds the value to the set,
+    /// returning whether the value was not in the set before.
+    ///
+    /// If the value was already in the set,the set is not modified.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RHashSet;
+    ///
+    /// let mut set = RHashSet::<u32>::new();
+    ///
+    /// assert_eq!(set.insert(0), true);
+    /// assert_eq!(set.insert(0), false);
+    /// assert_eq!(set.len(), 1);
+    ///
+    /// ```
+    pub fn insert(&mut self, value: T) -> bool {
+        self.map.insert(value, ()).is_none()
+    }
+
+    /// Reserves enough space to insert `reserved` extra elements without reallocating.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RHashSet;
+    ///
+    /// let mut set = RHashSet::<u32>::new();
+    /// set.reserve(10);
+    ///
+    /// ```
+    pub fn reserve(&mut self, reserved: usize) {
+        self.map.reserve(reserved);
+    }
+
+    /// Removes all the elements in the set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RHashSet;
+    ///
+    /// let mut set = vec![0, 3].into_iter().collect::<RHashSet<u32>>();
+    ///
+    /// set.clear();
+    ///
+    /// assert_eq!(set.contains(&0), false);
+    /// assert_eq!(set.contains(&3), false);
+    ///
+    /// ```
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    /// Returns the amount of elements in the set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RHashSet;
+    ///
+    /// let mut set = RHashSet::<u32>::new();
+    /// assert_eq!(set.len(), 0);
+    /// set.insert(0);
+    /// assert_eq!(set.len(), 1);
+    ///
+    /// ```
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns the capacity of the set, the amount of elements it can store without
+    /// reallocating.
+    ///
+    /// Note that this is a lower bound, since hash sets don't necessarily have an exact capacity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RHashSet;
+    ///
+    /// let set = RHashSet::<u32>::with_capacity(4);
+    /// assert!(set.capacity() >= 4);
+    ///
+    /// ```
+    pub fn capacity(&self) -> usize {
+        self.map.capacity()
+    }
+
+    /// Returns whether the set contains any elements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RHashSet;
+    ///
+    /// let mut set = RHashSet::<u32>::new();
+    /// assert!(set.is_empty());
+    /// set.insert(0);
+    /// assert!(!set.is_empty());
+    ///
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Iterates over the elements in the set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RHashSet;
+    ///
+    /// let set = vec![0, 3].into_iter().collect::<RHashSet<u32>>();
+    ///
+    /// let mut list = set.iter().cloned().collect::<Vec<_>>();
+    /// list.sort();
+    /// assert_eq!(list, vec![0, 3]);
+    ///
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            inner: self.map.keys(),
+        }
+    }
+
+    /// Clears the set, returning an iterator over all the elements that were removed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RHashSet;
+    ///
+    /// let mut set = vec![0, 3].into_iter().collect::<RHashSet<u32>>();
+    ///
+    /// let mut list = set.drain().collect::<Vec<_>>();
+    /// list.sort();
+    /// assert_eq!(list, vec![0, 3]);
+    ///
+    /// assert!(set.is_empty());
+    ///
+    /// ```
+    pub fn drain(&mut self) -> Drain<'_, T> {
+        Drain {
+            inner: self.map.drain(),
+        }
+    }
+
+    /// Iterates over the elements that are in `self`, `other`, or both,
+    /// without duplicates.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RHashSet;
+    ///
+    /// let a = vec![0, 1, 2].into_iter().collect::<RHashSet<u32>>();
+    /// let b = vec![1, 2, 3].into_iter().collect::<RHashSet<u32>>();
+    ///
+    /// let mut list = a.union(&b).cloned().collect::<Vec<_>>();
+    /// list.sort();
+    /// assert_eq!(list, vec![0, 1, 2, 3]);
+    ///
+    /// ```
+    pub fn union<'a>(&'a self, other: &'a RHashSet<T, S>) -> Union<'a, T, S> {
+        Union {
+            inner: self.iter().chain(other.difference(self)),
+        }
+    }
+
+    /// Iterates over the elements that are in both `self` and `other`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RHashSet;
+    ///
+    /// let a = vec![0, 1, 2].into_iter().collect::<RHashSet<u32>>();
+    /// let b = vec![1, 2, 3].into_iter().collect::<RHashSet<u32>>();
+    ///
+    /// let mut list = a.intersection(&b).cloned().collect::<Vec<_>>();
+    /// list.sort();
+    /// assert_eq!(list, vec![1, 2]);
+    ///
+    /// ```
+    pub fn intersection<'a>(&'a self, other: &'a RHashSet<T, S>) -> Intersection<'a, T, S> {
+        // Iterating over the smaller set,since every element is looked up in the other one.
+        let (iter, other) = if self.len() <= other.len() {
+            (self.iter(), other)
+        } else {
+            (other.iter(), self)
+        };
+        Intersection { iter, other }
+    }
+
+    /// Iterates over the elements that are in `self` but not in `other`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RHashSet;
+    ///
+    /// let a = vec![0, 1, 2].into_iter().collect::<RHashSet<u32>>();
+    /// let b = vec![1, 2, 3].into_iter().collect::<RHashSet<u32>>();
+    ///
+    /// assert_eq!(a.difference(&b).collect::<Vec<_>>(), vec![&0]);
+    /// assert_eq!(b.difference(&a).collect::<Vec<_>>(), vec![&3]);
+    ///
+    /// ```
+    pub fn difference<'a>(&'a self, other: &'a RHashSet<T, S>) -> Difference<'a, T, S> {
+        Difference {
+            iter: self.iter(),
+            other,
+        }
+    }
+
+    /// Iterates over the elements that are in either `self` or `other`,
+    /// but not in both.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RHashSet;
+    ///
+    /// let a = vec![0, 1, 2].into_iter().collect::<RHashSet<u32>>();
+    /// let b = vec![1, 2, 3].into_iter().collect::<RHashSet<u32>>();
+    ///
+    /// let mut list = a.symmetric_difference(&b).cloned().collect::<Vec<_>>();
+    /// list.sort();
+    /// assert_eq!(list, vec![0, 3]);
+    ///
+    /// ```
+    pub fn symmetric_difference<'a>(
+        &'a self,
+        other: &'a RHashSet<T, S>,
+    ) -> SymmetricDifference<'a, T, S> {
+        SymmetricDifference {
+            inner: self.difference(other).chain(other.difference(self)),
+        }
+    }
+
+    /// Returns whether `self` and `other` have no elements in common.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RHashSet;
+    ///
+    /// let a = vec![0, 1].into_iter().collect::<RHashSet<u32>>();
+    /// let b = vec![1, 2].into_iter().collect::<RHashSet<u32>>();
+    /// let c = vec![2, 3].into_iter().collect::<RHashSet<u32>>();
+    ///
+    /// assert!(!a.is_disjoint(&b));
+    /// assert!(a.is_disjoint(&c));
+    ///
+    /// ```
+    pub fn is_disjoint(&self, other: &RHashSet<T, S>) -> bool {
+        self.intersection(other).next().is_none()
+    }
+
+    /// Returns whether all the elements in `self` are in `other`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RHashSet;
+    ///
+    /// let a = vec![0, 1].into_iter().collect::<RHashSet<u32>>();
+    /// let b = vec![0, 1, 2].into_iter().collect::<RHashSet<u32>>();
+    ///
+    /// assert!(a.is_subset(&b));
+    /// assert!(!b.is_subset(&a));
+    ///
+    /// ```
+    pub fn is_subset(&self, other: &RHashSet<T, S>) -> bool {
+        self.len() <= other.len() && self.iter().all(|v| other.contains_p(v))
+    }
+
+    /// Returns whether all the elements in `other` are in `self`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RHashSet;
+    ///
+    /// let a = vec![0, 1, 2].into_iter().collect::<RHashSet<u32>>();
+    /// let b = vec![0, 1].into_iter().collect::<RHashSet<u32>>();
+    ///
+    /// assert!(a.is_superset(&b));
+    /// assert!(!b.is_superset(&a));
+    ///
+    /// ```
+    pub fn is_superset(&self, other: &RHashSet<T, S>) -> bool {
+        other.is_subset(self)
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// An iterator over the elements of an `RHashSet`.
+///
+/// This `struct` is created by the [`iter`] method on [`RHashSet`].
+///
+/// [`iter`]: RHashSet::iter
+#[repr(C)]
+#[derive(StableAbi)]
+pub struct Iter<'a, T> {
+    inner: Keys<'a, T, ()>,
+}
+
+impl<T> Clone for Iter<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Iter {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T: Debug> Debug for Iter<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        self.inner.next()
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// An iterator over the elements of an `RHashSet`, which takes them by value.
+///
+/// This `struct` is created by the `into_iter` method on [`RHashSet`].
+#[repr(C)]
+#[derive(StableAbi)]
+pub struct IntoIter<T> {
+    inner: map::IntoIter<T, ()>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.inner.next().map(|Tuple2(k, ())| k)
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// An iterator that removes all the elements of an `RHashSet`, returning them.
+///
+/// This `struct` is created by the [`drain`] method on [`RHashSet`].
+///
+/// [`drain`]: RHashSet::drain
+#[repr(C)]
+#[derive(StableAbi)]
+pub struct Drain<'a, T: 'a> {
+    inner: map::Drain<'a, T, ()>,
+}
+
+impl<'a, T> Iterator for Drain<'a, T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.inner.next().map(|Tuple2(k, ())| k)
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// An iterator over the union of two `RHashSet`s.
+///
+/// This `struct` is created by the [`union`] method on [`RHashSet`].
+///
+/// [`union`]: RHashSet::union
+pub struct Union<'a, T, S> {
+    inner: Chain<Iter<'a, T>, Difference<'a, T, S>>,
+}
+
+impl<'a, T, S> Iterator for Union<'a, T, S> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        self.inner.next()
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// An iterator over the intersection of two `RHashSet`s.
+///
+/// This `struct` is created by the [`intersection`] method on [`RHashSet`].
+///
+/// [`intersection`]: RHashSet::intersection
+pub struct Intersection<'a, T, S> {
+    iter: Iter<'a, T>,
+    other: &'a RHashSet<T, S>,
+}
+
+impl<'a, T, S> Iterator for Intersection<'a, T, S> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let other = self.other;
+        self.iter.find(|v| other.contains_p(v))
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+/// An iterator over the difference of two `RHashSet`s.
+///
+/// This `struct` is created by the [`difference`] method on [`RHashSet`].
+///
+/// [`difference`]: RHashSet::difference
+pub struct Difference<'a, T, S> {
+    iter: Iter<'a, T>,
+    other: &'a RHashSet<T, S>,
+}
+
+impl<'a, T, S> Iterator for Difference<'a, T, S> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let other = self.other;
+        self.iter.find(|v| !other.contains_p(v))
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+/// An iterator over the symmetric difference of two `RHashSet`s.
+///
+/// This `struct` is created by the [`symmetric_difference`] method on [`RHashSet`].
+///
+/// [`symmetric_difference`]: RHashSet::symmetric_difference
+pub struct SymmetricDifference<'a, T, S> {
+    inner: Chain<Difference<'a, T, S>, Difference<'a, T, S>>,
+}
+
+impl<'a, T, S> Iterator for SymmetricDifference<'a, T, S> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        self.inner.next()
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// This returns an `Iterator<Item= T >+!Send+!Sync`
+impl<T, S> IntoIterator for RHashSet<T, S> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter {
+            inner: self.map.into_iter(),
+        }
+    }
+}
+
+/// This returns an `Iterator<Item= &T > + !Send + !Sync + Clone`
+impl<'a, T, S> IntoIterator for &'a RHashSet<T, S> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T, S> From<HashSet<T, S>> for RHashSet<T, S>
+where
+    Self: Default,
+{
+    fn from(set: HashSet<T, S>) -> Self {
+        set.into_iter().collect()
+    }
+}
+
+impl<T, S> From<RHashSet<T, S>> for HashSet<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher + Default,
+{
+    fn from(this: RHashSet<T, S>) -> HashSet<T, S> {
+        this.into_iter().collect()
+    }
+}
+
+impl<T, S> FromIterator<T> for RHashSet<T, S>
+where
+    Self: Default,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut set = Self::default();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T, S> Extend<T> for RHashSet<T, S> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl<'a, T, S> Extend<&'a T> for RHashSet<T, S>
+where
+    T: Copy + 'a,
+{
+    #[inline]
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a T>,
+    {
+        self.extend(iter.into_iter().cloned());
+    }
+}
+
+impl<T, S> Default for RHashSet<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher + Default,
+{
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<T, S> Clone for RHashSet<T, S>
+where
+    T: Clone,
+    Self: Default,
+{
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T, S> Debug for RHashSet<T, S>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<T, S> Eq for RHashSet<T, S> where T: Eq {}
+
+impl<T, S> PartialEq for RHashSet<T, S>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|v| other.contains_p(v))
+    }
+}
+
+mod serde {
+    use super::*;
+
+    use ::serde::{
+        de::{SeqAccess, Visitor},
+        ser::SerializeSeq,
+        Deserialize, Deserializer, Serialize, Serializer,
+    };
+
+    struct RHashSetVisitor<T, S> {
+        _marker: NonOwningPhantom<RHashSet<T, S>>,
+    }
+
+    impl<T, S> RHashSetVisitor<T, S> {
+        fn new() -> Self {
+            RHashSetVisitor {
+                _marker: NonOwningPhantom::NEW,
+            }
+        }
+    }
+
+    impl<'de, T, S> Visitor<'de> for RHashSetVisitor<T, S>
+    where
+        T: Deserialize<'de>,
+        RHashSet<T, S>: Default,
+    {
+        type Value = RHashSet<T, S>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("an RHashSet")
+        }
+
+        fn visit_seq<A>(self, mut seq_access: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let capacity = seq_access.size_hint().unwrap_or(0);
+            let mut set = RHashSet::default();
+            set.reserve(capacity);
+
+            while let Some(value) = seq_access.next_element()? {
+                set.insert(value);
+            }
+
+            Ok(set)
+        }
+    }
+
+    impl<'de, T, S> Deserialize<'de> for RHashSet<T, S>
+    where
+        T: Deserialize<'de>,
+        Self: Default,
+    {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_seq(RHashSetVisitor::new())
+        }
+    }
+
+    impl<T, S> Serialize for RHashSet<T, S>
+    where
+        T: Serialize,
+    {
+        fn serialize<Z>(&self, serializer: Z) -> Result<Z::Ok, Z::Error>
+        where
+            Z: Serializer,
+        {
+            let mut seq = serializer.serialize_seq(Some(self.len()))?;
+            for value in self.iter() {
+                seq.serialize_element(value)?;
+            }
+            seq.end()
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use super::*;
+
+use fnv::FnvBuildHasher as FnVBH;
+
+use crate::std_types::{RNone, RSome, RString};
+
+fn new_stdset() -> HashSet<u32> {
+    vec![90, 10, 88, 77].into_iter().collect()
+}
+
+fn sorted<'a, I>(iter: I) -> Vec<u32>
+where
+    I: IntoIterator<Item = &'a u32>,
+{
+    let mut list = iter.into_iter().cloned().collect::<Vec<u32>>();
+    list.sort_unstable();
+    list
+}
+
+#[test]
+fn test_new_set() {
+    let mut set = RHashSet::new();
+    set.insert(10);
+    assert!(set.contains(&10));
+}
+
+#[test]
+fn test_default() {
+    let default_ = RHashSet::<u32>::default();
+    let new_ = RHashSet::<u32>::new();
+
+    assert_eq!(default_.len(), 0);
+    assert_eq!(default_.capacity(), 0);
+
+    assert_eq!(default_, new_);
+}
+
+#[test]
+fn with_hasher() {
+    let mut set = RHashSet::<RString, FnVBH>::with_capacity_and_hasher(10, FnVBH::default());
+    assert!(set.capacity() >= 10);
+
+    set.insert("foo".into());
+    set.insert("bar".into());
+    assert!(set.contains("foo"));
+    assert!(set.contains("bar"));
+    assert!(!set.contains("baz"));
+}
+
+#[test]
+fn insert_remove() {
+    let mut set = RHashSet::<RString>::new();
+
+    assert!(set.insert("hello".into()));
+    assert!(set.insert("world".into()));
+    assert!(!set.insert("hello".into()));
+    assert_eq!(set.len(), 2);
+
+    assert!(set.remove("hello"));
+    assert!(!set.remove("hello"));
+    assert!(!set.contains("hello"));
+
+    assert_eq!(set.take("world"), RSome(RString::from("world")));
+    assert_eq!(set.take("world"), RNone);
+    assert!(set.is_empty());
+
+    set.insert("what".into());
+    assert!(set.contains_p(&RString::from("what")));
+    assert!(set.remove_p(&RString::from("what")));
+    assert!(!set.contains_p(&RString::from("what")));
+}
+
+#[test]
+fn clear() {
+    let mut set = new_stdset().into_iter().collect::<RHashSet<u32>>();
+    assert_eq!(set.len(), 4);
+
+    set.clear();
+
+    assert!(set.is_empty());
+    assert!(!set.contains(&90));
+}
+
+#[test]
+fn from_hashset() {
+    let mut stdset = new_stdset();
+
+    let mut set: RHashSet<u32> = stdset.clone().into();
+
+    assert_eq!(set.len(), 4);
+
+    for value in set.drain() {
+        assert!(stdset.remove(&value), "value:{:?}", value);
+    }
+    assert_eq!(stdset.len(), 0);
+
+    assert!(set.is_empty(), "set length:{:?}", set.len());
+}
+
+#[test]
+fn into_hashset() {
+    let stdset = new_stdset();
+
+    let set: RHashSet<u32> = stdset.clone().into();
+
+    let stdset2: HashSet<_> = set.into();
+
+    assert_eq!(stdset2, stdset);
+}
+
+#[test]
+fn into_iter() {
+    let set = new_stdset().into_iter().collect::<RHashSet<u32>>();
+
+    let mut list = set.into_iter().collect::<Vec<u32>>();
+    list.sort_unstable();
+
+    assert_eq!(list, vec![10, 77, 88, 90]);
+}
+
+#[test]
+fn extend() {
+    let mut set = RHashSet::<u32>::new();
+
+    set.extend(vec![0, 1, 2]);
+    set.extend(&[2, 3]);
+
+    assert_eq!(sorted(&set), vec![0, 1, 2, 3]);
+}
+
+#[test]
+fn set_operations() {
+    let a = vec![0, 1, 2, 3].into_iter().collect::<RHashSet<u32>>();
+    let b = vec![2, 3, 4, 5].into_iter().collect::<RHashSet<u32>>();
+    let empty = RHashSet::<u32>::new();
+
+    assert_eq!(sorted(a.union(&b)), vec![0, 1, 2, 3, 4, 5]);
+    assert_eq!(sorted(a.intersection(&b)), vec![2, 3]);
+    assert_eq!(sorted(b.intersection(&a)), vec![2, 3]);
+    assert_eq!(sorted(a.difference(&b)), vec![0, 1]);
+    assert_eq!(sorted(b.difference(&a)), vec![4, 5]);
+    assert_eq!(sorted(a.symmetric_difference(&b)), vec![0, 1, 4, 5]);
+
+    assert_eq!(sorted(a.union(&empty)), vec![0, 1, 2, 3]);
+    assert_eq!(sorted(a.intersection(&empty)), Vec::<u32>::new());
+    assert_eq!(sorted(a.difference(&empty)), vec![0, 1, 2, 3]);
+}
+
+#[test]
+fn set_relations() {
+    let a = vec![0, 1, 2, 3].into_iter().collect::<RHashSet<u32>>();
+    let b = vec![1, 2].into_iter().collect::<RHashSet<u32>>();
+    let c = vec![4, 5].into_iter().collect::<RHashSet<u32>>();
+
+    assert!(b.is_subset(&a));
+    assert!(!a.is_subset(&b));
+    assert!(a.is_superset(&b));
+    assert!(!b.is_superset(&a));
+    assert!(a.is_subset(&a));
+
+    assert!(a.is_disjoint(&c));
+    assert!(!a.is_disjoint(&b));
+}
+
+#[test]
+fn test_serde() {
+    let set = vec!["90", "10", "88"]
+        .into_iter()
+        .map(RString::from)
+        .collect::<RHashSet<RString>>();
+
+    let json = r##" ["90", "10", "88"] "##;
+
+    let deserialized = serde_json::from_str::<RHashSet<RString>>(json).unwrap();
+
+    assert_eq!(deserialized, set);
+
+    let serialized = serde_json::to_string(&set).unwrap();
+
+    assert!(serialized.starts_with('['), "text:{}", serialized);
+    assert!(serialized.contains(r##""90""##), "text:{}", serialized);
+    assert!(serialized.contains(r##""10""##), "text:{}", serialized);
+    assert!(serialized.contains(r##""88""##), "text:{}", serialized);
+    assert!(serialized.ends_with(']'), "text:{}", serialized);
+
+    let redeserialized = serde_json::from_str::<RHashSet<RString>>(&serialized).unwrap();
+
+    assert_eq!(redeserialized, set);
+}
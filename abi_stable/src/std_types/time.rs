@@ -1,7 +1,15 @@
-//! Contains ffi-safe equivalent of `std::time::Duration`.
+//! Contains ffi-safe equivalents of `std::time::{Duration, Instant, SystemTime}`.
 
 use std::time::Duration;
 
+mod instant;
+mod system_time;
+
+pub use self::{instant::RInstant, system_time::RSystemTime};
+
+#[cfg(all(test, not(feature = "only_new_tests")))]
+mod test;
+
 /// Ffi-safe equivalent of `std::time::Duration` .
 ///
 /// # Example
@@ -0,0 +1,325 @@
+use std::{
+    ops::{Add, AddAssign, Sub, SubAssign},
+    time::{Duration, Instant},
+};
+
+use super::RDuration;
+
+/// Ffi-safe equivalent of `std::time::Instant`,
+/// a measurement of a monotonically nondecreasing clock.
+///
+/// This is stored as the amount of time since the origin of the clock.
+///
+/// # Clock
+///
+/// On Unix platforms (including Linux and macOS) this uses `CLOCK_MONOTONIC`,
+/// and on Windows this uses `QueryPerformanceCounter`.
+/// The origin of both clocks is fixed for the whole system (usually at boot),
+/// so it's the same for every library in the process,
+/// and RInstants created in different dynamic libraries can be compared,
+/// and subtracted from each other.
+///
+/// On every other platform (where abi_stable can't load dynamic libraries),
+/// the origin is the first time that the clock is read.
+///
+/// # Example
+///
+/// ```
+/// use abi_stable::std_types::{RDuration, RInstant};
+///
+/// let start = RInstant::now();
+/// let deadline = start + RDuration::from_secs(10);
+///
+/// assert!(start < deadline);
+/// assert_eq!(deadline - start, RDuration::from_secs(10));
+/// assert_eq!(deadline.duration_since(start), RDuration::from_secs(10));
+/// assert!(start.elapsed() < RDuration::from_secs(10));
+///
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Ord, PartialOrd, Hash, StableAbi)]
+#[repr(C)]
+pub struct RInstant {
+    pub(super) since_origin: RDuration,
+}
+
+impl RInstant {
+    /// Returns the current time of the monotonic clock.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RInstant;
+    ///
+    /// let first = RInstant::now();
+    /// let second = RInstant::now();
+    ///
+    /// assert!(first <= second);
+    ///
+    /// ```
+    pub fn now() -> Self {
+        Self {
+            since_origin: clock::now().into(),
+        }
+    }
+
+    /// Returns the amount of time elapsed from `earlier` to `self`,
+    /// or zero if `earlier` is later than `self`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RDuration, RInstant};
+    ///
+    /// let earlier = RInstant::now();
+    /// let later = earlier + RDuration::from_millis(1500);
+    ///
+    /// assert_eq!(later.duration_since(earlier), RDuration::from_millis(1500));
+    /// assert_eq!(earlier.duration_since(later), RDuration::from_secs(0));
+    ///
+    /// ```
+    pub fn duration_since(&self, earlier: RInstant) -> RDuration {
+        self.checked_duration_since(earlier)
+            .unwrap_or_else(|| RDuration::from_secs(0))
+    }
+
+    /// Returns the amount of time elapsed from `earlier` to `self`,
+    /// or `None` if `earlier` is later than `self`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RDuration, RInstant};
+    ///
+    /// let earlier = RInstant::now();
+    /// let later = earlier + RDuration::from_millis(1500);
+    ///
+    /// assert_eq!(later.checked_duration_since(earlier), Some(RDuration::from_millis(1500)));
+    /// assert_eq!(earlier.checked_duration_since(later), None);
+    ///
+    /// ```
+    pub fn checked_duration_since(&self, earlier: RInstant) -> Option<RDuration> {
+        Duration::from(self.since_origin)
+            .checked_sub(earlier.since_origin.into())
+            .map(RDuration::from)
+    }
+
+    /// Returns the amount of time elapsed since `self`,
+    /// or zero if `self` is later than the current time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RDuration, RInstant};
+    ///
+    /// let start = RInstant::now();
+    ///
+    /// assert!(start.elapsed() < RDuration::from_secs(60));
+    ///
+    /// ```
+    pub fn elapsed(&self) -> RDuration {
+        RInstant::now().duration_since(*self)
+    }
+
+    /// Returns `self + duration`,
+    /// or `None` if the result doesn't fit in an RInstant.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RDuration, RInstant};
+    ///
+    /// let start = RInstant::now();
+    ///
+    /// let later = start.checked_add(RDuration::from_secs(1)).unwrap();
+    /// assert_eq!(later - start, RDuration::from_secs(1));
+    ///
+    /// assert_eq!(start.checked_add(RDuration::from_secs(u64::MAX)), None);
+    ///
+    /// ```
+    pub fn checked_add(&self, duration: RDuration) -> Option<RInstant> {
+        Duration::from(self.since_origin)
+            .checked_add(duration.into())
+            .map(|since_origin| RInstant {
+                since_origin: since_origin.into(),
+            })
+    }
+
+    /// Returns `self - duration`,
+    /// or `None` if the result would be before the origin of the clock.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RDuration, RInstant};
+    ///
+    /// let start = RInstant::now();
+    /// let later = start + RDuration::from_secs(1);
+    ///
+    /// assert_eq!(later.checked_sub(RDuration::from_secs(1)), Some(start));
+    ///
+    /// assert_eq!(start.checked_sub(RDuration::from_secs(u64::MAX)), None);
+    ///
+    /// ```
+    pub fn checked_sub(&self, duration: RDuration) -> Option<RInstant> {
+        Duration::from(self.since_origin)
+            .checked_sub(duration.into())
+            .map(|since_origin| RInstant {
+                since_origin: since_origin.into(),
+            })
+    }
+}
+
+impl Add<RDuration> for RInstant {
+    type Output = RInstant;
+
+    /// # Panics
+    ///
+    /// Panics if the result doesn't fit in an RInstant.
+    fn add(self, duration: RDuration) -> RInstant {
+        self.checked_add(duration)
+            .expect("overflow when adding duration to RInstant")
+    }
+}
+
+impl AddAssign<RDuration> for RInstant {
+    fn add_assign(&mut self, duration: RDuration) {
+        *self = *self + duration;
+    }
+}
+
+impl Sub<RDuration> for RInstant {
+    type Output = RInstant;
+
+    /// # Panics
+    ///
+    /// Panics if the result would be before the origin of the clock.
+    fn sub(self, duration: RDuration) -> RInstant {
+        self.checked_sub(duration)
+            .expect("overflow when subtracting duration from RInstant")
+    }
+}
+
+impl SubAssign<RDuration> for RInstant {
+    fn sub_assign(&mut self, duration: RDuration) {
+        *self = *self - duration;
+    }
+}
+
+impl Sub<RInstant> for RInstant {
+    type Output = RDuration;
+
+    /// Equivalent to `self.duration_since(other)`.
+    fn sub(self, other: RInstant) -> RDuration {
+        self.duration_since(other)
+    }
+}
+
+impl_from_rust_repr! {
+    /// On Unix and Windows the conversion is approximate,
+    /// since the monotonic clock is read at a slightly different time than `Instant`.
+    impl From<Instant> for RInstant {
+        fn(v){
+            RInstant {
+                since_origin: clock::from_instant(v).into(),
+            }
+        }
+    }
+}
+
+impl_into_rust_repr! {
+    /// On Unix and Windows the conversion is approximate,
+    /// since the monotonic clock is read at a slightly different time than `Instant`.
+    impl Into<Instant> for RInstant {
+        fn(this){
+            clock::to_instant(this.since_origin.into())
+        }
+    }
+}
+
+/// Reads the monotonic clock and converts from/to `Instant`,
+/// with all durations being relative to the origin of the clock.
+#[cfg(any(unix, windows))]
+mod clock {
+    use std::time::{Duration, Instant};
+
+    #[cfg(unix)]
+    pub(super) fn now() -> Duration {
+        use std::mem::MaybeUninit;
+
+        let mut time = MaybeUninit::<libc::timespec>::uninit();
+        // Safety: `time` is only read after `clock_gettime` initializes it.
+        let time = unsafe {
+            let ret = libc::clock_gettime(libc::CLOCK_MONOTONIC, time.as_mut_ptr());
+            assert_eq!(ret, 0, "clock_gettime(CLOCK_MONOTONIC) failed");
+            time.assume_init()
+        };
+        Duration::new(time.tv_sec as u64, time.tv_nsec as u32)
+    }
+
+    #[cfg(windows)]
+    pub(super) fn now() -> Duration {
+        #[link(name = "kernel32")]
+        extern "system" {
+            fn QueryPerformanceCounter(count: *mut i64) -> i32;
+            fn QueryPerformanceFrequency(frequency: *mut i64) -> i32;
+        }
+
+        let mut ticks = 0i64;
+        let mut frequency = 0i64;
+        // Safety: both functions only write to the passed-in pointer,
+        // and never fail on Windows XP or later.
+        unsafe {
+            assert_ne!(QueryPerformanceCounter(&mut ticks), 0);
+            assert_ne!(QueryPerformanceFrequency(&mut frequency), 0);
+        }
+        let (ticks, frequency) = (ticks as u128, frequency as u128);
+        let nanos = ticks % frequency * 1_000_000_000 / frequency;
+        Duration::new((ticks / frequency) as u64, nanos as u32)
+    }
+
+    pub(super) fn from_instant(instant: Instant) -> Duration {
+        let std_now = Instant::now();
+        let now = now();
+        match instant.checked_duration_since(std_now) {
+            Some(after) => now + after,
+            None => now.checked_sub(std_now - instant).unwrap_or_default(),
+        }
+    }
+
+    pub(super) fn to_instant(since_origin: Duration) -> Instant {
+        let std_now = Instant::now();
+        let now = now();
+        match since_origin.checked_sub(now) {
+            Some(after) => std_now + after,
+            None => std_now - (now - since_origin),
+        }
+    }
+}
+
+/// Platforms that abi_stable can't load dynamic libraries in,
+/// so the origin only has to be shared within this copy of abi_stable.
+#[cfg(not(any(unix, windows)))]
+mod clock {
+    use std::time::{Duration, Instant};
+
+    use crate::{sabi_types::LateStaticRef, utils::leak_value};
+
+    static ORIGIN: LateStaticRef<&Instant> = LateStaticRef::new();
+
+    fn origin() -> Instant {
+        *ORIGIN.init(|| leak_value(Instant::now()))
+    }
+
+    pub(super) fn now() -> Duration {
+        origin().elapsed()
+    }
+
+    /// Instants before the origin are clamped to it.
+    pub(super) fn from_instant(instant: Instant) -> Duration {
+        instant.saturating_duration_since(origin())
+    }
+
+    pub(super) fn to_instant(since_origin: Duration) -> Instant {
+        origin() + since_origin
+    }
+}
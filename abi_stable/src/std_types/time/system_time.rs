@@ -0,0 +1,348 @@
+use std::{
+    convert::TryFrom,
+    ops::{Add, AddAssign, Sub, SubAssign},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{de::Error as _, Deserialize, Deserializer};
+
+use super::RDuration;
+
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+
+/// Ffi-safe equivalent of `std::time::SystemTime`,
+/// stored as the signed amount of time since the UNIX epoch
+/// (1970-01-01 00:00:00 UTC).
+///
+/// Because the representation doesn't depend on the platform,
+/// this can be used to pass timestamps between dynamic libraries
+/// (and serialize them) without any ad-hoc conventions.
+///
+/// # Example
+///
+/// ```
+/// use abi_stable::std_types::{RDuration, RSystemTime};
+///
+/// let time = RSystemTime::UNIX_EPOCH + RDuration::from_secs(86_400);
+/// assert_eq!(time.unix_seconds(), 86_400);
+/// assert_eq!(time.subsec_nanos(), 0);
+///
+/// let before = time - RDuration::from_millis(86_401_500);
+/// assert_eq!(before.unix_seconds(), -2);
+/// assert_eq!(before.subsec_nanos(), 500_000_000);
+///
+/// assert_eq!(time.duration_since(before), Ok(RDuration::from_millis(86_401_500)));
+/// assert_eq!(before.duration_since(time), Err(RDuration::from_millis(86_401_500)));
+///
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Ord, PartialOrd, Hash, Serialize, StableAbi)]
+#[repr(C)]
+pub struct RSystemTime {
+    /// Seconds since the UNIX epoch, rounded towards negative infinity.
+    seconds: i64,
+    /// Always less than `NANOS_PER_SEC`, counting forward from `seconds`.
+    subsec_nanos: u32,
+}
+
+impl RSystemTime {
+    /// The UNIX epoch, 1970-01-01 00:00:00 UTC.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RSystemTime;
+    ///
+    /// use std::time::{SystemTime, UNIX_EPOCH};
+    ///
+    /// assert_eq!(SystemTime::from(RSystemTime::UNIX_EPOCH), UNIX_EPOCH);
+    ///
+    /// ```
+    pub const UNIX_EPOCH: RSystemTime = RSystemTime {
+        seconds: 0,
+        subsec_nanos: 0,
+    };
+
+    /// Constructs an RSystemTime from the seconds since the UNIX epoch,
+    /// and the nanoseconds after that second.
+    ///
+    /// Negative `seconds` are before the UNIX epoch,
+    /// `subsec_nanos` always moves the time forward.
+    ///
+    /// Nanoseconds above a second are carried over into `seconds`,
+    /// saturating to the latest representable time if that overflows.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RDuration, RSystemTime};
+    ///
+    /// let time = RSystemTime::new(-1, 250_000_000);
+    /// assert_eq!(time, RSystemTime::UNIX_EPOCH - RDuration::from_millis(750));
+    ///
+    /// assert_eq!(RSystemTime::new(1, 1_500_000_000), RSystemTime::new(2, 500_000_000));
+    ///
+    /// assert_eq!(
+    ///     RSystemTime::new(i64::MAX, 1_500_000_000),
+    ///     RSystemTime::new(i64::MAX, 999_999_999),
+    /// );
+    ///
+    /// ```
+    pub const fn new(seconds: i64, subsec_nanos: u32) -> Self {
+        let carried = (subsec_nanos / NANOS_PER_SEC) as i64;
+        if seconds > i64::MAX - carried {
+            return Self {
+                seconds: i64::MAX,
+                subsec_nanos: NANOS_PER_SEC - 1,
+            };
+        }
+        Self {
+            seconds: seconds + carried,
+            subsec_nanos: subsec_nanos % NANOS_PER_SEC,
+        }
+    }
+
+    /// Returns the current time, as reported by the system clock.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RSystemTime;
+    ///
+    /// assert!(RSystemTime::now() > RSystemTime::UNIX_EPOCH);
+    ///
+    /// ```
+    pub fn now() -> Self {
+        SystemTime::now().into()
+    }
+
+    /// The seconds since the UNIX epoch, rounded towards negative infinity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RSystemTime;
+    ///
+    /// assert_eq!(RSystemTime::new(1_600_000_000, 5).unix_seconds(), 1_600_000_000);
+    /// assert_eq!(RSystemTime::new(-3, 5).unix_seconds(), -3);
+    ///
+    /// ```
+    pub const fn unix_seconds(&self) -> i64 {
+        self.seconds
+    }
+
+    /// The nanoseconds after `self.unix_seconds()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RSystemTime;
+    ///
+    /// assert_eq!(RSystemTime::new(1_600_000_000, 5).subsec_nanos(), 5);
+    /// assert_eq!(RSystemTime::new(-3, 5).subsec_nanos(), 5);
+    ///
+    /// ```
+    pub const fn subsec_nanos(&self) -> u32 {
+        self.subsec_nanos
+    }
+
+    /// Returns the amount of time elapsed from `earlier` to `self`.
+    ///
+    /// # Errors
+    ///
+    /// If `earlier` is later than `self`,
+    /// this returns how much later it is in the `Err` variant.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RDuration, RSystemTime};
+    ///
+    /// let earlier = RSystemTime::new(10, 0);
+    /// let later = RSystemTime::new(12, 500);
+    ///
+    /// assert_eq!(later.duration_since(earlier), Ok(RDuration::new(2, 500)));
+    /// assert_eq!(earlier.duration_since(later), Err(RDuration::new(2, 500)));
+    ///
+    /// ```
+    pub fn duration_since(&self, earlier: RSystemTime) -> Result<RDuration, RDuration> {
+        let diff = self.as_nanos() - earlier.as_nanos();
+        if diff >= 0 {
+            Ok(duration_from_nanos(diff as u128))
+        } else {
+            Err(duration_from_nanos((-diff) as u128))
+        }
+    }
+
+    /// Returns the amount of time elapsed since `self`.
+    ///
+    /// # Errors
+    ///
+    /// If `self` is later than the current time,
+    /// this returns how much later it is in the `Err` variant.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RDuration, RSystemTime};
+    ///
+    /// let time = RSystemTime::now() - RDuration::from_secs(60);
+    /// assert!(time.elapsed().unwrap() >= RDuration::from_secs(60));
+    ///
+    /// ```
+    pub fn elapsed(&self) -> Result<RDuration, RDuration> {
+        RSystemTime::now().duration_since(*self)
+    }
+
+    /// Returns `self + duration`,
+    /// or `None` if the result doesn't fit in an RSystemTime.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RDuration, RSystemTime};
+    ///
+    /// let time = RSystemTime::new(10, 0);
+    ///
+    /// assert_eq!(time.checked_add(RDuration::new(1, 5)), Some(RSystemTime::new(11, 5)));
+    /// assert_eq!(time.checked_add(RDuration::from_secs(u64::MAX)), None);
+    ///
+    /// ```
+    pub fn checked_add(&self, duration: RDuration) -> Option<RSystemTime> {
+        self.as_nanos()
+            .checked_add(duration.as_nanos() as i128)
+            .and_then(Self::from_nanos)
+    }
+
+    /// Returns `self - duration`,
+    /// or `None` if the result doesn't fit in an RSystemTime.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RDuration, RSystemTime};
+    ///
+    /// let time = RSystemTime::new(10, 0);
+    ///
+    /// assert_eq!(time.checked_sub(RDuration::new(1, 5)), Some(RSystemTime::new(8, 999_999_995)));
+    /// assert_eq!(time.checked_sub(RDuration::from_secs(u64::MAX)), None);
+    ///
+    /// ```
+    pub fn checked_sub(&self, duration: RDuration) -> Option<RSystemTime> {
+        self.as_nanos()
+            .checked_sub(duration.as_nanos() as i128)
+            .and_then(Self::from_nanos)
+    }
+
+    fn as_nanos(&self) -> i128 {
+        self.seconds as i128 * NANOS_PER_SEC as i128 + self.subsec_nanos as i128
+    }
+
+    fn from_nanos(nanos: i128) -> Option<Self> {
+        let seconds = i64::try_from(nanos.div_euclid(NANOS_PER_SEC as i128)).ok()?;
+        Some(Self {
+            seconds,
+            subsec_nanos: nanos.rem_euclid(NANOS_PER_SEC as i128) as u32,
+        })
+    }
+}
+
+fn duration_from_nanos(nanos: u128) -> RDuration {
+    RDuration::new(
+        (nanos / NANOS_PER_SEC as u128) as u64,
+        (nanos % NANOS_PER_SEC as u128) as u32,
+    )
+}
+
+impl Add<RDuration> for RSystemTime {
+    type Output = RSystemTime;
+
+    /// # Panics
+    ///
+    /// Panics if the result doesn't fit in an RSystemTime.
+    fn add(self, duration: RDuration) -> RSystemTime {
+        self.checked_add(duration)
+            .expect("overflow when adding duration to RSystemTime")
+    }
+}
+
+impl AddAssign<RDuration> for RSystemTime {
+    fn add_assign(&mut self, duration: RDuration) {
+        *self = *self + duration;
+    }
+}
+
+impl Sub<RDuration> for RSystemTime {
+    type Output = RSystemTime;
+
+    /// # Panics
+    ///
+    /// Panics if the result doesn't fit in an RSystemTime.
+    fn sub(self, duration: RDuration) -> RSystemTime {
+        self.checked_sub(duration)
+            .expect("overflow when subtracting duration from RSystemTime")
+    }
+}
+
+impl SubAssign<RDuration> for RSystemTime {
+    fn sub_assign(&mut self, duration: RDuration) {
+        *self = *self - duration;
+    }
+}
+
+/// Rejects `subsec_nanos` that aren't less than a second,
+/// which the serialized form of an RSystemTime never has.
+impl<'de> Deserialize<'de> for RSystemTime {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(rename = "RSystemTime")]
+        struct Proxy {
+            seconds: i64,
+            subsec_nanos: u32,
+        }
+
+        let Proxy {
+            seconds,
+            subsec_nanos,
+        } = Proxy::deserialize(deserializer)?;
+
+        if subsec_nanos >= NANOS_PER_SEC {
+            return Err(D::Error::custom(format_args!(
+                "subsec_nanos must be less than {}, found {}",
+                NANOS_PER_SEC, subsec_nanos,
+            )));
+        }
+
+        Ok(RSystemTime {
+            seconds,
+            subsec_nanos,
+        })
+    }
+}
+
+impl_from_rust_repr! {
+    impl From<SystemTime> for RSystemTime {
+        fn(v){
+            match v.duration_since(UNIX_EPOCH) {
+                Ok(after) => RSystemTime::UNIX_EPOCH + RDuration::from(after),
+                Err(e) => RSystemTime::UNIX_EPOCH - RDuration::from(e.duration()),
+            }
+        }
+    }
+}
+
+impl_into_rust_repr! {
+    /// # Panics
+    ///
+    /// Panics if the time can't be represented by the platform's `SystemTime`.
+    impl Into<SystemTime> for RSystemTime {
+        fn(this){
+            match this.duration_since(RSystemTime::UNIX_EPOCH) {
+                Ok(after) => UNIX_EPOCH + Duration::from(after),
+                Err(before) => UNIX_EPOCH - Duration::from(before),
+            }
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use super::*;
+
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+#[test]
+fn system_time_conversions() {
+    let list = vec![
+        UNIX_EPOCH,
+        UNIX_EPOCH + Duration::new(1_600_000_000, 123_456_789),
+        UNIX_EPOCH - Duration::new(1, 500_000_000),
+        UNIX_EPOCH - Duration::new(0, 1),
+        SystemTime::now(),
+    ];
+
+    for time in list {
+        let rtime = RSystemTime::from(time);
+        assert_eq!(SystemTime::from(rtime), time);
+    }
+
+    let before = RSystemTime::from(UNIX_EPOCH - Duration::new(1, 500_000_000));
+    assert_eq!(before.unix_seconds(), -2);
+    assert_eq!(before.subsec_nanos(), 500_000_000);
+
+    let before = RSystemTime::from(UNIX_EPOCH - Duration::new(0, 1));
+    assert_eq!(before.unix_seconds(), -1);
+    assert_eq!(before.subsec_nanos(), 999_999_999);
+}
+
+#[test]
+fn system_time_arithmetic() {
+    let mut time = RSystemTime::new(-1, 400_000_000);
+
+    time += RDuration::from_millis(1_700);
+    assert_eq!(time, RSystemTime::new(1, 100_000_000));
+
+    time -= RDuration::from_millis(300);
+    assert_eq!(time, RSystemTime::new(0, 800_000_000));
+
+    assert_eq!(
+        time.duration_since(RSystemTime::new(-5, 0)),
+        Ok(RDuration::new(5, 800_000_000))
+    );
+    assert_eq!(
+        RSystemTime::new(-5, 0).duration_since(time),
+        Err(RDuration::new(5, 800_000_000))
+    );
+    assert_eq!(time.duration_since(time), Ok(RDuration::from_secs(0)));
+
+    let max = RSystemTime::new(i64::MAX, 999_999_999);
+    assert_eq!(RSystemTime::new(i64::MAX, u32::MAX), max);
+    assert_eq!(
+        RSystemTime::new(i64::MAX - 1, 1_000_000_000),
+        RSystemTime::new(i64::MAX, 0)
+    );
+    let min = RSystemTime::new(i64::MIN, 0);
+    assert_eq!(max.checked_add(RDuration::new(0, 1)), None);
+    assert_eq!(min.checked_sub(RDuration::new(0, 1)), None);
+    assert_eq!(
+        max.duration_since(min),
+        Ok(RDuration::new(u64::MAX, 999_999_999))
+    );
+    assert_eq!(
+        min.checked_add(RDuration::new(u64::MAX, 999_999_999)),
+        Some(max)
+    );
+
+    assert!(min < time);
+    assert!(RSystemTime::new(0, 1) < RSystemTime::new(0, 2));
+    assert!(RSystemTime::new(-1, 999_999_999) < RSystemTime::UNIX_EPOCH);
+}
+
+#[test]
+fn system_time_serde() {
+    for &time in &[
+        RSystemTime::UNIX_EPOCH,
+        RSystemTime::new(-3, 5),
+        RSystemTime::now(),
+    ] {
+        let json = serde_json::to_string(&time).unwrap();
+        assert_eq!(serde_json::from_str::<RSystemTime>(&json).unwrap(), time);
+    }
+
+    assert_eq!(
+        serde_json::to_string(&RSystemTime::new(-3, 5)).unwrap(),
+        r##"{"seconds":-3,"subsec_nanos":5}"##,
+    );
+
+    assert_eq!(
+        serde_json::from_str::<RSystemTime>(r##"{"seconds":-3,"subsec_nanos":999999999}"##)
+            .unwrap(),
+        RSystemTime::new(-3, 999_999_999),
+    );
+    let err = serde_json::from_str::<RSystemTime>(r##"{"seconds":-3,"subsec_nanos":1000000000}"##)
+        .unwrap_err();
+    assert!(err.to_string().contains("subsec_nanos"), "{}", err);
+}
+
+#[test]
+fn instant_arithmetic() {
+    let start = RInstant::now();
+    let mut time = start;
+
+    time += RDuration::from_millis(1_500);
+    assert_eq!(time - start, RDuration::from_millis(1_500));
+    assert_eq!(start - time, RDuration::from_secs(0));
+    assert_eq!(start.checked_duration_since(time), None);
+
+    time -= RDuration::from_millis(500);
+    assert_eq!(time.duration_since(start), RDuration::from_secs(1));
+    assert_eq!(time - RDuration::from_secs(1), start);
+
+    assert!(start < time);
+    assert!(start.elapsed() < RDuration::from_secs(60));
+    assert_eq!(start.checked_add(RDuration::from_secs(u64::MAX)), None);
+    assert_eq!(start.checked_sub(RDuration::from_secs(u64::MAX)), None);
+}
+
+#[test]
+fn instant_conversions() {
+    let tolerance = Duration::from_millis(100);
+
+    let std_start = Instant::now();
+    let start = RInstant::now();
+
+    let converted = RInstant::from(std_start);
+    assert!(converted.duration_since(start) < tolerance.into());
+    assert!(start.duration_since(converted) < tolerance.into());
+
+    let later = std_start + Duration::from_secs(5);
+    let converted: Instant = RInstant::from(later).into();
+    assert!(converted.saturating_duration_since(later) < tolerance);
+    assert!(later.saturating_duration_since(converted) < tolerance);
+
+    let std_end = Instant::now();
+    let end = RInstant::now();
+    let std_elapsed = std_end - std_start;
+    let elapsed = Duration::from(end - start);
+    assert!(std_elapsed <= elapsed + tolerance);
+    assert!(elapsed <= std_elapsed + tolerance);
+}
+
+#[cfg(unix)]
+#[test]
+fn instant_is_clock_monotonic() {
+    let read = || {
+        let mut time: libc::timespec = unsafe { std::mem::zeroed() };
+        assert_eq!(
+            unsafe { libc::clock_gettime(libc::CLOCK_MONOTONIC, &mut time) },
+            0
+        );
+        RDuration::new(time.tv_sec as u64, time.tv_nsec as u32)
+    };
+
+    let before = read();
+    let now = RInstant::now();
+    let after = read();
+
+    assert!(before <= now.since_origin);
+    assert!(now.since_origin <= after);
+}
@@ -0,0 +1,1210 @@
+//! Contains an ffi-safe equivalent of `std::collections::VecDeque`.
+
+use std::{
+    cmp::Ordering,
+    collections::VecDeque,
+    fmt::{self, Debug},
+    hash::{Hash, Hasher},
+    iter::FromIterator,
+    marker::PhantomData,
+    mem::{self, ManuallyDrop, MaybeUninit},
+    ops::{Bound, Index, IndexMut, RangeBounds},
+    ptr, slice,
+};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use core_extensions::SelfOps;
+
+use crate::{
+    prefix_type::{PrefixTypeTrait, WithMetadata},
+    sabi_types::Constructor,
+    std_types::{
+        utypeid::{new_utypeid, UTypeId},
+        vec::Exactness,
+    },
+};
+
+#[cfg(all(test, not(feature = "only_new_tests")))]
+mod test;
+
+mod iters;
+
+pub use self::iters::{Drain, IntoIter, Iter, IterMut};
+
+/// Ffi-safe equivalent of `std::collections::VecDeque`,
+/// a double-ended queue implemented with a growable ring buffer.
+///
+/// Like `RVec`, the buffer is allocated and deallocated through a vtable
+/// owned by the dynamic library/binary that created the `RVecDeque`,
+/// so it can be freely passed across the ffi boundary and mutated on either side.
+///
+/// # Example
+///
+/// Here is a function that keeps only the last `max` events of a queue.
+///
+/// ```
+/// use abi_stable::{sabi_extern_fn, std_types::RVecDeque};
+///
+/// #[sabi_extern_fn]
+/// pub fn push_event(queue: &mut RVecDeque<u32>, event: u32, max: usize) {
+///     while max <= queue.len() {
+///         queue.pop_front();
+///     }
+///     queue.push_back(event);
+/// }
+///
+/// let mut queue = RVecDeque::new();
+///
+/// for event in 0..10 {
+///     push_event(&mut queue, event, 3);
+/// }
+///
+/// assert_eq!(queue.iter().cloned().collect::<Vec<u32>>(), vec![7, 8, 9]);
+///
+/// ```
+///
+#[repr(C)]
+#[derive(StableAbi)]
+pub struct RVecDeque<T> {
+    buffer: *mut T,
+    head: usize,
+    length: usize,
+    capacity: usize,
+    vtable: VecDequeVTable_Ref<T>,
+    _marker: PhantomData<T>,
+}
+
+impl<T> RVecDeque<T> {
+    /// Creates a new, empty `RVecDeque<T>`.
+    ///
+    /// This function does not allocate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let queue = RVecDeque::<u32>::new();
+    ///
+    /// assert!(queue.is_empty());
+    ///
+    /// ```
+    pub const fn new() -> Self {
+        Self::NEW
+    }
+
+    const NEW: Self = RVecDeque {
+        vtable: VTableGetter::<T>::LIB_VTABLE,
+        buffer: std::mem::align_of::<T>() as *mut T,
+        head: 0,
+        length: 0,
+        capacity: 0_usize.wrapping_sub((std::mem::size_of::<T>() == 0) as usize),
+        _marker: PhantomData,
+    };
+
+    /// Creates a new, empty `RVecDeque<T>`, with a capacity of `cap`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = RVecDeque::<u32>::with_capacity(7);
+    ///
+    /// assert_eq!(queue.len(), 0);
+    /// assert!(queue.capacity() >= 7);
+    ///
+    /// ```
+    pub fn with_capacity(cap: usize) -> Self {
+        Vec::with_capacity(cap).into()
+    }
+
+    #[allow(dead_code)]
+    // Used to test functions that change behavior when the vtable changes
+    fn set_vtable_for_testing(mut self) -> Self {
+        self.vtable = VTableGetter::<T>::LIB_VTABLE_FOR_TESTING;
+        self
+    }
+
+    /// Returns the amount of elements this `RVecDeque<T>` can store without reallocating.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = RVecDeque::new();
+    ///
+    /// assert_eq!(queue.capacity(), 0);
+    ///
+    /// queue.push_back(0);
+    /// assert_ne!(queue.capacity(), 0);
+    ///
+    /// ```
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the amount of elements in the `RVecDeque<T>`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = RVecDeque::new();
+    ///
+    /// assert_eq!(queue.len(), 0);
+    ///
+    /// queue.push_back(3);
+    /// queue.push_front(5);
+    /// assert_eq!(queue.len(), 2);
+    ///
+    /// ```
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Whether the length of the `RVecDeque<T>` is 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = RVecDeque::new();
+    ///
+    /// assert!(queue.is_empty());
+    ///
+    /// queue.push_back(8);
+    /// assert!(!queue.is_empty());
+    ///
+    /// ```
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Gets the position in the buffer of the element at the `index` position
+    /// in the queue.
+    ///
+    /// `index` must be less than or equal to the capacity.
+    #[inline]
+    fn wrap_index(&self, index: usize) -> usize {
+        let till_end = self.capacity - self.head;
+        if index < till_end {
+            self.head + index
+        } else {
+            index - till_end
+        }
+    }
+
+    #[inline]
+    fn ptr_at(&self, index: usize) -> *mut T {
+        unsafe { self.buffer.add(self.wrap_index(index)) }
+    }
+
+    /// Whether the elements wrap around the end of the buffer.
+    #[inline]
+    fn is_wrapped(&self) -> bool {
+        self.capacity - self.head < self.length
+    }
+
+    /// Moves the elements to the start of the buffer.
+    fn rotate_to_start(&mut self) {
+        if self.head == 0 || mem::size_of::<T>() == 0 {
+            self.head = 0;
+        } else if self.is_wrapped() {
+            unsafe {
+                slice::from_raw_parts_mut(self.buffer as *mut MaybeUninit<T>, self.capacity)
+                    .rotate_left(self.head);
+            }
+            self.head = 0;
+        } else {
+            unsafe {
+                ptr::copy(self.buffer.add(self.head), self.buffer, self.length);
+            }
+            self.head = 0;
+        }
+    }
+
+    /// Constructs a vec to do operations on the underlying buffer.
+    ///
+    /// # Safety
+    ///
+    /// This must not be called outside of functions that get stored in the vtable.
+    unsafe fn with_vec<U, F>(&mut self, f: F) -> U
+    where
+        F: FnOnce(&mut Vec<T>) -> U,
+    {
+        self.rotate_to_start();
+        let old = mem::replace(self, RVecDeque::new()).piped(ManuallyDrop::new);
+        let mut list = Vec::<T>::from_raw_parts(old.buffer, old.length, old.capacity);
+        let ret = f(&mut list);
+        ptr::write(self, list.into());
+        ret
+    }
+
+    /// Returns a reference to the element at the `index` position,
+    /// returning `None` if it's out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = RVecDeque::new();
+    /// queue.push_back(3);
+    /// queue.push_front(5);
+    ///
+    /// assert_eq!(queue.get(0), Some(&5));
+    /// assert_eq!(queue.get(1), Some(&3));
+    /// assert_eq!(queue.get(2), None);
+    ///
+    /// ```
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index < self.length {
+            unsafe { Some(&*self.ptr_at(index)) }
+        } else {
+            None
+        }
+    }
+
+    /// Returns a mutable reference to the element at the `index` position,
+    /// returning `None` if it's out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = RVecDeque::new();
+    /// queue.push_back(3);
+    /// queue.push_front(5);
+    ///
+    /// *queue.get_mut(1).unwrap() += 10;
+    ///
+    /// assert_eq!(queue.get(1), Some(&13));
+    /// assert_eq!(queue.get_mut(2), None);
+    ///
+    /// ```
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index < self.length {
+            unsafe { Some(&mut *self.ptr_at(index)) }
+        } else {
+            None
+        }
+    }
+
+    /// Returns a reference to the first element, returning `None` if the queue is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = RVecDeque::new();
+    /// assert_eq!(queue.front(), None);
+    ///
+    /// queue.push_back(3);
+    /// queue.push_front(5);
+    /// assert_eq!(queue.front(), Some(&5));
+    ///
+    /// ```
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Returns a mutable reference to the first element,
+    /// returning `None` if the queue is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = RVecDeque::new();
+    /// assert_eq!(queue.front_mut(), None);
+    ///
+    /// queue.push_back(3);
+    /// *queue.front_mut().unwrap() = 8;
+    /// assert_eq!(queue.front(), Some(&8));
+    ///
+    /// ```
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(0)
+    }
+
+    /// Returns a reference to the last element, returning `None` if the queue is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = RVecDeque::new();
+    /// assert_eq!(queue.back(), None);
+    ///
+    /// queue.push_back(3);
+    /// queue.push_front(5);
+    /// assert_eq!(queue.back(), Some(&3));
+    ///
+    /// ```
+    pub fn back(&self) -> Option<&T> {
+        self.len().checked_sub(1).and_then(|i| self.get(i))
+    }
+
+    /// Returns a mutable reference to the last element,
+    /// returning `None` if the queue is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = RVecDeque::new();
+    /// assert_eq!(queue.back_mut(), None);
+    ///
+    /// queue.push_back(3);
+    /// queue.push_back(5);
+    /// *queue.back_mut().unwrap() = 8;
+    /// assert_eq!(queue.back(), Some(&8));
+    ///
+    /// ```
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        match self.len().checked_sub(1) {
+            Some(i) => self.get_mut(i),
+            None => None,
+        }
+    }
+
+    /// Whether the queue contains an element equal to `value`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let queue = vec![3, 5, 8].into_iter().collect::<RVecDeque<u32>>();
+    ///
+    /// assert!(queue.contains(&5));
+    /// assert!(!queue.contains(&13));
+    ///
+    /// ```
+    pub fn contains(&self, value: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        let (front, back) = self.as_slices();
+        front.contains(value) || back.contains(value)
+    }
+
+    /// Appends `value` at the end of the queue.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = RVecDeque::new();
+    ///
+    /// queue.push_back(3);
+    /// queue.push_back(5);
+    ///
+    /// assert_eq!(queue.iter().collect::<Vec<_>>(), vec![&3, &5]);
+    ///
+    /// ```
+    pub fn push_back(&mut self, value: T) {
+        if self.length == self.capacity {
+            self.grow_capacity_to_1();
+        }
+        unsafe {
+            ptr::write(self.ptr_at(self.length), value);
+        }
+        self.length += 1;
+    }
+
+    /// Prepends `value` at the start of the queue.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = RVecDeque::new();
+    ///
+    /// queue.push_front(3);
+    /// queue.push_front(5);
+    ///
+    /// assert_eq!(queue.iter().collect::<Vec<_>>(), vec![&5, &3]);
+    ///
+    /// ```
+    pub fn push_front(&mut self, value: T) {
+        if self.length == self.capacity {
+            self.grow_capacity_to_1();
+        }
+        self.head = self.wrap_index(self.capacity - 1);
+        unsafe {
+            ptr::write(self.buffer.add(self.head), value);
+        }
+        self.length += 1;
+    }
+
+    /// Removes the last element and returns it,
+    /// returning `None` if the queue is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = vec![3, 5].into_iter().collect::<RVecDeque<u32>>();
+    ///
+    /// assert_eq!(queue.pop_back(), Some(5));
+    /// assert_eq!(queue.pop_back(), Some(3));
+    /// assert_eq!(queue.pop_back(), None);
+    ///
+    /// ```
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.length == 0 {
+            None
+        } else {
+            self.length -= 1;
+            unsafe { Some(ptr::read(self.ptr_at(self.length))) }
+        }
+    }
+
+    /// Removes the first element and returns it,
+    /// returning `None` if the queue is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = vec![3, 5].into_iter().collect::<RVecDeque<u32>>();
+    ///
+    /// assert_eq!(queue.pop_front(), Some(3));
+    /// assert_eq!(queue.pop_front(), Some(5));
+    /// assert_eq!(queue.pop_front(), None);
+    ///
+    /// ```
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.length == 0 {
+            None
+        } else {
+            let value = unsafe { ptr::read(self.buffer.add(self.head)) };
+            self.head = self.wrap_index(1);
+            self.length -= 1;
+            Some(value)
+        }
+    }
+
+    /// Returns the contents of the queue as a pair of slices,
+    /// the first one contains the front of the queue,
+    /// the second one contains the elements after it (it's empty when
+    /// the elements are stored contiguously).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = RVecDeque::with_capacity(4);
+    /// queue.push_back(3);
+    /// queue.push_back(5);
+    /// queue.push_front(8);
+    ///
+    /// let (front, back) = queue.as_slices();
+    /// assert_eq!([front, back].concat(), vec![8, 3, 5]);
+    ///
+    /// ```
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        unsafe {
+            let (front, back) = self.slice_lengths();
+            (
+                slice::from_raw_parts(self.buffer.add(self.head), front),
+                slice::from_raw_parts(self.buffer, back),
+            )
+        }
+    }
+
+    /// Returns the contents of the queue as a pair of mutable slices,
+    /// the first one contains the front of the queue,
+    /// the second one contains the elements after it (it's empty when
+    /// the elements are stored contiguously).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = RVecDeque::with_capacity(4);
+    /// queue.push_back(3);
+    /// queue.push_back(5);
+    /// queue.push_front(8);
+    ///
+    /// let (front, back) = queue.as_mut_slices();
+    /// front.iter_mut().chain(back).for_each(|x| *x *= 2);
+    ///
+    /// assert_eq!(queue.iter().cloned().collect::<Vec<u32>>(), vec![16, 6, 10]);
+    ///
+    /// ```
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        unsafe {
+            let (front, back) = self.slice_lengths();
+            (
+                slice::from_raw_parts_mut(self.buffer.add(self.head), front),
+                slice::from_raw_parts_mut(self.buffer, back),
+            )
+        }
+    }
+
+    fn slice_lengths(&self) -> (usize, usize) {
+        let till_end = self.capacity - self.head;
+        if self.length <= till_end {
+            (self.length, 0)
+        } else {
+            (till_end, self.length - till_end)
+        }
+    }
+
+    /// Rearranges the elements of the queue so that they're stored contiguously,
+    /// returning a mutable slice of all of them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = RVecDeque::with_capacity(4);
+    /// queue.push_back(3);
+    /// queue.push_back(5);
+    /// queue.push_front(8);
+    ///
+    /// assert_eq!(queue.make_contiguous(), &mut [8, 3, 5]);
+    /// assert_eq!(queue.as_slices(), (&[8, 3, 5][..], &[][..]));
+    ///
+    /// ```
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        if self.is_wrapped() {
+            self.rotate_to_start();
+        }
+        self.as_mut_slices().0
+    }
+
+    /// Returns an iterator over references to the elements of the queue,
+    /// from front to back.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = RVecDeque::new();
+    /// queue.push_back(3);
+    /// queue.push_front(5);
+    ///
+    /// assert_eq!(queue.iter().collect::<Vec<_>>(), vec![&5, &3]);
+    /// assert_eq!(queue.iter().rev().collect::<Vec<_>>(), vec![&3, &5]);
+    ///
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T> {
+        let (front, back) = self.as_slices();
+        Iter {
+            front: front.iter(),
+            back: back.iter(),
+        }
+    }
+
+    /// Returns an iterator over mutable references to the elements of the queue,
+    /// from front to back.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = RVecDeque::new();
+    /// queue.push_back(3);
+    /// queue.push_front(5);
+    ///
+    /// for x in queue.iter_mut() {
+    ///     *x += 1;
+    /// }
+    ///
+    /// assert_eq!(queue.iter().collect::<Vec<_>>(), vec![&6, &4]);
+    ///
+    /// ```
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let (front, back) = self.as_mut_slices();
+        IterMut {
+            front: front.iter_mut(),
+            back: back.iter_mut(),
+        }
+    }
+
+    /// Creates a draining iterator that removes the specified range in
+    /// the `RVecDeque<T>` and yields the removed items.
+    ///
+    /// Draining from either end of the queue doesn't move the remaining elements.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the index is out of bounds or if the start of the range is
+    /// greater than the end of the range.
+    ///
+    /// # Consumption
+    ///
+    /// The elements in the range will be removed even if the iterator
+    /// was dropped before yielding them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = (0..6).collect::<RVecDeque<u32>>();
+    ///
+    /// assert_eq!(queue.drain(..2).collect::<Vec<_>>(), vec![0, 1]);
+    /// assert_eq!(queue.drain(1..3).collect::<Vec<_>>(), vec![3, 4]);
+    /// assert_eq!(queue.iter().cloned().collect::<Vec<_>>(), vec![2, 5]);
+    ///
+    /// ```
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, T>
+    where
+        R: RangeBounds<usize>,
+    {
+        let start = match range.start_bound() {
+            Bound::Unbounded => 0,
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n.saturating_add(1),
+        };
+        let end = match range.end_bound() {
+            Bound::Unbounded => self.length,
+            Bound::Included(&n) => n.saturating_add(1),
+            Bound::Excluded(&n) => n,
+        };
+        assert!(
+            start <= end,
+            "drain start index ({}) is greater than its end index ({})",
+            start,
+            end,
+        );
+        assert!(
+            end <= self.length,
+            "drain end index ({}) is out of bounds for a queue of length {}",
+            end,
+            self.length,
+        );
+
+        let old_len = self.length;
+        // The elements after the drained range are leaked if the Drain is leaked.
+        self.length = start;
+
+        Drain {
+            deque: self,
+            start,
+            end,
+            front: start,
+            back: end,
+            old_len,
+        }
+    }
+
+    /// Truncates the `RVecDeque<T>` to `to` length,
+    /// dropping the elements at the back.
+    /// Does nothing if `self.len() <= to`.
+    ///
+    /// Note: this has no effect on the capacity of the `RVecDeque<T>`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = (0..6).collect::<RVecDeque<u32>>();
+    ///
+    /// queue.truncate(3);
+    /// assert_eq!(queue.iter().cloned().collect::<Vec<_>>(), vec![0, 1, 2]);
+    ///
+    /// queue.truncate(5555); // This is a no-op.
+    /// assert_eq!(queue.len(), 3);
+    ///
+    /// ```
+    pub fn truncate(&mut self, to: usize) {
+        if to < self.length {
+            let (front, back) = self.as_mut_slices();
+            let (front, back): (*mut [T], *mut [T]) = if to < front.len() {
+                (&mut front[to..], back)
+            } else {
+                let back_start = to - front.len();
+                (&mut [], &mut back[back_start..])
+            };
+            self.length = to;
+            unsafe {
+                ptr::drop_in_place(front);
+                ptr::drop_in_place(back);
+            }
+        }
+    }
+
+    /// Removes all the elements from collection.
+    ///
+    /// Note: this has no effect on the capacity of the `RVecDeque<T>`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = (0..6).collect::<RVecDeque<u32>>();
+    ///
+    /// queue.clear();
+    /// assert!(queue.is_empty());
+    /// assert_ne!(queue.capacity(), 0);
+    ///
+    /// ```
+    pub fn clear(&mut self) {
+        self.truncate(0);
+        self.head = 0;
+    }
+
+    /// Reserves `àdditional` additional capacity for extra elements.
+    /// This may reserve more than necessary for the additional capacity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = RVecDeque::<u32>::new();
+    ///
+    /// queue.reserve(10);
+    /// assert!(queue.capacity() >= 10);
+    ///
+    /// let cap = queue.capacity();
+    /// queue.extend(0..10);
+    /// assert_eq!(queue.capacity(), cap);
+    ///
+    /// ```
+    pub fn reserve(&mut self, additional: usize) {
+        self.resize_capacity(self.len() + additional, Exactness::Above)
+    }
+
+    /// Reserves `àdditional` additional capacity for extra elements.
+    ///
+    /// Prefer using `reserve` for most situations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = RVecDeque::<u32>::new();
+    ///
+    /// queue.reserve_exact(17);
+    /// assert_eq!(queue.capacity(), 17);
+    ///
+    /// let cap = queue.capacity();
+    /// queue.extend(0..17);
+    /// assert_eq!(queue.capacity(), cap);
+    ///
+    /// ```
+    pub fn reserve_exact(&mut self, additional: usize) {
+        self.resize_capacity(self.len() + additional, Exactness::Exact)
+    }
+
+    /// Shrinks the capacity of the `RVecDeque` to match its length.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = RVecDeque::<u32>::with_capacity(7);
+    ///
+    /// queue.extend(std::iter::repeat(11).take(4));
+    /// assert_eq!(queue.len(), 4);
+    /// assert_eq!(queue.capacity(), 7);
+    ///
+    /// queue.shrink_to_fit();
+    /// assert_eq!(queue.len(), 4);
+    /// assert_eq!(queue.capacity(), 4);
+    ///
+    /// ```
+    pub fn shrink_to_fit(&mut self) {
+        let vtable = self.vtable;
+        vtable.shrink_to_fit()(self);
+    }
+
+    #[inline]
+    fn grow_capacity_to_1(&mut self) {
+        let vtable = self.vtable;
+        vtable.grow_capacity_to()(self, self.capacity() + 1, Exactness::Above);
+    }
+
+    fn resize_capacity(&mut self, to: usize, exactness: Exactness) {
+        let vtable = self.vtable;
+        if self.capacity() < to {
+            vtable.grow_capacity_to()(self, to, exactness);
+        }
+    }
+
+    /// Converts this `RVecDeque<T>` into a `VecDeque<T>`.
+    ///
+    /// # Allocation
+    ///
+    /// If this is invoked outside of the dynamic library/binary that created it,
+    /// it will allocate a new `VecDeque<T>` and move the data into it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// use std::collections::VecDeque;
+    ///
+    /// let mut queue = RVecDeque::new();
+    /// queue.push_back(3);
+    /// queue.push_front(5);
+    ///
+    /// assert_eq!(queue.into_vec_deque(), vec![5, 3].into_iter().collect::<VecDeque<u32>>());
+    ///
+    /// ```
+    pub fn into_vec_deque(self) -> VecDeque<T> {
+        let this_vtable = self.vtable;
+        let other_vtable = VTableGetter::LIB_VTABLE;
+        if ::std::ptr::eq(this_vtable.0.to_raw_ptr(), other_vtable.0.to_raw_ptr())
+            || this_vtable.type_id() == other_vtable.type_id()
+        {
+            let mut this = ManuallyDrop::new(self);
+            this.rotate_to_start();
+            unsafe { Vec::from_raw_parts(this.buffer, this.length, this.capacity).into() }
+        } else {
+            self.into_iter().collect()
+        }
+    }
+
+    /// Creates a `VecDeque<T>`, copying all the elements of this `RVecDeque<T>`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// use std::collections::VecDeque;
+    ///
+    /// let queue = (0..4).collect::<RVecDeque<u32>>();
+    ///
+    /// assert_eq!(queue.to_vec_deque(), (0..4).collect::<VecDeque<u32>>());
+    ///
+    /// ```
+    pub fn to_vec_deque(&self) -> VecDeque<T>
+    where
+        T: Clone,
+    {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T> Clone for RVecDeque<T>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T> Default for RVecDeque<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Debug for RVecDeque<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T> PartialEq for RVecDeque<T>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T> Eq for RVecDeque<T> where T: Eq {}
+
+impl<T> PartialOrd for RVecDeque<T>
+where
+    T: PartialOrd,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T> Ord for RVecDeque<T>
+where
+    T: Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T> Hash for RVecDeque<T>
+where
+    T: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len().hash(state);
+        self.iter().for_each(|elem| elem.hash(state));
+    }
+}
+
+impl<T> Index<usize> for RVecDeque<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        let len = self.len();
+        self.get(index).unwrap_or_else(|| {
+            panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                len, index
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<usize> for RVecDeque<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        let len = self.len();
+        self.get_mut(index).unwrap_or_else(|| {
+            panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                len, index
+            )
+        })
+    }
+}
+
+impl_from_rust_repr! {
+    impl[T] From<VecDeque<T>> for RVecDeque<T> {
+        fn(this){
+            Vec::from(this).into()
+        }
+    }
+}
+
+impl_into_rust_repr! {
+    impl[T] Into<VecDeque<T>> for RVecDeque<T> {
+        fn(this){
+            this.into_vec_deque()
+        }
+    }
+}
+
+impl<T> From<Vec<T>> for RVecDeque<T> {
+    fn from(this: Vec<T>) -> Self {
+        let mut this = ManuallyDrop::new(this);
+        RVecDeque {
+            vtable: VTableGetter::<T>::LIB_VTABLE,
+            buffer: this.as_mut_ptr(),
+            head: 0,
+            length: this.len(),
+            capacity: this.capacity(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> FromIterator<T> for RVecDeque<T> {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        Vec::from_iter(iter).into()
+    }
+}
+
+impl<T> Extend<T> for RVecDeque<T> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let iter = iter.into_iter();
+        let (lower, _) = iter.size_hint();
+        self.reserve(lower);
+        for elem in iter {
+            self.push_back(elem);
+        }
+    }
+}
+
+impl<T> IntoIterator for RVecDeque<T> {
+    type Item = T;
+
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { deque: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a RVecDeque<T> {
+    type Item = &'a T;
+
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut RVecDeque<T> {
+    type Item = &'a mut T;
+
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+unsafe impl<T> Send for RVecDeque<T> where T: Send {}
+unsafe impl<T> Sync for RVecDeque<T> where T: Sync {}
+
+impl<T> Drop for RVecDeque<T> {
+    fn drop(&mut self) {
+        let vtable = self.vtable;
+        vtable.destructor()(self)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for RVecDeque<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        <Vec<T>>::deserialize(deserializer).map(Self::from)
+    }
+}
+
+impl<T> Serialize for RVecDeque<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Dummy type used to create a statically allocated constant that can only be
+/// accessed of the lifetime of T.
+struct VTableGetter<'a, T>(&'a T);
+
+impl<'a, T: 'a> VTableGetter<'a, T> {
+    const DEFAULT_VTABLE: VecDequeVTable<T> = VecDequeVTable {
+        type_id: Constructor(new_utypeid::<RVecDeque<()>>),
+        destructor: destructor_vec_deque,
+        grow_capacity_to: grow_capacity_to_vec_deque,
+        shrink_to_fit: shrink_to_fit_vec_deque,
+    };
+
+    staticref! {
+        const WM_DEFAULT: WithMetadata<VecDequeVTable<T>> =
+            WithMetadata::new(PrefixTypeTrait::METADATA, Self::DEFAULT_VTABLE);
+    }
+
+    // The VTABLE for this type in this executable/library
+    const LIB_VTABLE: VecDequeVTable_Ref<T> = VecDequeVTable_Ref(Self::WM_DEFAULT.as_prefix());
+
+    staticref! {
+        const WM_FOR_TESTING: WithMetadata<VecDequeVTable<T>> =
+            WithMetadata::new(
+                PrefixTypeTrait::METADATA,
+                VecDequeVTable {
+                    type_id: Constructor( new_utypeid::<RVecDeque<i32>> ),
+                    ..Self::DEFAULT_VTABLE
+                }
+            )
+    }
+
+    // Used to test functions that change behavior based on the vtable being used
+    const LIB_VTABLE_FOR_TESTING: VecDequeVTable_Ref<T> =
+        VecDequeVTable_Ref(Self::WM_FOR_TESTING.as_prefix());
+}
+
+#[repr(C)]
+#[derive(StableAbi)]
+#[sabi(kind(Prefix))]
+#[sabi(missing_field(panic))]
+struct VecDequeVTable<T> {
+    type_id: Constructor<UTypeId>,
+    destructor: extern "C" fn(&mut RVecDeque<T>),
+    grow_capacity_to: extern "C" fn(&mut RVecDeque<T>, usize, Exactness),
+    #[sabi(last_prefix_field)]
+    shrink_to_fit: extern "C" fn(&mut RVecDeque<T>),
+}
+
+extern "C" fn destructor_vec_deque<T>(this: &mut RVecDeque<T>) {
+    extern_fn_panic_handling! {
+        unsafe {
+            let (front, back) = this.as_mut_slices();
+            let (front, back): (*mut [T], *mut [T]) = (front, back);
+            // Declared before dropping the elements so that the buffer is
+            // deallocated even if a destructor panics.
+            let _buffer = Vec::from_raw_parts(this.buffer, 0, this.capacity);
+            ptr::drop_in_place(front);
+            ptr::drop_in_place(back);
+        }
+    }
+}
+
+extern "C" fn grow_capacity_to_vec_deque<T>(
+    this: &mut RVecDeque<T>,
+    to: usize,
+    exactness: Exactness,
+) {
+    extern_fn_panic_handling! {
+        unsafe{
+            this.with_vec(|list| {
+                let additional = to.saturating_sub(list.len());
+                match exactness {
+                    Exactness::Above => list.reserve(additional),
+                    Exactness::Exact => list.reserve_exact(additional),
+                }
+            })
+        }
+    }
+}
+
+extern "C" fn shrink_to_fit_vec_deque<T>(this: &mut RVecDeque<T>) {
+    extern_fn_panic_handling! {
+        unsafe{
+            this.with_vec(|list| {
+                list.shrink_to_fit();
+            })
+        }
+    }
+}
@@ -0,0 +1,285 @@
+use super::*;
+
+use std::iter::FusedIterator;
+
+/// An iterator over references to the elements of an `RVecDeque<T>`,
+/// returned by `RVecDeque::iter`.
+pub struct Iter<'a, T> {
+    pub(super) front: slice::Iter<'a, T>,
+    pub(super) back: slice::Iter<'a, T>,
+}
+
+impl<'a, T> Clone for Iter<'a, T> {
+    fn clone(&self) -> Self {
+        Iter {
+            front: self.front.clone(),
+            back: self.back.clone(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        match self.front.next() {
+            Some(x) => Some(x),
+            None => {
+                mem::swap(&mut self.front, &mut self.back);
+                self.front.next()
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        match self.back.next_back() {
+            Some(x) => Some(x),
+            None => {
+                mem::swap(&mut self.front, &mut self.back);
+                self.back.next_back()
+            }
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {
+    fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+}
+
+impl<'a, T> FusedIterator for Iter<'a, T> {}
+
+impl<'a, T> Debug for Iter<'a, T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// An iterator over mutable references to the elements of an `RVecDeque<T>`,
+/// returned by `RVecDeque::iter_mut`.
+pub struct IterMut<'a, T> {
+    pub(super) front: slice::IterMut<'a, T>,
+    pub(super) back: slice::IterMut<'a, T>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        match self.front.next() {
+            Some(x) => Some(x),
+            None => {
+                mem::swap(&mut self.front, &mut self.back);
+                self.front.next()
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<&'a mut T> {
+        match self.back.next_back() {
+            Some(x) => Some(x),
+            None => {
+                mem::swap(&mut self.front, &mut self.back);
+                self.back.next_back()
+            }
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {
+    fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+}
+
+impl<'a, T> FusedIterator for IterMut<'a, T> {}
+
+impl<'a, T> Debug for IterMut<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IterMut").field("len", &self.len()).finish()
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// An iterator that moves the elements out of an `RVecDeque<T>`,
+/// returned by `<RVecDeque<T> as IntoIterator>::into_iter`.
+pub struct IntoIter<T> {
+    pub(super) deque: RVecDeque<T>,
+}
+
+impl<T> IntoIter<T> {
+    /// Returns a slice over the remaining elements in the front of the queue.
+    ///
+    /// The remaining elements are the concatenation of
+    /// `as_slices().0` and `as_slices().1`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let queue = (0..4).collect::<RVecDeque<u32>>();
+    ///
+    /// let mut iter = queue.into_iter();
+    /// assert_eq!(iter.next(), Some(0));
+    ///
+    /// let (front, back) = iter.as_slices();
+    /// assert_eq!([front, back].concat(), vec![1, 2, 3]);
+    ///
+    /// ```
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        self.deque.as_slices()
+    }
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.deque.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.deque.len();
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        self.deque.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+impl<T> Debug for IntoIter<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IntoIter").field(&self.deque).finish()
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// A draining iterator over a range of an `RVecDeque<T>`,
+/// returned by `RVecDeque::drain`.
+///
+/// When dropped, this removes the elements in the range that weren't yielded,
+/// moving whichever side of the queue has fewer elements to fill the gap.
+pub struct Drain<'a, T> {
+    pub(super) deque: &'a mut RVecDeque<T>,
+    // The range being drained, in positions relative to the front of the queue.
+    pub(super) start: usize,
+    pub(super) end: usize,
+    // The range of elements that haven't been yielded yet.
+    pub(super) front: usize,
+    pub(super) back: usize,
+    // The length of the queue before draining.
+    pub(super) old_len: usize,
+}
+
+impl<'a, T> Iterator for Drain<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.front == self.back {
+            None
+        } else {
+            let value = unsafe { ptr::read(self.deque.ptr_at(self.front)) };
+            self.front += 1;
+            Some(value)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Drain<'a, T> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.front == self.back {
+            None
+        } else {
+            self.back -= 1;
+            unsafe { Some(ptr::read(self.deque.ptr_at(self.back))) }
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Drain<'a, T> {}
+
+impl<'a, T> FusedIterator for Drain<'a, T> {}
+
+impl<'a, T> Debug for Drain<'a, T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let remaining = (self.front..self.back)
+            .map(|i| unsafe { &*self.deque.ptr_at(i) })
+            .collect::<Vec<&T>>();
+        f.debug_tuple("Drain").field(&remaining).finish()
+    }
+}
+
+impl<'a, T> Drop for Drain<'a, T> {
+    fn drop(&mut self) {
+        self.for_each(drop);
+
+        let drained = self.end - self.start;
+        if drained == 0 {
+            self.deque.length = self.old_len;
+            return;
+        }
+
+        let tail_len = self.old_len - self.end;
+        let deque = &mut *self.deque;
+        unsafe {
+            if self.start <= tail_len {
+                // Moving the elements before the drained range towards the back.
+                for i in (0..self.start).rev() {
+                    ptr::copy_nonoverlapping(deque.ptr_at(i), deque.ptr_at(i + drained), 1);
+                }
+                deque.head = deque.wrap_index(drained);
+            } else {
+                // Moving the elements after the drained range towards the front.
+                for i in 0..tail_len {
+                    ptr::copy_nonoverlapping(
+                        deque.ptr_at(self.end + i),
+                        deque.ptr_at(self.start + i),
+                        1,
+                    );
+                }
+            }
+        }
+        deque.length = self.old_len - drained;
+    }
+}
@@ -0,0 +1,241 @@
+use super::*;
+
+use std::sync::Arc;
+
+use crate::std_types::RString;
+
+/// Creates a queue whose elements wrap around the end of the buffer.
+fn wrapped_deque(list: &[u32]) -> RVecDeque<u32> {
+    let mut deque = RVecDeque::with_capacity(list.len());
+    let half = list.len() / 2;
+    for &x in list[..half].iter().rev() {
+        deque.push_front(x);
+    }
+    for &x in &list[half..] {
+        deque.push_back(x);
+    }
+    deque
+}
+
+fn to_vec<T: Clone>(deque: &RVecDeque<T>) -> Vec<T> {
+    deque.iter().cloned().collect()
+}
+
+#[test]
+fn push_pop() {
+    let mut deque = RVecDeque::new();
+
+    for i in 0..20 {
+        deque.push_back(i * 2 + 1);
+        deque.push_front(i * 2);
+    }
+    assert_eq!(deque.len(), 40);
+    assert_eq!(deque.front(), Some(&38));
+    assert_eq!(deque.back(), Some(&39));
+    assert_eq!(deque[1], 36);
+
+    for i in (0..20).rev() {
+        assert_eq!(deque.pop_front(), Some(i * 2));
+        assert_eq!(deque.pop_back(), Some(i * 2 + 1));
+    }
+    assert_eq!(deque.pop_front(), None);
+    assert_eq!(deque.pop_back(), None);
+    assert!(deque.is_empty());
+}
+
+#[test]
+fn as_slices() {
+    let list = (0..8).collect::<Vec<u32>>();
+    let mut deque = wrapped_deque(&list);
+
+    {
+        let (front, back) = deque.as_slices();
+        assert!(!front.is_empty());
+        assert!(!back.is_empty());
+        assert_eq!([front, back].concat(), list);
+    }
+
+    assert_eq!(deque.make_contiguous(), &list[..]);
+    assert_eq!(deque.as_slices(), (&list[..], &[][..]));
+}
+
+#[test]
+fn iteration() {
+    let list = (0..8).collect::<Vec<u32>>();
+    let mut deque = wrapped_deque(&list);
+
+    assert_eq!(deque.iter().len(), 8);
+    assert_eq!(to_vec(&deque), list);
+    assert_eq!(
+        deque.iter().rev().cloned().collect::<Vec<u32>>(),
+        list.iter().rev().cloned().collect::<Vec<u32>>(),
+    );
+
+    let mut iter = deque.iter();
+    assert_eq!(iter.next(), Some(&0));
+    assert_eq!(iter.next_back(), Some(&7));
+    assert_eq!(iter.cloned().collect::<Vec<u32>>(), &list[1..7]);
+
+    for x in &mut deque {
+        *x *= 10;
+    }
+    assert_eq!(deque.get(7), Some(&70));
+
+    let mut iter = deque.into_iter();
+    assert_eq!(iter.next_back(), Some(70));
+    assert_eq!(iter.len(), 7);
+    assert_eq!(iter.collect::<Vec<u32>>(), vec![0, 10, 20, 30, 40, 50, 60]);
+}
+
+#[test]
+fn grow_wrapped() {
+    let list = (0..8).collect::<Vec<u32>>();
+    let mut deque = wrapped_deque(&list);
+
+    deque.push_front(100);
+    deque.push_back(200);
+    deque.reserve_exact(10);
+    assert!(deque.capacity() >= 20);
+
+    let mut expected = vec![100];
+    expected.extend_from_slice(&list);
+    expected.push(200);
+    assert_eq!(to_vec(&deque), expected);
+
+    deque.shrink_to_fit();
+    assert_eq!(deque.capacity(), 10);
+    assert_eq!(to_vec(&deque), expected);
+}
+
+#[test]
+fn drain() {
+    let list = (0..10).collect::<Vec<u32>>();
+
+    for &(start, end) in &[(0, 0), (0, 3), (2, 5), (6, 9), (7, 10), (0, 10), (4, 4)] {
+        let mut deque = wrapped_deque(&list);
+        let mut expected = list.clone();
+
+        let drained = deque.drain(start..end).collect::<Vec<u32>>();
+        let expected_drained = expected.drain(start..end).collect::<Vec<u32>>();
+
+        assert_eq!(drained, expected_drained, "range: {}..{}", start, end);
+        assert_eq!(to_vec(&deque), expected, "range: {}..{}", start, end);
+
+        deque.push_back(100);
+        deque.push_front(200);
+        assert_eq!(deque.front(), Some(&200));
+        assert_eq!(deque.back(), Some(&100));
+    }
+}
+
+#[test]
+fn drain_dropped_early() {
+    let pointer = Arc::new(());
+    let mut deque = std::iter::repeat(pointer.clone())
+        .take(10)
+        .collect::<RVecDeque<_>>();
+
+    {
+        let mut drain = deque.drain(3..8);
+        drop(drain.next());
+        drop(drain.next_back());
+    }
+    assert_eq!(deque.len(), 5);
+    assert_eq!(Arc::strong_count(&pointer), 6);
+
+    drop(deque.drain(..));
+    assert!(deque.is_empty());
+    assert_eq!(Arc::strong_count(&pointer), 1);
+}
+
+#[test]
+#[should_panic]
+fn drain_out_of_bounds() {
+    let mut deque = (0..4).collect::<RVecDeque<u32>>();
+    deque.drain(2..5);
+}
+
+#[test]
+fn truncate_and_drop() {
+    let pointer = Arc::new(());
+
+    let mut deque = RVecDeque::with_capacity(8);
+    for _ in 0..4 {
+        deque.push_front(pointer.clone());
+        deque.push_back(pointer.clone());
+    }
+    assert_eq!(Arc::strong_count(&pointer), 9);
+
+    deque.truncate(6);
+    assert_eq!(Arc::strong_count(&pointer), 7);
+
+    deque.truncate(2);
+    assert_eq!(Arc::strong_count(&pointer), 3);
+
+    drop(deque);
+    assert_eq!(Arc::strong_count(&pointer), 1);
+}
+
+#[test]
+fn zero_sized() {
+    let mut deque = RVecDeque::new();
+
+    for _ in 0..10 {
+        deque.push_front(());
+        deque.push_back(());
+    }
+    assert_eq!(deque.len(), 20);
+    assert_eq!(deque.drain(5..15).count(), 10);
+    assert_eq!(deque.into_vec_deque().len(), 10);
+}
+
+#[test]
+fn vec_deque_conversion() {
+    let list = (0..8).collect::<Vec<u32>>();
+    let deque = wrapped_deque(&list);
+    let std_deque = list.iter().cloned().collect::<VecDeque<u32>>();
+
+    assert_eq!(deque.to_vec_deque(), std_deque);
+    assert_eq!(deque.clone().into_vec_deque(), std_deque);
+    assert_eq!(
+        deque.clone().set_vtable_for_testing().into_vec_deque(),
+        std_deque
+    );
+
+    let deque_2 = RVecDeque::from(std_deque);
+    assert_eq!(deque_2, deque);
+}
+
+#[test]
+fn cmp_and_clone() {
+    let list = (0..8).collect::<Vec<u32>>();
+    let wrapped = wrapped_deque(&list);
+    let contiguous = list.iter().cloned().collect::<RVecDeque<u32>>();
+
+    assert_eq!(wrapped, contiguous);
+    assert_eq!(wrapped.clone(), contiguous);
+
+    let mut other = contiguous.clone();
+    other.push_back(8);
+    assert!(contiguous < other);
+    assert_ne!(contiguous, other);
+
+    assert_eq!(format!("{:?}", wrapped), format!("{:?}", list));
+}
+
+#[test]
+fn test_serde() {
+    let mut deque = RVecDeque::<RString>::new();
+    deque.push_back("world".into());
+    deque.push_front("hello".into());
+
+    let json = r##"["hello","world"]"##;
+
+    let deserialized = serde_json::from_str::<RVecDeque<RString>>(json).unwrap();
+
+    assert_eq!(deserialized, deque);
+
+    let serialized = serde_json::to_string(&deque).unwrap();
+
+    assert_eq!(serialized, json);
+}
@@ -0,0 +1,55 @@
+//! Helpers for polling futures in tests.
+
+#![allow(dead_code)]
+
+use std::{
+    future::Future,
+    task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+    thread::{self, Thread},
+};
+
+/// A `Waker` that does nothing when woken.
+pub(crate) fn noop_waker() -> Waker {
+    fn clone(_: *const ()) -> RawWaker {
+        RawWaker::new(std::ptr::null(), &VTABLE)
+    }
+    fn noop(_: *const ()) {}
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+
+    unsafe { Waker::from_raw(RawWaker::new(std::ptr::null(), &VTABLE)) }
+}
+
+/// A `Waker` that unparks the thread that created it.
+pub(crate) fn thread_waker() -> Waker {
+    unsafe fn clone(data: *const ()) -> RawWaker {
+        let thread = (*(data as *const Thread)).clone();
+        RawWaker::new(Box::into_raw(Box::new(thread)) as *const (), &VTABLE)
+    }
+    unsafe fn wake(data: *const ()) {
+        Box::from_raw(data as *mut Thread).unpark();
+    }
+    unsafe fn wake_by_ref(data: *const ()) {
+        (*(data as *const Thread)).unpark();
+    }
+    unsafe fn drop_(data: *const ()) {
+        drop(Box::from_raw(data as *mut Thread));
+    }
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, wake, wake_by_ref, drop_);
+
+    let data = Box::into_raw(Box::new(thread::current())) as *const ();
+    unsafe { Waker::from_raw(RawWaker::new(data, &VTABLE)) }
+}
+
+/// Runs `future` to completion on the current thread,
+/// parking the thread until the future wakes it.
+pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+    let waker = thread_waker();
+    let mut cx = Context::from_waker(&waker);
+    let mut future = Box::pin(future);
+    loop {
+        if let Poll::Ready(x) = future.as_mut().poll(&mut cx) {
+            return x;
+        }
+        thread::park();
+    }
+}
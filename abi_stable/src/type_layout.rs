@@ -39,6 +39,7 @@ mod tl_multi_tl;
 mod tl_other;
 mod tl_prefix;
 mod tl_reflection;
+mod validation;
 
 pub(crate) use self::iterators::ChainOnce;
 
@@ -64,8 +65,11 @@ pub use self::{
         CompGenericParams, FmtFullType, GenericParams, ModPath, ReprAttr, TLFieldOrFunction,
         TLPrimitive,
     },
-    tl_prefix::{GenericTLPrefixType, MonoTLPrefixType, TLPrefixType},
+    tl_prefix::{
+        GenericTLPrefixType, MonoTLPrefixType, OnMissingField, TLPrefixExtra, TLPrefixType,
+    },
     tl_reflection::{CompFieldAccessor, FieldAccessor},
+    validation::{validate_layout, InvalidLayout, InvalidLayoutKind, ValidationLimits},
 };
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -303,6 +307,16 @@ impl TypeLayout {
         self.mono.repr_attr()
     }
 
+    /// Gets the doc comments and what the field accessors do when fields are missing,
+    /// returning None if this is not the layout of a prefix type,
+    /// or if the type doesn't use the `#[sabi(with_prefix_extra)]` attribute.
+    pub fn prefix_extra(&self) -> Option<TLPrefixExtra> {
+        if !self.is_prefix_kind() {
+            return None;
+        }
+        TLPrefixExtra::from_strings(self.shared_vars.strings())
+    }
+
     /// Gets the `ModReflMode` for the type,
     /// whether this is a module whose definition can be reflected on at runtime.
     pub const fn mod_refl_mode(&self) -> ModReflMode {
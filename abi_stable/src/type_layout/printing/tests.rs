@@ -109,3 +109,30 @@ fn recursive_debug() {
         }
     }
 }
+
+#[test]
+fn unconstrained_type_params() {
+    use crate::{marker_type::UnsafeIgnoredType, sabi_types::RSmallBox};
+
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(unsafe_unconstrained(U))]
+    struct Unconstrained<T, U, V> {
+        first: T,
+        second: UnsafeIgnoredType<U>,
+        third: V,
+    }
+
+    let layout = <Unconstrained<u8, String, u32> as StableAbi>::LAYOUT;
+    let type_params = layout.generics().type_params();
+    assert_eq!(type_params.len(), 3);
+    assert_eq!(type_params[0].get().name(), "u8");
+    assert_eq!(type_params[2].get().name(), "u32");
+    assert_eq!(
+        layout.full_type().to_string(),
+        "Unconstrained<u8, OpaqueField, u32>"
+    );
+
+    let layout = <RSmallBox<u8, [usize; 2]> as StableAbi>::LAYOUT;
+    assert_eq!(layout.full_type().to_string(), "RSmallBox<u8, OpaqueField>");
+}
@@ -48,6 +48,11 @@ impl SharedVars {
     pub fn strings(&self) -> &'static str {
         self.mono.strings()
     }
+    /// The part of this that doesn't depend on generic parameters.
+    #[inline]
+    pub(super) fn mono(&self) -> &'static MonoSharedVars {
+        self.mono
+    }
     /// Many lifetimes that types in the `type_layout` module reference.
     #[inline]
     pub fn lifetime_indices(&self) -> &'static [LifetimeIndexPair] {
@@ -117,10 +122,13 @@ impl MonoSharedVars {
     /// A string that types in the `type_layout` module store substrings inside of.
     #[inline]
     pub fn strings(&self) -> &'static str {
-        unsafe {
-            let slice = slice::from_raw_parts(self.strings, self.strings_len as usize);
-            std::str::from_utf8_unchecked(slice)
-        }
+        unsafe { std::str::from_utf8_unchecked(self.strings_bytes()) }
+    }
+
+    /// The bytes of `strings`,which may not be valid UTF-8 in a corrupt layout.
+    #[inline]
+    pub(super) fn strings_bytes(&self) -> &'static [u8] {
+        unsafe { slice::from_raw_parts(self.strings, self.strings_len as usize) }
     }
 
     /// Many lifetimes that types in the `type_layout` module reference.
@@ -432,6 +432,224 @@ impl Tag {
     }
 }
 
+/// A view of the value of a `Tag`,returned by [`Tag::value`](./struct.Tag.html#method.value).
+///
+/// `Ignored` tags are viewed as the tag they wrap.
+///
+/// Comparing `TagValue`s treats `Int` and `UInt` values that are the same integer as equal,
+/// and looks through `Ignored` tags inside of arrays,sets,and maps,
+/// the elements of sets and entries of maps are compared regardless of their order.
+#[derive(Debug, Copy, Clone)]
+pub enum TagValue {
+    Null,
+    Bool(bool),
+    Int(i64),
+    UInt(u64),
+    Str(&'static str),
+    Array(&'static [Tag]),
+    Set(&'static [Tag]),
+    Map(&'static [KeyValue<Tag>]),
+}
+
+impl PartialEq for TagValue {
+    fn eq(&self, other: &Self) -> bool {
+        use self::TagValue as TV;
+
+        fn contains_all(this: &[Tag], other: &[Tag]) -> bool {
+            this.iter()
+                .all(|x| other.iter().any(|y| x.value() == y.value()))
+        }
+
+        fn contains_all_entries(this: &[KeyValue<Tag>], other: &[KeyValue<Tag>]) -> bool {
+            this.iter().all(|x| {
+                other
+                    .iter()
+                    .any(|y| x.key.value() == y.key.value() && x.value.value() == y.value.value())
+            })
+        }
+
+        match (*self, *other) {
+            (TV::Null, TV::Null) => true,
+            (TV::Bool(l), TV::Bool(r)) => l == r,
+            (TV::Int(l), TV::Int(r)) => l == r,
+            (TV::UInt(l), TV::UInt(r)) => l == r,
+            (TV::Int(l), TV::UInt(r)) | (TV::UInt(r), TV::Int(l)) => l >= 0 && l as u64 == r,
+            (TV::Str(l), TV::Str(r)) => l == r,
+            (TV::Array(l), TV::Array(r)) => {
+                l.len() == r.len() && l.iter().zip(r).all(|(l, r)| l.value() == r.value())
+            }
+            (TV::Set(l), TV::Set(r)) => contains_all(l, r) && contains_all(r, l),
+            (TV::Map(l), TV::Map(r)) => contains_all_entries(l, r) && contains_all_entries(r, l),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for TagValue {}
+
+/// Methods for reading the value of a `Tag`.
+///
+/// These look through `Ignored` tags,
+/// since ignoring a tag only affects layout checking.
+///
+/// # Example
+///
+#[cfg_attr(not(feature = "no_tagging_doctest"), doc = "```rust")]
+#[cfg_attr(feature = "no_tagging_doctest", doc = "```ignore")]
+/// use abi_stable::{tag, type_layout::Tag, StableAbi};
+///
+/// #[repr(C)]
+/// #[derive(StableAbi)]
+/// #[sabi(tag = r##" tag!{{ "version" => 3, "features" => tag!{{ "async", "serde" }} }} "##)]
+/// struct Interface;
+///
+/// let tag: &'static Tag = Interface::LAYOUT.tag();
+///
+/// assert_eq!(tag.get("version").and_then(Tag::as_u64), Some(3));
+/// assert_eq!(tag.get("missing"), None);
+///
+/// let features = tag.get("features").unwrap();
+/// assert!(features.contains(&Tag::str("serde")));
+/// assert_eq!(
+///     features.elements().iter().filter_map(Tag::as_str).collect::<Vec<_>>(),
+///     vec!["async", "serde"],
+/// );
+///
+/// ```
+impl Tag {
+    /// Gets the tag that this refers to,looking through `Ignored` tags.
+    fn resolve(&self) -> &Tag {
+        let mut this = self;
+        while let TagVariant::Ignored(ignored) = this.variant {
+            this = ignored;
+        }
+        this
+    }
+
+    /// Gets a view of the value of this tag.
+    pub fn value(&self) -> TagValue {
+        match self.resolve().variant {
+            TagVariant::Primitive(Primitive::Null) => TagValue::Null,
+            TagVariant::Primitive(Primitive::Bool(b)) => TagValue::Bool(b),
+            TagVariant::Primitive(Primitive::Int(n)) => TagValue::Int(n),
+            TagVariant::Primitive(Primitive::UInt(n)) => TagValue::UInt(n),
+            TagVariant::Primitive(Primitive::String_(s)) => TagValue::Str(s.as_str()),
+            TagVariant::Ignored(_) => unreachable!("resolve looks through Ignored tags"),
+            TagVariant::Array(arr) => TagValue::Array(arr.as_slice()),
+            TagVariant::Set(set) => TagValue::Set(set.as_slice()),
+            TagVariant::Map(map) => TagValue::Map(map.as_slice()),
+        }
+    }
+
+    /// Whether this is the Ignored variant.
+    pub fn is_ignored(&self) -> bool {
+        matches!(self.variant, TagVariant::Ignored { .. })
+    }
+
+    /// Gets the value of a Bool tag.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self.value() {
+            TagValue::Bool(b) => Some(b),
+            _ => None,
+        }
+    }
+
+    /// Gets the value of an Int tag,or of a UInt tag that fits in an `i64`.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self.value() {
+            TagValue::Int(n) => Some(n),
+            TagValue::UInt(n) if n <= i64::MAX as u64 => Some(n as i64),
+            _ => None,
+        }
+    }
+
+    /// Gets the value of a UInt tag,or of a non-negative Int tag.
+    ///
+    /// `tag!` constructs Int tags from integer literals,
+    /// so this is the way to read them as unsigned integers.
+    pub fn as_u64(&self) -> Option<u64> {
+        match self.value() {
+            TagValue::UInt(n) => Some(n),
+            TagValue::Int(n) if n >= 0 => Some(n as u64),
+            _ => None,
+        }
+    }
+
+    /// Gets the value of a String_ tag.
+    pub fn as_str(&self) -> Option<&'static str> {
+        match self.value() {
+            TagValue::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Gets the elements of an Array tag.
+    pub fn as_array(&self) -> Option<&'static [Tag]> {
+        match self.value() {
+            TagValue::Array(arr) => Some(arr),
+            _ => None,
+        }
+    }
+
+    /// Gets the elements of a Set tag.
+    pub fn as_set(&self) -> Option<&'static [Tag]> {
+        match self.value() {
+            TagValue::Set(set) => Some(set),
+            _ => None,
+        }
+    }
+
+    /// Gets the entries of a Map tag.
+    pub fn as_map(&self) -> Option<&'static [KeyValue<Tag>]> {
+        match self.value() {
+            TagValue::Map(map) => Some(map),
+            _ => None,
+        }
+    }
+
+    /// Gets the elements of an Array or Set tag,
+    /// returning an empty slice for every other variant.
+    pub fn elements(&self) -> &'static [Tag] {
+        match self.value() {
+            TagValue::Array(elems) | TagValue::Set(elems) => elems,
+            _ => &[],
+        }
+    }
+
+    /// Gets the value associated with the `key` string in a Map tag.
+    ///
+    /// Returns None if this is not a Map,or if it has no `key` entry.
+    pub fn get(&self, key: &str) -> Option<&'static Tag> {
+        self.as_map()?
+            .iter()
+            .find(|kv| kv.key.as_str() == Some(key))
+            .map(|kv| &kv.value)
+    }
+
+    /// Gets the value associated with the `key` tag in a Map tag.
+    ///
+    /// Returns None if this is not a Map,or if it has no `key` entry.
+    pub fn get_tag(&self, key: &Tag) -> Option<&'static Tag> {
+        self.as_map()?
+            .iter()
+            .find(|kv| kv.key.value() == key.value())
+            .map(|kv| &kv.value)
+    }
+
+    /// Whether this Array or Set tag contains `elem`,
+    /// or this Map tag has an `elem` key.
+    pub fn contains(&self, elem: &Tag) -> bool {
+        let elem = elem.value();
+        match self.value() {
+            TagValue::Array(elems) | TagValue::Set(elems) => {
+                elems.iter().any(|x| x.value() == elem)
+            }
+            TagValue::Map(map) => map.iter().any(|kv| kv.key.value() == elem),
+            _ => false,
+        }
+    }
+}
+
 fn sorted_ct_vec_from_iter<I>(iter: I) -> RVec<KeyValue<CheckableTag>>
 where
     I: IntoIterator<Item = (CheckableTag, CheckableTag)>,
@@ -263,3 +263,144 @@ fn check_null() {
         assert_ne!(elems.check_compatible(&null_checkable), Ok(()));
     }
 }
+
+const TAG_QUERIED: Tag = tag!({
+    "name" => "plugin",
+    "version" => 3,
+    "unsigned" => Tag::uint(u64::MAX),
+    "negative" => -1,
+    "enabled" => true,
+    "hidden" => Tag::ignored(&Tag::bool_(false)),
+    "features" => tag!({ "async", "serde" }),
+    "order" => tag!([ 0, 1, 2 ]),
+    0 => "zero",
+});
+
+#[test]
+fn query_primitives() {
+    let get = |key: &str| TAG_QUERIED.get(key).unwrap();
+
+    assert_eq!(get("name").as_str(), Some("plugin"));
+    assert_eq!(get("name").as_bool(), None);
+    assert_eq!(get("name").as_i64(), None);
+
+    assert_eq!(get("version").as_i64(), Some(3));
+    assert_eq!(get("version").as_u64(), Some(3));
+    assert_eq!(get("version").as_str(), None);
+
+    assert_eq!(get("unsigned").as_u64(), Some(u64::MAX));
+    assert_eq!(get("unsigned").as_i64(), None);
+
+    assert_eq!(get("negative").as_i64(), Some(-1));
+    assert_eq!(get("negative").as_u64(), None);
+
+    assert_eq!(get("enabled").as_bool(), Some(true));
+
+    assert!(get("hidden").is_ignored());
+    assert_eq!(get("hidden").as_bool(), Some(false));
+    assert_eq!(get("hidden").value(), TagValue::Bool(false));
+
+    assert_eq!(Tag::null().value(), TagValue::Null);
+    assert_eq!(Tag::null().as_bool(), None);
+}
+
+#[test]
+fn query_collections() {
+    assert_eq!(TAG_QUERIED.get("missing"), None);
+    assert_eq!(TAG_QUERIED.get_tag(&Tag::int(0)), Some(&Tag::str("zero")));
+    assert_eq!(TAG_QUERIED.get_tag(&Tag::uint(0)), Some(&Tag::str("zero")));
+    assert_eq!(TAG_QUERIED.get_tag(&Tag::int(1)), None);
+    assert!(TAG_QUERIED.contains(&Tag::str("enabled")));
+    assert!(!TAG_QUERIED.contains(&Tag::str("disabled")));
+    assert_eq!(TAG_QUERIED.elements(), &[]);
+    assert_eq!(TAG_QUERIED.as_map().map(<[_]>::len), Some(9));
+
+    let features = TAG_QUERIED.get("features").unwrap();
+    assert_eq!(features.as_array(), None);
+    assert_eq!(features.as_map(), None);
+    assert!(features.contains(&Tag::str("serde")));
+    assert!(!features.contains(&Tag::str("std")));
+    assert_eq!(
+        features
+            .elements()
+            .iter()
+            .filter_map(Tag::as_str)
+            .collect::<Vec<_>>(),
+        vec!["async", "serde"],
+    );
+
+    let order = TAG_QUERIED.get("order").unwrap();
+    assert_eq!(order.as_set(), None);
+    assert_eq!(
+        order
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(Tag::as_u64)
+            .collect::<Vec<_>>(),
+        vec![0, 1, 2],
+    );
+
+    // Non-map tags have no entries.
+    assert_eq!(order.get("0"), None);
+    assert_eq!(Tag::str("name").get("name"), None);
+}
+
+#[test]
+fn query_normalizes_integers() {
+    const SET: Tag = tag!({ 3, "three" });
+    assert!(SET.contains(&Tag::int(3)));
+    assert!(SET.contains(&Tag::uint(3)));
+    assert!(!SET.contains(&Tag::uint(4)));
+
+    const NEGATIVE: Tag = tag!([-1]);
+    assert!(NEGATIVE.contains(&Tag::int(-1)));
+    assert!(!NEGATIVE.contains(&Tag::uint(u64::MAX)));
+
+    assert_eq!(Tag::int(3).value(), Tag::uint(3).value());
+    assert_ne!(Tag::int(-1).value(), Tag::uint(u64::MAX).value());
+
+    const INTS: Tag = tag!([1, tag!({ 2 })]);
+    const UINTS: Tag = Tag::arr(rslice![Tag::uint(1), Tag::set(rslice![Tag::uint(2)])]);
+    assert_eq!(INTS.value(), UINTS.value());
+}
+
+#[test]
+fn query_looks_through_nested_ignored() {
+    const IGNORED: Tag = Tag::arr(rslice![
+        Tag::ignored(&Tag::int(0)),
+        Tag::set(rslice![Tag::ignored(&Tag::str("nested"))]),
+        Tag::map(rslice![Tag::kv(
+            Tag::str("key"),
+            Tag::ignored(&Tag::bool_(true))
+        )]),
+    ]);
+    const UNIGNORED: Tag = tag!([0, tag!({ "nested" }), tag!({ "key" => true })]);
+    const NESTED: Tag = Tag::arr(rslice![IGNORED]);
+    const SET_NESTED: Tag = tag!({ "nested" });
+    const SET_OTHER: Tag = tag!({ "other" });
+
+    assert_eq!(IGNORED.value(), UNIGNORED.value());
+    assert!(NESTED.contains(&UNIGNORED));
+    assert!(IGNORED.contains(&SET_NESTED));
+    assert!(!IGNORED.contains(&SET_OTHER));
+}
+
+#[test]
+fn query_compares_sets_and_maps_regardless_of_order() {
+    const SET_0: Tag = tag!({ 0, 1 });
+    const SET_1: Tag = tag!({ 1, 0 });
+    const SET_2: Tag = tag!({ 0, 2 });
+    assert_eq!(SET_0.value(), SET_1.value());
+    assert_ne!(SET_0.value(), SET_2.value());
+
+    const ARR_0: Tag = tag!([0, 1]);
+    const ARR_1: Tag = tag!([1, 0]);
+    assert_ne!(ARR_0.value(), ARR_1.value());
+
+    const MAP_0: Tag = tag!({ "a" => 0, "b" => 1 });
+    const MAP_1: Tag = tag!({ "b" => 1, "a" => 0 });
+    const MAP_2: Tag = tag!({ "a" => 1, "b" => 0 });
+    assert_eq!(MAP_0.value(), MAP_1.value());
+    assert_ne!(MAP_0.value(), MAP_2.value());
+}
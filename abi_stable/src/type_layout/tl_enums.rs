@@ -18,7 +18,7 @@ pub struct MonoTLEnum {
     field_count_len: u16,
 
     /// A ';' separated list of all variant names
-    variant_names: StartLen,
+    pub(super) variant_names: StartLen,
 
     /// All the fields of the enums,not separated by variant.
     pub(super) fields: CompTLFields,
@@ -241,6 +241,35 @@ macro_rules! declare_tl_discriminants {
                 }
             )*
 
+            /// Gets the amount of discriminants in this `TLDiscriminants`.
+            pub fn len(&self)->usize{
+                match self.inner {
+                    $(
+                        TLDiscrsInner::$variant{len,..}=>len as usize,
+                    )*
+                }
+            }
+
+            /// Whether this `TLDiscriminants` has no discriminants.
+            pub fn is_empty(&self)->bool{
+                self.len()==0
+            }
+
+            /// Gets the discriminant of the `index`th variant,
+            /// returning `None` if there is no `index`th variant.
+            pub fn get(&self,index:usize)->Option<TLDiscriminant>{
+                match self.inner {
+                    $(
+                        TLDiscrsInner::$variant{discriminants,len}=>{
+                            let slice=unsafe{
+                                RSlice::from_raw_parts(discriminants,len as usize)
+                            };
+                            slice.get(index).map(|&x| TLDiscriminant::$single(x as _) )
+                        }
+                    )*
+                }
+            }
+
             /// Gets the type of the discriminant in this `TLDiscriminants`.
             pub fn discriminant_repr(&self)->DiscriminantRepr{
                 match self.inner {
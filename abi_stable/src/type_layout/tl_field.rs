@@ -246,8 +246,12 @@ impl CompTLField {
         &self,
         indices: &'static [LifetimeIndexPair],
     ) -> LifetimeArrayOrSlice<'static> {
-        let comp = LifetimeRange::from_u21(self.lifetime_indices_bits());
-        comp.slicing(indices)
+        self.lifetime_range().slicing(indices)
+    }
+
+    /// The range of lifetimes that the field references.
+    pub(super) fn lifetime_range(&self) -> LifetimeRange {
+        LifetimeRange::from_u21(self.lifetime_indices_bits())
     }
 
     /// Gets the `FieldAccessor` for the type from `SharedVars`'s string slice,
@@ -84,6 +84,11 @@ impl CompTLFields {
         self.comp_fields_len as usize
     }
 
+    /// The function pointers in the fields.
+    pub(super) fn functions(&self) -> Option<&'static TLFunctions> {
+        self.functions
+    }
+
     /// Whether there are no fields.
     pub fn is_empty(&self) -> bool {
         self.comp_fields_len == 0
@@ -41,11 +41,11 @@ impl TLFunctions {
         }
     }
 
-    fn functions(&self) -> &'static [CompTLFunction] {
+    pub(super) fn functions(&self) -> &'static [CompTLFunction] {
         unsafe { std::slice::from_raw_parts(self.functions, self.functions_len as usize) }
     }
 
-    fn field_fn_ranges(&self) -> &'static [StartLen] {
+    pub(super) fn field_fn_ranges(&self) -> &'static [StartLen] {
         unsafe {
             std::slice::from_raw_parts(self.field_fn_ranges, self.field_fn_ranges_len as usize)
         }
@@ -197,8 +197,8 @@ pub struct CompTLFunction {
     param_names_len: u16,
     /// Stores `!0` if the return type is `()`.
     return_type_layout: u16,
-    paramret_lifetime_range: LifetimeRange,
-    param_type_layouts: TypeLayoutRange,
+    pub(super) paramret_lifetime_range: LifetimeRange,
+    pub(super) param_type_layouts: TypeLayoutRange,
 }
 
 impl CompTLFunction {
@@ -229,15 +229,11 @@ impl CompTLFunction {
         let lifetime_indices = shared_vars.lifetime_indices();
         let type_layouts = shared_vars.type_layouts();
 
-        let cs_offset = self.contiguous_strings_offset as usize;
-
-        let bound_lifetimes = cs_offset..cs_offset + (self.bound_lifetimes_len as usize);
-        let param_names =
-            bound_lifetimes.end..bound_lifetimes.end + (self.param_names_len as usize);
+        let [name, bound_lifetimes, param_names] = self.string_ranges();
 
         TLFunction {
             shared_vars: CmpIgnored::new(shared_vars),
-            name: strings.slice(self.name.to_range()),
+            name: strings.slice(name),
             bound_lifetimes: strings.slice(bound_lifetimes),
             param_names: strings.slice(param_names),
             param_type_layouts: self.param_type_layouts.expand(type_layouts),
@@ -245,6 +241,18 @@ impl CompTLFunction {
             return_type_layout: type_layouts.get(self.return_type_layout as usize).cloned(),
         }
     }
+
+    /// The ranges in `SharedVars`'s strings of the name,
+    /// the bound lifetimes,and the parameter names of the function.
+    pub(super) fn string_ranges(&self) -> [Range<usize>; 3] {
+        let cs_offset = self.contiguous_strings_offset as usize;
+
+        let bound_lifetimes = cs_offset..cs_offset + (self.bound_lifetimes_len as usize);
+        let param_names =
+            bound_lifetimes.end..bound_lifetimes.end + (self.param_names_len as usize);
+
+        [self.name.to_range(), bound_lifetimes, param_names]
+    }
 }
 
 ///////////////////////////////////////////////////////////////////////////////
@@ -260,10 +268,10 @@ pub struct TLFunction {
     pub name: RStr<'static>,
 
     /// The named lifetime parameters of the function itself (declared in `for<>`),
-    /// separated by ';'.
+    /// separated by ','.
     pub bound_lifetimes: RStr<'static>,
 
-    /// A ';' separated list of all the parameter names.
+    /// A ',' separated list of all the parameter names.
     pub param_names: RStr<'static>,
 
     /// All the type layouts of the parameters.
@@ -289,7 +297,7 @@ impl PartialEq for TLFunction {
 impl TLFunction {
     pub(crate) fn get_param_names(&self) -> GetParamNames {
         GetParamNames {
-            split: self.param_names.as_str().split(';'),
+            split: self.param_names.as_str().split(','),
             length: self.param_type_layouts.len(),
             current: 0,
         }
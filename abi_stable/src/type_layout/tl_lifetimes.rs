@@ -28,17 +28,25 @@ impl Display for LifetimeIndex {
 impl LifetimeRange {
     /// Expands this `LifetimeRange` into a `LifetimeArrayOrSlice`
     pub fn slicing(self, lifetime_indices: &[LifetimeIndexPair]) -> LifetimeArrayOrSlice<'_> {
-        let len = (self.len() + 1) / 2;
+        match self.slice_range() {
+            Some(range) => {
+                LifetimeArrayOrSlice::Slice(RSlice::from_slice(&lifetime_indices[range]))
+            }
+            None => LifetimeArrayOrSlice::Array(ArrayLen {
+                len: ((self.len() + 1) / 2) as u16,
+                array: LifetimeIndexArray::from_u20(self.bits).to_array(),
+            }),
+        }
+    }
+
+    /// The range of `LifetimeIndexPair`s that this refers to,
+    /// if it's a range into a slice.
+    pub(super) fn slice_range(self) -> Option<std::ops::Range<usize>> {
         if self.is_range() {
             let start = (self.bits & Self::START_MASK) as usize;
-            let end = start + len;
-            let x = RSlice::from_slice(&lifetime_indices[start..end]);
-            LifetimeArrayOrSlice::Slice(x)
+            Some(start..start + (self.len() + 1) / 2)
         } else {
-            LifetimeArrayOrSlice::Array(ArrayLen {
-                len: len as u16,
-                array: LifetimeIndexArray::from_u20(self.bits).to_array(),
-            })
+            None
         }
     }
 }
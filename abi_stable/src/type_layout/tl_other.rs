@@ -71,9 +71,9 @@ pub struct CompGenericParams {
     /// The names of the lifetimes declared by a type.
     lifetime: NulStr<'static>,
     /// The type parameters of a type,getting them from the containing TypeLayout.
-    types: StartLen,
+    pub(super) types: StartLen,
     /// The const parameters of a type,getting them from the containing TypeLayout.
-    consts: StartLen,
+    pub(super) consts: StartLen,
     lifetime_count: u8,
 }
 
@@ -1,5 +1,7 @@
 use super::*;
 
+use abi_stable_shared::type_layout::tl_prefix_extra as tpe;
+
 ////////////////////////////////////////////////////////////////////////////////
 
 /// Properties of prefix types
@@ -88,3 +90,75 @@ impl Display for TLPrefixType {
         Ok(())
     }
 }
+
+/////////////////////////////////////////////////////
+
+/// Extra information about prefix types,
+/// stored after the strings in the `SharedVars` of their `TypeLayout`
+/// when they use the `#[sabi(with_prefix_extra)]` attribute.
+///
+/// This is gotten with `TypeLayout::prefix_extra`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TLPrefixExtra {
+    missing_fields: &'static str,
+    docs: &'static str,
+}
+
+impl TLPrefixExtra {
+    pub(crate) fn from_strings(strings: &'static str) -> Option<Self> {
+        let (_, extra) = split_once(strings, tpe::SEPARATOR)?;
+        let (missing_fields, docs) = split_once(extra, tpe::SEPARATOR).unwrap_or((extra, ""));
+        Some(Self {
+            missing_fields,
+            docs,
+        })
+    }
+
+    /// The doc comments of the type,
+    /// which are empty if the type has none or they didn't fit in the layout.
+    pub fn type_docs(&self) -> &'static str {
+        self.docs.split(tpe::SEPARATOR).next().unwrap_or("")
+    }
+
+    /// The doc comments of the field at the `index` position,
+    /// which are empty if the field has none or they didn't fit in the layout.
+    pub fn field_docs(&self, index: usize) -> &'static str {
+        self.docs.split(tpe::SEPARATOR).nth(index + 1).unwrap_or("")
+    }
+
+    /// What the accessor of the field at the `index` position does when
+    /// the field is missing,
+    /// returning None if the field is always accessible.
+    pub fn on_missing_field(&self, index: usize) -> Option<OnMissingField> {
+        let code = char::from(*self.missing_fields.as_bytes().get(index)?);
+        Some(match code {
+            tpe::RETURN_OPTION => OnMissingField::ReturnOption,
+            tpe::PANIC => OnMissingField::Panic,
+            tpe::WITH => OnMissingField::With,
+            tpe::VALUE => OnMissingField::Value,
+            tpe::DEFAULT => OnMissingField::Default,
+            _ => return None,
+        })
+    }
+}
+
+fn split_once(s: &'static str, sep: char) -> Option<(&'static str, &'static str)> {
+    let pos = s.find(sep)?;
+    Some((&s[..pos], &s[pos + sep.len_utf8()..]))
+}
+
+/// What the accessor of a prefix type field does when the field is missing,
+/// which is determined by the `#[sabi(missing_field( .. ))]` attribute.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum OnMissingField {
+    /// Returns `None`.
+    ReturnOption,
+    /// Panics.
+    Panic,
+    /// Returns the value returned by a function.
+    With,
+    /// Returns a value.
+    Value,
+    /// Returns `Default::default()`.
+    Default,
+}
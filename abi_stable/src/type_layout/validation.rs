@@ -0,0 +1,444 @@
+//! Checks that a `TypeLayout` is well formed,
+//! before any of its accessors are used to compare it to another layout.
+//!
+//! The `TypeLayout`s of a dynamic library come from data inside of that library,
+//! which may be corrupt,
+//! so every range and index into its `SharedVars` is checked to be in bounds here.
+
+use super::*;
+
+use std::ops::Range;
+
+#[cfg(all(test, not(feature = "only_new_tests")))]
+mod tests;
+
+/// The limits on the type graph that `validate_layout` traverses.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ValidationLimits {
+    /// The maximum amount of types nested inside each other.
+    pub max_depth: usize,
+    /// The maximum amount of distinct types that can be traversed.
+    pub max_layouts: usize,
+}
+
+impl ValidationLimits {
+    /// The limits that `check_layout_compatibility` uses.
+    pub const DEFAULT: Self = Self {
+        max_depth: 256,
+        max_layouts: 1 << 16,
+    };
+}
+
+impl Default for ValidationLimits {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// The error returned by `validate_layout` when a `TypeLayout` is malformed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvalidLayout {
+    /// The name of the type with the malformed layout,
+    /// which is a placeholder if the name itself is invalid.
+    pub type_name: &'static str,
+    pub kind: InvalidLayoutKind,
+}
+
+/// What is malformed in an `InvalidLayout`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InvalidLayoutKind {
+    /// A string isn't valid UTF-8.
+    InvalidUtf8 { what: &'static str },
+    /// A range or index doesn't fit in the slice that it refers to.
+    OutOfBounds {
+        what: &'static str,
+        start: usize,
+        end: usize,
+        len: usize,
+    },
+    /// A range into a string doesn't start or end at a character boundary.
+    NotACharBoundary {
+        what: &'static str,
+        start: usize,
+        end: usize,
+    },
+    /// The `MonoTLData` and `GenericTLData` of the type are different variants.
+    MismatchedData {
+        nongeneric: TLDataDiscriminant,
+        generic: TLDataDiscriminant,
+    },
+    /// The field counts of the variants of an enum don't add up to its amount of fields.
+    EnumFieldCount {
+        variant_fields: usize,
+        fields: usize,
+    },
+    /// The alignment isn't representable in a `usize`.
+    InvalidAlignment { power_of_two: u8 },
+    /// The size isn't a multiple of the alignment.
+    SizeNotMultipleOfAlignment { size: usize, alignment: usize },
+    /// The type is nested deeper than `ValidationLimits::max_depth`.
+    TooDeep { max_depth: usize },
+    /// The type references more than `ValidationLimits::max_layouts` types.
+    TooManyLayouts { max_layouts: usize },
+}
+
+/// Checks that `layout`,and every layout it references,are well formed.
+///
+/// This checks that all the ranges and indices in the layouts are in bounds,
+/// that strings are valid UTF-8,and that sizes and alignments are sensible,
+/// stopping if the type graph exceeds any of the `limits`.
+///
+/// # Example
+///
+/// ```
+/// use abi_stable::{
+///     std_types::RVec,
+///     type_layout::{validate_layout, InvalidLayoutKind, ValidationLimits},
+///     StableAbi,
+/// };
+///
+/// let layout = <RVec<RVec<u8>> as StableAbi>::LAYOUT;
+///
+/// validate_layout(layout, ValidationLimits::DEFAULT).unwrap();
+///
+/// let limits = ValidationLimits {
+///     max_depth: 1,
+///     ..ValidationLimits::DEFAULT
+/// };
+/// let err = validate_layout(layout, limits).unwrap_err();
+/// assert_eq!(err.kind, InvalidLayoutKind::TooDeep { max_depth: 1 });
+///
+/// ```
+pub fn validate_layout(
+    layout: &'static TypeLayout,
+    limits: ValidationLimits,
+) -> Result<(), InvalidLayout> {
+    let mut visited = HashSet::<*const TypeLayout>::new();
+
+    // A depth-first traversal that doesn't recurse,
+    // so that deeply nested types can't overflow the stack.
+    let mut stack = Vec::<(Vec<&'static TypeLayout>, usize)>::new();
+    let mut next = Some(layout);
+
+    loop {
+        if let Some(layout) = next.take() {
+            if visited.insert(layout as *const TypeLayout) {
+                let invalid = |kind| InvalidLayout {
+                    type_name: type_name(layout),
+                    kind,
+                };
+                if stack.len() >= limits.max_depth {
+                    return Err(invalid(InvalidLayoutKind::TooDeep {
+                        max_depth: limits.max_depth,
+                    }));
+                }
+                if visited.len() > limits.max_layouts {
+                    return Err(invalid(InvalidLayoutKind::TooManyLayouts {
+                        max_layouts: limits.max_layouts,
+                    }));
+                }
+                validate_shallow(layout).map_err(invalid)?;
+                stack.push((referenced_layouts(layout), 0));
+            }
+        }
+
+        match stack.last_mut() {
+            Some((children, index)) => match children.get(*index) {
+                Some(&child) => {
+                    *index += 1;
+                    next = Some(child);
+                }
+                None => {
+                    stack.pop();
+                }
+            },
+            None => return Ok(()),
+        }
+    }
+}
+
+fn type_name(layout: &'static TypeLayout) -> &'static str {
+    std::str::from_utf8(name_bytes(layout.mono)).unwrap_or("<type with an invalid name>")
+}
+
+fn name_bytes(mono: &'static MonoTypeLayout) -> &'static [u8] {
+    unsafe { std::slice::from_raw_parts(mono.name, mono.name_len as usize) }
+}
+
+/// The layouts that `layout` references,
+/// which `validate_shallow` must have checked to be in bounds.
+fn referenced_layouts(layout: &'static TypeLayout) -> Vec<&'static TypeLayout> {
+    let mut layouts = layout
+        .shared_vars
+        .type_layouts()
+        .iter()
+        .map(|ctor| ctor.get())
+        .collect::<Vec<_>>();
+    if let Some(extra_checks) = layout.extra_checks() {
+        layouts.extend(extra_checks.nested_type_layouts().iter().cloned());
+    }
+    layouts
+}
+
+/// Validates `layout` without looking at the layouts it references.
+fn validate_shallow(layout: &'static TypeLayout) -> Result<(), InvalidLayoutKind> {
+    let usize_bits = mem::size_of::<usize>() * 8;
+    if usize::from(layout.alignment_power_of_two) >= usize_bits {
+        return Err(InvalidLayoutKind::InvalidAlignment {
+            power_of_two: layout.alignment_power_of_two,
+        });
+    }
+    let alignment = layout.alignment();
+    if layout.size % alignment != 0 {
+        return Err(InvalidLayoutKind::SizeNotMultipleOfAlignment {
+            size: layout.size,
+            alignment,
+        });
+    }
+
+    let mono = layout.mono;
+    let vars = Vars::new(layout.shared_vars, &mono.shared_vars)?;
+
+    std::str::from_utf8(name_bytes(mono))
+        .map_err(|_| InvalidLayoutKind::InvalidUtf8 { what: "type name" })?;
+
+    let nongeneric = mono.data.as_discriminant();
+    let generic = layout.data.as_discriminant();
+    if nongeneric != generic {
+        return Err(InvalidLayoutKind::MismatchedData {
+            nongeneric,
+            generic,
+        });
+    }
+
+    match mono.data {
+        MonoTLData::Primitive(_) | MonoTLData::Opaque => {}
+        MonoTLData::Struct { fields } | MonoTLData::Union { fields } => {
+            vars.check_fields(fields)?;
+        }
+        MonoTLData::Enum(enum_) => {
+            vars.check_fields(enum_.fields)?;
+            vars.check_str_range("variant names", enum_.variant_names.to_range())?;
+            let variant_fields = enum_
+                .field_count()
+                .iter()
+                .map(|&count| usize::from(count))
+                .sum::<usize>();
+            if variant_fields != enum_.fields.len() {
+                return Err(InvalidLayoutKind::EnumFieldCount {
+                    variant_fields,
+                    fields: enum_.fields.len(),
+                });
+            }
+        }
+        MonoTLData::PrefixType(prefix) => {
+            vars.check_fields(prefix.fields)?;
+            let first_suffix_field = usize::from(prefix.first_suffix_field);
+            check_range(
+                "first suffix field",
+                first_suffix_field..first_suffix_field,
+                prefix.fields.len(),
+            )?;
+        }
+    }
+
+    let phantom_fields =
+        unsafe { RSlice::from_raw_parts(mono.phantom_fields, mono.phantom_fields_len as usize) };
+    vars.check_fields(CompTLFields::from_fields(phantom_fields))?;
+
+    check_range(
+        "type parameters",
+        mono.generics.types.to_range(),
+        vars.type_layouts,
+    )?;
+    check_range(
+        "const parameters",
+        mono.generics.consts.to_range(),
+        layout.shared_vars.constants().len(),
+    )?;
+
+    Ok(())
+}
+
+/// The parts of the `SharedVars` of a type that its fields refer to.
+struct Vars {
+    /// The strings of the `SharedVars` and the `MonoSharedVars` of the type,
+    /// which are usually the same.
+    strings: [&'static str; 2],
+    lifetime_indices: usize,
+    type_layouts: usize,
+}
+
+impl Vars {
+    fn new(
+        shared_vars: &'static SharedVars,
+        mono_vars: &MonoSharedVars,
+    ) -> Result<Self, InvalidLayoutKind> {
+        let to_str = |vars: &MonoSharedVars| {
+            std::str::from_utf8(vars.strings_bytes())
+                .map_err(|_| InvalidLayoutKind::InvalidUtf8 { what: "strings" })
+        };
+        Ok(Self {
+            strings: [to_str(shared_vars.mono())?, to_str(mono_vars)?],
+            lifetime_indices: shared_vars
+                .lifetime_indices()
+                .len()
+                .min(mono_vars.lifetime_indices().len()),
+            type_layouts: shared_vars.type_layouts().len(),
+        })
+    }
+
+    fn check_str_range(
+        &self,
+        what: &'static str,
+        range: Range<usize>,
+    ) -> Result<(), InvalidLayoutKind> {
+        for strings in &self.strings {
+            check_range(what, range.clone(), strings.len())?;
+            if !strings.is_char_boundary(range.start) || !strings.is_char_boundary(range.end) {
+                return Err(InvalidLayoutKind::NotACharBoundary {
+                    what,
+                    start: range.start,
+                    end: range.end,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn check_lifetimes(
+        &self,
+        what: &'static str,
+        lifetimes: LifetimeRange,
+    ) -> Result<(), InvalidLayoutKind> {
+        match lifetimes.slice_range() {
+            Some(range) => check_range(what, range, self.lifetime_indices),
+            None => Ok(()),
+        }
+    }
+
+    fn check_type_layout(&self, what: &'static str, index: usize) -> Result<(), InvalidLayoutKind> {
+        check_range(what, index..index + 1, self.type_layouts)
+    }
+
+    fn check_fields(&self, fields: CompTLFields) -> Result<(), InvalidLayoutKind> {
+        for field in fields.comp_fields() {
+            self.check_str_range("field name", field.name_start_len().to_range())?;
+            self.check_lifetimes("field lifetimes", field.lifetime_range())?;
+            self.check_type_layout("field type", field.type_layout_index())?;
+        }
+
+        let functions = match fields.functions() {
+            Some(x) => x,
+            None => return Ok(()),
+        };
+        let fn_count = functions.functions().len();
+        for range in functions.field_fn_ranges() {
+            check_range("field function pointers", range.to_range(), fn_count)?;
+        }
+        for function in functions.functions() {
+            let [name, bound_lifetimes, param_names] = function.string_ranges();
+            self.check_str_range("function name", name)?;
+            self.check_str_range("function bound lifetimes", bound_lifetimes)?;
+            self.check_str_range("function parameter names", param_names)?;
+            self.check_lifetimes("function lifetimes", function.paramret_lifetime_range)?;
+
+            let params = function.param_type_layouts;
+            let inline = params.to_array();
+            for &index in &inline {
+                self.check_type_layout("function parameter type", usize::from(index))?;
+            }
+            let len = params.len();
+            if len > TypeLayoutRange::STORED_INLINE {
+                let start = usize::from(inline[TypeLayoutRange::STORED_INLINE - 1]) + 1;
+                let end = start + (len - TypeLayoutRange::STORED_INLINE);
+                check_range("function parameter types", start..end, self.type_layouts)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn check_range(
+    what: &'static str,
+    range: Range<usize>,
+    len: usize,
+) -> Result<(), InvalidLayoutKind> {
+    if range.start <= range.end && range.end <= len {
+        Ok(())
+    } else {
+        Err(InvalidLayoutKind::OutOfBounds {
+            what,
+            start: range.start,
+            end: range.end,
+            len,
+        })
+    }
+}
+
+impl Display for InvalidLayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "The layout of `{}` is invalid:\n{}",
+            self.type_name, self.kind
+        )
+    }
+}
+
+impl Display for InvalidLayoutKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidLayoutKind::InvalidUtf8 { what } => write!(f, "Invalid UTF-8 in the {}.", what),
+            InvalidLayoutKind::OutOfBounds {
+                what,
+                start,
+                end,
+                len,
+            } => write!(
+                f,
+                "The {} range {}..{} is out of bounds of a slice of length {}.",
+                what, start, end, len,
+            ),
+            InvalidLayoutKind::NotACharBoundary { what, start, end } => write!(
+                f,
+                "The {} range {}..{} is not at character boundaries.",
+                what, start, end,
+            ),
+            InvalidLayoutKind::MismatchedData {
+                nongeneric,
+                generic,
+            } => write!(
+                f,
+                "The MonoTLData::{:?} doesn't match the GenericTLData::{:?}.",
+                nongeneric, generic,
+            ),
+            InvalidLayoutKind::EnumFieldCount {
+                variant_fields,
+                fields,
+            } => write!(
+                f,
+                "The variants have {} fields in total,while the enum has {} fields.",
+                variant_fields, fields,
+            ),
+            InvalidLayoutKind::InvalidAlignment { power_of_two } => write!(
+                f,
+                "The alignment 2^{} is larger than the maximum alignment.",
+                power_of_two,
+            ),
+            InvalidLayoutKind::SizeNotMultipleOfAlignment { size, alignment } => write!(
+                f,
+                "The size {} is not a multiple of the alignment {}.",
+                size, alignment,
+            ),
+            InvalidLayoutKind::TooDeep { max_depth } => {
+                write!(f, "The type is nested more than {} types deep.", max_depth,)
+            }
+            InvalidLayoutKind::TooManyLayouts { max_layouts } => {
+                write!(f, "The type references more than {} types.", max_layouts,)
+            }
+        }
+    }
+}
+
+impl std::error::Error for InvalidLayout {}
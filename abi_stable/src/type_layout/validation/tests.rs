@@ -0,0 +1,352 @@
+use super::*;
+
+use core_extensions::matches;
+
+use crate::{
+    abi_stability::abi_checking::{check_layout_compatibility, AbiInstability},
+    std_types::{ROption, RString, RVec},
+    StableAbi,
+};
+
+#[repr(u8)]
+#[derive(StableAbi)]
+pub enum Shape {
+    Circle { radius: f32 },
+    Square(u32),
+    Nothing,
+}
+
+#[repr(C)]
+#[derive(StableAbi)]
+pub struct Canvas<'a> {
+    pub shapes: RVec<Shape>,
+    pub name: &'a RString,
+    pub draw: extern "C" fn(&Shape, scale: u32) -> bool,
+}
+
+#[repr(C)]
+#[derive(StableAbi)]
+pub struct Recursive {
+    pub next: ROption<&'static Recursive>,
+}
+
+const CANVAS: &TypeLayout = <Canvas<'static> as StableAbi>::LAYOUT;
+
+fn leak<T>(value: T) -> &'static T {
+    Box::leak(Box::new(value))
+}
+
+fn leak_slice<T>(values: Vec<T>) -> RSlice<'static, T> {
+    RSlice::from_slice(Box::leak(values.into_boxed_slice()))
+}
+
+/// Makes a copy of `CANVAS` whose `MonoTypeLayout` is modified by `f`.
+fn with_mono<F>(f: F) -> &'static TypeLayout
+where
+    F: FnOnce(&mut MonoTypeLayout),
+{
+    let mut mono = *CANVAS.mono;
+    f(&mut mono);
+    leak(TypeLayout {
+        mono: leak(mono),
+        ..*CANVAS
+    })
+}
+
+fn with_fields(fields: Vec<CompTLField>) -> &'static TypeLayout {
+    with_mono(|mono| mono.data = MonoTLData::struct_(leak_slice(fields)))
+}
+
+fn validate(layout: &'static TypeLayout) -> Result<(), InvalidLayoutKind> {
+    validate_layout(layout, ValidationLimits::DEFAULT).map_err(|e| e.kind)
+}
+
+fn field(name: StartLen, lifetimes: LifetimeRange, type_layout: u16) -> CompTLField {
+    CompTLField::std_field(name, lifetimes, type_layout)
+}
+
+#[test]
+fn valid_layouts() {
+    let layouts = [
+        CANVAS,
+        <Shape as StableAbi>::LAYOUT,
+        <Recursive as StableAbi>::LAYOUT,
+        <RVec<u8> as StableAbi>::LAYOUT,
+        <ROption<RString> as StableAbi>::LAYOUT,
+        <TypeLayout as StableAbi>::LAYOUT,
+    ];
+    for layout in layouts.iter() {
+        validate_layout(layout, ValidationLimits::DEFAULT).unwrap();
+    }
+}
+
+#[test]
+fn out_of_bounds_fields() {
+    let strings_len = CANVAS.shared_vars.strings().len();
+    let type_layouts_len = CANVAS.shared_vars.type_layouts().len();
+
+    assert_eq!(
+        validate(with_fields(vec![field(
+            StartLen::new(1000, 3),
+            LifetimeRange::EMPTY,
+            0
+        )])),
+        Err(InvalidLayoutKind::OutOfBounds {
+            what: "field name",
+            start: 1000,
+            end: 1003,
+            len: strings_len,
+        }),
+    );
+    assert_eq!(
+        validate(with_fields(vec![field(
+            StartLen::new(0, 1),
+            LifetimeRange::EMPTY,
+            1000
+        )])),
+        Err(InvalidLayoutKind::OutOfBounds {
+            what: "field type",
+            start: 1000,
+            end: 1001,
+            len: type_layouts_len,
+        }),
+    );
+    assert!(matches!(
+        validate(with_fields(vec![field(
+            StartLen::new(0, 1),
+            LifetimeRange::from_range(50..52),
+            0
+        )])),
+        Err(InvalidLayoutKind::OutOfBounds {
+            what: "field lifetimes",
+            start: 50,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn out_of_bounds_functions() {
+    let type_layouts_len = CANVAS.shared_vars.type_layouts().len();
+
+    let with_functions = |functions: Vec<CompTLFunction>, ranges: Vec<StartLen>| {
+        let functions = leak(TLFunctions::new(
+            leak_slice(functions),
+            leak_slice(ranges.into_iter().map(StartLen::to_u26).collect()),
+        ));
+        with_mono(|mono| {
+            mono.data = MonoTLData::derive_struct(CompTLFields::new(RSlice::EMPTY, Some(functions)))
+        })
+    };
+
+    assert_eq!(
+        validate(with_functions(vec![], vec![StartLen::new(0, 2)])),
+        Err(InvalidLayoutKind::OutOfBounds {
+            what: "field function pointers",
+            start: 0,
+            end: 2,
+            len: 0,
+        }),
+    );
+
+    let function =
+        |name: StartLen, return_type_layout: u16, param_type_layouts: TypeLayoutRange| {
+            CompTLFunction::new(
+                name.to_u26(),
+                0,
+                0,
+                0,
+                return_type_layout,
+                LifetimeRange::EMPTY.to_u21(),
+                param_type_layouts.to_u64(),
+            )
+        };
+
+    assert!(matches!(
+        validate(with_functions(
+            vec![function(StartLen::new(900, 1), !0, TypeLayoutRange::EMPTY)],
+            vec![]
+        )),
+        Err(InvalidLayoutKind::OutOfBounds {
+            what: "function name",
+            start: 900,
+            ..
+        })
+    ));
+
+    // The return type is `()` if its index is out of bounds.
+    validate(with_functions(
+        vec![function(StartLen::new(0, 1), 900, TypeLayoutRange::EMPTY)],
+        vec![],
+    ))
+    .unwrap();
+
+    assert!(matches!(
+        validate(with_functions(
+            vec![function(
+                StartLen::new(0, 1),
+                !0,
+                TypeLayoutRange::with_up_to_5(1, [900, 0, 0, 0, 0])
+            )],
+            vec![]
+        )),
+        Err(InvalidLayoutKind::OutOfBounds {
+            what: "function parameter type",
+            start: 900,
+            ..
+        })
+    ));
+
+    assert_eq!(
+        validate(with_functions(
+            vec![function(
+                StartLen::new(0, 1),
+                !0,
+                TypeLayoutRange::with_more_than_5(40, [0, 0, 0, 0, 0])
+            )],
+            vec![]
+        )),
+        Err(InvalidLayoutKind::OutOfBounds {
+            what: "function parameter types",
+            start: 1,
+            end: 36,
+            len: type_layouts_len,
+        }),
+    );
+}
+
+#[test]
+fn invalid_strings() {
+    const INVALID: &[u8] = b"\xFF\xFE";
+
+    let layout = with_mono(|mono| {
+        mono.name = INVALID.as_ptr();
+        mono.name_len = INVALID.len() as u16;
+    });
+    let err = validate_layout(layout, ValidationLimits::DEFAULT).unwrap_err();
+    assert_eq!(err.type_name, "<type with an invalid name>");
+    assert_eq!(
+        err.kind,
+        InvalidLayoutKind::InvalidUtf8 { what: "type name" }
+    );
+
+    let layout = with_mono(|mono| {
+        mono.shared_vars = MonoSharedVars::new(
+            unsafe { RStr::from_raw_parts(INVALID.as_ptr(), INVALID.len()) },
+            RSlice::EMPTY,
+        );
+    });
+    let err = validate_layout(layout, ValidationLimits::DEFAULT).unwrap_err();
+    assert_eq!(err.type_name, "Canvas");
+    assert_eq!(err.kind, InvalidLayoutKind::InvalidUtf8 { what: "strings" });
+
+    const MULTIBYTE: RStr<'static> = rstr!("ñ");
+    let layout = with_mono(|mono| {
+        mono.shared_vars = MonoSharedVars::new(MULTIBYTE, RSlice::EMPTY);
+        mono.data = MonoTLData::struct_(leak_slice(vec![field(
+            StartLen::new(1, 1),
+            LifetimeRange::EMPTY,
+            0,
+        )]));
+    });
+    assert_eq!(
+        validate(layout),
+        Err(InvalidLayoutKind::NotACharBoundary {
+            what: "field name",
+            start: 1,
+            end: 2,
+        }),
+    );
+}
+
+#[test]
+fn invalid_data() {
+    let layout = leak(TypeLayout {
+        data: GenericTLData::Opaque,
+        ..*CANVAS
+    });
+    assert_eq!(
+        validate(layout),
+        Err(InvalidLayoutKind::MismatchedData {
+            nongeneric: TLDataDiscriminant::Struct,
+            generic: TLDataDiscriminant::Opaque,
+        }),
+    );
+
+    let shape = <Shape as StableAbi>::LAYOUT;
+    let mut mono = *shape.mono;
+    if let MonoTLData::Enum(enum_) = &mut mono.data {
+        *enum_ = MonoTLEnum::new(enum_.variant_names, rslice![1, 1, 1], enum_.fields);
+    }
+    let layout = leak(TypeLayout {
+        mono: leak(mono),
+        ..*shape
+    });
+    assert_eq!(
+        validate(layout),
+        Err(InvalidLayoutKind::EnumFieldCount {
+            variant_fields: 3,
+            fields: 2,
+        }),
+    );
+}
+
+#[test]
+fn invalid_size_alignment() {
+    let layout = leak(TypeLayout {
+        alignment_power_of_two: 200,
+        ..*CANVAS
+    });
+    assert_eq!(
+        validate(layout),
+        Err(InvalidLayoutKind::InvalidAlignment { power_of_two: 200 }),
+    );
+
+    let layout = leak(TypeLayout {
+        size: 3,
+        ..*<u32 as StableAbi>::LAYOUT
+    });
+    assert_eq!(
+        validate(layout),
+        Err(InvalidLayoutKind::SizeNotMultipleOfAlignment {
+            size: 3,
+            alignment: 4,
+        }),
+    );
+}
+
+#[test]
+fn limits() {
+    let limits = |max_depth, max_layouts| ValidationLimits {
+        max_depth,
+        max_layouts,
+    };
+
+    // A type that references itself is only traversed once.
+    validate_layout(<Recursive as StableAbi>::LAYOUT, limits(6, 6)).unwrap();
+
+    let err = validate_layout(CANVAS, limits(2, 1000)).unwrap_err();
+    assert_eq!(err.kind, InvalidLayoutKind::TooDeep { max_depth: 2 });
+
+    let err = validate_layout(CANVAS, limits(1000, 3)).unwrap_err();
+    assert_eq!(
+        err.kind,
+        InvalidLayoutKind::TooManyLayouts { max_layouts: 3 }
+    );
+}
+
+#[test]
+fn checking_invalid_layouts() {
+    let invalid = with_fields(vec![field(StartLen::new(1000, 3), LifetimeRange::EMPTY, 0)]);
+
+    let err = check_layout_compatibility(CANVAS, invalid).unwrap_err();
+    let errs = err.flatten_errors();
+    assert_eq!(errs.len(), 1);
+    assert!(matches!(&errs[0], AbiInstability::InvalidLayout(e) if e.type_name == "Canvas"));
+
+    // Printing the errors doesn't touch the malformed layout.
+    assert!(err.to_string().contains("field name"));
+
+    let err = check_layout_compatibility(invalid, invalid).unwrap_err();
+    assert_eq!(err.flatten_errors().len(), 2);
+}
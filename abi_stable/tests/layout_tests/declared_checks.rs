@@ -0,0 +1,281 @@
+use abi_stable::{
+    abi_stability::{
+        abi_checking::{check_layout_compatibility_with_globals, AbiInstability, CheckingGlobals},
+        extra_checks::{DeclaredCheck, DeclaredCheckError, DeclaredCheckKind, DeclaredChecks},
+    },
+    marker_type::UnsafeIgnoredType,
+    std_types::RSlice,
+    tag,
+    type_layout::{Tag, TypeLayout},
+    GetStaticEquivalent, StableAbi,
+};
+
+use core_extensions::matches;
+
+#[repr(C)]
+#[derive(StableAbi)]
+#[sabi(
+    not_stableabi(V),
+    bound = "V:Version",
+    check_const(MAJOR = "V::MAJOR"),
+    check_subset(FEATURES = "V::FEATURES"),
+    check_ordered(COMMANDS = "V::COMMANDS")
+)]
+struct Interface<V> {
+    _marker: UnsafeIgnoredType<V>,
+}
+
+trait Version {
+    const MAJOR: i64;
+    const FEATURES: Tag;
+    const COMMANDS: Tag;
+}
+
+macro_rules! declare_versions {
+    (
+        $( $ty:ident = ($major:expr, $features:expr, $commands:expr); )*
+    ) => (
+        $(
+            #[derive(GetStaticEquivalent)]
+            struct $ty;
+
+            impl Version for $ty {
+                const MAJOR: i64 = $major;
+                const FEATURES: Tag = $features;
+                const COMMANDS: Tag = $commands;
+            }
+        )*
+    )
+}
+
+declare_versions! {
+    V1_0 = (1, tag!({ "a" }), tag!([ "open" ]));
+    V1_1 = (1, tag!({ "a", "b" }), tag!([ "open", "close" ]));
+    V1_1_Features = (1, tag!({ "a", "c" }), tag!([ "open", "close" ]));
+    V1_1_Commands = (1, tag!({ "a", "b" }), tag!([ "open", "read" ]));
+    V1_2 = (1, tag!({ "b", "a", "d" }), tag!([ "open", "close", "write" ]));
+    V2_0 = (2, tag!({ "a", "b" }), tag!([ "open", "close" ]));
+}
+
+const LAYOUT1_0: &TypeLayout = <Interface<V1_0> as StableAbi>::LAYOUT;
+const LAYOUT1_1: &TypeLayout = <Interface<V1_1> as StableAbi>::LAYOUT;
+const LAYOUT1_1F: &TypeLayout = <Interface<V1_1_Features> as StableAbi>::LAYOUT;
+const LAYOUT1_1C: &TypeLayout = <Interface<V1_1_Commands> as StableAbi>::LAYOUT;
+const LAYOUT1_2: &TypeLayout = <Interface<V1_2> as StableAbi>::LAYOUT;
+const LAYOUT2_0: &TypeLayout = <Interface<V2_0> as StableAbi>::LAYOUT;
+
+/// Different fields,and no `MAJOR` check.
+#[repr(C)]
+#[derive(StableAbi)]
+#[sabi(check_ordered(COMMANDS = r#"["open"]"#), check_subset(FEATURES = "[]"))]
+struct NoMajor;
+
+fn check(interface: &'static TypeLayout, implementation: &'static TypeLayout) -> bool {
+    let globals = CheckingGlobals::new();
+    match check_layout_compatibility_with_globals(interface, implementation, &globals) {
+        Ok(()) => true,
+        Err(e) => {
+            let errs = e.flatten_errors();
+            assert!(errs
+                .iter()
+                .any(|err| matches!(err, AbiInstability::ExtraCheckError { .. })));
+            false
+        }
+    }
+}
+
+#[test]
+fn generated_checks() {
+    let checks = LAYOUT1_1.extra_checks().unwrap();
+    let checks = unsafe { checks.obj.unchecked_downcast_as::<DeclaredChecks>() };
+
+    let names = checks
+        .checks()
+        .iter()
+        .map(|c| (c.name.as_str(), c.kind))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        vec![
+            ("MAJOR", DeclaredCheckKind::Const),
+            ("FEATURES", DeclaredCheckKind::Subset),
+            ("COMMANDS", DeclaredCheckKind::Ordered),
+        ],
+    );
+    assert_eq!(checks.get("MAJOR").unwrap().value.as_i64(), Some(1));
+    assert_eq!(checks.get("MISSING"), None);
+
+    let no_major = NoMajor::LAYOUT.extra_checks().unwrap();
+    let no_major = unsafe { no_major.obj.unchecked_downcast_as::<DeclaredChecks>() };
+    assert_eq!(
+        no_major.get("COMMANDS").unwrap().value.as_array(),
+        Some(&[Tag::str("open")][..]),
+    );
+    assert_eq!(
+        no_major.get("FEATURES").unwrap().value.as_set(),
+        Some(&[][..])
+    );
+
+    assert!(!no_major.to_string().is_empty());
+}
+
+#[test]
+fn compatibility() {
+    let compatible = [
+        (LAYOUT1_0, LAYOUT1_1),
+        (LAYOUT1_0, LAYOUT1_2),
+        (LAYOUT1_1, LAYOUT1_2),
+        (LAYOUT1_0, LAYOUT1_1F),
+        (LAYOUT1_0, LAYOUT1_1C),
+        (LAYOUT1_2, LAYOUT1_2),
+    ];
+    for (i, &(interface, implementation)) in compatible.iter().enumerate() {
+        assert!(check(interface, implementation), "index:{}", i);
+    }
+
+    let incompatible = [
+        // The interface has more features/commands.
+        (LAYOUT1_1, LAYOUT1_0),
+        (LAYOUT1_2, LAYOUT1_1),
+        // Features that aren't a superset.
+        (LAYOUT1_1, LAYOUT1_1F),
+        // Commands that don't start with the ones in the interface.
+        (LAYOUT1_1, LAYOUT1_1C),
+        // Different major version.
+        (LAYOUT1_1, LAYOUT2_0),
+        (LAYOUT2_0, LAYOUT1_1),
+    ];
+    for (i, &(interface, implementation)) in incompatible.iter().enumerate() {
+        assert!(!check(interface, implementation), "index:{}", i);
+    }
+}
+
+#[test]
+fn missing_check() {
+    let lay0 = LAYOUT1_0.extra_checks().unwrap();
+    let lay0 = unsafe { lay0.obj.unchecked_downcast_as::<DeclaredChecks>() };
+    let no_major = NoMajor::LAYOUT.extra_checks().unwrap();
+    let no_major = unsafe { no_major.obj.unchecked_downcast_as::<DeclaredChecks>() };
+
+    // Checks that are only in the implementation are ignored.
+    no_major.check_compatible(lay0).unwrap();
+    lay0.check_compatible(no_major).unwrap_err();
+}
+
+/// Subset and ordered checks whose values aren't collections.
+#[repr(C)]
+#[derive(StableAbi)]
+#[sabi(check_subset(FEATURES = "3"), check_ordered(COMMANDS = r#""open""#))]
+struct NotACollection;
+
+#[test]
+fn not_a_collection() {
+    assert!(!check(NotACollection::LAYOUT, NotACollection::LAYOUT));
+
+    let list = [
+        DeclaredCheck::subset("FEATURES", Tag::uint(3)),
+        DeclaredCheck::ordered("COMMANDS", Tag::str("open")),
+    ];
+    for check in &list {
+        let collection = Tag::arr(RSlice::from_slice(&[]));
+        let collection = DeclaredCheck {
+            value: collection,
+            ..*check
+        };
+
+        for &(l, r) in &[(check, check), (check, &collection), (&collection, check)] {
+            assert!(
+                matches!(
+                    l.check_compatible(r),
+                    Err(DeclaredCheckError::NotACollection { .. })
+                ),
+                "{} {}",
+                l,
+                r,
+            );
+        }
+    }
+}
+
+/// The same checks as `V1_1`,with unsigned integers and `Ignored` elements.
+#[repr(C)]
+#[derive(StableAbi)]
+#[sabi(
+    check_const(MAJOR = "Tag::uint(1)"),
+    check_subset(FEATURES = "MIXED_FEATURES"),
+    check_ordered(COMMANDS = r#"["open", "close"]"#)
+)]
+struct Mixed;
+
+const MIXED_FEATURES: Tag = Tag::set(abi_stable::rslice![
+    Tag::ignored(&Tag::str("a")),
+    Tag::str("b"),
+]);
+
+#[repr(C)]
+#[derive(StableAbi)]
+#[sabi(check_subset(NUMBERS = "[0, 1]"))]
+struct SignedNumbers;
+
+#[repr(C)]
+#[derive(StableAbi)]
+#[sabi(check_subset(NUMBERS = "NUMBERS"))]
+struct UnsignedNumbers;
+
+const NUMBERS: Tag = Tag::arr(abi_stable::rslice![
+    Tag::uint(1),
+    Tag::ignored(&Tag::uint(0)),
+]);
+
+#[test]
+fn normalized_values() {
+    let v1_0 = LAYOUT1_0.extra_checks().unwrap();
+    let v1_0 = unsafe { v1_0.obj.unchecked_downcast_as::<DeclaredChecks>() };
+    let v1_1 = LAYOUT1_1.extra_checks().unwrap();
+    let v1_1 = unsafe { v1_1.obj.unchecked_downcast_as::<DeclaredChecks>() };
+    let mixed = Mixed::LAYOUT.extra_checks().unwrap();
+    let mixed = unsafe { mixed.obj.unchecked_downcast_as::<DeclaredChecks>() };
+
+    v1_1.check_compatible(mixed).unwrap();
+    mixed.check_compatible(v1_1).unwrap();
+    mixed.check_compatible(v1_0).unwrap_err();
+
+    let signed = SignedNumbers::LAYOUT.extra_checks().unwrap();
+    let signed = unsafe { signed.obj.unchecked_downcast_as::<DeclaredChecks>() };
+    let unsigned = UnsignedNumbers::LAYOUT.extra_checks().unwrap();
+    let unsigned = unsafe { unsigned.obj.unchecked_downcast_as::<DeclaredChecks>() };
+
+    signed.check_compatible(unsigned).unwrap();
+    unsigned.check_compatible(signed).unwrap();
+}
+
+#[test]
+fn combined() {
+    {
+        let globals = CheckingGlobals::new();
+
+        check_layout_compatibility_with_globals(LAYOUT1_0, LAYOUT1_1, &globals).unwrap();
+        check_layout_compatibility_with_globals(LAYOUT1_1, LAYOUT1_2, &globals).unwrap();
+        check_layout_compatibility_with_globals(LAYOUT1_0, LAYOUT1_2, &globals).unwrap();
+    }
+    {
+        // V1_1_Features and V1_1 are both compatible with V1_0,
+        // but not with each other.
+        let globals = CheckingGlobals::new();
+
+        check_layout_compatibility_with_globals(LAYOUT1_0, LAYOUT1_1F, &globals).unwrap();
+        check_layout_compatibility_with_globals(LAYOUT1_0, LAYOUT1_1, &globals).unwrap_err();
+    }
+    {
+        let globals = CheckingGlobals::new();
+
+        check_layout_compatibility_with_globals(LAYOUT1_0, LAYOUT1_1C, &globals).unwrap();
+        check_layout_compatibility_with_globals(LAYOUT1_0, LAYOUT1_2, &globals).unwrap_err();
+    }
+}
+
+#[test]
+fn nested_type_layouts() {
+    let checks = LAYOUT1_1.extra_checks().unwrap();
+    assert!(checks.nested_type_layouts().is_empty());
+}
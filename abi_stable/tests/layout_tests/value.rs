@@ -307,8 +307,24 @@ fn same_different_abi_stability() {
         <RHashMap<RString, i32>>::LAYOUT,
         <RHashMap<i32, RString>>::LAYOUT,
         <RHashMap<i32, i32>>::LAYOUT,
+        <RHashSet<RString>>::LAYOUT,
+        <RHashSet<i32>>::LAYOUT,
+        <RBTreeMap<RString, RString>>::LAYOUT,
+        <RBTreeMap<i32, i32>>::LAYOUT,
+        <RBTreeSet<RString>>::LAYOUT,
+        <RBTreeSet<i32>>::LAYOUT,
         <RVec<()>>::LAYOUT,
         <RVec<i32>>::LAYOUT,
+        <RVecDeque<()>>::LAYOUT,
+        <RVecDeque<i32>>::LAYOUT,
+        <ROsStr<'_>>::LAYOUT,
+        <ROsString>::LAYOUT,
+        <RPath<'_>>::LAYOUT,
+        <RPathBuf>::LAYOUT,
+        <RCStr<'_>>::LAYOUT,
+        <RCString>::LAYOUT,
+        <RSystemTime>::LAYOUT,
+        <RInstant>::LAYOUT,
         <RSlice<'_, ()>>::LAYOUT,
         <RSlice<'_, i32>>::LAYOUT,
         <RSliceMut<'_, ()>>::LAYOUT,
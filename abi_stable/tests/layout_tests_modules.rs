@@ -27,6 +27,9 @@ mod layout_tests {
     #[cfg(all(test, not(feature = "only_new_tests")))]
     mod extra_checks_combined;
 
+    #[cfg(all(test, not(feature = "only_new_tests")))]
+    mod declared_checks;
+
     #[cfg(all(test, not(feature = "only_new_tests")))]
     mod stable_abi_attributes;
 
@@ -4,23 +4,177 @@ use super::*;
 
 use as_derive_utils::return_spanned_err;
 
-use syn::Ident;
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Expr, Ident, LitStr, Token,
+};
 
 use proc_macro2::Span;
 
-use abi_stable_shared::mangled_root_module_loader_name;
+use abi_stable_shared::{
+    mangled_lib_capabilities_name, mangled_lib_layout_name, mangled_lib_manifest_name,
+    mangled_lib_metadata_name, mangled_root_module_loader_name,
+};
 
 #[doc(hidden)]
-pub fn export_root_module_attr(_attr: TokenStream1, item: TokenStream1) -> TokenStream1 {
-    parse_or_compile_err(item, export_root_module_inner).into()
+pub fn export_root_module_attr(attr: TokenStream1, item: TokenStream1) -> TokenStream1 {
+    syn::parse::<ExportRootModuleArgs>(attr)
+        .and_then(|args| {
+            syn::parse::<ItemFn>(item).and_then(|item| export_root_module_inner(args, item))
+        })
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
 }
 
 #[cfg(test)]
-fn export_root_module_str(item: &str) -> Result<TokenStream2, syn::Error> {
-    syn::parse_str(item).and_then(export_root_module_inner)
+fn export_root_module_str(attr: &str, item: &str) -> Result<TokenStream2, syn::Error> {
+    let args = syn::parse_str::<ExportRootModuleArgs>(attr)?;
+    syn::parse_str(item).and_then(|item| export_root_module_inner(args, item))
 }
 
-fn export_root_module_inner(mut input: ItemFn) -> Result<TokenStream2, syn::Error> {
+/// The parameters of the `#[export_root_module(...)]` attribute.
+#[derive(Default)]
+struct ExportRootModuleArgs {
+    capabilities: Vec<Expr>,
+    manifest: Manifest,
+    serialized_layout: bool,
+}
+
+/// The parameters of `manifest(...)`.
+#[derive(Default)]
+struct Manifest {
+    display_name: Option<Expr>,
+    description: Option<Expr>,
+    authors: Vec<Expr>,
+    license: Option<Expr>,
+    homepage: Option<Expr>,
+    custom: Vec<(LitStr, Expr)>,
+}
+
+impl Parse for ExportRootModuleArgs {
+    fn parse(input: ParseStream<'_>) -> Result<Self, syn::Error> {
+        let mut this = Self::default();
+        while !input.is_empty() {
+            let param = input.parse::<Ident>()?;
+            if param == "serialized_layout" {
+                this.serialized_layout = true;
+                if !input.is_empty() {
+                    input.parse::<Token![,]>()?;
+                }
+                continue;
+            }
+            let content;
+            syn::parenthesized!(content in input);
+            if param == "capabilities" {
+                let list = Punctuated::<Expr, Token![,]>::parse_terminated(&content)?;
+                this.capabilities.extend(list);
+            } else if param == "manifest" {
+                this.manifest = content.parse::<Manifest>()?;
+            } else {
+                return_spanned_err!(param, "Unrecognized `#[export_root_module]` parameter");
+            }
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        Ok(this)
+    }
+}
+
+impl Parse for Manifest {
+    fn parse(input: ParseStream<'_>) -> Result<Self, syn::Error> {
+        let mut this = Self::default();
+        while !input.is_empty() {
+            let param = input.parse::<Ident>()?;
+            let field = if param == "display_name" {
+                &mut this.display_name
+            } else if param == "description" {
+                &mut this.description
+            } else if param == "license" {
+                &mut this.license
+            } else if param == "homepage" {
+                &mut this.homepage
+            } else if param == "authors" {
+                let content;
+                syn::parenthesized!(content in input);
+                let list = Punctuated::<Expr, Token![,]>::parse_terminated(&content)?;
+                this.authors.extend(list);
+                if !input.is_empty() {
+                    input.parse::<Token![,]>()?;
+                }
+                continue;
+            } else if param == "custom" {
+                let content;
+                syn::parenthesized!(content in input);
+                while !content.is_empty() {
+                    let key = content.parse::<LitStr>()?;
+                    content.parse::<Token![=]>()?;
+                    this.custom.push((key, content.parse::<Expr>()?));
+                    if !content.is_empty() {
+                        content.parse::<Token![,]>()?;
+                    }
+                }
+                if !input.is_empty() {
+                    input.parse::<Token![,]>()?;
+                }
+                continue;
+            } else {
+                return_spanned_err!(param, "Unrecognized `manifest(...)` parameter");
+            };
+            if field.is_some() {
+                return_spanned_err!(param, "`{}` was already specified", param);
+            }
+            input.parse::<Token![=]>()?;
+            *field = Some(input.parse::<Expr>()?);
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        Ok(this)
+    }
+}
+
+impl Manifest {
+    /// Outputs the expression that constructs the `LibManifest`.
+    fn tokens(&self) -> TokenStream2 {
+        let optional = [
+            ("with_display_name", &self.display_name),
+            ("with_description", &self.description),
+            ("with_license", &self.license),
+            ("with_homepage", &self.homepage),
+        ];
+        let setters = optional.iter().filter_map(|(method, value)| {
+            let method = Ident::new(method, Span::call_site());
+            value.as_ref().map(|value| quote!( .#method(#value) ))
+        });
+
+        let authors = &self.authors;
+        let custom_keys = self.custom.iter().map(|(key, _)| key);
+        let custom_values = self.custom.iter().map(|(_, value)| value);
+
+        quote!(
+            ::abi_stable::library::LibManifest::new()
+                #(#setters)*
+                .with_authors(::abi_stable::rslice![
+                    #( ::abi_stable::std_types::RStr::from_str(#authors) ),*
+                ])
+                .with_custom(::abi_stable::rslice![
+                    #(
+                        ::abi_stable::std_types::Tuple2(
+                            ::abi_stable::std_types::RStr::from_str(#custom_keys),
+                            ::abi_stable::std_types::RStr::from_str(#custom_values),
+                        )
+                    ),*
+                ])
+        )
+    }
+}
+
+fn export_root_module_inner(
+    args: ExportRootModuleArgs,
+    mut input: ItemFn,
+) -> Result<TokenStream2, syn::Error> {
     let vis = &input.vis;
 
     let unsafe_no_layout_constant_path =
@@ -51,6 +205,42 @@ fn export_root_module_inner(mut input: ItemFn) -> Result<TokenStream2, syn::Erro
     let original_fn_ident = &input.sig.ident;
 
     let export_name = Ident::new(&mangled_root_module_loader_name(), Span::call_site());
+    let metadata_name = Ident::new(&mangled_lib_metadata_name(), Span::call_site());
+    let capabilities_name = Ident::new(&mangled_lib_capabilities_name(), Span::call_site());
+    let capabilities = &args.capabilities;
+    let manifest_name = Ident::new(&mangled_lib_manifest_name(), Span::call_site());
+    let manifest = args.manifest.tokens();
+
+    let serialized_layout = if args.serialized_layout {
+        let layout_name = Ident::new(&mangled_lib_layout_name(), Span::call_site());
+        quote!(
+            // The layout of the root module,serialized by the build script with
+            // `abi_stable::library::write_serialized_layout`.
+            #[no_mangle]
+            #[cfg_attr(
+                any(
+                    target_os = "linux",
+                    target_os = "android",
+                    target_os = "freebsd",
+                    target_os = "dragonfly",
+                    target_os = "netbsd",
+                    target_os = "openbsd",
+                    target_os = "solaris",
+                    target_os = "illumos"
+                ),
+                link_section = ".sabi_layout"
+            )]
+            #vis static #layout_name: [u8; include_bytes!(concat!(
+                env!("OUT_DIR"),
+                "/sabi_serialized_layout.txt"
+            )).len()] = *include_bytes!(concat!(
+                env!("OUT_DIR"),
+                "/sabi_serialized_layout.txt"
+            ));
+        )
+    } else {
+        TokenStream2::new()
+    };
 
     Ok(quote!(
         #input
@@ -70,6 +260,44 @@ fn export_root_module_inner(mut input: ItemFn) -> Result<TokenStream2, syn::Erro
                 )
             }
         };
+
+        // Lets the metadata of the library be read without loading it,
+        // with `abi_stable::library::lib_metadata_from_path`.
+        #[no_mangle]
+        #[cfg_attr(
+            any(
+                target_os = "linux",
+                target_os = "android",
+                target_os = "freebsd",
+                target_os = "dragonfly",
+                target_os = "netbsd",
+                target_os = "openbsd",
+                target_os = "solaris",
+                target_os = "illumos"
+            ),
+            link_section = ".sabi_metadata"
+        )]
+        #vis static #metadata_name: ::abi_stable::library::LibMetadataSection = {
+            type __SABI_Module = <#ret_ty as ::abi_stable::library::IntoRootModuleResult>::Module;
+            ::abi_stable::__lib_metadata_section!(
+                <__SABI_Module as ::abi_stable::library::RootModule>::#assoc_constant.erased()
+            )
+        };
+
+        // Lets the host check the capabilities that the library requests
+        // before initializing the root module.
+        #[no_mangle]
+        #vis static #capabilities_name: ::abi_stable::library::LibCapabilities =
+            ::abi_stable::library::LibCapabilities::new(
+                ::abi_stable::rslice![ #( #capabilities ),* ]
+            );
+
+        // Lets the host read descriptive metadata about the library
+        // without initializing the root module.
+        #[no_mangle]
+        #vis static #manifest_name: ::abi_stable::library::LibManifest = #manifest;
+
+        #serialized_layout
     ))
 }
 
@@ -121,13 +349,115 @@ mod tests {
         ];
 
         for (item, expected_const) in list {
-            let str_out = export_root_module_str(item)
+            let str_out = export_root_module_str("", item)
                 .unwrap()
                 .to_string()
                 .chars()
                 .filter(|c| !c.is_whitespace())
                 .collect::<String>();
             assert!(str_out.contains(expected_const));
+            assert!(str_out.contains("link_section=\".sabi_metadata\""));
+            assert!(str_out.contains(&format!(
+                "__lib_metadata_section!(<__SABI_Moduleas::abi_stable::library::RootModule>::{}.erased())",
+                expected_const.trim_start_matches("RootModule>::"),
+            )));
+            assert!(str_out.contains("LibCapabilities::new(::abi_stable::rslice![])"));
+            assert!(!str_out.contains(".sabi_layout"));
+        }
+    }
+
+    #[test]
+    fn test_serialized_layout() {
+        for attr in &["serialized_layout", "serialized_layout, capabilities()"] {
+            let str_out = export_root_module_str(attr, "pub fn hello()->RString{}")
+                .unwrap()
+                .to_string()
+                .chars()
+                .filter(|c| !c.is_whitespace())
+                .collect::<String>();
+            assert!(str_out.contains("link_section=\".sabi_layout\""));
+            assert!(str_out.contains(
+                "*include_bytes!(concat!(env!(\"OUT_DIR\"),\"/sabi_serialized_layout.txt\"))"
+            ));
+        }
+
+        export_root_module_str("serialized_layout()", "pub fn hello()->RString{}").unwrap_err();
+    }
+
+    #[test]
+    fn test_capabilities() {
+        let str_out = export_root_module_str(
+            "capabilities(Capability::NETWORK, Capability::new(\"host:database\"),)",
+            "pub fn hello()->RString{}",
+        )
+        .unwrap()
+        .to_string()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>();
+        assert!(str_out.contains(
+            "LibCapabilities::new(::abi_stable::rslice![\
+             Capability::NETWORK,Capability::new(\"host:database\")])"
+        ));
+
+        for attr in &[
+            "capabilities",
+            "capability(Capability::NETWORK)",
+            "capabilities()()",
+        ] {
+            export_root_module_str(attr, "pub fn hello()->RString{}").unwrap_err();
+        }
+    }
+
+    #[test]
+    fn test_manifest() {
+        let str_out = |attr: &str| {
+            export_root_module_str(attr, "pub fn hello()->RString{}").map(|out| {
+                out.to_string()
+                    .chars()
+                    .filter(|c| !c.is_whitespace())
+                    .collect::<String>()
+            })
+        };
+
+        let out = str_out(
+            r#"manifest(
+                display_name = "Hello",
+                authors("foo", env!("BAR")),
+                license = LICENSE,
+                custom("repository" = "https://example.com", "build" = BUILD),
+            )"#,
+        )
+        .unwrap();
+        assert!(out.contains(
+            "::abi_stable::library::LibManifest::new()\
+             .with_display_name(\"Hello\")\
+             .with_license(LICENSE)\
+             .with_authors(::abi_stable::rslice![\
+             ::abi_stable::std_types::RStr::from_str(\"foo\"),\
+             ::abi_stable::std_types::RStr::from_str(env!(\"BAR\"))])"
+        ));
+        assert!(out.contains(
+            "::abi_stable::std_types::Tuple2(\
+             ::abi_stable::std_types::RStr::from_str(\"repository\"),\
+             ::abi_stable::std_types::RStr::from_str(\"https://example.com\"),)"
+        ));
+        assert!(!out.contains("with_description"));
+
+        let out = str_out("").unwrap();
+        assert!(out.contains(
+            "::abi_stable::library::LibManifest::new()\
+             .with_authors(::abi_stable::rslice![])\
+             .with_custom(::abi_stable::rslice![])"
+        ));
+
+        for attr in &[
+            "manifest(name = \"Hello\")",
+            "manifest(license = \"MIT\", license = \"MIT\")",
+            "manifest(custom(repository = \"https://example.com\"))",
+            "manifest(homepage)",
+        ] {
+            str_out(attr).unwrap_err();
         }
     }
 }
@@ -214,6 +214,7 @@ usable_traits! {
     io_read=(IoRead,"::std::io::Read",false,UB::DYN_TRAIT),
     io_buf_read=(IoBufRead,"::std::io::BufRead",false,UB::DYN_TRAIT),
     error=(Error,"::std::error::Error",false,UB::ROBJECT_AND_DYN_TRAIT),
+    stream=(Stream,"::futures_core::Stream",false,UB::DYN_TRAIT),
 }
 
 pub(crate) fn private_associated_type() -> syn::Ident {
@@ -35,7 +35,7 @@ use self::{
     lifetime_unelider::LifetimeUnelider,
     method_where_clause::MethodWhereClause,
     methods_tokenizer::MethodsTokenizer,
-    trait_definition::{TraitDefinition, TraitMethod},
+    trait_definition::{AsyncMethod, TraitDefinition, TraitMethod},
 };
 
 /// Variables passed to all the `*_items` functions here.
@@ -372,6 +372,13 @@ fn first_items(
 
         #[doc=#trait_to_docs]
         #[repr(transparent)]
+        // `_marker` is a zero-sized marker type defined in `abi_stable`,
+        // newer compilers lint against it because it has private fields.
+        #[allow(
+            unknown_lints,
+            repr_transparent_external_private_fields,
+            repr_transparent_non_zst_fields,
+        )]
         #[derive(::abi_stable::StableAbi)]
         #[sabi(bound=#used_to_bound)]
         #submod_vis struct #trait_to<#to_params>
@@ -77,9 +77,23 @@ impl<'a> ToTokens for MethodTokenizer<'a> {
             .as_ref()
             .filter(|_| !method.disable_inherent_default);
 
-        let lifetimes = Some(&method.lifetimes)
-            .filter(|l| !l.is_empty())
-            .into_iter();
+        // Methods returning futures return them boxed everywhere except in the trait
+        // and its impl for the trait object,
+        // with every borrow in the parameters unified into the lifetime of `&self`.
+        let boxed_future = method
+            .asyncness
+            .as_ref()
+            .filter(|_| !matches!(which_item, WhichItem::Trait | WhichItem::TraitImpl));
+
+        let async_token = method
+            .asyncness
+            .as_ref()
+            .and_then(|x| x.async_token)
+            .filter(|_| boxed_future.is_none());
+
+        let mut lifetimes = method.lifetimes.clone();
+        lifetimes.extend(boxed_future.and_then(|x| x.lifetime_def));
+        let lifetimes = Some(&lifetimes).filter(|l| !l.is_empty()).into_iter();
 
         let method_name = method.name;
         let method_span = method_name.span();
@@ -102,7 +116,18 @@ impl<'a> ToTokens for MethodTokenizer<'a> {
             }
         }
 
-        let self_param = match (is_method, &method.self_param) {
+        let self_param = match boxed_future {
+            Some(boxed_future) => match method.self_param {
+                SelfParam::ByRef { is_mutable, .. } => SelfParam::ByRef {
+                    lifetime: Some(boxed_future.lifetime),
+                    is_mutable,
+                },
+                SelfParam::ByVal => SelfParam::ByVal,
+            },
+            None => method.self_param.clone(),
+        };
+
+        let self_param = match (is_method, &self_param) {
             (
                 true,
                 SelfParam::ByRef {
@@ -159,11 +184,46 @@ impl<'a> ToTokens for MethodTokenizer<'a> {
                 }
             })
         });
-        let param_ty = method.params.iter().map(|param| &param.ty);
+        let param_ty: Vec<&syn::Type> = match boxed_future {
+            Some(boxed_future) => boxed_future.param_tys.iter().collect(),
+            None => method.params.iter().map(|param| &param.ty).collect(),
+        };
         let param_names_c = param_names_a.clone();
         let param_names_d = param_names_a.clone();
         let param_names_e = method.params.iter().map(|x| x.pattern);
-        let return_ty = method.output.iter();
+        let boxed_future_ty = boxed_future.map(|boxed_future| {
+            let lifetime = boxed_future.lifetime;
+            let output = &boxed_future.output;
+            if boxed_future.is_send {
+                quote_spanned!(method_span=> __sabi_re::RBoxFuture<#lifetime, #output>)
+            } else {
+                quote_spanned!(method_span=> __sabi_re::LocalRBoxFuture<#lifetime, #output>)
+            }
+        });
+        // The vtable functions can't have bounds on their lifetimes,
+        // so this zero-sized parameter implies that everything the future captures
+        // outlives it, letting it be boxed with a bounded constructor.
+        let outlives_param = boxed_future
+            .filter(|_| matches!(which_item, WhichItem::VtableDecl | WhichItem::VtableImpl))
+            .map(|boxed_future| {
+                let lifetime = boxed_future.lifetime;
+                let trait_lts = trait_def.generics.lifetimes().map(|x| &x.lifetime);
+                let trait_tys = trait_def.generics.type_params().map(|x| &x.ident);
+                quote_spanned!(method_span=>
+                    _outlives: ::std::marker::PhantomData<(
+                        & #lifetime _Self,
+                        #( & #lifetime & #trait_lts (), )*
+                        #( & #lifetime #trait_tys, )*
+                    )>
+                )
+            });
+        let outlives_arg =
+            boxed_future.map(|_| quote_spanned!(method_span=> ::std::marker::PhantomData));
+        let return_ty: Option<TokenStream2> = match &boxed_future_ty {
+            Some(ty) => Some(ty.clone()),
+            None => method.output.as_ref().map(ToTokens::to_token_stream),
+        };
+        let return_ty = return_ty.iter();
 
         let self_is_sized_bound = Some(&ctokens.self_sized)
             .filter(|_| is_method && method.self_param == SelfParam::ByVal);
@@ -197,6 +257,7 @@ impl<'a> ToTokens for MethodTokenizer<'a> {
                     unsafe extern "C" fn(
                         #self_param,
                         #( #param_names_a:#param_ty ,)*
+                        #outlives_param
                     ) #(-> #return_ty )*
             )
         } else {
@@ -225,9 +286,10 @@ impl<'a> ToTokens for MethodTokenizer<'a> {
                 #[allow(clippy::let_and_return)]
                 #(#[#other_attrs])*
                 #inherent_method_docs
-                #vis #unsafety #abi fn #method_name #(< #(#lifetimes,)* >)* (
+                #vis #unsafety #async_token #abi fn #method_name #(< #(#lifetimes,)* >)* (
                     #self_param,
                     #( #param_names_a:#param_ty ,)*
+                    #outlives_param
                 ) #(-> #return_ty )*
                 where
                     #self_is_sized_bound
@@ -252,8 +314,9 @@ impl<'a> ToTokens for MethodTokenizer<'a> {
                 method.semicolon.to_tokens(ts);
             }
             (WhichItem::TraitImpl, _) => {
+                let await_ = async_token.map(|_| quote_spanned!(method_span=> .await ));
                 ts.append_all(quote_spanned!(method_span=>{
-                    self.#method_name(#(#param_names_c,)*)
+                    self.#method_name(#(#param_names_c,)*) #await_
                 }));
             }
             (WhichItem::TraitObjectImpl, _) => {
@@ -262,14 +325,14 @@ impl<'a> ToTokens for MethodTokenizer<'a> {
                         is_mutable: false, ..
                     } => {
                         quote_spanned!(method_span=>
-                            __method(self.obj.sabi_as_rref(),#(#param_names_c,)*)
+                            __method(self.obj.sabi_as_rref(),#(#param_names_c,)* #outlives_arg)
                         )
                     }
                     SelfParam::ByRef {
                         is_mutable: true, ..
                     } => {
                         quote_spanned!(method_span=>
-                            __method(self.obj.sabi_as_rmut(),#(#param_names_c,)*)
+                            __method(self.obj.sabi_as_rmut(),#(#param_names_c,)* #outlives_arg)
                         )
                     }
                     SelfParam::ByVal => {
@@ -287,6 +350,34 @@ impl<'a> ToTokens for MethodTokenizer<'a> {
                 match default_ {
                     Some(default_) => {
                         let block = &default_.block;
+                        let block = match (&boxed_future_ty, &method.asyncness) {
+                            (
+                                Some(future_ty),
+                                Some(AsyncMethod {
+                                    async_token: Some(_),
+                                    ..
+                                }),
+                            ) => {
+                                quote_spanned!(method_span=>
+                                    <#future_ty>::new(async move {
+                                        #( let #param_names_e=#param_names_d; )*
+                                        #block
+                                    })
+                                )
+                            }
+                            (Some(future_ty), _) => {
+                                quote_spanned!(method_span=>
+                                    #( let #param_names_e=#param_names_d; )*
+                                    <#future_ty>::new(#block)
+                                )
+                            }
+                            (None, _) => {
+                                quote_spanned!(method_span=>
+                                    #( let #param_names_e=#param_names_d; )*
+                                    #block
+                                )
+                            }
+                        };
                         ts.append_all(quote_spanned!(method_span=>
                                 #ptr_constraint
                             {
@@ -297,9 +388,6 @@ impl<'a> ToTokens for MethodTokenizer<'a> {
                                         }
                                     }
                                     None=>{
-                                        #(
-                                            let #param_names_e=#param_names_d;
-                                        )*
                                         #block
                                     }
                                 }
@@ -347,6 +435,11 @@ impl<'a> ToTokens for MethodTokenizer<'a> {
                     None => quote_spanned!(method_span=> ret ),
                 };
 
+                let transmute_ret = match &boxed_future_ty {
+                    Some(future_ty) => quote_spanned!(method_span=> <#future_ty>::new(ret) ),
+                    None => transmute_ret,
+                };
+
                 ts.append_all(quote_spanned!(method_span=>{
                     let ret = ::abi_stable::extern_fn_panic_handling!{no_early_return;
                         __Trait::#method_name(
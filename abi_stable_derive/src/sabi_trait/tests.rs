@@ -31,6 +31,32 @@ fn must_not_pass() {
                 const X: usize;
             }
         ",
+        // Futures can only borrow the trait object.
+        "
+            trait Qux {
+                async fn qux(self);
+            }
+        ",
+        "
+            trait Qux {
+                fn qux(&self) -> impl std::fmt::Debug;
+            }
+        ",
+        // Futures can't borrow for lifetimes other than that of `&self`.
+        "
+            trait Qux {
+                async fn qux<'a>(&self, x: &'a str);
+            }
+        ",
+        "
+            trait Qux {
+                fn qux<'a, 's>(&'s self, x: &'a str) -> impl Future<Output = ()> + Send;
+            }
+        ",
+        "
+            #[sabi(use_dyntrait)]
+            trait Events: Stream<Item = u32> {}
+        ",
     ];
     for elem in list {
         if derive_sabi_trait(elem).is_ok() {
@@ -52,6 +78,18 @@ fn must_pass() {
                 fn baz(self);
             }
         ",
+        "
+            trait AsyncBaz {
+                async fn baz(&self, x: &str) -> u32;
+                async fn qux(&mut self) {}
+                fn send(&self) -> impl Future<Output = u32> + Send;
+            }
+        ",
+        "
+            trait AsyncGenerics<'a, T> {
+                async fn baz<'s>(&'s self, x: &'s str, y: &'a T) -> u32;
+            }
+        ",
     ];
 
     for elem in list {
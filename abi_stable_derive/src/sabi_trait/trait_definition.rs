@@ -415,6 +415,10 @@ pub(crate) struct TraitMethod<'a> {
     /// Whether the return type borrows from self
     pub(crate) return_borrow_kind: Option<BorrowKind>,
 
+    /// Describes the future that this method returns,
+    /// if it's an `async fn` or returns an `impl Future`.
+    pub(crate) asyncness: Option<AsyncMethod<'a>>,
+
     pub(crate) where_clause: MethodWhereClause<'a>,
     /// The default implementation of the method.
     pub(crate) default: Option<DefaultMethod<'a>>,
@@ -440,6 +444,26 @@ pub(crate) struct MethodParam<'a> {
     pub(crate) pattern: &'a syn::Pat,
 }
 
+/// Describes a method that returns a future,
+/// which is returned as an `RBoxFuture`/`LocalRBoxFuture` from the vtable.
+#[derive(Debug, Clone)]
+pub(crate) struct AsyncMethod<'a> {
+    /// The `async` keyword,if this is an `async fn`.
+    pub(crate) async_token: Option<&'a syn::token::Async>,
+    /// Whether the future is `Send`,
+    /// true when the method returns `impl Future<Output = T> + Send`.
+    pub(crate) is_send: bool,
+    /// The `Future::Output` type.
+    pub(crate) output: syn::Type,
+    /// The lifetime of the boxed future,which is the lifetime of the `&self` parameter.
+    pub(crate) lifetime: &'a Lifetime,
+    /// The declaration of `lifetime`,if the method doesn't declare it.
+    pub(crate) lifetime_def: Option<&'a LifetimeDef>,
+    /// The types of the parameters,with elided lifetimes replaced with `lifetime`,
+    /// since everything that the future borrows must outlive it.
+    pub(crate) param_tys: Vec<syn::Type>,
+}
+
 impl<'a> TraitMethod<'a> {
     pub fn new(
         mwa: MethodWithAttrs<'a>,
@@ -546,6 +570,12 @@ impl<'a> TraitMethod<'a> {
             });
         }
 
+        let asyncness = AsyncMethod::new(decl, &self_param, &params, output.as_ref(), arena)
+            .unwrap_or_else(|e| {
+                errors.push_err(e);
+                None
+            });
+
         errors.into_result()?;
 
         Ok(Some(Self {
@@ -560,6 +590,7 @@ impl<'a> TraitMethod<'a> {
             params,
             output,
             return_borrow_kind,
+            asyncness,
             where_clause,
             default,
             semicolon: mwa.item.semi_token.as_ref(),
@@ -581,11 +612,17 @@ impl<'a> TraitMethod<'a> {
     {
         let mut errors = LinearResult::ok(());
 
+        let async_tys = self
+            .asyncness
+            .iter_mut()
+            .flat_map(|x| x.param_tys.iter_mut().chain(iter::once(&mut x.output)));
+
         for param in self
             .params
             .iter_mut()
             .map(|x| &mut x.ty)
             .chain(self.output.as_mut())
+            .chain(async_tys)
         {
             replace_self_path::replace_self_path(param, replace_with.clone(), &mut is_assoc_type)
                 .combine_into_err(&mut errors);
@@ -594,6 +631,120 @@ impl<'a> TraitMethod<'a> {
     }
 }
 
+impl<'a> AsyncMethod<'a> {
+    /// Returns `None` if the method is neither an `async fn`,
+    /// nor returns an `impl Future`.
+    fn new(
+        decl: &'a syn::Signature,
+        self_param: &SelfParam<'a>,
+        params: &[MethodParam<'a>],
+        output: Option<&syn::Type>,
+        arena: &'a Arenas,
+    ) -> Result<Option<Self>, syn::Error> {
+        let (output, is_send) = match (&decl.asyncness, output) {
+            (Some(_), Some(output)) => (output.clone(), false),
+            (Some(_), None) => (syn::parse_quote!(()), false),
+            (None, Some(syn::Type::ImplTrait(impl_trait))) => future_output(impl_trait)?,
+            (None, _) => return Ok(None),
+        };
+
+        let mut self_lifetime = match self_param {
+            SelfParam::ByRef { lifetime, .. } => *lifetime,
+            SelfParam::ByVal => return_spanned_err!(
+                decl.ident,
+                "Methods returning futures in #[sabi_trait] traits \
+                 must take `&self` or `&mut self`.",
+            ),
+        };
+
+        // Other lifetimes could end before the future,
+        // since it's only required to live as long as `&self`.
+        if let Some(lt) = decl
+            .generics
+            .lifetimes()
+            .find(|lt| Some(&lt.lifetime) != self_lifetime)
+        {
+            return_spanned_err!(
+                lt,
+                "Methods returning futures in #[sabi_trait] traits \
+                 can only declare the lifetime of `&self`.",
+            )
+        }
+
+        let mut lifetime_def = None::<&'a LifetimeDef>;
+
+        let param_tys = params
+            .iter()
+            .map(|param| {
+                let mut ty = param.ty.clone();
+                let props = LifetimeUnelider::new(&mut self_lifetime).visit_type(&mut ty);
+                lifetime_def = lifetime_def.or(props.additional_lifetime_def);
+                ty
+            })
+            .collect();
+
+        let lifetime = match self_lifetime {
+            Some(lt) => lt,
+            None => {
+                let def = arena.alloc(syn::parse_str::<LifetimeDef>("'_self")?);
+                lifetime_def = Some(def);
+                &def.lifetime
+            }
+        };
+
+        Ok(Some(Self {
+            async_token: decl.asyncness.as_ref(),
+            is_send,
+            output,
+            lifetime,
+            lifetime_def,
+            param_tys,
+        }))
+    }
+}
+
+/// Gets the `Output` type of an `impl Future<Output = T>` type,
+/// and whether it has a `Send` bound.
+fn future_output(impl_trait: &syn::TypeImplTrait) -> Result<(syn::Type, bool), syn::Error> {
+    let mut output = None::<syn::Type>;
+    let mut is_send = false;
+
+    for bound in &impl_trait.bounds {
+        let last_segment = match bound {
+            TypeParamBound::Trait(x) => x.path.segments.last(),
+            TypeParamBound::Lifetime(_) => None,
+        };
+        let last_segment = match last_segment {
+            Some(x) => x,
+            None => continue,
+        };
+
+        if last_segment.ident == "Send" {
+            is_send = true;
+        } else if last_segment.ident == "Future" {
+            if let syn::PathArguments::AngleBracketed(args) = &last_segment.arguments {
+                for arg in &args.args {
+                    match arg {
+                        syn::GenericArgument::Binding(b) if b.ident == "Output" => {
+                            output = Some(b.ty.clone());
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+
+    match output {
+        Some(output) => Ok((output, is_send)),
+        None => Err(spanned_err!(
+            impl_trait,
+            "Expected an `impl Future<Output = T>` return type,\
+             which is the only `impl Trait` type that #[sabi_trait] methods can return.",
+        )),
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 
 /// Used to print the generic parameters of a trait,
@@ -802,6 +953,12 @@ where
                                     "Serialize is not currently supported."
                                 ));
                             }
+                            WhichTrait::Stream => {
+                                errors.push_err(spanned_err!(
+                                    trait_bound.path,
+                                    "Stream is not currently supported."
+                                ));
+                            }
                             WhichTrait::Eq | WhichTrait::PartialOrd => {
                                 set_impld(&mut trait_struct.partial_eq, span);
                             }
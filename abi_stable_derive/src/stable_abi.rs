@@ -50,8 +50,8 @@ mod tests;
 
 use self::{
     attribute_parsing::{
-        parse_attrs_for_stable_abi, ASTypeParamBound, ConstIdents, LayoutConstructor,
-        StabilityKind, StableAbiOptions,
+        parse_attrs_for_stable_abi, ASTypeParamBound, ConstIdents, DeclaredCheckKind,
+        LayoutConstructor, StabilityKind, StableAbiOptions,
     },
     common_tokens::CommonTokens,
     nonexhaustive::{tokenize_enum_info, tokenize_nonexhaustive_items},
@@ -176,17 +176,66 @@ pub(crate) fn derive(mut data: DeriveInput) -> Result<TokenStream2, syn::Error>
         }
     }
 
+    let declared_checks_const;
+    let extra_checks_ref;
+    match (&config.extra_checks, config.declared_checks.is_empty()) {
+        (Some(extra_checks), _) => {
+            declared_checks_const = TokenStream2::new();
+            extra_checks_ref = Some(quote!(&#extra_checks));
+        }
+        (None, false) => {
+            let checks = config.declared_checks.iter().map(|check| {
+                let name = check.name.to_string();
+                let to_tag = |value: &syn::Expr| quote!(__sabi_re::FromLiteral(#value).to_tag());
+                let value = match (check.kind, &check.value) {
+                    (DeclaredCheckKind::Const, value) => to_tag(value),
+                    (kind, syn::Expr::Array(array)) => {
+                        let ctor = if kind == DeclaredCheckKind::Subset {
+                            quote!(set)
+                        } else {
+                            quote!(arr)
+                        };
+                        let elems = array.elems.iter().map(to_tag);
+                        quote!(
+                            __sabi_re::Tag::#ctor(__sabi_re::RSlice::from_slice(&[ #(#elems,)* ]))
+                        )
+                    }
+                    (_, value) => to_tag(value),
+                };
+                let ctor = match check.kind {
+                    DeclaredCheckKind::Const => quote!(const_),
+                    DeclaredCheckKind::Subset => quote!(subset),
+                    DeclaredCheckKind::Ordered => quote!(ordered),
+                };
+                quote!( __sabi_re::DeclaredCheck::#ctor(#name, #value) )
+            });
+            declared_checks_const = quote!(
+                const __SABI_DECLARED_CHECKS: &'static __sabi_re::DeclaredChecks =
+                    &__sabi_re::DeclaredChecks::new(
+                        __sabi_re::RSlice::from_slice(&[ #(#checks,)* ])
+                    );
+            );
+            extra_checks_ref = Some(quote!(Self::__SABI_DECLARED_CHECKS));
+        }
+        (None, true) => {
+            declared_checks_const = TokenStream2::new();
+            extra_checks_ref = None;
+        }
+    }
+
     let extra_checks_const;
     let extra_checks_arg;
-    match &config.extra_checks {
+    match &extra_checks_ref {
         Some(extra_checks) => {
             extra_checks_const = quote!(
+                #declared_checks_const
+
                 const __SABI_EXTRA_CHECKS:
                     &'static ::std::mem::ManuallyDrop<__sabi_re::StoredExtraChecks>
                 =
                     &std::mem::ManuallyDrop::new(
                         __sabi_re::StoredExtraChecks::from_const(
-                            &#extra_checks,
+                            #extra_checks,
                             __sabi_re::TD_Opaque,
                             __sabi_re::ExtraChecks_MV::VTABLE,
                         )
@@ -425,6 +474,12 @@ pub(crate) fn derive(mut data: DeriveInput) -> Result<TokenStream2, syn::Error>
     let generics_header =
         GenParamsIn::with_after_types(ds.generics, InWhat::ImplHeader, storage_opt);
 
+    if let StabilityKind::Prefix(prefix) = &config.kind {
+        if config.with_prefix_extra {
+            prefix.push_extra_info(config, shared_vars);
+        }
+    }
+
     shared_vars.extract_errs()?;
 
     let mono_shared_vars_tokenizer = shared_vars.mono_shared_vars_tokenizer();
@@ -54,6 +54,7 @@ pub(crate) struct StableAbiOptions<'a> {
 
     pub(crate) tags: Option<syn::Expr>,
     pub(crate) extra_checks: Option<syn::Expr>,
+    pub(crate) declared_checks: Vec<DeclaredCheck>,
 
     pub(crate) layout_ctor: FieldMap<LayoutConstructor>,
 
@@ -64,6 +65,11 @@ pub(crate) struct StableAbiOptions<'a> {
 
     pub(crate) doc_hidden_attr: Option<&'a TokenStream2>,
 
+    /// The doc comments of the type.
+    pub(crate) docs: String,
+    /// The doc comments of each field.
+    pub(crate) field_docs: FieldMap<String>,
+
     pub(crate) mod_refl_mode: ModReflMode<usize>,
 
     pub(crate) impl_interfacetype: Option<ImplInterfaceType>,
@@ -76,6 +82,9 @@ pub(crate) struct StableAbiOptions<'a> {
 
     pub(crate) allow_type_macros: bool,
     pub(crate) with_field_indices: bool,
+    /// Whether to store the doc comments and the `#[sabi(missing_field(..))]` attributes
+    /// of a prefix type in its layout.
+    pub(crate) with_prefix_extra: bool,
 }
 
 //////////////////////
@@ -88,6 +97,24 @@ pub struct ConstIdents {
 
 //////////////////////
 
+/// A check declared with the
+/// `#[sabi(check_const(..))]`/`#[sabi(check_subset(..))]`/`#[sabi(check_ordered(..))]`
+/// attributes.
+pub(crate) struct DeclaredCheck {
+    pub(crate) name: Ident,
+    pub(crate) kind: DeclaredCheckKind,
+    pub(crate) value: syn::Expr,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum DeclaredCheckKind {
+    Const,
+    Subset,
+    Ordered,
+}
+
+//////////////////////
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 pub(crate) enum ASTypeParamBound {
     NoBound,
@@ -216,6 +243,16 @@ impl<'a> StableAbiOptions<'a> {
             }
         };
 
+        match (this.with_prefix_extra, &kind) {
+            (Some(_), StabilityKind::Prefix(_)) | (None, _) => {}
+            (Some(span), _) => {
+                errors.push_err(syn_err!(
+                    span,
+                    "The #[sabi(with_prefix_extra)] attribute can only be used on prefix types."
+                ));
+            }
+        }
+
         match (repr.variant, ds.data_variant) {
             (Repr::Transparent, DataVariant::Struct) => {}
             (Repr::Transparent, _) => {
@@ -276,6 +313,14 @@ impl<'a> StableAbiOptions<'a> {
             None
         };
 
+        if let (Some(extra_checks), Some(_)) = (&this.extra_checks, this.declared_checks.first()) {
+            errors.push_err(spanned_err!(
+                extra_checks,
+                "Cannot use the `#[sabi(extra_checks=\"\")]` attribute \
+                 together with the `#[sabi(check_*(..))]` attributes."
+            ));
+        }
+
         let const_idents = ConstIdents {
             strings: parse_str_as_ident(&format!("_SHARED_VARS_STRINGS_{}", ds.name)),
         };
@@ -294,15 +339,19 @@ impl<'a> StableAbiOptions<'a> {
             override_field_accessor: this.override_field_accessor,
             tags: this.tags,
             extra_checks: this.extra_checks,
+            declared_checks: this.declared_checks,
             impl_interfacetype: this.impl_interfacetype,
             phantom_fields,
             phantom_type_params: this.phantom_type_params,
             phantom_const_params: this.phantom_const_params,
             allow_type_macros: this.allow_type_macros,
             with_field_indices: this.with_field_indices,
+            with_prefix_extra: this.with_prefix_extra.is_some(),
             const_idents,
             mod_refl_mode,
             doc_hidden_attr,
+            docs: this.docs,
+            field_docs: this.field_docs,
         })
     }
 }
@@ -320,6 +369,7 @@ struct StableAbiAttrs<'a> {
 
     tags: Option<syn::Expr>,
     extra_checks: Option<syn::Expr>,
+    declared_checks: Vec<DeclaredCheck>,
 
     first_suffix_field: FirstSuffixField,
     default_on_missing_fields: Option<OnMissingField<'a>>,
@@ -350,8 +400,12 @@ struct StableAbiAttrs<'a> {
 
     allow_type_macros: bool,
     with_field_indices: bool,
+    with_prefix_extra: Option<Span>,
     is_hidden: bool,
 
+    docs: String,
+    field_docs: FieldMap<String>,
+
     errors: LinearResult<()>,
 }
 
@@ -407,6 +461,7 @@ where
     this.override_field_accessor = FieldMap::defaulted(ds);
     this.accessor_bounds = FieldMap::defaulted(ds);
     this.changed_types = FieldMap::defaulted(ds);
+    this.field_docs = FieldMap::defaulted(ds);
     this.ne_variants.resize(
         ds.variants.len(),
         UncheckedNEVariant {
@@ -458,6 +513,13 @@ where
             Ok(Meta::List(list)) => {
                 parse_attr_list(this, pctx, list, arenas).combine_into_err(&mut this.errors);
             }
+            Ok(Meta::NameValue(MetaNameValue {
+                lit: Lit::Str(ref lit),
+                ref path,
+                ..
+            })) if path.equals_str("doc") => {
+                parse_doc_comment(this, pctx, &lit.value());
+            }
             Err(e) => {
                 this.errors.push_err(e);
             }
@@ -467,6 +529,21 @@ where
     Ok(())
 }
 
+/// Appends a line of a doc comment (A `#[doc = "..."]` attribute) to the docs
+/// of the type or field it's attached to.
+fn parse_doc_comment<'a>(this: &mut StableAbiAttrs<'a>, pctx: ParseContext<'a>, line: &str) {
+    let docs = match pctx {
+        ParseContext::TypeAttr { .. } => &mut this.docs,
+        ParseContext::Field { field, .. } => &mut this.field_docs[field],
+        ParseContext::Variant { .. } => return,
+    };
+    if !docs.is_empty() {
+        docs.push('\n');
+    }
+    let line = line.strip_prefix(' ').unwrap_or(line);
+    docs.extend(line.chars().filter(|&c| c != '\0'));
+}
+
 /// Parses an individual attribute list (A `#[attribute( .. )] attribute`).
 fn parse_attr_list<'a>(
     this: &mut StableAbiAttrs<'a>,
@@ -840,6 +917,50 @@ fn parse_sabi_attr<'a>(
                     }
                     Ok(())
                 })?;
+            } else if ident == "check_const" || ident == "check_subset" || ident == "check_ordered"
+            {
+                let kind = if ident == "check_const" {
+                    DeclaredCheckKind::Const
+                } else if ident == "check_subset" {
+                    DeclaredCheckKind::Subset
+                } else {
+                    DeclaredCheckKind::Ordered
+                };
+
+                fn dc_err(ident: &Ident, tokens: &dyn ToTokens) -> syn::Error {
+                    spanned_err!(
+                        tokens,
+                        "invalid #[{}(..)] attribute\
+                         (it must be a list of `NAME = \"expression\"`).",
+                        ident,
+                    )
+                }
+
+                with_nested_meta(&ident.to_string(), list.nested, |attr| {
+                    match attr {
+                        Meta::NameValue(MetaNameValue { path, lit, .. }) => {
+                            let name = path.into_ident().map_err(|p| dc_err(ident, &p))?;
+                            let value = match lit {
+                                Lit::Str(lit) => parse_lit_as_expr(&lit)?,
+                                lit => syn::Expr::Lit(syn::ExprLit {
+                                    attrs: Vec::new(),
+                                    lit,
+                                }),
+                            };
+                            if this.declared_checks.iter().any(|c| c.name == name) {
+                                return_spanned_err!(
+                                    name,
+                                    "Cannot declare multiple checks named `{}`",
+                                    name,
+                                );
+                            }
+                            this.declared_checks
+                                .push(DeclaredCheck { name, kind, value });
+                        }
+                        x => this.errors.push_err(dc_err(ident, &x)),
+                    }
+                    Ok(())
+                })?;
             } else if ident == "impl_InterfaceType" {
                 if this.impl_interfacetype.is_some() {
                     return_spanned_err!(ident, "Cannot use this attribute multiple times")
@@ -872,6 +993,8 @@ fn parse_sabi_attr<'a>(
                 this.allow_type_macros = true;
             } else if word == "with_field_indices" {
                 this.with_field_indices = true;
+            } else if word == "with_prefix_extra" {
+                this.with_prefix_extra = Some(word.span());
             } else if word == "impl_prefix_stable_abi" {
                 this.kind = UncheckedStabilityKind::Value {
                     impl_prefix_stable_abi: true,
@@ -28,14 +28,20 @@ impl<'a> GenericParams<'a> {
                     .to_u10()
             });
 
+            // Every type parameter is stored,in declaration order,
+            // the ones that aren't required to implement `StableAbi`
+            // are stored as an opaque `PhantomData<TypeParam>`.
             let type_param_bounds = config
                 .type_param_bounds
                 .iter()
-                .filter(|(_, bounds)| **bounds == ASTypeParamBound::NoBound)
                 .map(|(type_param, &bounds)| {
                     let type_ = {
                         let x = type_from_ident(type_param.clone());
-                        arenas.alloc(x)
+                        if bounds == ASTypeParamBound::StableAbi {
+                            arenas.alloc(x)
+                        } else {
+                            arenas.alloc(syn::parse_quote!(__sabi_re::PhantomData<#x>))
+                        }
                     };
 
                     let layout_ctor = bounds.into_::<LayoutConstructor>();
@@ -46,23 +52,27 @@ impl<'a> GenericParams<'a> {
                 });
 
             let mut iter = type_param_bounds.chain(phantom_type_params);
-            let first = iter.next().unwrap_or(0);
-            let mut last = first;
-            for elem in iter {
-                assert!(
-                    first <= elem,
-                    "BUG:\
-                    The type parameters must all be stored contiguously in the SharedVars.\n\
-                    last={} elem={}\
-                    ",
-                    last,
-                    elem,
-                );
-                last = elem;
-            }
-            StartLen {
-                start: first,
-                len: last - first,
+            match iter.next() {
+                Some(first) => {
+                    let mut last = first;
+                    for elem in iter {
+                        assert!(
+                            first <= elem,
+                            "BUG:\
+                            The type parameters must all be stored contiguously in the SharedVars.\n\
+                            last={} elem={}\
+                            ",
+                            last,
+                            elem,
+                        );
+                        last = elem;
+                    }
+                    StartLen {
+                        start: first,
+                        len: last - first + 1,
+                    }
+                }
+                None => StartLen { start: 0, len: 0 },
             }
         };
 
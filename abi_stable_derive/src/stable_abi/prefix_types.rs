@@ -1,6 +1,6 @@
 //! Code generation for prefix-types.
 
-use abi_stable_shared::const_utils::low_bit_mask_u64;
+use abi_stable_shared::{const_utils::low_bit_mask_u64, type_layout::tl_prefix_extra};
 
 use core_extensions::{matches, SelfOps};
 
@@ -21,6 +21,7 @@ use crate::{literals_constructors::rstr_tokenizer, parse_utils::parse_str_as_ide
 use super::{
     attribute_parsing::{StabilityKind, StableAbiOptions},
     reflection::FieldAccessor,
+    shared_vars::SharedVars,
     CommonTokens,
 };
 
@@ -205,6 +206,48 @@ impl<'a> PrefixKind<'a> {
     }
 }
 
+impl<'a> PrefixKind<'a> {
+    /// Stores what the accessor of each field does when the field is missing,
+    /// and the doc comments of the type, after the strings in `shared_vars`.
+    ///
+    /// The doc comments are left out if they don't fit.
+    pub(crate) fn push_extra_info(
+        &self,
+        config: &StableAbiOptions<'a>,
+        shared_vars: &mut SharedVars<'a>,
+    ) {
+        use self::tl_prefix_extra as tpe;
+        use self::OnMissingField as OMF;
+
+        let codes = self
+            .fields
+            .iter()
+            .map(|(_, field)| match field {
+                AccessorOrMaybe::Accessor => tpe::ALWAYS_ACCESSIBLE,
+                AccessorOrMaybe::Maybe(MaybeAccessor { on_missing, .. }) => match on_missing {
+                    OMF::ReturnOption => tpe::RETURN_OPTION,
+                    OMF::Panic => tpe::PANIC,
+                    OMF::With { .. } => tpe::WITH,
+                    OMF::Value { .. } => tpe::VALUE,
+                    OMF::Default_ => tpe::DEFAULT,
+                },
+            })
+            .collect::<String>();
+
+        let mut with_docs = codes.clone();
+        with_docs.push(tpe::SEPARATOR);
+        with_docs.push_str(&config.docs);
+        for (_, docs) in config.field_docs.iter() {
+            with_docs.push(tpe::SEPARATOR);
+            with_docs.push_str(docs);
+        }
+
+        if !shared_vars.push_trailing_section(&with_docs) {
+            shared_vars.push_trailing_section(&codes);
+        }
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 /////                 Code generation
 ////////////////////////////////////////////////////////////////////////////////
@@ -10,6 +10,8 @@ use super::{
     attribute_parsing::LayoutConstructor, tl_multi_tl::TypeLayoutIndex, CommonTokens, ConstIdents,
 };
 
+use abi_stable_shared::type_layout::tl_prefix_extra;
+
 use as_derive_utils::{syn_err, to_token_fn::ToTokenFnMut};
 
 use core_extensions::SelfOps;
@@ -103,6 +105,19 @@ impl<'a> SharedVars<'a> {
         }
     }
 
+    /// Appends `section` after all the strings that are referenced with a `StartLen`,
+    /// returning false if it doesn't fit.
+    ///
+    /// This must be called after pushing all the other strings.
+    pub(crate) fn push_trailing_section(&mut self, section: &str) -> bool {
+        if self.strings.len() + 1 + section.len() >= (1 << 16) {
+            return false;
+        }
+        self.strings.push(tl_prefix_extra::SEPARATOR);
+        self.strings.push_str(section);
+        true
+    }
+
     pub(crate) fn combine_err(&mut self, r: Result<(), syn::Error>) {
         self.extra_errs.combine_err(r);
     }
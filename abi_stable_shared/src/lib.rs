@@ -11,6 +11,7 @@ pub mod type_layout {
     pub mod tl_field_macro;
     pub mod tl_lifetimes_macro;
     pub mod tl_multi_tl_macro;
+    pub mod tl_prefix_extra;
     pub mod tl_type_layout_index;
 }
 
@@ -81,3 +82,26 @@ where
 pub fn mangled_root_module_loader_name() -> String {
     mangle_ident("lib_header", "root module loader")
 }
+
+/// Gets the name of the static that contains the metadata of an abi_stable library,
+/// which is stored in the `.sabi_metadata` ELF section.
+pub fn mangled_lib_metadata_name() -> String {
+    mangle_ident("lib_metadata", "root module metadata")
+}
+
+/// Gets the name of the static that contains the serialized layout of the root module
+/// of an abi_stable library,which is stored in the `.sabi_layout` ELF section.
+pub fn mangled_lib_layout_name() -> String {
+    mangle_ident("lib_layout", "root module serialized layout")
+}
+
+/// Gets the name of the static that contains the capabilities
+/// requested by an abi_stable library.
+pub fn mangled_lib_capabilities_name() -> String {
+    mangle_ident("lib_capabilities", "root module capabilities")
+}
+
+/// Gets the name of the static that contains the manifest of an abi_stable library.
+pub fn mangled_lib_manifest_name() -> String {
+    mangle_ident("lib_manifest", "root module manifest")
+}
@@ -0,0 +1,27 @@
+//! The encoding of the extra information about prefix types
+//! that the `StableAbi` derive stores after the strings in `SharedVars`,
+//! with the `#[sabi(with_prefix_extra)]` attribute.
+//!
+//! The strings are followed by a `SEPARATOR`,and then by these `SEPARATOR`-separated parts:
+//!
+//! - The missing field codes,one ascii character for each field.
+//!
+//! - The doc comments of the type (this and the following parts are optional).
+//!
+//! - The doc comments of each field.
+
+/// Separates the extra information from the other strings,and the parts of it.
+pub const SEPARATOR: char = '\0';
+
+/// The code for fields whose accessor always returns the field.
+pub const ALWAYS_ACCESSIBLE: char = 'a';
+/// The code for fields whose accessor returns `None` when the field is missing.
+pub const RETURN_OPTION: char = 'o';
+/// The code for fields whose accessor panics when the field is missing.
+pub const PANIC: char = 'p';
+/// The code for fields whose accessor calls a function when the field is missing.
+pub const WITH: char = 'w';
+/// The code for fields whose accessor returns a value when the field is missing.
+pub const VALUE: char = 'v';
+/// The code for fields whose accessor returns `Default::default()` when the field is missing.
+pub const DEFAULT: char = 'd';
@@ -1,13 +1,24 @@
-use std::{fs, path::PathBuf};
+use std::{
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
 
 use abi_stable::{
-    library::lib_header_from_path,
+    library::{lib_header_from_path, lib_metadata_from_path, serialize_layout, LibMetadata},
     //abi_stability::check_layout_compatibility,
-    reflection::export_module::MRItem,
+    reflection::{
+        export_layout::{ELTypeSummary, ELTypes},
+        export_module::{markdown::markdown_pages, MRItem},
+        type_graph::TypeGraph,
+    },
+    type_layout::TypeLayout,
 };
 
 use core_extensions::SelfOps;
 
+use serde::Serialize;
+
 use structopt::StructOpt;
 
 ///////////////////////////////////////////////////////////////////////////////
@@ -17,6 +28,11 @@ use structopt::StructOpt;
 enum Command {
     /// Extracts the module structure of an abi_stable library,
     /// with the typenames of each function parameter/return type.
+    ///
+    /// This includes the doc comments of modules and their fields
+    /// (for modules declared with `#[sabi(with_prefix_extra)]`),
+    /// the accessibility of the fields of modules,
+    /// and the package,version,and tag of each type.
     #[structopt(name = "mods")]
     #[structopt(author = "_")]
     Modules {
@@ -36,6 +52,215 @@ enum Command {
         #[structopt(long = "--compact")]
         compact_json: bool,
     },
+    /// Outputs the layout of the root module of an abi_stable library,
+    /// and of every type that it references.
+    ///
+    /// This includes the size,alignment,and representation attribute of each type,
+    /// their fields,the signatures of function pointers,and their generic parameters.
+    #[structopt(name = "layout")]
+    #[structopt(author = "_")]
+    Layout {
+        /// The path to the library.
+        library_path: PathBuf,
+
+        /// Which file to output the layout to.
+        #[structopt(short = "o", long = "out-file")]
+        #[structopt(parse(from_os_str))]
+        output_file: Option<PathBuf>,
+
+        /// Whether to output the layout to stdout.
+        #[structopt(short = "s")]
+        output_stdout: bool,
+
+        /// Whether to output the layout as json,instead of text.
+        #[structopt(long = "--json")]
+        json: bool,
+
+        /// Whether to outputed json is compact
+        #[structopt(long = "--compact")]
+        compact_json: bool,
+
+        /// Whether to read the layout from the `.sabi_layout` ELF section
+        /// instead of loading the library.
+        ///
+        /// This requires the root module to be exported with
+        /// `#[export_root_module(serialized_layout)]`,
+        /// and the layout is always output as text.
+        #[structopt(long = "--no-load", conflicts_with = "json")]
+        no_load: bool,
+    },
+    /// Lists every type that the root module of an abi_stable library references,
+    /// with the package,version,and module path where each type was declared.
+    #[structopt(name = "types")]
+    #[structopt(author = "_")]
+    Types {
+        /// The path to the library.
+        library_path: PathBuf,
+
+        /// Which file to output the list of types to.
+        #[structopt(short = "o", long = "out-file")]
+        #[structopt(parse(from_os_str))]
+        output_file: Option<PathBuf>,
+
+        /// Whether to output the list of types to stdout.
+        #[structopt(short = "s")]
+        output_stdout: bool,
+
+        /// Whether to output the list of types as json,instead of text.
+        #[structopt(long = "--json")]
+        json: bool,
+
+        /// Whether to outputed json is compact
+        #[structopt(long = "--compact")]
+        compact_json: bool,
+    },
+    /// Generates Markdown documentation for the root module of an abi_stable library,
+    /// with one page for each module.
+    ///
+    /// The pages include the signatures of functions,
+    /// the availability of the fields of modules,
+    /// and the variants of the nonexhaustive enums that they reference.
+    #[structopt(name = "docs")]
+    #[structopt(author = "_")]
+    Docs {
+        /// The path to the library.
+        library_path: PathBuf,
+
+        /// Which directory to output the pages to.
+        #[structopt(short = "o", long = "out-dir", default_value = ".")]
+        #[structopt(parse(from_os_str))]
+        output_dir: PathBuf,
+    },
+    /// Outputs the graph of all the types that the root module of an
+    /// abi_stable library references,in the Graphviz DOT format.
+    ///
+    /// Prefix types,nonexhaustive enums,and `#[sabi_trait]` vtables are highlighted.
+    #[structopt(name = "graph")]
+    #[structopt(author = "_")]
+    Graph {
+        /// The path to the library.
+        library_path: PathBuf,
+
+        /// Which file to output the graph to.
+        #[structopt(short = "o", long = "out-file")]
+        #[structopt(parse(from_os_str))]
+        output_file: Option<PathBuf>,
+
+        /// Whether to output the graph to stdout.
+        #[structopt(short = "s")]
+        output_stdout: bool,
+    },
+    /// Outputs the metadata of an abi_stable library without loading it,
+    /// by reading its `.sabi_metadata` ELF section.
+    ///
+    /// This includes the name and version of the library,
+    /// the abi_stable version it uses,
+    /// whether the layout of its root module is checked when it's loaded,
+    /// and whether the layout was serialized into the `.sabi_layout` section.
+    ///
+    /// This only works for ELF libraries (eg: on Linux).
+    #[structopt(name = "metadata")]
+    #[structopt(author = "_")]
+    Metadata {
+        /// The path to the library.
+        library_path: PathBuf,
+
+        /// Which file to output the metadata to.
+        #[structopt(short = "o", long = "out-file")]
+        #[structopt(parse(from_os_str))]
+        output_file: Option<PathBuf>,
+
+        /// Whether to output the metadata to stdout.
+        #[structopt(short = "s")]
+        output_stdout: bool,
+
+        /// Whether to output the metadata as json,instead of text.
+        #[structopt(long = "--json")]
+        json: bool,
+
+        /// Whether to outputed json is compact
+        #[structopt(long = "--compact")]
+        compact_json: bool,
+    },
+}
+
+/// The serializable version of `LibMetadata`.
+#[derive(Serialize)]
+struct MetadataOutput<'a> {
+    base_name: &'a str,
+    name: &'a str,
+    version: &'a str,
+    abi_major: u32,
+    abi_minor: u32,
+    is_abi_compatible: bool,
+    is_layout_checked: bool,
+    has_serialized_layout: bool,
+}
+
+impl<'a> MetadataOutput<'a> {
+    fn new(metadata: &'a LibMetadata) -> Self {
+        Self {
+            base_name: &metadata.base_name,
+            name: &metadata.name,
+            version: &metadata.version,
+            abi_major: metadata.abi_header.abi_major,
+            abi_minor: metadata.abi_header.abi_minor,
+            is_abi_compatible: metadata.abi_header.is_valid(),
+            is_layout_checked: metadata.is_layout_checked,
+            has_serialized_layout: metadata.serialized_layout.is_some(),
+        }
+    }
+}
+
+/// Gets the layout of the root module of the library at `library_path`,
+/// exiting the process if it can't be loaded.
+fn root_module_layout(library_path: &Path) -> &'static TypeLayout {
+    let lib_header = lib_header_from_path(library_path).unwrap();
+
+    lib_header.layout().unwrap_or_else(|| {
+        println!(
+            "The dynamic library does not support reflection:\n    {}",
+            library_path.display(),
+        );
+        std::process::exit(1);
+    })
+}
+
+/// Reads the metadata of the library at `library_path` without loading it,
+/// exiting the process if it can't be read.
+fn read_metadata(library_path: &Path) -> LibMetadata {
+    lib_metadata_from_path(library_path).unwrap_or_else(|e| {
+        println!("{}", e);
+        std::process::exit(1);
+    })
+}
+
+/// Writes `contents` to `output_file` if it's `Some`,
+/// and to stdout if `output_file` is `None` or `output_stdout` is true.
+fn output(contents: &str, output_file: &Option<PathBuf>, output_stdout: bool) {
+    if let Some(output_file) = output_file {
+        if let Err(e) = fs::write(output_file, contents) {
+            panic!(
+                "Error writing to file:\n{}\nError:\n{}\n",
+                output_file.display(),
+                e,
+            );
+        }
+    }
+    if output_file.is_none() || output_stdout {
+        println!("{}", contents);
+    }
+}
+
+fn to_json<T>(value: &T, compact_json: bool) -> String
+where
+    T: Serialize,
+{
+    if compact_json {
+        serde_json::to_string(value).unwrap()
+    } else {
+        serde_json::to_string_pretty(value).unwrap()
+    }
 }
 
 fn main() {
@@ -76,36 +301,131 @@ version of abi_stable to be loaded successfully.
             output_stdout,
             compact_json,
         } => {
-            let lib_header = lib_header_from_path(library_path.as_ref()).unwrap();
-
-            let layout = lib_header.layout().unwrap_or_else(|| {
-                println!(
-                    "The dynamic library does not support reflection:\n    {}",
-                    library_path.display(),
-                );
-                std::process::exit(1);
-            });
+            let layout = root_module_layout(&library_path);
 
             let root_mod = MRItem::from_type_layout(layout);
 
-            let json = &if compact_json {
-                serde_json::to_string(&root_mod).unwrap()
+            output(
+                &to_json(&root_mod, compact_json),
+                &output_file,
+                output_stdout,
+            );
+        }
+        Command::Layout {
+            library_path,
+            output_file,
+            output_stdout,
+            json,
+            compact_json,
+            no_load,
+        } => {
+            let contents = if no_load {
+                let metadata = read_metadata(&library_path);
+                metadata.serialized_layout.unwrap_or_else(|| {
+                    println!(
+                        "The dynamic library does not have a serialized layout:\n    {}",
+                        library_path.display(),
+                    );
+                    std::process::exit(1);
+                })
+            } else if json {
+                let layout = root_module_layout(&library_path);
+                let types = ELTypes::from_type_graph(&TypeGraph::from_type_layout(layout));
+                to_json(&types, compact_json)
             } else {
-                serde_json::to_string_pretty(&root_mod).unwrap()
+                serialize_layout(root_module_layout(&library_path))
             };
 
-            if let Some(output_file) = &output_file {
-                if let Err(e) = fs::write(output_file, json) {
-                    panic!(
-                        "Error writing to file:\n{}\nError:\n{}\n",
-                        output_file.display(),
-                        e,
-                    );
+            output(&contents, &output_file, output_stdout);
+        }
+        Command::Types {
+            library_path,
+            output_file,
+            output_stdout,
+            json,
+            compact_json,
+        } => {
+            let layout = root_module_layout(&library_path);
+
+            let types = ELTypeSummary::from_type_graph(&TypeGraph::from_type_layout(layout));
+
+            let contents = if json {
+                to_json(&types, compact_json)
+            } else {
+                let mut contents = String::new();
+                for type_ in &types {
+                    writeln!(contents, "{}", type_).unwrap();
                 }
+                contents
+            };
+
+            output(&contents, &output_file, output_stdout);
+        }
+        Command::Docs {
+            library_path,
+            output_dir,
+        } => {
+            let layout = root_module_layout(&library_path);
+
+            if let Err(e) = fs::create_dir_all(&output_dir) {
+                panic!(
+                    "Error creating directory:\n{}\nError:\n{}\n",
+                    output_dir.display(),
+                    e,
+                );
             }
-            if output_file.is_none() || output_stdout {
-                println!("{}", json);
+            for page in markdown_pages(layout) {
+                let path = output_dir.join(&page.file_name);
+                output(&page.contents, &Some(path), false);
             }
         }
+        Command::Graph {
+            library_path,
+            output_file,
+            output_stdout,
+        } => {
+            let layout = root_module_layout(&library_path);
+
+            let graph = TypeGraph::from_type_layout(layout).to_string();
+
+            output(&graph, &output_file, output_stdout);
+        }
+        Command::Metadata {
+            library_path,
+            output_file,
+            output_stdout,
+            json,
+            compact_json,
+        } => {
+            let metadata = read_metadata(&library_path);
+            let metadata = MetadataOutput::new(&metadata);
+
+            let contents = if json {
+                to_json(&metadata, compact_json)
+            } else {
+                format!(
+                    "name: {}\n\
+                     base name: {}\n\
+                     version: {}\n\
+                     abi_stable ABI version: {}.{}{}\n\
+                     layout checked: {}\n\
+                     serialized layout: {}",
+                    metadata.name,
+                    metadata.base_name,
+                    metadata.version,
+                    metadata.abi_major,
+                    metadata.abi_minor,
+                    if metadata.is_abi_compatible {
+                        ""
+                    } else {
+                        " (incompatible with this program)"
+                    },
+                    metadata.is_layout_checked,
+                    metadata.has_serialized_layout,
+                )
+            };
+
+            output(&contents, &output_file, output_stdout);
+        }
     }
 }